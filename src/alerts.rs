@@ -0,0 +1,202 @@
+use crate::config::AlertRule;
+use crate::news::Story;
+use crate::util::atomic::write_atomic;
+use crate::util::sanitize::html_to_text;
+use anyhow::{Context, Result};
+use regex::Regex;
+use reqwest::Client;
+use serde_json::json;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+use url::Url;
+
+// Where a bare `ntfy` topic name is published
+const NTFY_SERVER: &str = "https://ntfy.sh/";
+
+/// A story that matched an alert rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertHit {
+    /// Name of the rule that matched.
+    pub alert: String,
+    pub story: Story,
+    /// UNIX epoch seconds
+    pub matched_at: i64,
+}
+
+/// Everything any alert ever matched, persisted as `alerts.json` in the state directory.
+/// Independent of the read history: a hit stays until the rule's hits are cleared.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AlertLog {
+    hits: Vec<AlertHit>,
+    /// When the Alerts menu was last opened; later hits count as new.
+    #[serde(default)]
+    reviewed_at: i64,
+}
+
+/// Compiled `[[alerts]]` rules.
+pub struct Matcher {
+    rules: Vec<(String, Vec<String>, Vec<Regex>)>,
+}
+
+impl Matcher {
+    /// Fails on an invalid regex (config validation reports those first).
+    pub fn new(rules: &[AlertRule]) -> Result<Self> {
+        let mut compiled = Vec::new();
+        for r in rules {
+            let keywords = r.keywords.iter().map(|k| k.to_lowercase()).collect();
+            let patterns = r.regex.iter().map(|p| Regex::new(p)).collect::<Result<_, _>>()?;
+            compiled.push((r.name.clone(), keywords, patterns));
+        }
+        Ok(Matcher { rules: compiled })
+    }
+
+    /// Names of the rules `story` matches, checking title and summary.
+    pub fn matches<'a>(&'a self, story: &'a Story) -> impl Iterator<Item = &'a str> + 'a {
+        let text = match &story.summary {
+            Some(summary) => format!("{}\n{}", story.title, html_to_text(summary)),
+            None => story.title.clone(),
+        };
+        let lower = text.to_lowercase();
+        self.rules
+            .iter()
+            .filter(move |(_, keywords, patterns)| {
+                keywords.iter().any(|k| lower.contains(k.as_str())) || patterns.iter().any(|p| p.is_match(&text))
+            })
+            .map(|(name, _, _)| name.as_str())
+    }
+}
+
+impl AlertLog {
+    pub fn load() -> Self {
+        if let Some(path) = alerts_file_path()
+            && let Ok(contents) = fs::read_to_string(&path)
+            && let Ok(log) = serde_json::from_str::<AlertLog>(&contents)
+        {
+            return log;
+        }
+        AlertLog::default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = alerts_file_path() {
+            let json = serde_json::to_string_pretty(self)?;
+            write_atomic(&path, json.as_bytes(), true)?;
+        }
+        Ok(())
+    }
+
+    /// Add hits for `stories`; a story is recorded once per rule. Returns how many were added.
+    pub fn record(&mut self, matcher: &Matcher, stories: &[Story]) -> usize {
+        let now = time::OffsetDateTime::now_utc().unix_timestamp();
+        let mut added = 0;
+        for story in stories {
+            for alert in matcher.matches(story) {
+                if self.hits.iter().any(|h| h.alert == alert && h.story.link == story.link) {
+                    continue;
+                }
+                let mut story = story.clone();
+                story.is_new = false;
+                self.hits.push(AlertHit { alert: alert.to_string(), story, matched_at: now });
+                added += 1;
+            }
+        }
+        added
+    }
+
+    pub fn hits(&self) -> &[AlertHit] {
+        &self.hits
+    }
+
+    /// Hits recorded since the last [`AlertLog::mark_reviewed`].
+    pub fn unreviewed(&self) -> usize {
+        self.hits.iter().filter(|h| h.matched_at > self.reviewed_at).count()
+    }
+
+    pub fn mark_reviewed(&mut self) {
+        self.reviewed_at = time::OffsetDateTime::now_utc().unix_timestamp();
+    }
+
+    /// Drop every hit of one rule.
+    pub fn clear(&mut self, alert: &str) {
+        self.hits.retain(|h| h.alert != alert);
+    }
+}
+
+/// Record matches of the configured alerts in `stories`; returns the hits that are new.
+/// Callers report an error and carry on, since alerts must never get in the way of fetching;
+/// hits that couldn't be saved are found again next time.
+pub fn check(rules: &[AlertRule], stories: &[Story]) -> Result<Vec<AlertHit>> {
+    if rules.is_empty() {
+        return Ok(Vec::new());
+    }
+    let matcher = Matcher::new(rules).context("invalid alert rule")?;
+    let mut log = AlertLog::load();
+    let before = log.hits.len();
+    if log.record(&matcher, stories) > 0 {
+        log.save().context("failed to save alerts")?;
+    }
+    Ok(log.hits.split_off(before))
+}
+
+/// Send `hits` to the `webhook` and `ntfy` targets of their rules. A failing target doesn't
+/// stop the others; returns what failed, one message per target.
+pub async fn push(client: &Client, rules: &[AlertRule], hits: &[AlertHit]) -> Vec<String> {
+    let mut failed = Vec::new();
+    for rule in rules {
+        let matched: Vec<&AlertHit> = hits.iter().filter(|h| h.alert == rule.name).collect();
+        if matched.is_empty() {
+            continue;
+        }
+        if let Some(url) = &rule.webhook {
+            let payload: Vec<serde_json::Value> = matched
+                .iter()
+                .map(|h| json!({ "alert": h.alert, "title": h.story.title, "link": h.story.link, "source": h.story.source }))
+                .collect();
+            if let Err(e) = post(client, url, &serde_json::Value::Array(payload)).await {
+                failed.push(format!("Alert {}: webhook failed: {:#}", rule.name, e));
+            }
+        }
+        if let Some(topic) = &rule.ntfy {
+            let Some((server, topic)) = ntfy_target(topic) else {
+                failed.push(format!("Alert {}: invalid ntfy topic \"{}\"", rule.name, topic));
+                continue;
+            };
+            // ntfy's JSON publishing: POST to the server root with the topic in the body
+            for h in &matched {
+                let message = json!({
+                    "topic": topic,
+                    "title": h.story.title,
+                    "message": format!("{} ({})", h.story.source, h.alert),
+                    "click": h.story.link,
+                });
+                if let Err(e) = post(client, server.as_str(), &message).await {
+                    failed.push(format!("Alert {}: ntfy failed: {:#}", rule.name, e));
+                    break;
+                }
+            }
+        }
+    }
+    failed
+}
+
+/// Server and topic of an `ntfy` setting: a bare topic name is on ntfy.sh.
+pub fn ntfy_target(ntfy: &str) -> Option<(Url, String)> {
+    let valid = |t: &str| !t.is_empty() && t.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    let ntfy = ntfy.trim();
+    if !ntfy.contains('/') {
+        return valid(ntfy).then(|| (Url::parse(NTFY_SERVER).expect("valid URL"), ntfy.to_string()));
+    }
+    let mut url = Url::parse(ntfy).ok().filter(|u| matches!(u.scheme(), "http" | "https"))?;
+    let topic = url.path_segments()?.next_back().filter(|t| valid(t))?.to_string();
+    url.path_segments_mut().ok()?.pop();
+    Some((url, topic))
+}
+
+async fn post(client: &Client, url: &str, body: &serde_json::Value) -> Result<()> {
+    client.post(url).json(body).send().await?.error_for_status()?;
+    Ok(())
+}
+
+fn alerts_file_path() -> Option<PathBuf> {
+    crate::paths::state_dir().map(|d| d.join("alerts.json"))
+}
@@ -0,0 +1,84 @@
+//! What the interactive screens share for the whole session.
+use anyhow::Result;
+use news_cli::config::RuntimeConfig;
+use news_cli::history::SeenStories;
+use news_cli::news::provider::FetchContext;
+use news_cli::news::{CancellationToken, FeedProgress, Fetched, NewsClient};
+use reqwest::Client;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// One long-lived HTTP client for every screen, so a refresh reuses pooled connections,
+/// TLS sessions and cached DNS answers instead of starting cold. Cheap to clone.
+#[derive(Clone)]
+pub struct AppContext {
+    fetch: FetchContext,
+    prefetch: Arc<Mutex<Prefetch>>,
+}
+
+/// Fetches started at launch, while the main menu is up, so their screens open at once: the
+/// News screen's stories and, with `[stats] prefetch = true`, the Stats screen's values. The
+/// first visit takes each; quitting or a config reload cancels what nobody took.
+#[derive(Default)]
+struct Prefetch {
+    news: Option<crate::tui::Fetch>,
+    stats: Option<crate::stats::Prefetched>,
+}
+
+impl AppContext {
+    pub fn new(cfg: &RuntimeConfig) -> Result<Self> {
+        Ok(AppContext { fetch: FetchContext::new(cfg.fetch.clone())?, prefetch: Arc::default() })
+    }
+
+    pub fn set_news_prefetch(&self, fetch: crate::tui::Fetch) {
+        if let Some(old) = self.prefetch.lock().unwrap().news.replace(fetch) {
+            old.cancel();
+        }
+    }
+
+    /// The stories fetch started at launch, unless already taken.
+    pub fn take_news_prefetch(&self) -> Option<crate::tui::Fetch> {
+        self.prefetch.lock().unwrap().news.take()
+    }
+
+    pub fn set_stats_prefetch(&self, loads: crate::stats::Prefetched) {
+        if let Some(old) = self.prefetch.lock().unwrap().stats.replace(loads) {
+            old.cancel();
+        }
+    }
+
+    /// The Stats loads started at launch, unless already taken.
+    pub fn take_stats_prefetch(&self) -> Option<crate::stats::Prefetched> {
+        self.prefetch.lock().unwrap().stats.take()
+    }
+
+    /// Stop the launch fetches nobody took.
+    pub fn cancel_prefetch(&self) {
+        let mut prefetch = self.prefetch.lock().unwrap();
+        if let Some(news) = prefetch.news.take() {
+            news.cancel();
+        }
+        if let Some(stats) = prefetch.stats.take() {
+            stats.cancel();
+        }
+    }
+
+    /// The shared client, with `[fetch]`'s timeouts and user agent.
+    pub fn client(&self) -> &Client {
+        &self.fetch.client
+    }
+
+    /// Fetch every feed in `cfg`, until `cancel` (see [`NewsClient::fetch_all_cancellable`]),
+    /// reporting each feed to `progress`.
+    pub async fn fetch(
+        &self,
+        cfg: &RuntimeConfig,
+        history: &SeenStories,
+        cancel: &CancellationToken,
+        progress: UnboundedSender<FeedProgress>,
+    ) -> Fetched {
+        let mut client = NewsClient::with_context(cfg, self.fetch.clone().with_data_saver(cfg.data_saver));
+        client.report_progress(progress);
+        client.fetch_all_cancellable(history, cancel).await
+    }
+}
@@ -0,0 +1,73 @@
+use crate::news::Story;
+use crate::util::atomic::write_atomic;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub story: Story,
+    /// UNIX epoch seconds
+    pub saved_at: i64,
+}
+
+/// Stories the user explicitly saved; kept until removed, independent of read state.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Bookmarks {
+    items: Vec<Bookmark>,
+}
+
+impl Bookmarks {
+    pub fn load() -> Self {
+        if let Some(path) = bookmarks_file_path()
+            && let Ok(contents) = fs::read_to_string(&path)
+            && let Ok(b) = serde_json::from_str::<Bookmarks>(&contents)
+        {
+            return b;
+        }
+        Bookmarks::default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = bookmarks_file_path() {
+            let json = serde_json::to_string_pretty(self)?;
+            write_atomic(&path, json.as_bytes(), true)?;
+        }
+        Ok(())
+    }
+
+    /// Oldest first.
+    pub fn items(&self) -> &[Bookmark] {
+        &self.items
+    }
+
+    pub fn contains(&self, link: &str) -> bool {
+        self.items.iter().any(|b| b.story.link == link)
+    }
+
+    /// Add `story` unless already bookmarked; returns whether it was added.
+    pub fn add(&mut self, story: &Story) -> bool {
+        if self.contains(&story.link) {
+            return false;
+        }
+        self.toggle(story)
+    }
+
+    /// Add or remove `story`; returns whether it is bookmarked afterwards.
+    pub fn toggle(&mut self, story: &Story) -> bool {
+        if self.contains(&story.link) {
+            self.items.retain(|b| b.story.link != story.link);
+            false
+        } else {
+            self.items.push(Bookmark {
+                story: story.clone(),
+                saved_at: time::OffsetDateTime::now_utc().unix_timestamp(),
+            });
+            true
+        }
+    }
+}
+
+fn bookmarks_file_path() -> Option<PathBuf> {
+    crate::paths::state_dir().map(|d| d.join("bookmarks.json"))
+}
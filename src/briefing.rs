@@ -0,0 +1,138 @@
+//! Opt-in condensed briefing of the top stories, written by an LLM behind an OpenAI-compatible
+//! chat completions API (OpenAI itself, or a local ollama at `http://localhost:11434/v1`).
+use crate::config::{BriefingConfig, FetchLimits};
+use crate::news::{build_client_with, max_feed_bytes, read_body_capped, strict, Story};
+use crate::util::sanitize::html_to_text;
+use anyhow::{bail, Context, Result};
+use futures_util::future::join_all;
+use regex::Regex;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+use std::time::Duration;
+use url::Url;
+
+const DEFAULT_TOP: usize = 20;
+// Per-article cap when include_text is on, to keep the request within model context
+const MAX_ARTICLE_CHARS: usize = 3000;
+
+const DEFAULT_PROMPT: &str = "You are a news editor. Write a short daily briefing from the \
+stories below: group related stories, lead with the most significant, one or two sentences \
+each, plain text with '-' bullets. Do not invent facts that are not in the input.";
+
+#[derive(Deserialize)]
+struct Completion {
+    choices: Vec<Choice>,
+}
+
+#[derive(Deserialize)]
+struct Choice {
+    message: Message,
+}
+
+#[derive(Deserialize)]
+struct Message {
+    content: String,
+}
+
+/// The stories a briefing covers: the `top` most recent.
+pub fn select(cfg: &BriefingConfig, stories: &[Story]) -> Vec<Story> {
+    let mut picked = stories.to_vec();
+    picked.sort_by_key(|s| std::cmp::Reverse(s.published));
+    picked.truncate(cfg.top.unwrap_or(DEFAULT_TOP));
+    picked
+}
+
+/// Ask the configured model for a briefing of `stories` (see [`select`]). Their pages, for
+/// `include_text`, are fetched like feeds with `limits`, so `[fetch] strict` covers them.
+pub async fn generate(cfg: &BriefingConfig, limits: &FetchLimits, stories: &[Story]) -> Result<String> {
+    if stories.is_empty() {
+        bail!("no stories to brief");
+    }
+    // Models can take a while on long inputs; the feed client's 20s would cut them off
+    let client = Client::builder()
+        .user_agent("news-cli/0.1")
+        .connect_timeout(Duration::from_secs(5))
+        .timeout(Duration::from_secs(180))
+        .build()?;
+    let texts = if cfg.include_text {
+        let pages = build_client_with(limits)?;
+        let strict = limits.strict == Some(true);
+        join_all(stories.iter().map(|s| async {
+            // Full-content feeds already carry the article
+            match &s.content {
+                Some(content) => Some(crate::util::html::to_text(content, None)),
+                None => article_text(&pages, strict, &s.link).await,
+            }
+        }))
+        .await
+    } else {
+        vec![None; stories.len()]
+    };
+    let mut input = String::new();
+    for (i, (s, text)) in stories.iter().zip(texts).enumerate() {
+        input.push_str(&format!("{}. [{}] {}\n", i + 1, s.source, s.title));
+        let body = text.or_else(|| s.summary.as_deref().map(html_to_text));
+        if let Some(body) = body.filter(|b| !b.is_empty()) {
+            input.push_str(&format!("   {}\n", truncate_chars(&body, MAX_ARTICLE_CHARS)));
+        }
+    }
+
+    let api_key = cfg
+        .api_key
+        .clone()
+        .filter(|k| !k.is_empty())
+        .or_else(|| std::env::var("NEWS_CLI_BRIEFING_KEY").ok().filter(|k| !k.is_empty()));
+    let url = format!("{}/chat/completions", cfg.api_base.trim_end_matches('/'));
+    let mut req = client.post(&url).json(&json!({
+        "model": cfg.model,
+        "temperature": 0.3,
+        "messages": [
+            { "role": "system", "content": cfg.prompt.as_deref().unwrap_or(DEFAULT_PROMPT) },
+            { "role": "user", "content": input },
+        ],
+    }));
+    if let Some(key) = api_key {
+        req = req.bearer_auth(key);
+    }
+    let resp = req.send().await.with_context(|| format!("failed to reach {}", url))?;
+    let status = resp.status();
+    if !status.is_success() {
+        let detail = resp.text().await.unwrap_or_default();
+        bail!("{} returned {}: {}", url, status, truncate_chars(detail.trim(), 300));
+    }
+    let completion: Completion = resp.json().await.context("unexpected response from model API")?;
+    let text = completion
+        .choices
+        .into_iter()
+        .next()
+        .map(|c| c.message.content.trim().to_string())
+        .unwrap_or_default();
+    if text.is_empty() {
+        bail!("the model returned an empty briefing");
+    }
+    Ok(text)
+}
+
+// Best effort: a page that can't be fetched falls back to the feed summary
+async fn article_text(client: &Client, strict: bool, link: &str) -> Option<String> {
+    if !link.starts_with("http") {
+        return None;
+    }
+    // The client only keeps host names off private addresses; the link may name one outright
+    if strict && !Url::parse(link).is_ok_and(|u| strict::check_url(&u).is_ok()) {
+        return None;
+    }
+    let resp = client.get(link).timeout(Duration::from_secs(15)).send().await.ok()?.error_for_status().ok()?;
+    let page = read_body_capped(resp, max_feed_bytes()).await.ok()?;
+    let page = String::from_utf8_lossy(&page);
+    let noise = Regex::new(r"(?is)<script.*?</script>|<style.*?</style>|<noscript.*?</noscript>").expect("valid regex");
+    Some(html_to_text(&noise.replace_all(&page, " ")))
+}
+
+fn truncate_chars(s: &str, max: usize) -> String {
+    match s.char_indices().nth(max) {
+        Some((i, _)) => format!("{}…", &s[..i]),
+        None => s.to_string(),
+    }
+}
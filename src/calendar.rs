@@ -0,0 +1,103 @@
+//! Upcoming economic releases (CPI, rate decisions, GDP, ...) for the Calendar screen. The
+//! default source is Forex Factory's weekly calendar feed, which names each event's country
+//! by its currency ("USD", "CAD", "EUR", ...).
+use crate::config::CalendarConfig;
+use crate::error::Result;
+use reqwest::Client;
+use serde::Deserialize;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// `calendar.url` when unset.
+pub const DEFAULT_URL: &str = "https://nfs.faireconomy.media/ff_calendar_thisweek.json";
+
+/// How much a release tends to move markets, as the calendar rates it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Impact {
+    Low,
+    Medium,
+    High,
+}
+
+impl Impact {
+    /// "low", "medium" or "high", in any case.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "low" => Some(Impact::Low),
+            "medium" => Some(Impact::Medium),
+            "high" => Some(Impact::High),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    /// When it is released (UNIX seconds).
+    pub time: i64,
+    /// Currency code of the country, e.g. "CAD".
+    pub country: String,
+    pub title: String,
+    pub impact: Impact,
+    pub previous: Option<String>,
+    pub forecast: Option<String>,
+}
+
+// One entry of the feed
+#[derive(Deserialize)]
+struct RawEvent {
+    title: String,
+    country: String,
+    // RFC 3339 with the calendar's offset
+    date: String,
+    #[serde(default)]
+    impact: String,
+    #[serde(default)]
+    forecast: String,
+    #[serde(default)]
+    previous: String,
+}
+
+/// Events in a calendar feed (a JSON array in Forex Factory's format). Entries without a
+/// valid date are skipped; holidays and unrated events count as low impact.
+pub fn parse(json: &str) -> Result<Vec<Event>> {
+    let raw: Vec<RawEvent> = serde_json::from_str(json)?;
+    let optional = |s: String| {
+        let s = s.trim().to_string();
+        (!s.is_empty()).then_some(s)
+    };
+    Ok(raw
+        .into_iter()
+        .filter_map(|e| {
+            Some(Event {
+                time: OffsetDateTime::parse(&e.date, &Rfc3339).ok()?.unix_timestamp(),
+                country: e.country.trim().to_ascii_uppercase(),
+                title: e.title.trim().to_string(),
+                impact: Impact::parse(&e.impact).unwrap_or(Impact::Low),
+                previous: optional(e.previous),
+                forecast: optional(e.forecast),
+            })
+        })
+        .collect())
+}
+
+/// Events for the configured countries at or above the configured impact, released at
+/// `now` or later, soonest first.
+pub fn upcoming(events: Vec<Event>, cfg: &CalendarConfig, now: i64) -> Vec<Event> {
+    let least = cfg.impact.as_deref().and_then(Impact::parse).unwrap_or(Impact::Low);
+    let mut out: Vec<Event> = events
+        .into_iter()
+        .filter(|e| e.time >= now && e.impact >= least)
+        .filter(|e| cfg.countries.iter().any(|c| c.trim().eq_ignore_ascii_case(&e.country)))
+        .collect();
+    out.sort_by_key(|e| e.time);
+    out
+}
+
+/// Fetch the calendar and keep what [`upcoming`] keeps. Unreachable or failing servers are
+/// [`Error::Network`](crate::Error::Network), a feed that isn't one [`Error::Parse`](crate::Error::Parse).
+pub async fn fetch(client: &Client, cfg: &CalendarConfig) -> Result<Vec<Event>> {
+    let url = cfg.url.as_deref().unwrap_or(DEFAULT_URL);
+    let response = client.get(url).send().await?.error_for_status()?;
+    let events = parse(&response.text().await?)?;
+    Ok(upcoming(events, cfg, OffsetDateTime::now_utc().unix_timestamp()))
+}
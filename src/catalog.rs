@@ -0,0 +1,57 @@
+//! A built-in catalog of curated feeds by category, for the Feed catalog screen: a first
+//! setup goes from the two built-in feeds to a useful list without hunting for feed URLs.
+//! Everything here is free to read and published as RSS or Atom.
+
+/// What a catalog feed is about; the screen shows one section each, in [`Category::ALL`] order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    World,
+    Tech,
+    Science,
+    Finance,
+}
+
+impl Category {
+    pub const ALL: [Category; 4] = [Category::World, Category::Tech, Category::Science, Category::Finance];
+}
+
+/// One curated feed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub name: &'static str,
+    pub url: &'static str,
+    pub category: Category,
+    /// One line on what it covers
+    pub about: &'static str,
+}
+
+const fn entry(category: Category, name: &'static str, url: &'static str, about: &'static str) -> Entry {
+    Entry { name, url, category, about }
+}
+
+/// The catalog, grouped by category.
+pub const CATALOG: &[Entry] = &[
+    entry(Category::World, "BBC World", "https://feeds.bbci.co.uk/news/world/rss.xml", "International news from the BBC"),
+    entry(Category::World, "The Guardian World", "https://www.theguardian.com/world/rss", "World news from The Guardian"),
+    entry(Category::World, "NPR News", "https://feeds.npr.org/1001/rss.xml", "US and world headlines from NPR"),
+    entry(Category::World, "Al Jazeera", "https://www.aljazeera.com/xml/rss/all.xml", "News with a focus on the Middle East and the Global South"),
+    entry(Category::World, "DW", "https://rss.dw.com/rdf/rss-en-all", "Deutsche Welle, Germany's international broadcaster"),
+    entry(Category::Tech, "HN Front", "https://hnrss.org/frontpage", "The Hacker News front page"),
+    entry(Category::Tech, "Lobsters", "https://lobste.rs/rss", "Computing links, discussed"),
+    entry(Category::Tech, "Ars Technica", "https://feeds.arstechnica.com/arstechnica/index", "Technology, science and policy"),
+    entry(Category::Tech, "The Verge", "https://www.theverge.com/rss/index.xml", "Consumer technology and culture"),
+    entry(Category::Tech, "LWN.net", "https://lwn.net/headlines/rss", "Linux and free software"),
+    entry(Category::Science, "Quanta Magazine", "https://api.quantamagazine.org/feed/", "Mathematics, physics, biology and computer science"),
+    entry(Category::Science, "ScienceDaily", "https://www.sciencedaily.com/rss/all.xml", "Research news across the sciences"),
+    entry(Category::Science, "NASA", "https://www.nasa.gov/news-release/feed/", "NASA news releases"),
+    entry(Category::Science, "New Scientist", "https://www.newscientist.com/feed/home/", "Science and technology news"),
+    entry(Category::Finance, "Federal Reserve", "https://www.federalreserve.gov/feeds/press_all.xml", "Press releases of the US central bank"),
+    entry(Category::Finance, "ECB", "https://www.ecb.europa.eu/rss/press.html", "Press releases of the European Central Bank"),
+    entry(Category::Finance, "MarketWatch", "https://feeds.content.dowjones.io/public/rss/mw_topstories", "Markets and business top stories"),
+    entry(Category::Finance, "CNBC", "https://search.cnbc.com/rs/search/combinedcms/view.xml?partnerId=wrss01&id=100003114", "Business and market headlines"),
+];
+
+/// The feeds of one category, in catalog order.
+pub fn in_category(category: Category) -> impl Iterator<Item = &'static Entry> {
+    CATALOG.iter().filter(move |e| e.category == category)
+}
@@ -0,0 +1,320 @@
+use anyhow::{bail, Result};
+use news_cli::config::overrides::{self, Override};
+use news_cli::config::remote::Verify;
+use news_cli::digest::DigestFormat;
+
+pub enum Command {
+    /// Default: the interactive menu UI.
+    Interactive,
+    /// Fetch all feeds once and write the stories to the selected sinks.
+    Fetch { to: Vec<String>, only_new: bool, mark_seen: bool },
+    /// Fetch repeatedly, writing only stories not seen before.
+    Watch { to: Vec<String>, interval_secs: Option<u64> },
+    /// Check every configured feed and print a health table.
+    Doctor,
+    /// Print an LLM briefing of the top stories (needs a [briefing] section).
+    Briefing,
+    /// Serve the merged story list over HTTP as feeds and an HTML page.
+    Serve { addr: Option<String> },
+    /// Write stories as Markdown or CSV to stdout.
+    Export { format: ExportFormat, since_secs: Option<u64>, only: Option<ExportOnly> },
+    /// Write or mail a digest of the stories since the last one.
+    Digest { format: Option<DigestFormat>, to: Vec<String>, out: Option<String> },
+    /// Validate a config file (default: the `--feeds` TOML or the standard location).
+    ConfigCheck { path: Option<String> },
+    /// Write the seen-story history in portable form (to stdout without a path).
+    HistoryExport { path: Option<String> },
+    /// Merge an exported history (`-` for stdin) into this one.
+    HistoryImport { path: String },
+    /// Add the sites in a plain or Markdown list of URLs (`-` for stdin) as feeds.
+    ImportUrls { path: String },
+    /// Fetch all feeds and keep the stories under a name.
+    SnapshotSave { name: String },
+    /// Browse a saved snapshot in the news list.
+    SnapshotView { name: String },
+    /// Print the saved snapshots.
+    SnapshotList,
+    /// Store a secret (read from the terminal or stdin) for `"secret:<name>"` config values.
+    SecretSet { name: String },
+    /// Delete a stored secret.
+    SecretRemove { name: String },
+    Help,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Csv,
+}
+
+/// Export saved state instead of a fresh fetch.
+#[derive(Clone, PartialEq, Eq)]
+pub enum ExportOnly {
+    Bookmarked,
+    Opened,
+    /// Stories carrying this tag (lowercased).
+    Tagged(String),
+}
+
+pub struct Cli {
+    pub feeds_override: Option<String>,
+    /// `--feeds-sha256` / `--feeds-key` for a remote `--feeds` config.
+    pub verify: Verify,
+    /// Hidden `--fixture-dir`: read the feeds from the files in a directory (for tests).
+    pub fixture_dir: Option<String>,
+    /// Hidden `--fixture-http`: fetch a remote `--feeds` config over plain http from this
+    /// machine (for tests' local servers).
+    pub fixture_http: bool,
+    /// `--set key=value`, in order.
+    pub overrides: Vec<Override>,
+    pub command: Command,
+}
+
+pub fn parse(args: impl Iterator<Item = String>) -> Result<Cli> {
+    let mut args = args.peekable();
+    let mut feeds_override: Option<String> = None;
+    let mut fixture_dir: Option<String> = None;
+    let mut fixture_http = false;
+    let mut subcommand: Option<String> = None;
+    let mut positionals: Vec<String> = Vec::new();
+    let mut to: Vec<String> = Vec::new();
+    let mut only_new = false;
+    let mut mark_seen = false;
+    let mut interval_secs: Option<u64> = None;
+    let mut addr: Option<String> = None;
+    let mut format: Option<String> = None;
+    let mut out: Option<String> = None;
+    let mut since_secs: Option<u64> = None;
+    let mut only: Option<ExportOnly> = None;
+    let mut sets: Vec<Override> = Vec::new();
+    let mut verify = Verify::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--feeds" => {
+                if let Some(p) = args.next() { feeds_override = Some(p); }
+            }
+            "--feeds-sha256" => match args.next() {
+                Some(v) => verify.sha256 = Some(v),
+                None => bail!("--feeds-sha256 requires a value"),
+            },
+            "--feeds-key" => match args.next() {
+                Some(v) => verify.key = Some(v),
+                None => bail!("--feeds-key requires a value"),
+            },
+            // Not in the help: deterministic feeds for end-to-end tests
+            "--fixture-dir" => match args.next() {
+                Some(v) => fixture_dir = Some(v),
+                None => bail!("--fixture-dir requires a value"),
+            },
+            "--fixture-http" => fixture_http = true,
+            "-h" | "--help" => {
+                return Ok(Cli { feeds_override, verify, fixture_dir, fixture_http, overrides: sets, command: Command::Help });
+            }
+            "--set" => match args.next() {
+                Some(v) => sets.push(
+                    overrides::parse_set(&v).ok_or_else(|| anyhow::anyhow!("invalid --set value: {} (expected key=value)", v))?,
+                ),
+                None => bail!("--set requires a value"),
+            },
+            // Same as --set data_saver=true
+            "--data-saver" => sets.push(Override {
+                key: "data_saver".into(),
+                value: "true".into(),
+                origin: "--data-saver".into(),
+            }),
+            "fetch" | "watch" | "doctor" | "serve" | "briefing" | "export" | "digest" | "config" | "history"
+            | "snapshot" | "secret" | "import-urls"
+                if subcommand.is_none() =>
+            {
+                subcommand = Some(arg)
+            }
+            "--to" => match args.next() {
+                // Accept both repeated flags and comma-separated lists
+                Some(v) => to.extend(v.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty())),
+                None => bail!("--to requires a value"),
+            },
+            "--new" => only_new = true,
+            "--mark-seen" => mark_seen = true,
+            "--interval" => {
+                let v = args.next().unwrap_or_default();
+                interval_secs = Some(
+                    v.parse()
+                        .map_err(|_| anyhow::anyhow!("invalid --interval value: {}", v))?,
+                );
+            }
+            "--addr" => match args.next() {
+                Some(v) => addr = Some(v),
+                None => bail!("--addr requires a value"),
+            },
+            // Checked below: export and digest take different formats
+            "--format" => match args.next() {
+                Some(v) => format = Some(v),
+                None => bail!("--format requires a value"),
+            },
+            "--out" => match args.next() {
+                Some(v) => out = Some(v),
+                None => bail!("--out requires a value"),
+            },
+            "--since" => {
+                let v = args.next().unwrap_or_default();
+                since_secs = Some(parse_age(&v).ok_or_else(|| anyhow::anyhow!("invalid --since value: {} (e.g. 7d, 12h, 2w)", v))?);
+            }
+            "--bookmarked" | "--opened" | "--tag" => {
+                let o = match arg.as_str() {
+                    "--bookmarked" => ExportOnly::Bookmarked,
+                    "--opened" => ExportOnly::Opened,
+                    _ => match args.next().map(|v| v.trim().to_lowercase()) {
+                        Some(v) if !v.is_empty() => ExportOnly::Tagged(v),
+                        _ => bail!("--tag requires a value"),
+                    },
+                };
+                if only.as_ref().is_some_and(|prev| *prev != o) {
+                    bail!("only one of --bookmarked, --opened and --tag can be given");
+                }
+                only = Some(o);
+            }
+            other if subcommand.is_some() && (!other.starts_with('-') || other == "-") => positionals.push(arg),
+            other => bail!("unknown argument: {}", other),
+        }
+    }
+
+    let command = match subcommand.as_deref() {
+        Some("fetch") => Command::Fetch { to, only_new, mark_seen },
+        Some("watch") => Command::Watch { to, interval_secs },
+        Some("doctor") => Command::Doctor,
+        Some("serve") => Command::Serve { addr },
+        Some("briefing") => Command::Briefing,
+        Some("export") => {
+            let format = match format.as_deref() {
+                None | Some("md" | "markdown") => ExportFormat::Markdown,
+                Some("csv") => ExportFormat::Csv,
+                Some(v) => bail!("invalid --format value: {} (expected md or csv)", v),
+            };
+            Command::Export { format, since_secs, only }
+        }
+        Some("digest") => {
+            let format = match format.as_deref() {
+                None => None,
+                Some(v) => Some(
+                    DigestFormat::parse(v)
+                        .ok_or_else(|| anyhow::anyhow!("invalid --format value: {} (expected md or html)", v))?,
+                ),
+            };
+            Command::Digest { format, to, out }
+        }
+        Some("config") => match positionals.first().map(|s| s.as_str()) {
+            Some("check") => Command::ConfigCheck {
+                path: positionals.get(1).cloned().or_else(|| feeds_override.clone()),
+            },
+            _ => bail!("usage: news-cli config check [path]"),
+        },
+        Some("history") => match (positionals.first().map(|s| s.as_str()), positionals.get(1)) {
+            (Some("export"), path) => Command::HistoryExport { path: path.cloned() },
+            (Some("import"), Some(path)) => Command::HistoryImport { path: path.clone() },
+            _ => bail!("usage: news-cli history export [path] | history import <path>"),
+        },
+        Some("import-urls") => match positionals.as_slice() {
+            [path] => Command::ImportUrls { path: path.clone() },
+            _ => bail!("usage: news-cli import-urls <path|->"),
+        },
+        Some("snapshot") => match (positionals.first().map(|s| s.as_str()), positionals.get(1)) {
+            (Some("save"), Some(name)) => Command::SnapshotSave { name: name.clone() },
+            (Some("view"), Some(name)) => Command::SnapshotView { name: name.clone() },
+            (Some("list"), None) => Command::SnapshotList,
+            _ => bail!("usage: news-cli snapshot save <name> | snapshot view <name> | snapshot list"),
+        },
+        Some("secret") => match (positionals.first().map(|s| s.as_str()), positionals.get(1)) {
+            (Some("set"), Some(name)) => Command::SecretSet { name: name.clone() },
+            (Some("remove"), Some(name)) => Command::SecretRemove { name: name.clone() },
+            _ => bail!("usage: news-cli secret set <name> | secret remove <name>"),
+        },
+        _ => Command::Interactive,
+    };
+    Ok(Cli { feeds_override, verify, fixture_dir, fixture_http, overrides: sets, command })
+}
+
+/// `30m`, `12h`, `7d` or `2w` in seconds; a bare number is days.
+fn parse_age(v: &str) -> Option<u64> {
+    let (num, unit) = match v.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => v.split_at(i),
+        None => (v, "d"),
+    };
+    let n: u64 = num.parse().ok()?;
+    let secs = match unit {
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86_400,
+        "w" => 7 * 86_400,
+        _ => return None,
+    };
+    n.checked_mul(secs)
+}
+
+pub fn print_help() {
+    println!("news-cli");
+    println!("Usage: news-cli [--feeds <path>] [--set <key>=<value>...] [--data-saver] [command]");
+    println!("  --feeds <path>         config.toml, OPML list or RSS/Atom file; or an https URL of a shared config");
+    println!("  --feeds-sha256 <hex>   Only use a remote --feeds file with this SHA-256");
+    println!("  --feeds-key <base64>   Only use a remote --feeds file signed by this Ed25519 key (URL + \".sig\")");
+    println!("  --set <k>=<v>          Override a config key for this run (also NEWS_CLI_<KEY>, '__' for '.')");
+    println!("  --data-saver           Use less data for this run (data_saver = true in config.toml)");
+    println!();
+    println!("Commands (default: interactive menu; press '?' on any screen for its keys):");
+    println!("  fetch [--new] [--mark-seen] [--to <sink>...]    Fetch once and write stories to sinks");
+    println!("  watch [--interval <secs>] [--to <sink>...]      Poll feeds, writing only unseen stories");
+    println!("  doctor                                          Check feed health (DNS/TLS/HTTP, parse, freshness) and data use");
+    println!("  serve [--addr <host:port>]                      Serve the merged stories as RSS/Atom/JSON Feed + HTML");
+    println!("  briefing                                        Print an LLM briefing of the top stories");
+    println!("  export [--format md|csv] [--since <age>] [--bookmarked | --opened | --tag <tag>]");
+    println!("                                                  Write stories to stdout (age: 12h, 7d, 2w)");
+    println!("  digest [--format md|html] [--out <path>] [--to <sink>...]");
+    println!("                                                  Write or mail the stories new since the last digest");
+    println!("  config check [path]                             Validate config.toml and report problems");
+    println!("  history export [path]                           Write the seen-story history as portable JSON");
+    println!("  history import <path|->                         Merge an exported history into this one");
+    println!("  import-urls <path|->                            Add the sites in a list of URLs as feeds");
+    println!("  snapshot save <name>                            Fetch all feeds and keep the stories as <name>");
+    println!("  snapshot view <name>                            Browse a saved snapshot in the news list");
+    println!("  snapshot list                                   List saved snapshots");
+    println!("  secret set <name>                               Store a secret (typed, or a line on stdin)");
+    println!("  secret remove <name>                            Delete a stored secret");
+    println!();
+    println!("Sinks (repeat --to or separate with commas; default: plain):");
+    println!("  plain            Human-readable lines on stdout");
+    println!("  json             One JSON object per story on stdout");
+    println!("  file:<path>      Append JSON lines to a file");
+    println!("  maildir:<dir>    Deliver one message per story into a Maildir");
+    println!("  webhook:<url>    POST each batch as a JSON array");
+    println!("  notify           Desktop notification via notify-send");
+    println!();
+    println!("Feed kinds ([[feeds]] kind = ..., default: rss):");
+    println!("  rss              RSS/Atom from url (http(s) URL or local file)");
+    println!("  command          Run command; it prints stories as JSON (array or one object per line)");
+    println!("  mastodon         Public posts of account (user or #tag) on instance");
+    println!("  bluesky          Public posts of account (handle, DID or at:// list URI)");
+    println!("  youtube          Uploads of account (channel ID or @handle); videos play in mpv or player");
+    println!("  github           Releases of account (owner/repo, ...), or \"notifications\" with token");
+    println!("  imap             Unread mail in folder (default INBOX) on instance (host[:port], TLS)");
+    println!();
+    println!("Files (override with NEWS_CLI_CONFIG_DIR / NEWS_CLI_STATE_DIR / NEWS_CLI_CACHE_DIR):");
+    println!("  config.toml         $XDG_CONFIG_HOME/news-cli (~/.config/news-cli)");
+    println!("  secrets.age         $XDG_CONFIG_HOME/news-cli (~/.config/news-cli)");
+    println!("  seen_stories.json   $XDG_STATE_HOME/news-cli (~/.local/state/news-cli)");
+    println!("  alerts.json         $XDG_STATE_HOME/news-cli (~/.local/state/news-cli)");
+    println!("  reading_log.jsonl   $XDG_STATE_HOME/news-cli (~/.local/state/news-cli)");
+    println!("  session.json        $XDG_STATE_HOME/news-cli (~/.local/state/news-cli)");
+    println!("  collapsed.json      $XDG_STATE_HOME/news-cli (~/.local/state/news-cli)");
+    println!("  digest.json         $XDG_STATE_HOME/news-cli (~/.local/state/news-cli)");
+    println!("  feeds.json          $XDG_STATE_HOME/news-cli (~/.local/state/news-cli)");
+    println!("  tags.json           $XDG_STATE_HOME/news-cli (~/.local/state/news-cli)");
+    println!("  queue.json          $XDG_STATE_HOME/news-cli (~/.local/state/news-cli)");
+    println!("  muted.json          $XDG_STATE_HOME/news-cli (~/.local/state/news-cli)");
+    println!("  snapshots/          $XDG_STATE_HOME/news-cli (~/.local/state/news-cli)");
+    println!("  stories.json        $XDG_CACHE_HOME/news-cli (~/.cache/news-cli)");
+    println!("  stats.json          $XDG_CACHE_HOME/news-cli (~/.cache/news-cli)");
+    println!("  translations.json   $XDG_CACHE_HOME/news-cli (~/.cache/news-cli)");
+    println!("  feeds/              $XDG_CACHE_HOME/news-cli (~/.cache/news-cli)");
+    println!();
+    println!("The config.toml sections and the keys of each screen are described in README.md.");
+}
@@ -0,0 +1,49 @@
+use crate::util::atomic::write_atomic;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+/// Sources folded in the news list (left arrow or 'z' on their section), so only their
+/// header shows; persisted as `collapsed.json` in the state directory.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CollapsedSources {
+    sources: Vec<String>,
+}
+
+impl CollapsedSources {
+    pub fn load() -> Self {
+        if let Some(path) = collapsed_file_path()
+            && let Ok(contents) = fs::read_to_string(&path)
+            && let Ok(c) = serde_json::from_str::<CollapsedSources>(&contents)
+        {
+            return c;
+        }
+        CollapsedSources::default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = collapsed_file_path() {
+            let json = serde_json::to_string_pretty(self)?;
+            write_atomic(&path, json.as_bytes(), true)?;
+        }
+        Ok(())
+    }
+
+    pub fn contains(&self, source: &str) -> bool {
+        self.sources.iter().any(|s| s == source)
+    }
+
+    /// Fold `source`, or unfold it if it was; returns whether it is folded now.
+    pub fn toggle(&mut self, source: &str) -> bool {
+        if let Some(i) = self.sources.iter().position(|s| s == source) {
+            self.sources.remove(i);
+            return false;
+        }
+        self.sources.push(source.to_string());
+        true
+    }
+}
+
+fn collapsed_file_path() -> Option<PathBuf> {
+    crate::paths::state_dir().map(|d| d.join("collapsed.json"))
+}
@@ -0,0 +1,23 @@
+use news_cli::briefing;
+use news_cli::config::RuntimeConfig;
+use news_cli::history::SeenStories;
+use news_cli::news;
+use news_cli::tr;
+use anyhow::{bail, Result};
+
+/// Print a briefing of the current top stories, with the stories listed as sources.
+pub async fn run(cfg: &RuntimeConfig, history: &SeenStories) -> Result<()> {
+    let Some(bcfg) = &cfg.briefing else {
+        bail!("briefings are off; add a [briefing] section to config.toml (see --help)");
+    };
+    let stories = briefing::select(bcfg, &news::collect_stories(cfg, history).await?);
+    let text = briefing::generate(bcfg, &cfg.fetch, &stories).await?;
+    println!("{}", text);
+    println!();
+    println!("{}", tr!("briefing.sources"));
+    for (i, s) in stories.iter().enumerate() {
+        println!("{:>3}. [{}] {}", i + 1, s.source, s.title);
+        println!("     {}", s.link);
+    }
+    Ok(())
+}
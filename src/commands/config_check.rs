@@ -0,0 +1,36 @@
+use news_cli::config::{self, Severity};
+use news_cli::tr;
+use anyhow::{bail, Result};
+use console::style;
+use std::path::PathBuf;
+
+/// `news-cli config check [path]`: report parse errors and validation issues for a config file.
+pub fn run(path: Option<PathBuf>) -> Result<()> {
+    let Some(path) = path.or_else(config::default_config_path) else {
+        bail!("no config path given and neither XDG_CONFIG_HOME nor HOME is set");
+    };
+    if !path.is_file() {
+        println!("{}", tr!("config.check_missing", path.display()));
+        return Ok(());
+    }
+
+    let (cfg, source) = config::parse_file(&path)?;
+    let issues = config::validate(&cfg, Some(&source));
+    for issue in &issues {
+        let line = issue.to_string();
+        match issue.severity {
+            Severity::Error => println!("{}", style(line).red()),
+            Severity::Warning => println!("{}", style(line).yellow()),
+        }
+    }
+
+    let errors = issues.iter().filter(|i| i.severity == Severity::Error).count();
+    if errors > 0 {
+        bail!("{}: {} error(s), {} warning(s)", path.display(), errors, issues.len() - errors);
+    }
+    println!("{}", tr!("config.check_ok", path.display(), cfg.feeds.len(), issues.len()));
+    for file in config::include::files(&path, &cfg.include)? {
+        println!("{}", tr!("config.check_includes", file.display()));
+    }
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use super::export::{md_link, md_text};
+use super::serve::escape;
+use anyhow::{bail, Result};
+use news_cli::config::RuntimeConfig;
+use news_cli::digest::{DigestFormat, DigestLog};
+use news_cli::history::SeenStories;
+use news_cli::news::{self, Story};
+use news_cli::sink::{Document, MailSink, SinkSet};
+use news_cli::tr;
+use time::OffsetDateTime;
+
+/// Fetch, keep the stories no earlier digest had and write them grouped by source to the
+/// `to` sinks, `out` (else `[digest] path`, `-` for stdout) and/or mail them to
+/// `[digest] to`. Without any of these they go to stdout. Nothing new means no digest and
+/// no mail. History is not updated.
+pub async fn run(
+    cfg: &RuntimeConfig,
+    history: &SeenStories,
+    format: Option<DigestFormat>,
+    to: &[String],
+    out: Option<String>,
+) -> Result<()> {
+    let dcfg = &cfg.digest;
+    let format = format
+        .or_else(|| dcfg.format.as_deref().and_then(DigestFormat::parse))
+        .unwrap_or(DigestFormat::Markdown);
+    let mut specs = to.to_vec();
+    match out.or_else(|| dcfg.path.clone()).as_deref() {
+        Some("-") => specs.push("plain".to_string()),
+        Some(path) => specs.push(format!("file:{}", path)),
+        None => {}
+    }
+    let mail = !dcfg.to.is_empty();
+    // Mail alone replaces the default of printing
+    let mut sinks = if specs.is_empty() && mail { SinkSet::default() } else { SinkSet::from_specs(&specs)? };
+    if mail {
+        sinks.push(Box::new(MailSink::new(dcfg)));
+    }
+
+    let stories = news::collect_stories(cfg, history).await?;
+    super::check_alerts(&cfg.alerts, &stories);
+    let mut log = DigestLog::load();
+    let fresh = log.unsent(stories);
+    if fresh.is_empty() {
+        eprintln!("{}", tr!("digest.nothing_new"));
+        return Ok(());
+    }
+
+    let now = OffsetDateTime::now_utc();
+    let day = cfg.dates.format_as(now.unix_timestamp(), "%Y-%m-%d").unwrap_or_default();
+    let title = format!("{} {}", dcfg.subject(), day);
+    let groups = by_source(cfg, &fresh);
+    let body = match format {
+        DigestFormat::Markdown => markdown(cfg, &title, &groups),
+        DigestFormat::Html => html(cfg, &title, &groups),
+    };
+    let doc = Document { title, body, format, stories: fresh.len() };
+    let failed = sinks.emit_document(&doc).await;
+    if !failed.is_empty() {
+        bail!("{}", failed.join("\n"));
+    }
+    // Only once delivered, so a failed run is retried in full next time
+    log.record(&fresh, now.unix_timestamp());
+    log.save()
+}
+
+// Feeds in config order (then any others by name), each newest first
+fn by_source<'a>(cfg: &RuntimeConfig, stories: &'a [Story]) -> Vec<(&'a str, Vec<&'a Story>)> {
+    let mut sources: Vec<&str> = stories.iter().map(|s| s.source.as_str()).collect();
+    sources.sort_by_key(|name| (cfg.feeds.iter().position(|f| f.name == *name).unwrap_or(usize::MAX), *name));
+    sources.dedup();
+    sources
+        .into_iter()
+        .map(|name| {
+            let mut group: Vec<&Story> = stories.iter().filter(|s| s.source == name).collect();
+            group.sort_by_key(|s| std::cmp::Reverse(s.published));
+            (name, group)
+        })
+        .collect()
+}
+
+fn markdown(cfg: &RuntimeConfig, title: &str, groups: &[(&str, Vec<&Story>)]) -> String {
+    let mut out = format!("# {}\n", md_text(title));
+    for (source, stories) in groups {
+        out.push_str(&format!("\n## {}\n\n", md_text(source)));
+        for s in stories {
+            out.push_str(&format!("- [{}]({})", md_text(&s.title), md_link(&s.link)));
+            if let Some(when) = s.published.and_then(|ts| cfg.dates.format(ts)) {
+                out.push_str(&format!(" ({})", when));
+            }
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn html(cfg: &RuntimeConfig, title: &str, groups: &[(&str, Vec<&Story>)]) -> String {
+    let mut out = format!(
+        "<!doctype html>\n<html><head><meta charset=\"utf-8\"><title>{0}</title>\
+         <style>body{{font-family:sans-serif;max-width:48em;margin:auto;padding:0 1em}}li{{margin:.4em 0}}small{{color:#666}}</style>\
+         </head><body>\n<h1>{0}</h1>\n",
+        escape(title)
+    );
+    for (source, stories) in groups {
+        out.push_str(&format!("<h2>{}</h2>\n<ul>\n", escape(source)));
+        for s in stories {
+            let when = s.published.and_then(|ts| cfg.dates.format(ts)).unwrap_or_default();
+            out.push_str(&format!(
+                "<li><a href=\"{}\">{}</a> <small>{}</small></li>\n",
+                escape(&s.link),
+                escape(&s.title),
+                escape(&when)
+            ));
+        }
+        out.push_str("</ul>\n");
+    }
+    out.push_str("</body></html>\n");
+    out
+}
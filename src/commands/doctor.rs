@@ -0,0 +1,377 @@
+use news_cli::config::edit::ConfigFile;
+use news_cli::config::{Feed, RuntimeConfig};
+use news_cli::news::provider::{FetchContext, ProviderRegistry};
+use news_cli::news::{discover, read_body_capped};
+use news_cli::news::status::{self, FeedStatus};
+use news_cli::tr;
+use news_cli::util::sanitize::sanitize_for_terminal;
+use anyhow::Result;
+use console::{style, Term};
+use feed_rs::parser;
+use futures_util::future::join_all;
+use reqwest::{header, StatusCode};
+use std::error::Error as _;
+use std::collections::BTreeMap;
+use std::{fs, path::Path, time::Instant};
+use url::Url;
+
+/// One row of the health table.
+struct Health {
+    name: String,
+    status: String,
+    ok: bool,
+    millis: Option<u128>,
+    entries: Option<usize>,
+    latest: Option<i64>,
+    conditional: &'static str,
+    note: String,
+    /// The feed a web page configured as the feed names, to offer in its place
+    discovered: Option<Url>,
+}
+
+impl Health {
+    fn new(feed: &Feed) -> Self {
+        Health {
+            name: sanitize_for_terminal(&feed.name),
+            status: String::new(),
+            ok: false,
+            millis: None,
+            entries: None,
+            latest: None,
+            conditional: "-",
+            note: String::new(),
+            discovered: None,
+        }
+    }
+}
+
+/// Check every configured feed and print a table, so dead or slow feeds are easy to spot.
+pub async fn run(cfg: &RuntimeConfig) -> Result<()> {
+    let ctx = &FetchContext::new(cfg.fetch.clone())?.with_data_saver(cfg.data_saver);
+    let providers = &ProviderRegistry::default();
+    let checks = cfg.feeds.iter().map(|f| async move {
+        // With the feed's own timeouts, user agent and size cap
+        let ctx = match ctx.for_feed(f) {
+            Ok(ctx) => ctx,
+            Err(e) => {
+                let mut h = Health::new(f);
+                h.note = format!("{:#}", e);
+                return h;
+            }
+        };
+        // Strict mode keeps the probes off local files and addresses, as in a refresh
+        if let Err(e) = ctx.check_strict(f) {
+            let mut h = Health::new(f);
+            h.note = format!("{:#}", e);
+            return h;
+        }
+        match f.kind() {
+            "rss" => check_feed(&ctx, f).await,
+            _ => check_provider(&ctx, providers, f).await,
+        }
+    });
+    let rows = join_all(checks).await;
+    print_table(cfg, &rows);
+    let failing = rows.iter().filter(|r| !r.ok).count();
+    println!();
+    if failing == 0 {
+        println!("{}", tr!("doctor.healthy", rows.len()));
+    } else {
+        println!("{}", tr!("doctor.problems", failing, rows.len()));
+    }
+    print_transfers(cfg, &status::load());
+    offer_discovered(cfg, &rows)
+}
+
+// Feeds configured as a web page that names its feed: on a terminal, offer to put that feed
+// in the config instead; otherwise say what to change
+fn offer_discovered(cfg: &RuntimeConfig, rows: &[Health]) -> Result<()> {
+    let found: Vec<(&Feed, &Url)> =
+        cfg.feeds.iter().zip(rows).filter_map(|(f, h)| Some((f, h.discovered.as_ref()?))).collect();
+    if found.is_empty() {
+        return Ok(());
+    }
+    println!();
+    let Some(path) = cfg.path.as_deref().filter(|_| Term::stdout().is_term() && Term::stderr().is_term()) else {
+        for (feed, url) in found {
+            println!("{}", tr!("doctor.set_url", url, sanitize_for_terminal(&feed.name)));
+        }
+        return Ok(());
+    };
+    let mut file = ConfigFile::open(path)?;
+    let mut changed = false;
+    for (feed, url) in found {
+        let prompt = tr!("doctor.use_feed", url, sanitize_for_terminal(&feed.name));
+        if dialoguer::Confirm::new().with_prompt(prompt).default(true).interact()? {
+            changed |= file.set_feed_url(&feed.name, &feed.url, url.as_str());
+        }
+    }
+    if changed {
+        file.save()?;
+        println!("{}", tr!("doctor.updated", path.display()));
+    }
+    Ok(())
+}
+
+// Other kinds have no URL to probe; run the provider and report what it returned
+async fn check_provider(ctx: &FetchContext, providers: &ProviderRegistry, feed: &Feed) -> Health {
+    let mut h = Health::new(feed);
+    h.status = feed.kind().to_string();
+    if providers.get(feed.kind()).is_none() {
+        h.note = "unknown feed kind".into();
+        return h;
+    }
+    let started = Instant::now();
+    match providers.fetch(ctx, feed).await {
+        Ok(stories) => {
+            h.ok = true;
+            h.millis = Some(started.elapsed().as_millis());
+            h.entries = Some(stories.len());
+            h.latest = stories.iter().filter_map(|s| s.published).max();
+            if stories.is_empty() {
+                h.note = "no entries".into();
+            }
+        }
+        Err(e) => h.note = format!("{:#}", e),
+    }
+    h
+}
+
+async fn check_feed(ctx: &FetchContext, feed: &Feed) -> Health {
+    let mut h = Health::new(feed);
+    if Path::new(&feed.url).is_file() {
+        let started = Instant::now();
+        match fs::read(&feed.url) {
+            Ok(bytes) => {
+                h.millis = Some(started.elapsed().as_millis());
+                h.status = "file".into();
+                inspect_body(&mut h, &bytes, None, None);
+            }
+            Err(e) => {
+                h.status = "file error".into();
+                h.note = e.to_string();
+            }
+        }
+        return h;
+    }
+
+    let url = match Url::parse(&feed.url) {
+        Ok(u) => u,
+        Err(e) => {
+            h.status = "bad URL".into();
+            h.note = e.to_string();
+            return h;
+        }
+    };
+
+    // DNS first, so a dead domain reads as such rather than a generic connect error
+    if let Some(host) = url.host_str() {
+        let port = url.port_or_known_default().unwrap_or(443);
+        if let Err(e) = tokio::net::lookup_host((host, port)).await {
+            h.status = "DNS error".into();
+            h.note = e.to_string();
+            return h;
+        }
+    }
+
+    // Queue behind other feeds on the same host before timing the request
+    ctx.wait_turn(&url).await;
+    let started = Instant::now();
+    let resp = match ctx.client.get(url.clone()).send().await {
+        Ok(r) => r,
+        Err(e) => {
+            h.status = classify_error(&e).into();
+            h.note = error_chain(&e);
+            return h;
+        }
+    };
+    let status = resp.status();
+    h.status = format!("HTTP {}", status.as_u16());
+    let content_type = resp.headers().get(header::CONTENT_TYPE).and_then(|v| v.to_str().ok()).map(String::from);
+    let etag = resp.headers().get(header::ETAG).cloned();
+    let last_modified = resp.headers().get(header::LAST_MODIFIED).cloned();
+    let body = read_body_capped(resp, ctx.max_bytes()).await;
+    h.millis = Some(started.elapsed().as_millis());
+    if !status.is_success() {
+        h.note = status.canonical_reason().unwrap_or("").to_string();
+        return h;
+    }
+    match body {
+        Ok(bytes) => inspect_body(&mut h, &bytes, content_type.as_deref(), Some(&url)),
+        Err(e) => {
+            h.note = e.to_string();
+            return h;
+        }
+    }
+
+    // Conditional requests: replay the validators and see whether the server answers 304
+    h.conditional = if etag.is_none() && last_modified.is_none() {
+        "no"
+    } else {
+        let mut req = ctx.client.get(url);
+        if let Some(v) = etag {
+            req = req.header(header::IF_NONE_MATCH, v);
+        }
+        if let Some(v) = last_modified {
+            req = req.header(header::IF_MODIFIED_SINCE, v);
+        }
+        match ctx.send(req).await {
+            Ok(r) if r.status() == StatusCode::NOT_MODIFIED => "yes",
+            Ok(_) => "ignored",
+            Err(_) => "?",
+        }
+    };
+    h
+}
+
+fn inspect_body(h: &mut Health, bytes: &[u8], content_type: Option<&str>, base: Option<&Url>) {
+    match parser::parse(bytes) {
+        Ok(feed) => {
+            h.ok = true;
+            h.entries = Some(feed.entries.len());
+            h.latest = feed
+                .entries
+                .iter()
+                .filter_map(|e| e.published.or(e.updated).map(|d| d.timestamp()))
+                .max();
+            if feed.entries.is_empty() {
+                h.note = "no entries".into();
+            }
+        }
+        Err(_) if discover::looks_like_html(content_type, bytes) => {
+            h.ok = false;
+            h.status = "web page".into();
+            h.note = discover::not_a_feed(bytes, base);
+            h.discovered = base.and_then(|b| discover::feed_links(&String::from_utf8_lossy(bytes), b).into_iter().next());
+        }
+        Err(e) => {
+            h.ok = false;
+            h.note = format!("parse error: {}", e);
+        }
+    }
+}
+
+fn classify_error(e: &reqwest::Error) -> &'static str {
+    if e.is_timeout() {
+        return "timeout";
+    }
+    let chain = error_chain(e).to_ascii_lowercase();
+    if chain.contains("certificate") || chain.contains("tls") || chain.contains("handshake") {
+        "TLS error"
+    } else if chain.contains("dns") || chain.contains("resolve") {
+        "DNS error"
+    } else if e.is_connect() {
+        "connect error"
+    } else if e.is_redirect() {
+        "redirect loop"
+    } else {
+        "HTTP error"
+    }
+}
+
+// reqwest's top-level message is vague ("error sending request"); the cause is in the source chain
+fn error_chain(e: &reqwest::Error) -> String {
+    let mut parts = vec![e.to_string()];
+    let mut source = e.source();
+    while let Some(s) = source {
+        parts.push(s.to_string());
+        source = s.source();
+    }
+    parts.join(": ")
+}
+
+// What the last fetch of each feed downloaded (from feeds.json), so heavy feeds stand out
+fn print_transfers(cfg: &RuntimeConfig, statuses: &BTreeMap<String, FeedStatus>) {
+    let rows: Vec<[String; 7]> = cfg
+        .feeds
+        .iter()
+        .filter_map(|f| {
+            let status = statuses.get(&f.name)?;
+            let t = status.last_transfer.as_ref()?;
+            Some([
+                sanitize_for_terminal(&f.name),
+                t.version.clone().unwrap_or_else(|| "-".into()),
+                t.requests.to_string(),
+                format_bytes(t.bytes),
+                format!("{} ms", t.millis),
+                t.cache_hits.to_string(),
+                format_bytes(status.total_bytes),
+            ])
+        })
+        .collect();
+    if rows.is_empty() {
+        return;
+    }
+    println!();
+    println!("{}", style(tr!("doctor.last_fetch")).bold());
+    let headers: Vec<String> = tr!("doctor.transfer_columns").split_whitespace().map(String::from).collect();
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in &rows {
+        for (i, c) in row.iter().enumerate() {
+            widths[i] = widths[i].max(c.chars().count());
+        }
+    }
+    let line = |cells: &[String]| {
+        let padded: Vec<String> = cells.iter().zip(&widths).map(|(c, w)| format!("{:<w$}", c, w = *w)).collect();
+        padded.join("  ").trim_end().to_string()
+    };
+    println!("{}", style(line(&headers)).bold());
+    for row in &rows {
+        println!("{}", line(row));
+    }
+    let total: u64 = statuses.values().map(|s| s.total_bytes).sum();
+    println!();
+    println!("{}", tr!("doctor.downloaded", format_bytes(total)));
+}
+
+fn format_bytes(n: u64) -> String {
+    match n {
+        0..1024 => format!("{} B", n),
+        1024..1_048_576 => format!("{:.1} KB", n as f64 / 1024.0),
+        _ => format!("{:.1} MB", n as f64 / 1_048_576.0),
+    }
+}
+
+fn print_table(cfg: &RuntimeConfig, rows: &[Health]) {
+    let cells: Vec<[String; 7]> = rows
+        .iter()
+        .map(|r| {
+            [
+                r.name.clone(),
+                r.status.clone(),
+                r.millis.map(|m| format!("{} ms", m)).unwrap_or_else(|| "-".into()),
+                r.entries.map(|n| n.to_string()).unwrap_or_else(|| "-".into()),
+                r.latest.and_then(|ts| cfg.dates.format_as(ts, "%Y-%m-%d")).unwrap_or_else(|| "-".into()),
+                r.conditional.to_string(),
+                sanitize_for_terminal(&r.note),
+            ]
+        })
+        .collect();
+    let headers: Vec<&str> = tr!("doctor.columns").split_whitespace().collect();
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in &cells {
+        for (i, c) in row.iter().enumerate() {
+            widths[i] = widths[i].max(c.chars().count());
+        }
+    }
+
+    let header_line: Vec<String> = headers
+        .iter()
+        .zip(&widths)
+        .map(|(h, w)| format!("{:<w$}", h, w = *w))
+        .collect();
+    println!("{}", style(header_line.join("  ").trim_end()).bold());
+    for (row, health) in cells.iter().zip(rows) {
+        let mut line: Vec<String> = Vec::new();
+        for (i, c) in row.iter().enumerate() {
+            let padded = format!("{:<w$}", c, w = widths[i]);
+            // Color only the status column; padding is applied first so alignment survives
+            line.push(if i == 1 {
+                if health.ok { style(padded).green().to_string() } else { style(padded).red().to_string() }
+            } else {
+                padded
+            });
+        }
+        println!("{}", line.join("  ").trim_end());
+    }
+}
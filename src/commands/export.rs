@@ -0,0 +1,164 @@
+use crate::cli::{ExportFormat, ExportOnly};
+use anyhow::Result;
+use news_cli::bookmarks::Bookmarks;
+use news_cli::config::RuntimeConfig;
+use news_cli::history::SeenStories;
+use news_cli::news;
+use news_cli::reading;
+use news_cli::tags::Tags;
+use std::collections::HashSet;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+/// One exported line.
+struct Row {
+    title: String,
+    link: String,
+    source: String,
+    /// Published time for fetched, bookmarked and tagged stories, open time for opened ones
+    date: Option<i64>,
+    /// The user's note on the story ('n' in the detail view)
+    note: Option<String>,
+}
+
+/// Print stories as Markdown (grouped by day, newest first) or CSV. Without `only` the feeds
+/// are fetched fresh and `since` applies to the publish date; bookmarks filter by when they
+/// were saved, tagged stories by when they were tagged and opens by when they were opened.
+/// Notes on the stories come along. History is not updated.
+pub async fn run(
+    cfg: &RuntimeConfig,
+    history: &SeenStories,
+    format: ExportFormat,
+    since_secs: Option<u64>,
+    only: Option<ExportOnly>,
+) -> Result<()> {
+    let cutoff = since_secs.map(|s| OffsetDateTime::now_utc().unix_timestamp() - s as i64);
+    let in_range = |ts: Option<i64>| match cutoff {
+        Some(c) => ts.is_some_and(|t| t >= c),
+        None => true,
+    };
+    let mut rows: Vec<Row> = match only {
+        None => {
+            let stories = news::collect_stories(cfg, history).await?;
+            super::check_alerts(&cfg.alerts, &stories);
+            stories
+                .into_iter()
+                .filter(|s| in_range(s.published))
+                .map(|s| Row { title: s.title, link: s.link, source: s.source, date: s.published, note: None })
+                .collect()
+        }
+        Some(ExportOnly::Bookmarked) => Bookmarks::load()
+            .items()
+            .iter()
+            .filter(|b| in_range(Some(b.saved_at)))
+            .map(|b| Row {
+                title: b.story.title.clone(),
+                link: b.story.link.clone(),
+                source: b.story.source.clone(),
+                date: b.story.published,
+                note: None,
+            })
+            .collect(),
+        Some(ExportOnly::Tagged(tag)) => Tags::load()
+            .tagged(&tag)
+            .into_iter()
+            .filter(|t| in_range(Some(t.tagged_at)))
+            .map(|t| Row {
+                title: t.story.title.clone(),
+                link: t.story.link.clone(),
+                source: t.story.source.clone(),
+                date: t.story.published,
+                note: None,
+            })
+            .collect(),
+        Some(ExportOnly::Opened) => {
+            // Latest open of each story
+            let mut seen = HashSet::new();
+            let mut events = reading::load();
+            events.reverse();
+            events
+                .into_iter()
+                .filter(|e| in_range(Some(e.at)) && seen.insert(e.link.clone()))
+                .map(|e| Row { title: e.title, link: e.link, source: e.source, date: Some(e.at), note: None })
+                .collect()
+        }
+    };
+    let tags = Tags::load();
+    for r in &mut rows {
+        r.note = tags.note(&r.link).map(str::to_string);
+    }
+    rows.sort_by_key(|r| std::cmp::Reverse(r.date));
+    let out = match format {
+        ExportFormat::Markdown => markdown(cfg, &rows),
+        ExportFormat::Csv => csv(&rows),
+    };
+    print!("{}", out);
+    Ok(())
+}
+
+fn markdown(cfg: &RuntimeConfig, rows: &[Row]) -> String {
+    let mut out = String::new();
+    let mut current: Option<String> = None;
+    for r in rows {
+        let heading = r
+            .date
+            .and_then(|t| cfg.dates.format_as(t, "%Y-%m-%d"))
+            .unwrap_or_else(|| "Undated".to_string());
+        if current.as_deref() != Some(&heading) {
+            if current.is_some() {
+                out.push('\n');
+            }
+            out.push_str(&format!("## {}\n\n", heading));
+            current = Some(heading);
+        }
+        out.push_str(&format!("- [{}]({}) ({})\n", md_text(&r.title), md_link(&r.link), md_text(&r.source)));
+        for line in r.note.iter().flat_map(|n| n.lines()) {
+            out.push_str(&format!("  > {}\n", md_text(line)));
+        }
+    }
+    out
+}
+
+// Keep titles from breaking out of the link text
+pub(super) fn md_text(s: &str) -> String {
+    let mut out = String::new();
+    for c in s.chars() {
+        if matches!(c, '\\' | '[' | ']' | '*' | '_' | '`' | '<' | '>') {
+            out.push('\\');
+        }
+        out.push(if c == '\n' { ' ' } else { c });
+    }
+    out
+}
+
+pub(super) fn md_link(link: &str) -> String {
+    link.replace('(', "%28").replace(')', "%29").replace(' ', "%20")
+}
+
+fn csv(rows: &[Row]) -> String {
+    let mut out = String::from("title,link,source,date,note\r\n");
+    for r in rows {
+        let date = r
+            .date
+            .and_then(|t| OffsetDateTime::from_unix_timestamp(t).ok())
+            .and_then(|d| d.format(&Rfc3339).ok())
+            .unwrap_or_default();
+        let note = r.note.as_deref().unwrap_or_default();
+        let fields = [r.title.as_str(), r.link.as_str(), r.source.as_str(), date.as_str(), note];
+        let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&line.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// RFC 4180: quote fields containing a separator, quote or line break; double inner quotes.
+/// Fields starting with a formula character are prefixed with `'` so spreadsheets don't run them.
+fn csv_field(f: &str) -> String {
+    let f = if f.starts_with(['=', '+', '-', '@']) { format!("'{}", f) } else { f.to_string() };
+    if f.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", f.replace('"', "\"\""))
+    } else {
+        f
+    }
+}
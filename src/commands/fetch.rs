@@ -0,0 +1,29 @@
+use news_cli::config::RuntimeConfig;
+use news_cli::history::SeenStories;
+use news_cli::news;
+use news_cli::sink::SinkSet;
+use anyhow::Result;
+
+pub async fn run(
+    cfg: &RuntimeConfig,
+    history: &mut SeenStories,
+    to: &[String],
+    only_new: bool,
+    mark_seen: bool,
+) -> Result<()> {
+    let mut sinks = SinkSet::from_specs(to)?;
+    let mut stories = news::collect_stories(cfg, history).await?;
+    super::check_alerts(&cfg.alerts, &stories);
+    if only_new {
+        stories.retain(|s| s.is_new);
+    }
+    super::report(sinks.emit(&stories).await);
+
+    if mark_seen {
+        for s in &stories {
+            history.mark_as_seen(&s.link);
+        }
+        history.save()?;
+    }
+    Ok(())
+}
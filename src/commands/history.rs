@@ -0,0 +1,39 @@
+use anyhow::{Context, Result};
+use news_cli::history::SeenStories;
+use news_cli::tr;
+use news_cli::util::atomic::write_atomic;
+use std::io::Read;
+use std::path::Path;
+
+/// `news-cli history export [path]`: the seen-story history as portable JSON, to stdout
+/// without a path.
+pub fn export(path: Option<&str>) -> Result<()> {
+    let history = SeenStories::load();
+    let json = serde_json::to_string_pretty(&history.export())?;
+    match path {
+        None | Some("-") => println!("{}", json),
+        Some(p) => {
+            write_atomic(Path::new(p), json.as_bytes(), false).with_context(|| format!("failed to write {}", p))?;
+            println!("{}", tr!("history.exported", history.len(), p));
+        }
+    }
+    Ok(())
+}
+
+/// `news-cli history import <path>`: merge an export from another machine (or `-` for
+/// stdin) into the local history.
+pub fn import(path: &str) -> Result<()> {
+    let json = if path == "-" {
+        let mut s = String::new();
+        std::io::stdin().read_to_string(&mut s)?;
+        s
+    } else {
+        std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path))?
+    };
+    let imported = SeenStories::from_export(&json).with_context(|| format!("failed to import {}", path))?;
+    let mut history = SeenStories::load();
+    let added = history.merge(&imported);
+    history.save()?;
+    println!("{}", tr!("history.imported", imported.len(), added, history.len()));
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use anyhow::{bail, Context, Result};
+use feed_rs::parser;
+use futures_util::future::join_all;
+use news_cli::config::edit::ConfigFile;
+use news_cli::config::{self, RuntimeConfig};
+use news_cli::news::discover;
+use news_cli::news::provider::FetchContext;
+use news_cli::tr;
+use news_cli::util::sanitize::{html_to_text, sanitize_for_terminal};
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::PathBuf;
+use url::Url;
+
+/// What a listed URL turned out to be.
+enum Probed {
+    /// A feed, at the URL itself or named by the page there, and its title
+    Feed(Url, Option<String>),
+    /// No feed to be found there, and why
+    Nothing(String),
+}
+
+/// `news-cli import-urls <path|->`: add the sites in a plain or Markdown list (see
+/// [`discover::listed_urls`]) to config.toml as `[[feeds]]`. Each is fetched for its title,
+/// and a web page stands in for the feed it names; feeds already configured are left out.
+/// Without a config file yet, one is started at the default location.
+pub async fn run(cfg: &RuntimeConfig, feeds_override: Option<&str>, path: &str) -> Result<()> {
+    let text = if path == "-" {
+        let mut s = String::new();
+        std::io::stdin().read_to_string(&mut s)?;
+        s
+    } else {
+        std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path))?
+    };
+    let listed = discover::listed_urls(&text);
+    if listed.is_empty() {
+        bail!("no http(s) URLs in {}", path);
+    }
+    let target = target(cfg, feeds_override)?;
+
+    // The built-in feeds don't count when there is no file yet
+    let existing = if cfg.path.is_some() { &cfg.feeds[..] } else { &[] };
+    let mut configured: HashSet<String> = existing.iter().map(|f| same(&f.url)).collect();
+    let mut names: HashSet<String> = existing.iter().map(|f| f.name.clone()).collect();
+    let (known, listed): (Vec<_>, Vec<_>) = listed.into_iter().partition(|(u, _)| configured.contains(&same(u.as_str())));
+    for (url, _) in &known {
+        println!("{}", tr!("import.already", url));
+    }
+
+    let ctx = &FetchContext::new(cfg.fetch.clone())?.with_data_saver(cfg.data_saver);
+    let probed = join_all(listed.iter().map(|(url, _)| probe(ctx, url))).await;
+    let mut added = Vec::new();
+    for ((listed, label), probed) in listed.iter().zip(probed) {
+        let (feed, title) = match probed {
+            Probed::Feed(feed, title) => (feed, title),
+            Probed::Nothing(why) => {
+                println!("{}", tr!("import.skipped", listed, why));
+                continue;
+            }
+        };
+        if !configured.insert(same(feed.as_str())) {
+            println!("{}", tr!("import.already", feed));
+            continue;
+        }
+        let name = title
+            .map(|t| sanitize_for_terminal(&t))
+            .filter(|t| !t.is_empty())
+            .or_else(|| label.as_deref().map(sanitize_for_terminal))
+            .unwrap_or_else(|| feed.host_str().unwrap_or("Feed").to_string());
+        let name = unique(&mut names, name);
+        added.push((name, feed));
+    }
+    if added.is_empty() {
+        println!("{}", tr!("import.none_added"));
+        return Ok(());
+    }
+    if !target.exists() {
+        if let Some(dir) = target.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&target, "")?;
+    }
+    let mut file = ConfigFile::open(&target)?;
+    for (name, feed) in &added {
+        file.add_feed(name, feed.as_str())?;
+        println!("{}", tr!("import.added_feed", name, feed));
+    }
+    file.save()?;
+    println!("{}", tr!("import.added", added.len(), target.display()));
+    Ok(())
+}
+
+// The config file the feeds go into: without one yet, the default location (a new file
+// with only them)
+fn target(cfg: &RuntimeConfig, feeds_override: Option<&str>) -> Result<PathBuf> {
+    if let Some(path) = &cfg.path {
+        return Ok(path.clone());
+    }
+    match config::default_config_path().filter(|_| feeds_override.is_none()) {
+        Some(path) => Ok(path),
+        None => bail!("import-urls adds feeds to a config.toml; --feeds names a single feed"),
+    }
+}
+
+// The feed at `url`, or the first one the page there names
+async fn probe(ctx: &FetchContext, url: &Url) -> Probed {
+    let (body, at) = match get(ctx, url).await {
+        Ok(got) => got,
+        Err(e) => return Probed::Nothing(format!("{:#}", e)),
+    };
+    if let Ok(feed) = parser::parse(&body[..]) {
+        return Probed::Feed(url.clone(), feed.title.map(|t| html_to_text(&t.content)));
+    }
+    let html = String::from_utf8_lossy(&body);
+    let Some(link) = discover::feed_links(&html, &at).into_iter().next() else {
+        return Probed::Nothing("not a feed, and names none".into());
+    };
+    let page_title = discover::page_title(&html);
+    match get(ctx, &link).await.map(|(body, _)| parser::parse(&body[..])) {
+        Ok(Ok(feed)) => Probed::Feed(link, feed.title.map(|t| html_to_text(&t.content)).or(page_title)),
+        Ok(Err(e)) => Probed::Nothing(format!("the feed it names ({}) doesn't parse: {}", link, e)),
+        Err(e) => Probed::Nothing(format!("the feed it names ({}) failed: {:#}", link, e)),
+    }
+}
+
+// The body, and the URL it came from after redirects
+async fn get(ctx: &FetchContext, url: &Url) -> Result<(Vec<u8>, Url)> {
+    let response = ctx.send(ctx.client.get(url.clone())).await?.error_for_status()?;
+    let at = response.url().clone();
+    Ok((ctx.read_body(response, ctx.max_bytes()).await?, at))
+}
+
+// Feed URLs compare without case or a trailing slash
+fn same(url: &str) -> String {
+    url.trim().trim_end_matches('/').to_ascii_lowercase()
+}
+
+// `name`, or "name (2)" and so on when a feed is called that already
+fn unique(names: &mut HashSet<String>, name: String) -> String {
+    let mut candidate = name.clone();
+    let mut n = 2;
+    while names.contains(&candidate) {
+        candidate = format!("{} ({})", name, n);
+        n += 1;
+    }
+    names.insert(candidate.clone());
+    candidate
+}
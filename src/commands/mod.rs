@@ -0,0 +1,32 @@
+//! Non-interactive subcommands. Output goes through `news_cli::sink`, never the menu UI.
+pub mod briefing;
+pub mod config_check;
+pub mod digest;
+pub mod doctor;
+pub mod export;
+pub mod fetch;
+pub mod history;
+pub mod import_urls;
+pub mod secret;
+pub mod serve;
+pub mod snapshot;
+pub mod watch;
+
+use news_cli::alerts::{self, AlertHit};
+use news_cli::config::AlertRule;
+use news_cli::news::Story;
+
+/// [`alerts::check`], printing a failure rather than stopping the command.
+pub fn check_alerts(rules: &[AlertRule], stories: &[Story]) -> Vec<AlertHit> {
+    alerts::check(rules, stories).unwrap_or_else(|e| {
+        eprintln!("{:#}", e);
+        Vec::new()
+    })
+}
+
+/// Print what a sink or alert push reported as failed, one line each.
+pub fn report(failed: Vec<String>) {
+    for message in failed {
+        eprintln!("{}", message);
+    }
+}
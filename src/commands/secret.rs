@@ -0,0 +1,53 @@
+use anyhow::{bail, Result};
+use console::Term;
+use news_cli::config::{self, SecretsConfig};
+use news_cli::secrets::{self, Store};
+use news_cli::tr;
+use std::io::BufRead;
+use std::path::PathBuf;
+
+/// `news-cli secret set <name>`: store a value typed on the terminal (hidden) or the first
+/// line of stdin.
+pub fn set(feeds: Option<&str>, name: &str) -> Result<()> {
+    let store = store(feeds, name)?;
+    let value = if Term::stdout().is_term() && Term::stderr().is_term() {
+        dialoguer::Password::new().with_prompt(tr!("secret.prompt", name)).interact()?
+    } else {
+        let mut line = String::new();
+        std::io::stdin().lock().read_line(&mut line)?;
+        line.trim_end_matches(['\r', '\n']).to_string()
+    };
+    if value.is_empty() {
+        bail!("empty value; nothing stored");
+    }
+    store.set(name, &value)?;
+    println!("{}", tr!("secret.stored", name, secrets::PREFIX));
+    Ok(())
+}
+
+/// `news-cli secret remove <name>`.
+pub fn remove(feeds: Option<&str>, name: &str) -> Result<()> {
+    if !store(feeds, name)?.remove(name)? {
+        bail!("no secret \"{}\"", name);
+    }
+    println!("{}", tr!("secret.removed", name));
+    Ok(())
+}
+
+// The store [secrets] in the config picks. The config is only parsed: loading it would look
+// up the secrets it refers to, maybe the one being set.
+fn store(feeds: Option<&str>, name: &str) -> Result<Store> {
+    if !secrets::valid_name(name) {
+        bail!("invalid secret name \"{}\" (letters, digits, '-', '_' and '.')", name);
+    }
+    let path = feeds
+        .filter(|p| p.to_ascii_lowercase().ends_with(".toml"))
+        .map(PathBuf::from)
+        .or_else(config::default_config_path)
+        .filter(|p| p.is_file());
+    let section: Option<SecretsConfig> = match path {
+        Some(path) => config::parse_file(&path)?.0.secrets,
+        None => None,
+    };
+    Ok(Store::from_config(section.as_ref())?)
+}
@@ -0,0 +1,247 @@
+use news_cli::config::RuntimeConfig;
+use news_cli::history::SeenStories;
+use news_cli::news::{NewsClient, Story};
+use news_cli::tr;
+use anyhow::{Context, Result};
+use serde_json::json;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use time::format_description::well_known::{Rfc2822, Rfc3339};
+use time::OffsetDateTime;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+pub const DEFAULT_ADDR: &str = "127.0.0.1:8787";
+
+// Request heads beyond this are not from a feed reader
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// The merged story list as last fetched.
+struct Snapshot {
+    stories: Vec<Story>,
+    updated: OffsetDateTime,
+}
+
+/// Serve the merged story list as RSS, Atom, JSON Feed and an HTML page, refetching every
+/// `refresh_interval_secs` (default 300; longer with `data_saver`). History is neither used
+/// nor updated.
+pub async fn run(cfg: &RuntimeConfig, addr: Option<&str>) -> Result<()> {
+    let addr = addr.unwrap_or(DEFAULT_ADDR);
+    let client = NewsClient::new(cfg)?;
+    // Every story is "new" to a remote reader; it keeps its own read state
+    let history = SeenStories::default();
+    let stories = client.fetch(&history).await?;
+    super::check_alerts(&cfg.alerts, &stories);
+    let snapshot = Arc::new(RwLock::new(Snapshot {
+        stories: prepare(stories),
+        updated: OffsetDateTime::now_utc(),
+    }));
+
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("failed to listen on {}", addr))?;
+    println!("{}", tr!("serve.serving", snapshot.read().unwrap().stories.len(), listener.local_addr()?));
+
+    let interval = Duration::from_secs(cfg.refresh_interval().max(1));
+    let shared = snapshot.clone();
+    let rules = cfg.alerts.clone();
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(interval).await;
+            match client.fetch(&history).await {
+                // An all-feeds outage keeps serving the previous list
+                Ok(stories) if !stories.is_empty() => {
+                    super::check_alerts(&rules, &stories);
+                    *shared.write().unwrap() = Snapshot { stories: prepare(stories), updated: OffsetDateTime::now_utc() };
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("{}", tr!("fetch.all_failed", e)),
+            }
+        }
+    });
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let snapshot = snapshot.clone();
+        tokio::spawn(async move {
+            let _ = tokio::time::timeout(Duration::from_secs(10), handle(stream, &snapshot)).await;
+        });
+    }
+}
+
+// Newest first; links a remote reader can't open (imap:) are left out
+fn prepare(mut stories: Vec<Story>) -> Vec<Story> {
+    stories.retain(|s| s.link.starts_with("http://") || s.link.starts_with("https://"));
+    stories.sort_by_key(|s| std::cmp::Reverse(s.published));
+    stories
+}
+
+async fn handle(mut stream: TcpStream, snapshot: &RwLock<Snapshot>) -> Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 || buf.len() + n > MAX_REQUEST_BYTES {
+            return Ok(());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    let head = String::from_utf8_lossy(&buf);
+    let mut request_line = head.lines().next().unwrap_or("").split_whitespace();
+    let (method, target) = (request_line.next().unwrap_or(""), request_line.next().unwrap_or("/"));
+    let host = head
+        .lines()
+        .find_map(|l| l.split_once(':').filter(|(k, _)| k.eq_ignore_ascii_case("host")).map(|(_, v)| v.trim()))
+        .unwrap_or(DEFAULT_ADDR)
+        .to_string();
+    let path = target.split('?').next().unwrap_or("/");
+
+    let (status, content_type, body) = if method != "GET" && method != "HEAD" {
+        ("405 Method Not Allowed", "text/plain", "Only GET is supported.\n".to_string())
+    } else {
+        let snap = snapshot.read().unwrap();
+        let base = format!("http://{}", host);
+        match path {
+            "/" => ("200 OK", "text/html; charset=utf-8", render_html(&snap)),
+            "/feed.rss" => ("200 OK", "application/rss+xml; charset=utf-8", render_rss(&snap, &base)),
+            "/feed.atom" => ("200 OK", "application/atom+xml; charset=utf-8", render_atom(&snap, &base)),
+            "/feed.json" => ("200 OK", "application/feed+json", render_json_feed(&snap, &base)),
+            _ => ("404 Not Found", "text/plain", "Not found.\n".to_string()),
+        }
+    };
+    let mut response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    )
+    .into_bytes();
+    if method != "HEAD" {
+        response.extend_from_slice(body.as_bytes());
+    }
+    stream.write_all(&response).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+pub(crate) fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn date(ts: Option<i64>) -> Option<OffsetDateTime> {
+    ts.and_then(|t| OffsetDateTime::from_unix_timestamp(t).ok())
+}
+
+fn render_rss(snap: &Snapshot, base: &str) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<rss version=\"2.0\"><channel>\n");
+    out.push_str(&format!("<title>news-cli</title><link>{}/</link><description>Merged news-cli feeds</description>\n", escape(base)));
+    if let Ok(d) = snap.updated.format(&Rfc2822) {
+        out.push_str(&format!("<lastBuildDate>{}</lastBuildDate>\n", d));
+    }
+    for s in &snap.stories {
+        out.push_str("<item>");
+        out.push_str(&format!("<title>{}</title><link>{}</link>", escape(&s.title), escape(&s.link)));
+        out.push_str(&format!("<guid isPermaLink=\"true\">{}</guid>", escape(&s.link)));
+        out.push_str(&format!("<category>{}</category>", escape(&s.source)));
+        if let Some(d) = date(s.published).and_then(|d| d.format(&Rfc2822).ok()) {
+            out.push_str(&format!("<pubDate>{}</pubDate>", d));
+        }
+        if let Some(summary) = &s.summary {
+            out.push_str(&format!("<description>{}</description>", escape(summary)));
+        }
+        out.push_str("</item>\n");
+    }
+    out.push_str("</channel></rss>\n");
+    out
+}
+
+fn render_atom(snap: &Snapshot, base: &str) -> String {
+    let updated = snap.updated.format(&Rfc3339).unwrap_or_default();
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    out.push_str(&format!(
+        "<title>news-cli</title><id>{0}/feed.atom</id><link rel=\"self\" href=\"{0}/feed.atom\"/><link href=\"{0}/\"/><updated>{1}</updated>\n",
+        escape(base),
+        updated
+    ));
+    for s in &snap.stories {
+        // Atom requires <updated>; undated entries use the fetch time
+        let when = date(s.published).and_then(|d| d.format(&Rfc3339).ok()).unwrap_or_else(|| updated.clone());
+        out.push_str("<entry>");
+        out.push_str(&format!(
+            "<title>{0}</title><id>{1}</id><link href=\"{1}\"/><updated>{2}</updated><category term=\"{3}\"/>",
+            escape(&s.title),
+            escape(&s.link),
+            when,
+            escape(&s.source)
+        ));
+        if let Some(a) = &s.author {
+            out.push_str(&format!("<author><name>{}</name></author>", escape(a)));
+        }
+        if let Some(summary) = &s.summary {
+            out.push_str(&format!("<summary type=\"html\">{}</summary>", escape(summary)));
+        }
+        out.push_str("</entry>\n");
+    }
+    out.push_str("</feed>\n");
+    out
+}
+
+fn render_json_feed(snap: &Snapshot, base: &str) -> String {
+    let items: Vec<_> = snap
+        .stories
+        .iter()
+        .map(|s| {
+            let mut item = json!({ "id": s.link, "url": s.link, "title": s.title, "tags": [s.source] });
+            if let Some(d) = date(s.published).and_then(|d| d.format(&Rfc3339).ok()) {
+                item["date_published"] = json!(d);
+            }
+            if let Some(a) = &s.author {
+                item["authors"] = json!([{ "name": a }]);
+            }
+            if let Some(summary) = &s.summary {
+                item["content_html"] = json!(summary);
+            }
+            item
+        })
+        .collect();
+    json!({
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": "news-cli",
+        "home_page_url": format!("{}/", base),
+        "feed_url": format!("{}/feed.json", base),
+        "items": items,
+    })
+    .to_string()
+}
+
+fn render_html(snap: &Snapshot) -> String {
+    let mut out = String::from(
+        "<!doctype html>\n<html><head><meta charset=\"utf-8\"><meta name=\"viewport\" content=\"width=device-width\">\
+         <title>news-cli</title>\
+         <link rel=\"alternate\" type=\"application/rss+xml\" href=\"/feed.rss\">\
+         <link rel=\"alternate\" type=\"application/atom+xml\" href=\"/feed.atom\">\
+         <link rel=\"alternate\" type=\"application/feed+json\" href=\"/feed.json\">\
+         <style>body{font-family:sans-serif;max-width:48em;margin:auto;padding:0 1em}li{margin:.4em 0}small{color:#666}</style>\
+         </head><body>\n<h1>news-cli</h1>\n",
+    );
+    let updated = snap.updated.format(&Rfc3339).unwrap_or_default();
+    out.push_str(&format!(
+        "<p><small>{} stories, updated {} &middot; <a href=\"/feed.rss\">RSS</a> &middot; <a href=\"/feed.atom\">Atom</a> &middot; <a href=\"/feed.json\">JSON Feed</a></small></p>\n<ul>\n",
+        snap.stories.len(),
+        updated
+    ));
+    for s in &snap.stories {
+        out.push_str(&format!(
+            "<li><a href=\"{}\">{}</a> <small>{}</small></li>\n",
+            escape(&s.link),
+            escape(&s.title),
+            escape(&s.source)
+        ));
+    }
+    out.push_str("</ul>\n</body></html>\n");
+    out
+}
@@ -0,0 +1,32 @@
+use anyhow::Result;
+use news_cli::config::RuntimeConfig;
+use news_cli::history::SeenStories;
+use news_cli::news;
+use news_cli::snapshot;
+use news_cli::tr;
+use time::OffsetDateTime;
+
+/// `news-cli snapshot save <name>`: fetch every feed and keep the stories, [NEW] badges
+/// included, for `snapshot view`. History is not updated.
+pub async fn save(cfg: &RuntimeConfig, history: &SeenStories, name: &str) -> Result<()> {
+    // Fail on a bad name before fetching anything
+    if !snapshot::valid_name(name) {
+        anyhow::bail!("invalid snapshot name \"{}\" (use letters, digits, '-', '_' and '.')", name);
+    }
+    let stories = news::collect_stories(cfg, history).await?;
+    let path = snapshot::save(name, OffsetDateTime::now_utc().unix_timestamp(), &stories)?;
+    println!("{}", tr!("snapshot.saved", stories.len(), name, path.display()));
+    Ok(())
+}
+
+/// `news-cli snapshot list`: name, date and story count of each snapshot, oldest first.
+pub fn list(cfg: &RuntimeConfig) -> Result<()> {
+    let all = snapshot::list();
+    if all.is_empty() {
+        println!("{}", tr!("snapshot.none"));
+    }
+    for s in all {
+        println!("{}", tr!("snapshot.entry", s.name, cfg.dates.format(s.taken_at).unwrap_or_default(), s.stories));
+    }
+    Ok(())
+}
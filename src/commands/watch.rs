@@ -0,0 +1,42 @@
+use news_cli::alerts;
+use news_cli::config::RuntimeConfig;
+use news_cli::history::SeenStories;
+use news_cli::news;
+use news_cli::sink::SinkSet;
+use news_cli::tr;
+use anyhow::Result;
+use std::time::Duration;
+
+/// Poll all feeds forever; each cycle emits only unseen stories and records them in history.
+/// New alert matches are also pushed to the alerts' `webhook` / `ntfy` targets.
+pub async fn run(
+    cfg: &RuntimeConfig,
+    history: &mut SeenStories,
+    to: &[String],
+    interval_secs: Option<u64>,
+) -> Result<()> {
+    let mut sinks = SinkSet::from_specs(to)?;
+    // For alert pushes (webhook / ntfy)
+    let client = news::build_client_with(&cfg.fetch)?;
+    let interval_secs = interval_secs.unwrap_or_else(|| cfg.refresh_interval()).max(1);
+    loop {
+        match news::collect_stories(cfg, history).await {
+            Ok(stories) => {
+                let hits = super::check_alerts(&cfg.alerts, &stories);
+                super::report(alerts::push(&client, &cfg.alerts, &hits).await);
+                let fresh: Vec<_> = stories.into_iter().filter(|s| s.is_new).collect();
+                if !fresh.is_empty() {
+                    super::report(sinks.emit(&fresh).await);
+                    for s in &fresh {
+                        history.mark_as_seen(&s.link);
+                    }
+                    if let Err(e) = history.save() {
+                        eprintln!("{}", tr!("history.save_failed", e));
+                    }
+                }
+            }
+            Err(e) => eprintln!("{}", tr!("fetch.all_failed", e)),
+        }
+        tokio::time::sleep(Duration::from_secs(interval_secs)).await;
+    }
+}
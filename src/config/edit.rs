@@ -0,0 +1,202 @@
+//! Changing single settings of config.toml in place, for the Settings screen (and feeds, for
+//! `doctor` and `import-urls`). The file is edited with `toml_edit`, so comments, key order
+//! and the rest of its layout survive.
+use super::{errors, AppConfig};
+use crate::error::{Error, Result};
+use crate::util::atomic::write_atomic;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table, Value};
+
+/// What a setting accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Text,
+    /// A whole number, 0 or more
+    Count,
+    /// A number, 0 or more
+    Number,
+    Flag,
+    /// One of these words
+    Choice(&'static [&'static str]),
+}
+
+/// A key the Settings screen offers; `fetch.timeout_secs` is `timeout_secs` in `[fetch]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Setting {
+    pub key: &'static str,
+    pub kind: Kind,
+}
+
+const fn setting(key: &'static str, kind: Kind) -> Setting {
+    Setting { key, kind }
+}
+
+/// The settings offered, in the order shown. Feeds, alerts and the other tables are left to
+/// the file.
+pub const SETTINGS: &[Setting] = &[
+    setting("refresh_interval_secs", Kind::Count),
+    setting("open_command", Kind::Text),
+    setting("player", Kind::Text),
+    setting("speak_command", Kind::Text),
+    setting("export_dir", Kind::Text),
+    setting("pdf_command", Kind::Text),
+    setting("archive_url", Kind::Text),
+    setting("archive_paywalled", Kind::Flag),
+    setting("max_age_days", Kind::Count),
+    setting("feed_order", Kind::Choice(&["config", "alphabetical", "most_new", "pinned"])),
+    setting("list_times", Kind::Choice(&["relative", "absolute", "off"])),
+    setting("images", Kind::Choice(&["auto", "kitty", "iterm", "sixel", "off"])),
+    setting("display_timezone", Kind::Text),
+    setting("date_format", Kind::Text),
+    setting("restore_session", Kind::Flag),
+    setting("data_saver", Kind::Flag),
+    setting("max_title_width", Kind::Count),
+    setting("confirm_open", Kind::Choice(&["off", "unusual", "always"])),
+    setting("language", Kind::Choice(&["en", "de"])),
+    setting("theme.header", Kind::Text),
+    setting("theme.new_badge", Kind::Text),
+    setting("theme.selection", Kind::Text),
+    setting("theme.no_color", Kind::Flag),
+    setting("fetch.connect_timeout_secs", Kind::Count),
+    setting("fetch.timeout_secs", Kind::Count),
+    setting("fetch.max_bytes", Kind::Count),
+    setting("fetch.user_agent", Kind::Text),
+    setting("fetch.per_host_rps", Kind::Number),
+    setting("fetch.jitter_ms", Kind::Count),
+];
+
+/// A config file opened for editing.
+pub struct ConfigFile {
+    path: PathBuf,
+    doc: DocumentMut,
+}
+
+impl ConfigFile {
+    pub fn open(path: &Path) -> Result<Self> {
+        let txt = fs::read_to_string(path)
+            .map_err(|e| Error::Config(format!("failed to read config: {}: {}", path.display(), e)))?;
+        let doc = txt
+            .parse::<DocumentMut>()
+            .map_err(|e| Error::Config(format!("invalid config {}:\n{}", path.display(), e)))?;
+        Ok(ConfigFile { path: path.to_path_buf(), doc })
+    }
+
+    /// The value as written in the file (strings unquoted); `None` if unset.
+    pub fn get(&self, key: &str) -> Option<String> {
+        let (table, name) = split(key);
+        let parent = match table {
+            Some(t) => self.doc.get(t)?.as_table()?,
+            None => self.doc.as_table(),
+        };
+        let mut value = parent.get(name)?.as_value()?.clone();
+        value.decor_mut().clear();
+        Some(match value.as_str() {
+            Some(s) => s.to_string(),
+            None => value.to_string(),
+        })
+    }
+
+    /// Set `setting` from what the user typed; an empty value removes the key, so the
+    /// default applies again.
+    pub fn set(&mut self, setting: &Setting, input: &str) -> Result<()> {
+        let input = input.trim();
+        let (table, name) = split(setting.key);
+        let parent = match table {
+            Some(t) => {
+                let entry = self.doc.entry(t).or_insert_with(|| Item::Table(Table::new()));
+                entry
+                    .as_table_mut()
+                    .ok_or_else(|| Error::Config(format!("[{}] is not a table", t)))?
+            }
+            None => self.doc.as_table_mut(),
+        };
+        if input.is_empty() {
+            parent.remove(name);
+            return Ok(());
+        }
+        let invalid = |expected: &str| Error::Config(format!("{}: expected {}, got \"{}\"", setting.key, expected, input));
+        let value: Value = match setting.kind {
+            Kind::Text => input.into(),
+            Kind::Count => {
+                let n: u64 = input.parse().map_err(|_| invalid("a whole number"))?;
+                i64::try_from(n).map_err(|_| invalid("a smaller number"))?.into()
+            }
+            Kind::Number => match input.parse::<f64>() {
+                Ok(n) if n.is_finite() && n >= 0.0 => n.into(),
+                _ => return Err(invalid("a number")),
+            },
+            Kind::Flag => match input {
+                "true" | "yes" | "on" => true.into(),
+                "false" | "no" | "off" => false.into(),
+                _ => return Err(invalid("true or false")),
+            },
+            Kind::Choice(words) if words.contains(&input) => input.into(),
+            Kind::Choice(words) => return Err(invalid(&words.join(", "))),
+        };
+        // Replacing the value in place keeps a trailing comment on its line
+        match parent.get_mut(name).and_then(Item::as_value_mut) {
+            Some(old) => {
+                let decor = old.decor().clone();
+                *old = value;
+                *old.decor_mut() = decor;
+            }
+            None => {
+                parent.insert(name, toml_edit::value(value));
+            }
+        }
+        Ok(())
+    }
+
+    /// Point the `[[feeds]]` entry named `name` (at `old` now) to `url`; `false` if the file
+    /// has no such entry, e.g. when the feed comes from built-in defaults.
+    pub fn set_feed_url(&mut self, name: &str, old: &str, url: &str) -> bool {
+        let Some(feeds) = self.doc.get_mut("feeds").and_then(Item::as_array_of_tables_mut) else { return false };
+        let entry = feeds.iter_mut().find(|t| {
+            t.get("name").and_then(Item::as_str) == Some(name) && t.get("url").and_then(Item::as_str) == Some(old)
+        });
+        let Some(value) = entry.and_then(|t| t.get_mut("url")).and_then(Item::as_value_mut) else { return false };
+        let decor = value.decor().clone();
+        *value = url.into();
+        *value.decor_mut() = decor;
+        true
+    }
+
+    /// Append a `[[feeds]]` entry with `name` and `url`.
+    pub fn add_feed(&mut self, name: &str, url: &str) -> Result<()> {
+        let feeds = self.doc.entry("feeds").or_insert_with(|| Item::ArrayOfTables(ArrayOfTables::new()));
+        let feeds = feeds
+            .as_array_of_tables_mut()
+            .ok_or_else(|| Error::Config("feeds is not a list of [[feeds]] tables".into()))?;
+        let mut feed = Table::new();
+        feed.insert("name", toml_edit::value(name));
+        feed.insert("url", toml_edit::value(url));
+        feeds.push(feed);
+        Ok(())
+    }
+
+    /// Write the file back, unless the result would no longer load: then nothing is written
+    /// and the problems are returned.
+    pub fn save(&self) -> Result<()> {
+        let txt = self.doc.to_string();
+        let parsed: AppConfig = toml::from_str(&txt).map_err(|e| Error::Config(e.to_string()))?;
+        let errors = errors(&parsed, Some(&txt));
+        if !errors.is_empty() {
+            return Err(Error::Config(errors.join("\n")));
+        }
+        write_atomic(&self.path, txt.as_bytes(), false)?;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+// "fetch.timeout_secs" as (Some("fetch"), "timeout_secs")
+fn split(key: &str) -> (Option<&str>, &str) {
+    match key.split_once('.') {
+        Some((table, name)) => (Some(table), name),
+        None => (None, key),
+    }
+}
@@ -0,0 +1,178 @@
+//! `include = ["feeds.d/*.toml", "alerts.toml"]` in config.toml: more files of feeds and
+//! settings, so a long feed list can live in one file per topic (e.g. in a dotfiles repo).
+//! Paths are relative to the including file; `*` and `?` match within a file name, and a
+//! pattern's files are read in name order. Each file is merged into what came before, in
+//! order: lists (`[[feeds]]`, `[[alerts]]`, `tracking_params`) are joined, tables merged key
+//! by key, and a value set differently in two files, or two list entries of the same
+//! `name`, is a conflict. Included files can't include more.
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// The files `patterns` (from the config at `path`) stand for, in merge order, each once. A
+/// path without wildcards must exist; a pattern may match nothing.
+pub fn files(path: &Path, patterns: &[String]) -> Result<Vec<PathBuf>> {
+    let base = path.parent().unwrap_or(Path::new(""));
+    let mut found: Vec<PathBuf> = Vec::new();
+    for pattern in patterns {
+        let full = base.join(pattern);
+        let name = full.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        let dir = full.parent().unwrap_or(Path::new(""));
+        if dir.to_string_lossy().contains(['*', '?']) {
+            return Err(Error::Config(format!("include \"{}\": wildcards only work in the file name", pattern)));
+        }
+        let mut matched: Vec<PathBuf> = if name.contains(['*', '?']) {
+            let entries = match fs::read_dir(if dir.as_os_str().is_empty() { Path::new(".") } else { dir }) {
+                Ok(entries) => entries,
+                // A feeds.d that isn't there yet has no files
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(Error::Config(format!("include \"{}\": {}: {}", pattern, dir.display(), e))),
+            };
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_name().to_str().is_some_and(|n| wildcard(name, n)))
+                .map(|e| dir.join(e.file_name()))
+                .filter(|p| p.is_file())
+                .collect()
+        } else if full.is_file() {
+            vec![full]
+        } else {
+            return Err(Error::Config(format!("include \"{}\": no such file {}", pattern, full.display())));
+        };
+        matched.sort();
+        for p in matched {
+            if !found.contains(&p) {
+                found.push(p);
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// The config at `path` (read as `txt`) with its includes merged in, or `None` when it has
+/// none. Every conflict is reported at once.
+pub fn merge(path: &Path, txt: &str) -> Result<Option<Table>> {
+    let invalid = |p: &Path, e: &dyn std::fmt::Display| Error::Config(format!("invalid config {}:\n{}", p.display(), e));
+    let mut root: Table = txt.parse().map_err(|e| invalid(path, &e))?;
+    let patterns = match root.get("include") {
+        None => return Ok(None),
+        Some(value) => value
+            .clone()
+            .try_into::<Vec<String>>()
+            .map_err(|_| invalid(path, &"include must be a list of paths, e.g. include = [\"feeds.d/*.toml\"]"))?,
+    };
+    let mut origins = Origins::default();
+    origins.record(&root, "", path);
+    let mut conflicts = Vec::new();
+    for file in files(path, &patterns)? {
+        let txt = fs::read_to_string(&file)
+            .map_err(|e| Error::Config(format!("failed to read config: {}: {}", file.display(), e)))?;
+        let table: Table = txt.parse().map_err(|e| invalid(&file, &e))?;
+        if table.contains_key("include") {
+            conflicts.push(format!("{}: include only works in {}", file.display(), path.display()));
+            continue;
+        }
+        merge_table(&mut root, table, "", &file, &mut origins, &mut conflicts);
+    }
+    if !conflicts.is_empty() {
+        return Err(Error::Config(format!(
+            "conflicting config in {} and its includes:\n{}",
+            path.display(),
+            conflicts.iter().map(|c| format!("  {}", c)).collect::<Vec<_>>().join("\n")
+        )));
+    }
+    Ok(Some(root))
+}
+
+/// Which file set each key, and each named list entry (`feeds "World"`), for messages.
+#[derive(Default)]
+struct Origins(HashMap<String, PathBuf>);
+
+impl Origins {
+    fn record(&mut self, table: &Table, prefix: &str, file: &Path) {
+        for (key, value) in table {
+            self.record_value(value, &join(prefix, key), file);
+        }
+    }
+
+    fn record_value(&mut self, value: &Value, key: &str, file: &Path) {
+        match value {
+            Value::Table(t) => self.record(t, key, file),
+            Value::Array(items) => {
+                for name in items.iter().filter_map(entry_name) {
+                    self.0.entry(format!("{} \"{}\"", key, name)).or_insert_with(|| file.to_path_buf());
+                }
+            }
+            _ => {
+                self.0.entry(key.to_string()).or_insert_with(|| file.to_path_buf());
+            }
+        }
+    }
+
+    fn of(&self, key: &str) -> String {
+        self.0.get(key).map(|p| p.display().to_string()).unwrap_or_else(|| "an earlier file".into())
+    }
+}
+
+fn merge_table(into: &mut Table, from: Table, prefix: &str, file: &Path, origins: &mut Origins, conflicts: &mut Vec<String>) {
+    for (key, value) in from {
+        let at = join(prefix, &key);
+        match (into.get_mut(&key), value) {
+            (None, value) => {
+                origins.record_value(&value, &at, file);
+                into.insert(key, value);
+            }
+            (Some(Value::Table(mine)), Value::Table(theirs)) => merge_table(mine, theirs, &at, file, origins, conflicts),
+            (Some(Value::Array(mine)), Value::Array(theirs)) => {
+                for item in theirs {
+                    if let Some(name) = entry_name(&item) {
+                        let entry = format!("{} \"{}\"", at, name);
+                        if mine.iter().any(|m| entry_name(m) == Some(name)) {
+                            conflicts.push(format!("{} is in {} and in {}", entry, origins.of(&entry), file.display()));
+                            continue;
+                        }
+                        origins.record_value(&Value::Array(vec![item.clone()]), &at, file);
+                    }
+                    mine.push(item);
+                }
+            }
+            (Some(mine), theirs) if *mine == theirs => {}
+            (Some(_), _) => conflicts.push(format!("{} is set in {} and in {}", at, origins.of(&at), file.display())),
+        }
+    }
+}
+
+// The `name` of a `[[feeds]]` or `[[alerts]]` entry
+fn entry_name(item: &Value) -> Option<&str> {
+    item.as_table()?.get("name")?.as_str()
+}
+
+fn join(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() { key.to_string() } else { format!("{}.{}", prefix, key) }
+}
+
+// `*` (any run of characters) and `?` (one character) against a whole file name
+fn wildcard(pattern: &str, name: &str) -> bool {
+    let (p, n): (Vec<char>, Vec<char>) = (pattern.chars().collect(), name.chars().collect());
+    let (mut pi, mut ni) = (0, 0);
+    // Where the last `*` was, and how much of the name it had taken then
+    let mut star: Option<(usize, usize)> = None;
+    while ni < n.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == n[ni]) {
+            pi += 1;
+            ni += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ni));
+            pi += 1;
+        } else if let Some((sp, sn)) = star {
+            pi = sp + 1;
+            ni = sn + 1;
+            star = Some((sp, sn + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
@@ -0,0 +1,996 @@
+pub mod edit;
+pub mod include;
+pub mod opml;
+pub mod overrides;
+pub mod remote;
+pub mod schedule;
+mod validate;
+
+use crate::util::dates::DateDisplay;
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::{Path, PathBuf}};
+
+pub use validate::{validate, Severity};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Feed {
+    pub name: String,
+    // Feed URL or local file (kind = "rss"); unused by other kinds
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub url: String,
+    // Source provider; "rss" when omitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    // kind = "command": program + args printing JSON stories to stdout
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    // kind = "mastodon" / "bluesky" / "github" / "imap": server to query (Bluesky defaults to the
+    // public AppView, GitHub to api.github.com)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    // kind = "mastodon" / "bluesky" / "youtube" / "github": whose posts to show, see
+    // news::provider; kind = "imap": user name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    // kind = "github": API token (default: $GITHUB_TOKEN); kind = "imap": password
+    // (default: $NEWS_CLI_IMAP_PASSWORD)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    // kind = "imap": mailbox folder to read (default: INBOX)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+    // Per-feed overrides of the [fetch] limits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    // Hide stories published more than this many days ago (overrides the global max_age_days)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age_days: Option<u64>,
+    // Multiplies this feed's stories' scores in Top Stories (default 1.0; 0 leaves them out)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    // Emoji or short tag shown before the feed's header and stories, e.g. "📰"; "" for none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    // What the feed covers ("tech", "world", ...; see CATEGORY_ICONS); picks the icon when
+    // `icon` is unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    // Listed before unpinned feeds on the News screen (unless feed_order is "config")
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pin: bool,
+    // Only fetched on these days, e.g. "weekdays", "mon-fri", "sat,sun" (see schedule)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days: Option<String>,
+    // Only fetched between these hours, e.g. "6-10", or "22-2" past midnight
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hours: Option<String>,
+    // Stories need a subscription to read: marked 🔒, and opened through `archive_url`
+    // with `archive_paywalled`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub paywalled: bool,
+}
+
+/// Icons for `category` values, used when a feed sets no `icon` of its own.
+pub const CATEGORY_ICONS: &[(&str, &str)] = &[
+    ("news", "📰"),
+    ("world", "🌍"),
+    ("politics", "📢"),
+    ("business", "💼"),
+    ("finance", "💹"),
+    ("tech", "💻"),
+    ("science", "🔬"),
+    ("health", "💊"),
+    ("sports", "⚽"),
+    ("entertainment", "🎬"),
+    ("music", "🎵"),
+    ("gaming", "🎮"),
+    ("video", "📺"),
+    ("podcasts", "🎧"),
+    ("weather", "⛅"),
+    ("local", "🏠"),
+    ("security", "🔒"),
+    ("dev", "🔧"),
+    ("releases", "📦"),
+    ("social", "💬"),
+    ("mail", "📧"),
+];
+
+impl Feed {
+    pub fn kind(&self) -> &str {
+        self.kind.as_deref().unwrap_or("rss")
+    }
+
+    /// The icon to show for this feed: `icon`, else its category's, if any.
+    pub fn icon(&self) -> Option<&str> {
+        match self.icon.as_deref() {
+            Some(icon) => Some(icon.trim()).filter(|i| !i.is_empty()),
+            None => {
+                let category = self.category.as_deref()?.trim();
+                CATEGORY_ICONS.iter().find(|(c, _)| c.eq_ignore_ascii_case(category)).map(|(_, icon)| *icon)
+            }
+        }
+    }
+
+    /// Whether the feed's `days` and `hours` allow fetching it at `now` (UNIX epoch seconds)
+    /// on the clock of `dates`.
+    pub fn is_active(&self, dates: &DateDisplay, now: i64) -> bool {
+        let Some((day, hour)) = dates.weekday_and_hour(now) else { return true };
+        schedule::allows(self.days.as_deref(), self.hours.as_deref(), day, hour)
+    }
+
+    /// `days` and `hours` as configured, e.g. "mon-fri, 6-10"; `None` when either is unset.
+    pub fn schedule(&self) -> Option<String> {
+        let parts: Vec<&str> = [self.days.as_deref(), self.hours.as_deref()].into_iter().flatten().collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    /// The limits this feed sets itself; see [`FetchLimits::or`] for merging with `[fetch]`.
+    pub fn limits(&self) -> FetchLimits {
+        FetchLimits {
+            connect_timeout_secs: self.connect_timeout_secs,
+            timeout_secs: self.timeout_secs,
+            max_bytes: self.max_bytes,
+            user_agent: self.user_agent.clone(),
+            per_host_rps: None,
+            jitter_ms: None,
+            respect_robots: None,
+            strict: None,
+        }
+    }
+}
+
+/// Timeouts, body size cap and user agent for fetching feeds. `[fetch]` sets them for all
+/// feeds; the same keys on a `[[feeds]]` entry override them for that feed. The per-host
+/// rate limit applies across feeds, so it is only read from `[fetch]`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FetchLimits {
+    // Seconds to establish a connection (default 5)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_secs: Option<u64>,
+    // Seconds for the whole request including the body (default 20)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    // Largest feed body accepted, in bytes (default 5 MB)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<u64>,
+    // User-Agent header (default "news-cli/0.1")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    // Only in [fetch]: requests per second to any one host (default 2), and up to this many
+    // milliseconds of random extra delay per request (default 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_host_rps: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jitter_ms: Option<u64>,
+    // Only in [fetch]: pace each host by the Crawl-delay in its robots.txt (default false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub respect_robots: Option<bool>,
+    // Only in [fetch]: for configs from elsewhere, refuse hosts on private, loopback and
+    // link-local addresses, local files and command feeds, and redirect chains longer than
+    // news::strict::MAX_REDIRECTS (default false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+}
+
+impl FetchLimits {
+    /// `self`, with anything unset taken from `fallback`.
+    pub fn or(&self, fallback: &FetchLimits) -> FetchLimits {
+        FetchLimits {
+            connect_timeout_secs: self.connect_timeout_secs.or(fallback.connect_timeout_secs),
+            timeout_secs: self.timeout_secs.or(fallback.timeout_secs),
+            max_bytes: self.max_bytes.or(fallback.max_bytes),
+            user_agent: self.user_agent.clone().or_else(|| fallback.user_agent.clone()),
+            per_host_rps: self.per_host_rps.or(fallback.per_host_rps),
+            jitter_ms: self.jitter_ms.or(fallback.jitter_ms),
+            respect_robots: self.respect_robots.or(fallback.respect_robots),
+            strict: self.strict.or(fallback.strict),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AppConfig {
+    pub feeds: Vec<Feed>,
+    // More files of feeds and settings merged in after this one, relative to it; `*` and `?`
+    // match within file names, e.g. ["feeds.d/*.toml"] (see `config::include`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    // Browser command; `{url}` is replaced by the link, otherwise it is appended
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_command: Option<String>,
+    // Video player for YouTube links (default: mpv, which streams via yt-dlp); `{url}` as above
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub player: Option<String>,
+    // Text-to-speech command reading headlines aloud ('v'); the text goes to its standard input,
+    // or replaces `{text}` (default: say on macOS, espeak-ng elsewhere)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speak_command: Option<String>,
+    // Folder 'e' saves a list or story to as HTML (default: the Downloads folder)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub export_dir: Option<String>,
+    // Converter making a PDF of each saved page, with `{html}` and `{pdf}` replaced by the
+    // two files (else both are appended), e.g. "wkhtmltopdf {html} {pdf}" (default: none)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pdf_command: Option<String>,
+    // Archive service for paywalled or dead stories ('a' in the detail view); `{url}` as above
+    // (default: the Wayback Machine)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_url: Option<String>,
+    // Before opening a story's link: "off" (default), "unusual" (ask when it redirects to
+    // another site, uses international lookalike characters or an IP address) or "always"
+    // (ask, showing where the link leads)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirm_open: Option<String>,
+    // Lead images in the detail view: "auto" (default; detect the terminal), "kitty",
+    // "iterm", "sixel" or "off"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub images: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>,
+    // Default polling interval for `news-cli watch`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_interval_secs: Option<u64>,
+    // Hide stories published more than this many days ago from the news list; undated
+    // stories are always shown
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age_days: Option<u64>,
+    // Timezone for story dates: an IANA name ("Europe/Berlin"), "UTC" or "local" (default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_timezone: Option<String>,
+    // strftime layout for story dates (default "%Y-%m-%d %H:%M")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
+    // Open the News screen where the last session left it (source, selected story, filter)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restore_session: Option<bool>,
+    // Open stories of `paywalled` feeds through `archive_url` instead of the original
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_paywalled: Option<bool>,
+    // UI language ("en", "de"); default from LC_ALL / LC_MESSAGES / LANG
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    // How the News screen orders feeds: "config" (default), "alphabetical", "most_new" or
+    // "pinned"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feed_order: Option<String>,
+    // When stories in lists were published: "relative" ("2h ago", default), "absolute"
+    // (date_format) or "off"; 'd' switches between the first two
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub list_times: Option<String>,
+    // Cut story titles in lists to this many terminal columns (with '…'); default: as many
+    // as the terminal has
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_title_width: Option<usize>,
+    // Use less data, e.g. on a metered connection: no lead images, smaller feed bodies
+    // (DATA_SAVER_MAX_BYTES at most), repeat fetches always conditional, and refresh
+    // intervals DATA_SAVER_SLOWDOWN times as long. `--data-saver` turns it on for one run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_saver: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<StatsConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetch: Option<FetchLimits>,
+    // Extra query parameters to strip from story links (on top of utm_*, gclid, ref, ...).
+    // A trailing '*' matches by prefix.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tracking_params: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<ThemeConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sync: Option<SyncConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub briefing: Option<BriefingConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub calendar: Option<CalendarConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weather: Option<WeatherConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translation: Option<TranslationConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digest: Option<DigestConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alerts: Vec<AlertRule>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reading_stats: Option<ReadingStatsConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scoring: Option<ScoringConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secrets: Option<SecretsConfig>,
+}
+
+/// The order of feeds on the News screen. Pinned feeds come first in every order but
+/// `Config`; ties keep config order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FeedOrder {
+    #[default]
+    Config,
+    Alphabetical,
+    // Most new stories first
+    MostNew,
+    Pinned,
+}
+
+impl FeedOrder {
+    /// "config", "alphabetical", "most_new" or "pinned".
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "config" => Some(FeedOrder::Config),
+            "alphabetical" => Some(FeedOrder::Alphabetical),
+            "most_new" => Some(FeedOrder::MostNew),
+            "pinned" => Some(FeedOrder::Pinned),
+            _ => None,
+        }
+    }
+}
+
+/// When opening a link asks first (see `open_url`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConfirmOpen {
+    #[default]
+    Off,
+    /// Only for links that redirect elsewhere, look like another site or fail the check
+    Unusual,
+    Always,
+}
+
+impl ConfirmOpen {
+    /// "off", "unusual" or "always".
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "off" => Some(ConfirmOpen::Off),
+            "unusual" => Some(ConfirmOpen::Unusual),
+            "always" => Some(ConfirmOpen::Always),
+            _ => None,
+        }
+    }
+}
+
+/// How story lists show when each story was published.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListTimes {
+    /// "2h ago"
+    #[default]
+    Relative,
+    /// In `date_format`
+    Absolute,
+    Off,
+}
+
+impl ListTimes {
+    /// "relative", "absolute" or "off".
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "relative" => Some(ListTimes::Relative),
+            "absolute" => Some(ListTimes::Absolute),
+            "off" => Some(ListTimes::Off),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RuntimeConfig {
+    /// The config.toml this was read from; `None` for the built-in defaults or a single feed.
+    pub path: Option<PathBuf>,
+    /// The files `include` merged in, in order.
+    pub included: Vec<PathBuf>,
+    pub feeds: Vec<Feed>,
+    pub open_command: Option<String>,
+    pub player: Option<String>,
+    pub speak_command: Option<String>,
+    pub export_dir: Option<String>,
+    pub pdf_command: Option<String>,
+    pub archive_url: Option<String>,
+    pub confirm_open: ConfirmOpen,
+    pub images: Option<String>,
+    pub header: Option<String>,
+    pub refresh_interval_secs: Option<u64>,
+    pub max_age_days: Option<u64>,
+    pub dates: DateDisplay,
+    pub restore_session: bool,
+    pub archive_paywalled: bool,
+    pub language: Option<String>,
+    pub feed_order: FeedOrder,
+    pub list_times: ListTimes,
+    pub max_title_width: Option<usize>,
+    pub data_saver: bool,
+    pub stats: StatsConfig,
+    pub fetch: FetchLimits,
+    pub tracking_params: Vec<String>,
+    pub theme: ThemeConfig,
+    pub sync: Option<SyncConfig>,
+    pub briefing: Option<BriefingConfig>,
+    pub calendar: Option<CalendarConfig>,
+    pub weather: Option<WeatherConfig>,
+    pub translation: Option<TranslationConfig>,
+    pub digest: DigestConfig,
+    pub alerts: Vec<AlertRule>,
+    pub reading_stats: ReadingStatsConfig,
+    pub scoring: ScoringConfig,
+}
+
+/// Largest feed body accepted with `data_saver`, whatever `max_bytes` says.
+pub const DATA_SAVER_MAX_BYTES: u64 = 1024 * 1024;
+
+/// How many times longer refresh intervals and cache lifetimes are with `data_saver`.
+pub const DATA_SAVER_SLOWDOWN: u64 = 4;
+
+/// `refresh_interval_secs` when none is configured.
+pub const DEFAULT_REFRESH_INTERVAL_SECS: u64 = 300;
+
+/// `archive_url` when none is configured.
+pub const DEFAULT_ARCHIVE_URL: &str = "https://web.archive.org/web/{url}";
+
+impl RuntimeConfig {
+    /// Where `link` is archived: `archive_url` with `{url}` replaced by it, or it appended.
+    pub fn archive_link(&self, link: &str) -> String {
+        archive_link(self.archive_url.as_deref().unwrap_or(DEFAULT_ARCHIVE_URL), link)
+    }
+
+    /// Seconds between polls of `watch` and `serve`: `refresh_interval_secs` (default 300),
+    /// [`DATA_SAVER_SLOWDOWN`] times as long with `data_saver`.
+    pub fn refresh_interval(&self) -> u64 {
+        self.slowed(self.refresh_interval_secs.unwrap_or(DEFAULT_REFRESH_INTERVAL_SECS))
+    }
+
+    /// `interval` (of refreshing something), lengthened with `data_saver`.
+    pub fn slowed(&self, interval: u64) -> u64 {
+        match self.data_saver {
+            true => interval.saturating_mul(DATA_SAVER_SLOWDOWN),
+            false => interval,
+        }
+    }
+
+    /// Whether the feed named `source` is marked `paywalled`.
+    pub fn is_paywalled(&self, source: &str) -> bool {
+        self.feeds.iter().any(|f| f.name == source && f.paywalled)
+    }
+
+    /// The link opening `story` should go to: its archived copy for paywalled feeds with
+    /// `archive_paywalled`, else the story's own.
+    pub fn open_link(&self, story: &crate::news::Story) -> String {
+        match self.archive_paywalled && self.is_paywalled(&story.source) {
+            true => self.archive_link(&story.link),
+            false => story.link.clone(),
+        }
+    }
+}
+
+/// `template` with `{url}` replaced by `link`, or `link` appended if it has no `{url}`.
+pub fn archive_link(template: &str, link: &str) -> String {
+    match template.contains("{url}") {
+        true => template.replace("{url}", link),
+        false => format!("{}{}", template, link),
+    }
+}
+
+impl From<AppConfig> for RuntimeConfig {
+    fn from(parsed: AppConfig) -> Self {
+        RuntimeConfig {
+            path: None,
+            included: Vec::new(),
+            feeds: parsed.feeds,
+            open_command: parsed.open_command,
+            player: parsed.player,
+            speak_command: parsed.speak_command,
+            export_dir: parsed.export_dir,
+            pdf_command: parsed.pdf_command,
+            archive_url: parsed.archive_url,
+            confirm_open: parsed.confirm_open.as_deref().and_then(ConfirmOpen::parse).unwrap_or_default(),
+            images: parsed.images,
+            header: parsed.header,
+            refresh_interval_secs: parsed.refresh_interval_secs,
+            max_age_days: parsed.max_age_days,
+            // Checked by validate; a bad value falls back to the defaults
+            dates: DateDisplay::new(parsed.display_timezone.as_deref(), parsed.date_format.as_deref())
+                .unwrap_or_default(),
+            restore_session: parsed.restore_session.unwrap_or(false),
+            archive_paywalled: parsed.archive_paywalled.unwrap_or(false),
+            language: parsed.language,
+            // Checked by validate; an unknown value keeps config order
+            feed_order: parsed.feed_order.as_deref().and_then(FeedOrder::parse).unwrap_or_default(),
+            list_times: parsed.list_times.as_deref().and_then(ListTimes::parse).unwrap_or_default(),
+            max_title_width: parsed.max_title_width,
+            data_saver: parsed.data_saver.unwrap_or(false),
+            stats: parsed.stats.unwrap_or_default(),
+            fetch: parsed.fetch.unwrap_or_default(),
+            tracking_params: parsed.tracking_params,
+            theme: parsed.theme.unwrap_or_default(),
+            sync: parsed.sync,
+            briefing: parsed.briefing,
+            calendar: parsed.calendar,
+            weather: parsed.weather,
+            translation: parsed.translation,
+            digest: parsed.digest.unwrap_or_default(),
+            alerts: parsed.alerts,
+            reading_stats: parsed.reading_stats.unwrap_or_default(),
+            scoring: parsed.scoring.unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct StatsConfig {
+    // StatsCan vector id for population (Canada total, quarterly)
+    pub statscan_population_vector: Option<String>,
+    // StatsCan/CMHC housing starts vector id (monthly), will fetch last 4 months
+    pub housing_starts_vector: Option<String>,
+    // Optional override for BoC yield curve series: map label->series id
+    pub boc_yield_series: Option<std::collections::HashMap<String, String>>,
+    // Minutes the Stats screen shows fetched values before fetching them again (default 60)
+    pub cache_minutes: Option<u64>,
+    // Load the Stats screen's values at launch, while the main menu is up (default false)
+    pub prefetch: Option<bool>,
+    pub fx: Option<FxConfig>,
+    pub crypto: Option<CryptoConfig>,
+    // Groups of STATS_ITEMS in the order to show them; items not listed are hidden.
+    // Default: all of them, in that order.
+    pub layout: Option<Vec<StatsGroup>>,
+    // Labels to show instead of the built-in ones, by item name
+    #[serde(default)]
+    pub labels: std::collections::HashMap<String, String>,
+}
+
+/// Names of the Stats screen's items, for `stats.layout` and `stats.labels`.
+pub const STATS_ITEMS: &[&str] = &["policy_rate", "inflation", "population", "housing", "yield_curve", "fx", "crypto"];
+
+/// A `[[stats.layout]]` entry: items shown together, under `title` if set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StatsGroup {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub items: Vec<String>,
+}
+
+/// Coin prices for the Stats screen (`[stats.crypto]`); without it the section isn't shown.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct CryptoConfig {
+    // CoinGecko ids: "bitcoin", "ethereum", ...
+    #[serde(default)]
+    pub coins: Vec<String>,
+    // What to price them in (default "usd")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+}
+
+impl CryptoConfig {
+    /// `currency` in lower case, as CoinGecko expects it; "usd" if unset.
+    pub fn currency(&self) -> String {
+        self.currency.as_deref().unwrap_or("usd").trim().to_ascii_lowercase()
+    }
+}
+
+/// Exchange rates for the Stats screen (`[stats.fx]`).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct FxConfig {
+    // "USD/CAD", "EUR/USD", ...: units of the second currency per unit of the first
+    #[serde(default)]
+    pub pairs: Vec<String>,
+}
+
+impl FxConfig {
+    /// "usd/cad" as ("USD", "CAD"); None unless both are three-letter codes and differ.
+    pub fn parse_pair(pair: &str) -> Option<(String, String)> {
+        let (base, quote) = pair.split_once('/')?;
+        let code = |c: &str| {
+            let c = c.trim().to_ascii_uppercase();
+            (c.len() == 3 && c.chars().all(|ch| ch.is_ascii_alphabetic())).then_some(c)
+        };
+        let (base, quote) = (code(base)?, code(quote)?);
+        (base != quote).then_some((base, quote))
+    }
+}
+
+/// Logging of opened stories for the Reading Stats screen (see `news_cli::reading`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReadingStatsConfig {
+    // Set to false to stop logging opens; what was logged stays until the log is deleted
+    #[serde(default = "default_track")]
+    pub track: bool,
+    // Periods to switch between, in days; "all time" is always offered as well
+    #[serde(default = "default_periods")]
+    pub periods: Vec<u32>,
+}
+
+impl Default for ReadingStatsConfig {
+    fn default() -> Self {
+        ReadingStatsConfig { track: default_track(), periods: default_periods() }
+    }
+}
+
+fn default_track() -> bool {
+    true
+}
+
+fn default_periods() -> Vec<u32> {
+    vec![7, 30, 365]
+}
+
+/// Ranking for the Top Stories screen (see `news_cli::scoring`); every key is optional.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ScoringConfig {
+    // Added per word found (case-insensitive) in the title or summary; negative buries,
+    // e.g. { rust = 2.0, crypto = -1.0 }
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub keywords: std::collections::BTreeMap<String, f64>,
+    // Hours after which a story's score has halved (default 12)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub half_life_hours: Option<f64>,
+    // How much Hacker News / Reddit points count (default 1.0; 0 ignores them)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub points_weight: Option<f64>,
+    // Only stories published within this many hours are ranked (default 24)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_hours: Option<u64>,
+    // Number of stories shown (default 20)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top: Option<usize>,
+    // List stories with similar titles as one collapsible topic (default true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_topics: Option<bool>,
+}
+
+/// Where `"secret:<name>"` values are looked up (see `news_cli::secrets`).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SecretsConfig {
+    // "keyring" (default: the system keyring) or "file" (secrets.age in the config
+    // directory, unlocked with $NEWS_CLI_SECRETS_PASSPHRASE or a prompt)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store: Option<String>,
+}
+
+/// Share read/starred state with a self-hosted reader (see `news_cli::sync`).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SyncConfig {
+    // Reader API to talk to; only "miniflux" so far
+    pub backend: String,
+    // Base URL of the reader, e.g. https://miniflux.example.com
+    pub url: String,
+    // API key (default: $NEWS_CLI_SYNC_TOKEN)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+/// Named rule for `[[alerts]]`; a story matches if any keyword or regex does.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AlertRule {
+    pub name: String,
+    // Case-insensitive substrings of the title or summary, e.g. "CVE-"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    // Regular expressions (case-sensitive unless they start with (?i))
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub regex: Vec<String>,
+    // `news-cli watch` POSTs new matches here as a JSON array of {alert, title, link, source}
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>,
+    // ...and/or to this ntfy topic: a name on ntfy.sh ("my-oncall") or a full topic URL
+    // on another server ("https://ntfy.example.com/oncall")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ntfy: Option<String>,
+}
+
+/// LLM briefing of the top stories (see `news_cli::briefing`). Off unless this section exists.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct BriefingConfig {
+    // OpenAI-compatible API base, e.g. https://api.openai.com/v1 or http://localhost:11434/v1
+    pub api_base: String,
+    // Bearer key (default: $NEWS_CLI_BRIEFING_KEY); local servers usually need none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    pub model: String,
+    // Number of most recent stories to include (default: 20)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top: Option<usize>,
+    // Also send each article's page text, not just headline and summary (slower, more tokens)
+    #[serde(default)]
+    pub include_text: bool,
+    // Replaces the built-in instructions to the model
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+}
+
+/// Upcoming economic releases (see `news_cli::calendar`). Off unless this section exists.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct CalendarConfig {
+    // Countries by currency code, as the calendar lists them: "USD", "CAD", "EUR", ...
+    pub countries: Vec<String>,
+    // Least impact to list: "low" (default, everything), "medium" or "high"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub impact: Option<String>,
+    // Calendar feed in Forex Factory's JSON format (default: this week's from faireconomy.media)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Today's weather on the Morning briefing (see `news_cli::weather`). Off unless this section
+/// exists.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct WeatherConfig {
+    pub latitude: f64,
+    pub longitude: f64,
+    // Name to show for the place, e.g. "Berlin"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub place: Option<String>,
+    // "metric" (default: °C, km/h) or "imperial" (°F, mph)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub units: Option<String>,
+    // Forecast API in Open-Meteo's format (default: api.open-meteo.com)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl WeatherConfig {
+    /// Whether `units` asks for °F and mph.
+    pub fn imperial(&self) -> bool {
+        self.units.as_deref() == Some("imperial")
+    }
+}
+
+/// Machine translation of stories in other languages (see `news_cli::translate`). Off unless
+/// this section exists.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TranslationConfig {
+    // "deepl" or "libretranslate"
+    pub backend: String,
+    // API endpoint; DeepL defaults to https://api-free.deepl.com, LibreTranslate needs one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    // API key (default: $NEWS_CLI_TRANSLATE_KEY); self-hosted LibreTranslate usually needs none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    // Language to translate into, as a two-letter code (default: "en")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    // Only translate stories detected as these languages, e.g. ["de", "fr"] (default: any
+    // language other than target)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
+    // "alongside" (default) shows the translation under the original, "instead" replaces it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display: Option<String>,
+}
+
+impl TranslationConfig {
+    pub fn target(&self) -> &str {
+        self.target.as_deref().unwrap_or("en")
+    }
+
+    /// Whether translations replace the original title and summary.
+    pub fn instead(&self) -> bool {
+        self.display.as_deref() == Some("instead")
+    }
+}
+
+/// Where `news-cli digest` goes (see `news_cli::digest`). Without a path or recipients it is
+/// printed.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct DigestConfig {
+    // "markdown" (default) or "html"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    // File the digest is written to, replaced on every run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    // Mail it to these addresses, through smtp or sendmail
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub to: Vec<String>,
+    // Sender (default "news-cli <news-cli@localhost>")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    // Subject, followed by the date (default "News digest")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    // Mail server as host[:port] (default port 587); port 465 is TLS from the start, others
+    // switch to TLS when the server offers STARTTLS
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smtp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smtp_user: Option<String>,
+    // Default: $NEWS_CLI_SMTP_PASSWORD
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smtp_password: Option<String>,
+    // Command reading the message on stdin, e.g. "sendmail -t"; used when smtp isn't set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sendmail: Option<String>,
+}
+
+impl DigestConfig {
+    pub fn from(&self) -> &str {
+        self.from.as_deref().unwrap_or(crate::digest::DEFAULT_FROM)
+    }
+
+    pub fn subject(&self) -> &str {
+        self.subject.as_deref().unwrap_or("News digest")
+    }
+}
+
+/// Styles are console "dotted" specs: colors (`red`, `208`), `on_<color>` backgrounds and
+/// attributes (`bold`, `dim`, `underlined`, `reverse`), e.g. `"yellow.bold"`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ThemeConfig {
+    // Section headers in the news list
+    pub header: Option<String>,
+    // The [NEW] badge
+    pub new_badge: Option<String>,
+    // Highlighted row in arrow-key navigation
+    pub selection: Option<String>,
+    // Stats: rising/normal and falling/inverted values
+    pub positive: Option<String>,
+    pub negative: Option<String>,
+    // Per-feed accent for section headers, keyed by feed name
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub feeds: std::collections::BTreeMap<String, String>,
+    // Disable all colors (the NO_COLOR environment variable does the same)
+    #[serde(default)]
+    pub no_color: bool,
+}
+
+pub fn load(feeds_override: Option<String>) -> Result<RuntimeConfig> {
+    // If an override is provided, try to interpret it:
+    if let Some(path_str) = feeds_override {
+        let p = PathBuf::from(&path_str);
+        if p.is_file() {
+            // If it's a TOML, parse as config; an OPML list gives the feeds; otherwise treat
+            // as a single local feed
+            let lc = path_str.to_ascii_lowercase();
+            if lc.ends_with(".toml") {
+                return load_file(&p);
+            } else if lc.ends_with(".opml") {
+                let xml = fs::read_to_string(&p)
+                    .map_err(|e| Error::Config(format!("failed to read {}: {}", p.display(), e)))?;
+                let feeds = opml::feeds(&xml).map_err(|e| Error::Config(format!("{}: {}", p.display(), e)))?;
+                return built_in(feeds);
+            } else {
+                let name = p
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("local-feed")
+                    .to_string();
+                return built_in(vec![Feed {
+                    name,
+                    url: path_str,
+                    ..Feed::default()
+                }]);
+            }
+        } else {
+            // Not a file; if it's likely a URL, wrap as a single feed
+            if path_str.starts_with("http://") || path_str.starts_with("https://") {
+                return built_in(vec![Feed {
+                    name: "Custom".into(),
+                    url: path_str,
+                    ..Feed::default()
+                }]);
+            }
+        }
+    }
+
+    // Otherwise, try default config path
+    if let Some(path) = default_config_path() {
+        if path.is_file() {
+            return load_file(&path);
+        }
+    }
+
+    // Built-in minimal defaults
+    built_in(vec![
+        Feed {
+            name: "HN Front".into(),
+            url: "https://hnrss.org/frontpage".into(),
+            ..Feed::default()
+        },
+        Feed {
+            name: "BBC World".into(),
+            url: "https://feeds.bbci.co.uk/news/world/rss.xml".into(),
+            ..Feed::default()
+        },
+    ])
+}
+
+/// The defaults with `feeds`, and any overrides (see [`overrides`]).
+fn built_in(feeds: Vec<Feed>) -> Result<RuntimeConfig> {
+    let mut parsed = overrides::apply(AppConfig { feeds, ..AppConfig::default() })?;
+    let errors = errors(&parsed, None);
+    if !errors.is_empty() {
+        return Err(Error::Config(format!("invalid config overrides:\n{}", errors.join("\n"))));
+    }
+    crate::secrets::resolve_config(&mut parsed)?;
+    Ok(parsed.into())
+}
+
+/// Read and validate one config file (with its includes), remembering where it came from.
+pub fn load_file(path: &Path) -> Result<RuntimeConfig> {
+    let parsed = read_file(path)?;
+    // Already read once, so only a file removed since could fail here
+    let included = include::files(path, &parsed.include).unwrap_or_default();
+    Ok(RuntimeConfig { path: Some(path.to_path_buf()), included, ..parsed.into() })
+}
+
+/// Parse a config file, merging in its includes, without validating it. TOML errors carry
+/// line/column and a snippet; all errors are [`Error::Config`]. The text returned is the
+/// file's own.
+pub fn parse_file(path: &Path) -> Result<(AppConfig, String)> {
+    let txt = fs::read_to_string(path)
+        .map_err(|e| Error::Config(format!("failed to read config: {}: {}", path.display(), e)))?;
+    let parsed: AppConfig = match include::merge(path, &txt)? {
+        Some(merged) => merged.try_into().map_err(|e| {
+            Error::Config(format!("invalid config {} (with its includes):\n{}", path.display(), e))
+        })?,
+        None => toml::from_str(&txt).map_err(|e| Error::Config(format!("invalid config {}:\n{}", path.display(), e)))?,
+    };
+    Ok((parsed, txt))
+}
+
+/// Parse, apply overrides and validate, then fill in secrets; any error-level issue aborts
+/// with all problems listed.
+fn read_file(path: &Path) -> Result<AppConfig> {
+    let (parsed, txt) = parse_file(path)?;
+    let mut parsed = overrides::apply(parsed)?;
+    let errors = errors(&parsed, Some(&txt));
+    if !errors.is_empty() {
+        return Err(Error::Config(format!(
+            "invalid config {}:\n{}\n(run `news-cli config check` for details)",
+            path.display(),
+            errors.join("\n")
+        )));
+    }
+    crate::secrets::resolve_config(&mut parsed)?;
+    Ok(parsed)
+}
+
+// Error-level issues only; warnings are for `config check`
+fn errors(parsed: &AppConfig, source: Option<&str>) -> Vec<String> {
+    validate(parsed, source)
+        .into_iter()
+        .filter(|i| i.severity == Severity::Error)
+        .map(|i| i.to_string())
+        .collect()
+}
+
+/// One local feed per file in `dir`, named after the file without its extension and in name
+/// order; they replace the configured feeds when testing with `--fixture-dir`.
+pub fn fixture_feeds(dir: &Path) -> Result<Vec<Feed>> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| Error::Config(format!("failed to read fixture dir: {}: {}", dir.display(), e)))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|p| p.is_file())
+        .collect();
+    paths.sort();
+    Ok(paths
+        .into_iter()
+        .map(|p| Feed {
+            name: p.file_stem().and_then(|s| s.to_str()).unwrap_or("fixture").to_string(),
+            url: p.to_string_lossy().into_owned(),
+            ..Feed::default()
+        })
+        .collect())
+}
+
+pub fn default_config_path() -> Option<PathBuf> {
+    crate::paths::config_dir().map(|d| d.join("config.toml"))
+}
@@ -0,0 +1,44 @@
+//! Feeds from an OPML subscription list, as feed readers export it: each `<outline>` with an
+//! `xmlUrl` is a feed, named by its `title` (else `text`), and the folder outline around it
+//! becomes its `category`.
+use super::Feed;
+use crate::error::{Error, Result};
+use crate::util::html::{attr, decode_entities};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// The feeds listed in `xml`, in document order; a list without any is an [`Error::Parse`].
+pub fn feeds(xml: &str) -> Result<Vec<Feed>> {
+    static OUTLINE: OnceLock<Regex> = OnceLock::new();
+    let re = OUTLINE.get_or_init(|| {
+        Regex::new(r#"(?i)<outline\b((?:[^>"']|"[^"]*"|'[^']*')*?)(/?)>|</outline\s*>"#).expect("valid regex")
+    });
+    let value = |attrs: &str, name: &str| attr(attrs, name).map(|v| decode_entities(v.trim())).filter(|v| !v.is_empty());
+    // The folder each open outline stands for (`None` for an open feed outline)
+    let mut folders: Vec<Option<String>> = Vec::new();
+    let mut feeds = Vec::new();
+    for c in re.captures_iter(xml) {
+        let Some(attrs) = c.get(1) else {
+            folders.pop();
+            continue;
+        };
+        let attrs = attrs.as_str();
+        let url = value(attrs, "xmlUrl");
+        let name = value(attrs, "title").or_else(|| value(attrs, "text"));
+        if let Some(url) = &url {
+            feeds.push(Feed {
+                name: name.clone().unwrap_or_else(|| url.clone()),
+                url: url.clone(),
+                category: folders.iter().rev().flatten().next().map(|f| f.to_lowercase()),
+                ..Feed::default()
+            });
+        }
+        if &c[2] != "/" {
+            folders.push(if url.is_none() { name } else { None });
+        }
+    }
+    if feeds.is_empty() {
+        return Err(Error::Parse("no feeds in the OPML file (no <outline> with an xmlUrl)".into()));
+    }
+    Ok(feeds)
+}
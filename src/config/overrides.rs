@@ -0,0 +1,136 @@
+//! Config values set outside config.toml, for containers and one-off experiments:
+//! `NEWS_CLI_<KEY>` environment variables and `--set key=value` on the command line (which
+//! wins). Keys are dotted paths into the config (`theme.no_color`, `fetch.timeout_secs`,
+//! `feeds.0.url`); variable names write the dots as `__` (`NEWS_CLI_FETCH__TIMEOUT_SECS`).
+//! Values are read as TOML (`5`, `true`, `["a", "b"]`), or else taken as a string.
+use super::AppConfig;
+use crate::error::{Error, Result};
+use std::sync::OnceLock;
+use toml::{Table, Value};
+
+const ENV_PREFIX: &str = "NEWS_CLI_";
+
+/// Variables read for other purposes (directories, secrets); never config keys.
+const RESERVED: &[&str] = &[
+    "NEWS_CLI_CONFIG_DIR",
+    "NEWS_CLI_STATE_DIR",
+    "NEWS_CLI_CACHE_DIR",
+    "NEWS_CLI_SYNC_TOKEN",
+    "NEWS_CLI_BRIEFING_KEY",
+    "NEWS_CLI_TRANSLATE_KEY",
+    "NEWS_CLI_SMTP_PASSWORD",
+    "NEWS_CLI_IMAP_PASSWORD",
+    "NEWS_CLI_SECRETS_PASSPHRASE",
+];
+
+/// One value to put into the config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Override {
+    pub key: String,
+    pub value: String,
+    /// Where it came from, for messages: the variable name or `--set <key>`.
+    pub origin: String,
+}
+
+static FROM_CLI: OnceLock<Vec<Override>> = OnceLock::new();
+
+/// `--set key=value` as an [`Override`]; `None` without a `=` or key.
+pub fn parse_set(arg: &str) -> Option<Override> {
+    let (key, value) = arg.split_once('=')?;
+    let key = key.trim();
+    (!key.is_empty()).then(|| Override {
+        key: key.to_string(),
+        value: value.to_string(),
+        origin: format!("--set {}", key),
+    })
+}
+
+/// Remember the command line's `--set`s for every config loaded from now on (including
+/// reloads). Call once at startup; later calls are ignored.
+pub fn init(sets: Vec<Override>) {
+    let _ = FROM_CLI.set(sets);
+}
+
+/// `NEWS_CLI_<KEY>` variables among `vars`, in name order.
+pub fn from_env(vars: impl Iterator<Item = (String, String)>) -> Vec<Override> {
+    let mut found: Vec<Override> = vars
+        .filter(|(name, _)| name.starts_with(ENV_PREFIX) && !RESERVED.contains(&name.as_str()))
+        .map(|(name, value)| Override {
+            key: name[ENV_PREFIX.len()..].to_ascii_lowercase().replace("__", "."),
+            value,
+            origin: name,
+        })
+        .collect();
+    found.sort_by(|a, b| a.origin.cmp(&b.origin));
+    found
+}
+
+/// Every override in effect: the environment's, then the command line's.
+pub fn active() -> Vec<Override> {
+    let mut all = from_env(std::env::vars());
+    all.extend(FROM_CLI.get().cloned().unwrap_or_default());
+    all
+}
+
+/// `parsed` with the [`active`] overrides put in.
+pub(super) fn apply(parsed: AppConfig) -> Result<AppConfig> {
+    apply_all(parsed, &active())
+}
+
+/// `parsed` with `overrides` put in, in order; a key the config doesn't have is an error.
+pub fn apply_all(parsed: AppConfig, overrides: &[Override]) -> Result<AppConfig> {
+    if overrides.is_empty() {
+        return Ok(parsed);
+    }
+    let mut root = Table::try_from(&parsed).map_err(|e| Error::Config(e.to_string()))?;
+    for o in overrides {
+        set(&mut root, &o.key, value(&o.value)).map_err(|e| Error::Config(format!("{}: {}", o.origin, e)))?;
+    }
+    root.try_into().map_err(|e| {
+        let origins: Vec<&str> = overrides.iter().map(|o| o.origin.as_str()).collect();
+        Error::Config(format!("invalid config override ({}): {}", origins.join(", "), e))
+    })
+}
+
+// `5`, `true` or `["a"]` as TOML; anything else (e.g. `Europe/Berlin`) as a string
+fn value(raw: &str) -> Value {
+    toml::from_str::<Table>(&format!("v = {}", raw))
+        .ok()
+        .and_then(|mut t| t.remove("v"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+// Put `value` at `key`, creating tables on the way; numbers index arrays (`feeds.0`)
+fn set(root: &mut Table, key: &str, value: Value) -> std::result::Result<(), String> {
+    let parts: Vec<&str> = key.split('.').collect();
+    if parts.iter().any(|p| p.is_empty()) {
+        return Err(format!("invalid key \"{}\"", key));
+    }
+    let (last, path) = parts.split_last().expect("split yields a part");
+    let mut here = root;
+    for (i, part) in path.iter().enumerate() {
+        let at = parts[..=i].join(".");
+        let next = here.entry(part.to_string()).or_insert_with(|| Value::Table(Table::new()));
+        here = match next {
+            Value::Table(t) => t,
+            Value::Array(items) => {
+                let index: usize = parts[i + 1].parse().map_err(|_| format!("{} is a list; index it ({}.0)", at, at))?;
+                match items.get_mut(index) {
+                    Some(Value::Table(t)) if i + 2 < parts.len() => {
+                        // Skip the index part
+                        return set(t, &parts[i + 2..].join("."), value);
+                    }
+                    Some(item) if i + 2 == parts.len() => {
+                        *item = value;
+                        return Ok(());
+                    }
+                    Some(_) => return Err(format!("{}.{} is not a table", at, index)),
+                    None => return Err(format!("{} has no entry {}", at, index)),
+                }
+            }
+            _ => return Err(format!("{} is not a table", at)),
+        };
+    }
+    here.insert(last.to_string(), value);
+    Ok(())
+}
@@ -0,0 +1,199 @@
+//! `--feeds https://…/team.toml` (or `.opml`): one feed list shared by a team, downloaded at
+//! every start and kept in the cache directory, so a start without the network uses the last
+//! copy. A config can be pinned: `--feeds-sha256` requires the file to have that SHA-256,
+//! `--feeds-key` (an Ed25519 public key) requires `<url>.sig` to hold the key's signature of
+//! it. Only a pinned config is trusted like a local one; one that isn't is fetched with
+//! `[fetch] strict = true` and may not set anything in [`PINNED_ONLY`] (see [`check_unpinned`]).
+use crate::error::{Error, Result};
+use crate::news::{build_client, max_feed_bytes, read_body_capped};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ring::digest::{digest, SHA256};
+use ring::signature::{UnparsedPublicKey, ED25519};
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+use url::Url;
+
+/// Settings an unpinned config can't have: they run programs, write files, read more config
+/// files, or send credentials (a `$NEWS_CLI_*` default) to a server the config names.
+pub const PINNED_ONLY: &[&str] =
+    &["include", "open_command", "player", "speak_command", "pdf_command", "export_dir", "sync", "briefing", "translation", "digest"];
+
+/// Feed kinds an unpinned config can't have: programs to run, and mailboxes logged into with
+/// `$NEWS_CLI_IMAP_PASSWORD`.
+pub const PINNED_ONLY_KINDS: &[&str] = &["command", "imap"];
+
+/// What a downloaded config must match before it is used.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Verify {
+    /// SHA-256 of the file, in hex
+    pub sha256: Option<String>,
+    /// Ed25519 public key (the 32 bytes, base64) whose signature `<url>.sig` holds
+    pub key: Option<String>,
+}
+
+/// The local copy of a remote config.
+#[derive(Debug)]
+pub struct Remote {
+    pub path: PathBuf,
+    /// Why the cached copy is used instead of a fresh one
+    pub offline: Option<Error>,
+}
+
+impl Verify {
+    /// Whether a checksum or key vouches for the file.
+    pub fn is_pinned(&self) -> bool {
+        self.sha256.is_some() || self.key.is_some()
+    }
+}
+
+/// Whether `--feeds` names a remote config.toml or OPML list (rather than a single feed).
+pub fn is_remote(feeds: &str) -> bool {
+    let Ok(url) = Url::parse(feeds) else { return false };
+    let path = url.path().to_ascii_lowercase();
+    matches!(url.scheme(), "http" | "https") && (path.ends_with(".toml") || path.ends_with(".opml"))
+}
+
+/// Download the config at `url`, check it against `verify` (and [`check_unpinned`] without
+/// pins) and cache it. When the server can't be reached the cached copy is used, if it still
+/// passes; a file that fails is an [`Error::Config`] either way. `local_http` lets tests
+/// serve the file over plain http from this machine.
+pub async fn fetch(url: &str, verify: &Verify, local_http: bool) -> Result<Remote> {
+    let parsed = Url::parse(url).map_err(|e| Error::Config(format!("invalid --feeds URL {}: {}", url, e)))?;
+    let local = local_http && matches!(parsed.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+    if parsed.scheme() != "https" && !local {
+        return Err(Error::Config(format!("remote configs are only fetched over https: {}", url)));
+    }
+    let path = cache_path(&parsed)?;
+    match download(url, verify).await {
+        Ok(body) => {
+            check(&body.config, body.signature.as_deref(), verify)?;
+            if !verify.is_pinned() {
+                check_unpinned(url, &path, &body.config)?;
+            }
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            crate::util::atomic::write_atomic(&path, &body.config, false)?;
+            Ok(Remote { path, offline: None })
+        }
+        Err(e) if e.is_transient() => {
+            let Ok(cached) = std::fs::read(&path) else { return Err(e) };
+            // The signature isn't cached; a pinned key has to be checked against a fresh one
+            if verify.key.is_some() {
+                return Err(e);
+            }
+            check(&cached, None, verify)?;
+            if !verify.is_pinned() {
+                check_unpinned(url, &path, &cached)?;
+            }
+            Ok(Remote { path, offline: Some(e) })
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Check `config` against `verify`: its SHA-256, and `signature` (base64 or the raw 64
+/// bytes) by the key.
+pub fn check(config: &[u8], signature: Option<&[u8]>, verify: &Verify) -> Result<()> {
+    if let Some(expected) = &verify.sha256 {
+        let actual: String = digest(&SHA256, config).as_ref().iter().map(|b| format!("{:02x}", b)).collect();
+        if !actual.eq_ignore_ascii_case(expected.trim()) {
+            return Err(Error::Config(format!("remote config checksum mismatch: expected {}, got {}", expected.trim(), actual)));
+        }
+    }
+    if let Some(key) = &verify.key {
+        let key = STANDARD
+            .decode(key.trim())
+            .ok()
+            .filter(|k| k.len() == 32)
+            .ok_or_else(|| Error::Config("--feeds-key must be a base64 Ed25519 public key (32 bytes)".into()))?;
+        let signature = signature.ok_or_else(|| Error::Config("remote config has no signature (.sig)".into()))?;
+        let signature = match signature.len() {
+            64 => signature.to_vec(),
+            _ => STANDARD
+                .decode(String::from_utf8_lossy(signature).trim())
+                .map_err(|_| Error::Config("remote config signature (.sig) is neither base64 nor 64 bytes".into()))?,
+        };
+        UnparsedPublicKey::new(&ED25519, &key)
+            .verify(config, &signature)
+            .map_err(|_| Error::Config("remote config signature doesn't match --feeds-key".into()))?;
+    }
+    Ok(())
+}
+
+/// Refuse a config from `url` (cached at `path`) that nothing pins if it sets anything in
+/// [`PINNED_ONLY`], a feed of a [`PINNED_ONLY_KINDS`] kind or with a `command`, a GitHub
+/// feed's `instance` (the API server `$GITHUB_TOKEN` is sent to), or a `"secret:…"` value
+/// (which could send a keyring secret to a server of its choosing). An OPML list only names
+/// feeds, which strict mode keeps to the internet.
+pub fn check_unpinned(url: &str, path: &Path, config: &[u8]) -> Result<()> {
+    if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("opml")) {
+        return Ok(());
+    }
+    // Invalid TOML is reported when the config is loaded
+    let Ok(table) = String::from_utf8_lossy(config).parse::<Table>() else { return Ok(()) };
+    let mut refused: Vec<String> = PINNED_ONLY.iter().filter(|k| table.contains_key(**k)).map(|k| k.to_string()).collect();
+    for feed in table.get("feeds").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_table) {
+        let name = feed.get("name").and_then(Value::as_str).unwrap_or("?");
+        let kind = feed.get("kind").and_then(Value::as_str);
+        if let Some(kind) = kind.filter(|k| PINNED_ONLY_KINDS.contains(k)) {
+            refused.push(format!("feeds \"{}\" kind = \"{}\"", name, kind));
+        } else if feed.contains_key("command") {
+            refused.push(format!("feeds \"{}\" command", name));
+        } else if kind == Some("github") && feed.contains_key("instance") {
+            refused.push(format!("feeds \"{}\" instance", name));
+        }
+    }
+    secrets(&Value::Table(table), "", &mut refused);
+    if refused.is_empty() {
+        return Ok(());
+    }
+    Err(Error::Config(format!(
+        "remote config {} can't set {} unless it is pinned with --feeds-sha256 or --feeds-key",
+        url,
+        refused.join(", ")
+    )))
+}
+
+// The keys under `at` holding a "secret:" value
+fn secrets(value: &Value, at: &str, found: &mut Vec<String>) {
+    match value {
+        Value::String(s) if s.starts_with("secret:") => found.push(format!("{} = \"secret:…\"", at)),
+        Value::Table(t) => {
+            for (key, value) in t {
+                secrets(value, &if at.is_empty() { key.clone() } else { format!("{}.{}", at, key) }, found);
+            }
+        }
+        Value::Array(items) => {
+            for (i, value) in items.iter().enumerate() {
+                secrets(value, &format!("{}.{}", at, i), found);
+            }
+        }
+        _ => {}
+    }
+}
+
+struct Download {
+    config: Vec<u8>,
+    signature: Option<Vec<u8>>,
+}
+
+async fn download(url: &str, verify: &Verify) -> Result<Download> {
+    let client = build_client()?;
+    let config = read_body_capped(client.get(url).send().await?.error_for_status()?, max_feed_bytes()).await?;
+    let signature = match verify.key {
+        Some(_) => Some(read_body_capped(client.get(format!("{}.sig", url)).send().await?.error_for_status()?, 1024).await?),
+        None => None,
+    };
+    Ok(Download { config, signature })
+}
+
+// `<cache>/remote/<hash of the URL>-<file name>`, keeping the .toml or .opml extension
+fn cache_path(url: &Url) -> Result<PathBuf> {
+    let dir = crate::paths::cache_dir()
+        .ok_or_else(|| Error::Config("no cache directory for the remote config (set HOME)".into()))?;
+    let hash: String = digest(&SHA256, url.as_str().as_bytes()).as_ref()[..8].iter().map(|b| format!("{:02x}", b)).collect();
+    let name = url.path_segments().and_then(|mut s| s.next_back()).unwrap_or("config.toml");
+    let name: String = name.chars().map(|c| if c.is_alphanumeric() || c == '.' || c == '-' { c } else { '_' }).collect();
+    Ok(dir.join("remote").join(format!("{}-{}", hash, name)))
+}
@@ -0,0 +1,50 @@
+//! When a feed is worth fetching: `days` and `hours` on a `[[feeds]]` entry, e.g. market
+//! news on weekdays only, or a morning briefing between 6 and 10. Times are on the clock of
+//! `display_timezone` (else the local timezone).
+
+const DAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+/// `days` as the weekdays it allows (index 0 = Monday): "weekdays", "weekends", a range
+/// ("mon-fri", "fri-mon" wraps) or a list of either ("mon,wed-fri").
+pub fn parse_days(s: &str) -> Option<[bool; 7]> {
+    let day = |name: &str| DAYS.iter().position(|d| name.trim().to_lowercase().starts_with(d));
+    let mut allowed = [false; 7];
+    for part in s.split(',') {
+        let (first, last) = match part.trim().to_lowercase().as_str() {
+            "weekdays" => (0, 4),
+            "weekends" => (5, 6),
+            part => match part.split_once('-') {
+                Some((a, b)) => (day(a)?, day(b)?),
+                None => (day(part)?, day(part)?),
+            },
+        };
+        let mut d = first;
+        loop {
+            allowed[d] = true;
+            if d == last {
+                break;
+            }
+            d = (d + 1) % 7;
+        }
+    }
+    Some(allowed)
+}
+
+/// `hours` as the start and end hour: "6-10" is from 06:00 until 10:00, "22-2" runs past
+/// midnight. Start and end are 0 to 24 and differ.
+pub fn parse_hours(s: &str) -> Option<(u32, u32)> {
+    let (a, b) = s.split_once('-')?;
+    let (start, end) = (a.trim().parse::<u32>().ok()?, b.trim().parse::<u32>().ok()?);
+    (start <= 24 && end <= 24 && start % 24 != end % 24).then_some((start % 24, end % 24))
+}
+
+/// Whether `hour` (0-23) on `day` (0 = Monday) is within `days` and `hours`; settings that
+/// don't parse (see `config check`) don't restrict anything.
+pub fn allows(days: Option<&str>, hours: Option<&str>, day: u32, hour: u32) -> bool {
+    let day_ok = days.and_then(parse_days).is_none_or(|allowed| allowed[day as usize % 7]);
+    let hour_ok = hours.and_then(parse_hours).is_none_or(|(start, end)| match start < end {
+        true => (start..end).contains(&hour),
+        false => hour >= start || hour < end,
+    });
+    day_ok && hour_ok
+}
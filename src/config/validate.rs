@@ -0,0 +1,911 @@
+use super::{AppConfig, ConfirmOpen, FeedOrder, ListTimes, FetchLimits, FxConfig, CATEGORY_ICONS, STATS_ITEMS};
+use crate::alerts;
+use crate::digest::DigestFormat;
+use crate::news::provider::ProviderRegistry;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use url::Url;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A semantic problem in an otherwise well-formed config file.
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub severity: Severity,
+    /// Where the problem is, e.g. `feeds[2].url (line 14)`.
+    pub location: String,
+    pub message: String,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}: {}: {}", level, self.location, self.message)
+    }
+}
+
+/// Checks that serde can't express: duplicates, URL syntax, empty values.
+/// `source` is the raw TOML text, used to attach line numbers to `[[feeds]]` entries.
+pub fn validate(cfg: &AppConfig, source: Option<&str>) -> Vec<Issue> {
+    let mut issues: Vec<Issue> = Vec::new();
+    let feed_lines = source.map(feed_header_lines).unwrap_or_default();
+    let feed_loc = |i: usize, field: &str| match feed_lines.get(i) {
+        Some(line) => format!("feeds[{}].{} (line {})", i, field, line),
+        None => format!("feeds[{}].{}", i, field),
+    };
+
+    if cfg.feeds.is_empty() {
+        issues.push(Issue {
+            severity: Severity::Warning,
+            location: "feeds".into(),
+            message: "no feeds configured; the News screen will be empty".into(),
+        });
+    }
+
+    if let Some(fetch) = &cfg.fetch {
+        issues.extend(check_limits(fetch, |field| format!("fetch.{}", field)));
+    }
+    if cfg.max_title_width.is_some_and(|w| w < 10) {
+        issues.push(Issue {
+            severity: Severity::Error,
+            location: "max_title_width".into(),
+            message: "max_title_width must be at least 10".into(),
+        });
+    }
+    if cfg.max_age_days == Some(0) {
+        issues.push(Issue {
+            severity: Severity::Error,
+            location: "max_age_days".into(),
+            message: "max_age_days must be at least 1".into(),
+        });
+    }
+    if cfg.archive_paywalled == Some(true) && !cfg.feeds.iter().any(|f| f.paywalled) {
+        issues.push(Issue {
+            severity: Severity::Warning,
+            location: "archive_paywalled".into(),
+            message: "no feed sets paywalled = true, so archive_paywalled changes nothing".into(),
+        });
+    }
+    if let Some(template) = &cfg.archive_url
+        && !matches!(
+            Url::parse(&super::archive_link(template, "https://example.com/")).map(|u| u.scheme().to_string()).as_deref(),
+            Ok("http" | "https")
+        )
+    {
+        issues.push(Issue {
+            severity: Severity::Error,
+            location: "archive_url".into(),
+            message: "archive_url must be an http(s) URL, e.g. \"https://archive.ph/newest/{url}\"".into(),
+        });
+    }
+
+    let mut names: HashMap<&str, usize> = HashMap::new();
+    let mut urls: HashMap<&str, usize> = HashMap::new();
+    for (i, f) in cfg.feeds.iter().enumerate() {
+        if let Some(icon) = &f.icon
+            && (icon.chars().count() > 8 || icon.chars().any(|c| c.is_control()))
+        {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: feed_loc(i, "icon"),
+                message: "icon must be an emoji or a short tag (at most 8 characters)".into(),
+            });
+        }
+        if let Some(category) = &f.category
+            && f.icon.is_none()
+            && f.icon().is_none()
+        {
+            let known: Vec<&str> = CATEGORY_ICONS.iter().map(|(c, _)| *c).collect();
+            issues.push(Issue {
+                severity: Severity::Warning,
+                location: feed_loc(i, "category"),
+                message: format!("no icon for category \"{}\" (known: {})", category, known.join(", ")),
+            });
+        }
+        let name = f.name.trim();
+        if name.is_empty() {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: feed_loc(i, "name"),
+                message: "feed name must not be empty".into(),
+            });
+        } else if let Some(first) = names.insert(name, i) {
+            // Stories are grouped by feed name, so duplicates would merge two feeds into one section
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: feed_loc(i, "name"),
+                message: format!("duplicate feed name \"{}\" (already used by feeds[{}])", name, first),
+            });
+        }
+        issues.extend(check_limits(&f.limits(), |field| feed_loc(i, field)));
+        if f.weight.is_some_and(|w| !(w.is_finite() && w >= 0.0)) {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: feed_loc(i, "weight"),
+                message: "weight must be a number of at least 0".into(),
+            });
+        }
+        if f.max_age_days == Some(0) {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: feed_loc(i, "max_age_days"),
+                message: "max_age_days must be at least 1".into(),
+            });
+        }
+        if let Some(days) = &f.days
+            && super::schedule::parse_days(days).is_none()
+        {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: feed_loc(i, "days"),
+                message: format!("\"{}\" is not a list of days (e.g. \"weekdays\", \"mon-fri\", \"sat,sun\")", days),
+            });
+        }
+        if let Some(hours) = &f.hours
+            && super::schedule::parse_hours(hours).is_none()
+        {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: feed_loc(i, "hours"),
+                message: format!("\"{}\" is not an hour range (e.g. \"6-10\", or \"22-2\" past midnight)", hours),
+            });
+        }
+
+        match f.kind() {
+            "rss" => {}
+            "command" => {
+                if blank(&f.command) {
+                    issues.push(Issue {
+                        severity: Severity::Error,
+                        location: feed_loc(i, "command"),
+                        message: "kind = \"command\" needs a command to run".into(),
+                    });
+                }
+                continue;
+            }
+            kind @ ("mastodon" | "bluesky") => {
+                if kind == "mastodon" && blank(&f.instance) {
+                    issues.push(Issue {
+                        severity: Severity::Error,
+                        location: feed_loc(i, "instance"),
+                        message: "kind = \"mastodon\" needs an instance, e.g. \"mastodon.social\"".into(),
+                    });
+                }
+                if blank(&f.account) {
+                    issues.push(Issue {
+                        severity: Severity::Error,
+                        location: feed_loc(i, "account"),
+                        message: format!("kind = \"{}\" needs an account", kind),
+                    });
+                }
+                continue;
+            }
+            "youtube" => {
+                let account = f.account.as_deref().unwrap_or("").trim();
+                if !account.starts_with("UC") && !account.starts_with('@') {
+                    issues.push(Issue {
+                        severity: Severity::Error,
+                        location: feed_loc(i, "account"),
+                        message: "kind = \"youtube\" needs a channel ID (UC...) or handle (@name) as account".into(),
+                    });
+                }
+                continue;
+            }
+            "github" => {
+                let account = f.account.as_deref().unwrap_or("").trim();
+                if account == "notifications" {
+                    if blank(&f.token) && std::env::var_os("GITHUB_TOKEN").is_none() {
+                        issues.push(Issue {
+                            severity: Severity::Warning,
+                            location: feed_loc(i, "token"),
+                            message: "notifications need a token; set token or $GITHUB_TOKEN".into(),
+                        });
+                    }
+                } else if account.is_empty() {
+                    issues.push(Issue {
+                        severity: Severity::Error,
+                        location: feed_loc(i, "account"),
+                        message: "kind = \"github\" needs repositories (owner/repo) or \"notifications\" as account".into(),
+                    });
+                } else if let Some(bad) = account
+                    .split([',', ' '])
+                    .filter(|r| !r.is_empty())
+                    .find(|r| r.split('/').filter(|p| !p.is_empty()).count() != 2)
+                {
+                    issues.push(Issue {
+                        severity: Severity::Error,
+                        location: feed_loc(i, "account"),
+                        message: format!("\"{}\" is not an owner/repo name", bad),
+                    });
+                }
+                continue;
+            }
+            "imap" => {
+                if blank(&f.instance) {
+                    issues.push(Issue {
+                        severity: Severity::Error,
+                        location: feed_loc(i, "instance"),
+                        message: "kind = \"imap\" needs the IMAP server as instance, e.g. \"imap.example.com\"".into(),
+                    });
+                }
+                if blank(&f.account) {
+                    issues.push(Issue {
+                        severity: Severity::Error,
+                        location: feed_loc(i, "account"),
+                        message: "kind = \"imap\" needs the user name as account".into(),
+                    });
+                }
+                if blank(&f.token) && std::env::var_os("NEWS_CLI_IMAP_PASSWORD").is_none() {
+                    issues.push(Issue {
+                        severity: Severity::Warning,
+                        location: feed_loc(i, "token"),
+                        message: "no password; set token or $NEWS_CLI_IMAP_PASSWORD".into(),
+                    });
+                }
+                continue;
+            }
+            kind => {
+                // Providers registered by library users are not known here
+                let known = ProviderRegistry::default().kinds();
+                if !known.contains(&kind) {
+                    issues.push(Issue {
+                        severity: Severity::Error,
+                        location: feed_loc(i, "kind"),
+                        message: format!("unknown feed kind \"{}\" (expected one of: {})", kind, known.join(", ")),
+                    });
+                }
+                continue;
+            }
+        }
+        let unused = [
+            ("command", &f.command),
+            ("instance", &f.instance),
+            ("account", &f.account),
+            ("token", &f.token),
+            ("folder", &f.folder),
+        ];
+        for (field, _) in unused.iter().filter(|(_, v)| v.is_some()) {
+            issues.push(Issue {
+                severity: Severity::Warning,
+                location: feed_loc(i, field),
+                message: format!("{} is not used by RSS feeds and will be ignored", field),
+            });
+        }
+
+        let url = f.url.trim();
+        if url.is_empty() {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: feed_loc(i, "url"),
+                message: "feed url must not be empty".into(),
+            });
+            continue;
+        }
+        if let Some(first) = urls.insert(url, i) {
+            issues.push(Issue {
+                severity: Severity::Warning,
+                location: feed_loc(i, "url"),
+                message: format!("duplicate feed url (already used by feeds[{}])", first),
+            });
+        }
+        if let Some(problem) = check_feed_url(url) {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: feed_loc(i, "url"),
+                message: problem,
+            });
+        }
+    }
+
+    if let Some(images) = &cfg.images
+        && !matches!(images.as_str(), "auto" | "off" | "kitty" | "iterm" | "sixel")
+    {
+        issues.push(Issue {
+            severity: Severity::Error,
+            location: "images".into(),
+            message: format!("unknown image mode \"{}\" (expected auto, off, kitty, iterm or sixel)", images),
+        });
+    }
+
+    if let Some(tz) = &cfg.display_timezone
+        && !tz.eq_ignore_ascii_case("local")
+        && let Err(e) = crate::util::dates::parse_zone(tz)
+    {
+        issues.push(Issue {
+            severity: Severity::Error,
+            location: "display_timezone".into(),
+            message: e.to_string(),
+        });
+    }
+    if let Some(format) = &cfg.date_format
+        && let Err(e) = crate::util::dates::check_format(format)
+    {
+        issues.push(Issue {
+            severity: Severity::Error,
+            location: "date_format".into(),
+            message: e.to_string(),
+        });
+    }
+
+    if let Some(language) = &cfg.language
+        && crate::i18n::supported(language).is_none()
+    {
+        issues.push(Issue {
+            severity: Severity::Error,
+            location: "language".into(),
+            message: format!("no messages for language \"{}\" (expected {})", language, crate::i18n::LOCALES.join(" or ")),
+        });
+    }
+
+    if let Some(order) = &cfg.feed_order
+        && FeedOrder::parse(order).is_none()
+    {
+        issues.push(Issue {
+            severity: Severity::Error,
+            location: "feed_order".into(),
+            message: format!(
+                "unknown feed order \"{}\" (expected \"config\", \"alphabetical\", \"most_new\" or \"pinned\")",
+                order
+            ),
+        });
+    }
+
+    if let Some(times) = &cfg.list_times
+        && ListTimes::parse(times).is_none()
+    {
+        issues.push(Issue {
+            severity: Severity::Error,
+            location: "list_times".into(),
+            message: format!("unknown list_times \"{}\" (expected \"relative\", \"absolute\" or \"off\")", times),
+        });
+    }
+
+    if let Some(confirm) = &cfg.confirm_open
+        && ConfirmOpen::parse(confirm).is_none()
+    {
+        issues.push(Issue {
+            severity: Severity::Error,
+            location: "confirm_open".into(),
+            message: format!("unknown confirm_open \"{}\" (expected \"off\", \"unusual\" or \"always\")", confirm),
+        });
+    }
+
+    for (i, p) in cfg.tracking_params.iter().enumerate() {
+        if p.trim().is_empty() || p.trim() == "*" {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: format!("tracking_params[{}]", i),
+                message: "pattern must name a parameter (\"*\" alone would strip every query)".into(),
+            });
+        }
+    }
+
+    if let Some(stats) = &cfg.stats {
+        let vectors = [
+            ("stats.statscan_population_vector", &stats.statscan_population_vector),
+            ("stats.housing_starts_vector", &stats.housing_starts_vector),
+        ];
+        for (key, value) in vectors {
+            if let Some(v) = value {
+                let digits = v.trim_start_matches(['v', 'V']);
+                if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+                    issues.push(Issue {
+                        severity: Severity::Error,
+                        location: key.into(),
+                        message: format!("\"{}\" is not a StatsCan vector id (expected e.g. \"v1\" or \"1\")", v),
+                    });
+                }
+            }
+        }
+        for (i, pair) in stats.fx.iter().flat_map(|fx| fx.pairs.iter()).enumerate() {
+            if FxConfig::parse_pair(pair).is_none() {
+                issues.push(Issue {
+                    severity: Severity::Error,
+                    location: format!("stats.fx.pairs[{}]", i),
+                    message: format!("\"{}\" is not a currency pair like \"USD/CAD\"", pair),
+                });
+            }
+        }
+        let mut listed: Vec<&str> = Vec::new();
+        for (g, group) in stats.layout.iter().flatten().enumerate() {
+            for (i, item) in group.items.iter().enumerate() {
+                let location = format!("stats.layout[{}].items[{}]", g, i);
+                if !STATS_ITEMS.contains(&item.as_str()) {
+                    issues.push(Issue {
+                        severity: Severity::Error,
+                        location,
+                        message: format!("unknown stats item \"{}\" (expected one of {})", item, STATS_ITEMS.join(", ")),
+                    });
+                } else if listed.contains(&item.as_str()) {
+                    issues.push(Issue {
+                        severity: Severity::Warning,
+                        location,
+                        message: format!("\"{}\" is already listed; it is shown only the first time", item),
+                    });
+                } else {
+                    listed.push(item);
+                }
+            }
+        }
+        for name in stats.labels.keys().filter(|k| !STATS_ITEMS.contains(&k.as_str())) {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: format!("stats.labels.{}", name),
+                message: format!("unknown stats item \"{}\" (expected one of {})", name, STATS_ITEMS.join(", ")),
+            });
+        }
+        if let Some(crypto) = &stats.crypto {
+            for (i, coin) in crypto.coins.iter().enumerate() {
+                if coin.trim().is_empty() || coin.contains([',', ' ']) {
+                    issues.push(Issue {
+                        severity: Severity::Error,
+                        location: format!("stats.crypto.coins[{}]", i),
+                        message: format!("\"{}\" is not a CoinGecko coin id like \"bitcoin\"", coin),
+                    });
+                }
+            }
+            let currency = crypto.currency();
+            if currency.is_empty() || !currency.chars().all(|c| c.is_ascii_alphanumeric()) {
+                issues.push(Issue {
+                    severity: Severity::Error,
+                    location: "stats.crypto.currency".into(),
+                    message: format!("\"{}\" is not a currency code like \"usd\"", currency),
+                });
+            }
+        }
+    }
+
+    if let Some(theme) = &cfg.theme {
+        let slots = [
+            ("theme.header".to_string(), &theme.header),
+            ("theme.new_badge".to_string(), &theme.new_badge),
+            ("theme.selection".to_string(), &theme.selection),
+            ("theme.positive".to_string(), &theme.positive),
+            ("theme.negative".to_string(), &theme.negative),
+        ];
+        let feed_slots = theme
+            .feeds
+            .iter()
+            .map(|(name, spec)| (format!("theme.feeds.\"{}\"", name), spec));
+        let specs = slots
+            .iter()
+            .filter_map(|(k, v)| v.as_ref().map(|v| (k.clone(), v)))
+            .chain(feed_slots);
+        for (key, spec) in specs {
+            if let Some(bad) = unknown_style_part(spec) {
+                issues.push(Issue {
+                    severity: Severity::Error,
+                    location: key,
+                    message: format!("unknown color or attribute \"{}\" in \"{}\"", bad, spec),
+                });
+            }
+        }
+        for name in theme.feeds.keys() {
+            if !cfg.feeds.iter().any(|f| &f.name == name) {
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    location: format!("theme.feeds.\"{}\"", name),
+                    message: "no feed with this name is configured".into(),
+                });
+            }
+        }
+    }
+
+    if let Some(sync) = &cfg.sync {
+        if sync.backend != "miniflux" {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "sync.backend".into(),
+                message: format!("unsupported sync backend \"{}\" (expected \"miniflux\")", sync.backend),
+            });
+        }
+        if !matches!(Url::parse(&sync.url).map(|u| u.scheme().to_string()).as_deref(), Ok("http" | "https")) {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "sync.url".into(),
+                message: format!("\"{}\" is not an absolute http(s) URL", sync.url),
+            });
+        }
+        if blank(&sync.token) && std::env::var_os("NEWS_CLI_SYNC_TOKEN").is_none() {
+            issues.push(Issue {
+                severity: Severity::Warning,
+                location: "sync.token".into(),
+                message: "no API token; set token or $NEWS_CLI_SYNC_TOKEN".into(),
+            });
+        }
+    }
+
+    if let Some(store) = cfg.secrets.as_ref().and_then(|s| s.store.as_deref())
+        && !matches!(store, "keyring" | "file")
+    {
+        issues.push(Issue {
+            severity: Severity::Error,
+            location: "secrets.store".into(),
+            message: format!("unknown secrets store \"{}\" (expected \"keyring\" or \"file\")", store),
+        });
+    }
+
+    let mut alert_names: HashMap<&str, usize> = HashMap::new();
+    for (i, a) in cfg.alerts.iter().enumerate() {
+        let name = a.name.trim();
+        if name.is_empty() {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: format!("alerts[{}].name", i),
+                message: "alert name must not be empty".into(),
+            });
+        } else if let Some(first) = alert_names.insert(name, i) {
+            // Hits are stored by name, so two rules would share one history
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: format!("alerts[{}].name", i),
+                message: format!("duplicate alert name \"{}\" (already used by alerts[{}])", name, first),
+            });
+        }
+        if a.keywords.iter().all(|k| k.trim().is_empty()) && a.regex.is_empty() {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: format!("alerts[{}]", i),
+                message: "alert needs at least one keyword or regex".into(),
+            });
+        }
+        for (j, pattern) in a.regex.iter().enumerate() {
+            if let Err(e) = Regex::new(pattern) {
+                issues.push(Issue {
+                    severity: Severity::Error,
+                    location: format!("alerts[{}].regex[{}]", i, j),
+                    message: format!("invalid regex: {}", e),
+                });
+            }
+        }
+        if let Some(webhook) = &a.webhook
+            && !matches!(Url::parse(webhook).map(|u| u.scheme().to_string()).as_deref(), Ok("http" | "https"))
+        {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: format!("alerts[{}].webhook", i),
+                message: format!("\"{}\" is not an absolute http(s) URL", webhook),
+            });
+        }
+        if let Some(ntfy) = &a.ntfy
+            && alerts::ntfy_target(ntfy).is_none()
+        {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: format!("alerts[{}].ntfy", i),
+                message: format!("\"{}\" is neither a topic name nor an http(s) topic URL", ntfy),
+            });
+        }
+    }
+
+    if let Some(reading) = &cfg.reading_stats
+        && reading.periods.contains(&0)
+    {
+        issues.push(Issue {
+            severity: Severity::Error,
+            location: "reading_stats.periods".into(),
+            message: "periods are days and must be at least 1 (all time is always shown)".into(),
+        });
+    }
+
+    if let Some(calendar) = &cfg.calendar {
+        if calendar.countries.is_empty() {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "calendar.countries".into(),
+                message: "list at least one country, by currency code (e.g. [\"USD\", \"CAD\"])".into(),
+            });
+        }
+        for (i, country) in calendar.countries.iter().enumerate() {
+            let code = country.trim();
+            if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+                issues.push(Issue {
+                    severity: Severity::Error,
+                    location: format!("calendar.countries[{}]", i),
+                    message: format!("\"{}\" is not a currency code like \"USD\"", country),
+                });
+            }
+        }
+        if let Some(impact) = &calendar.impact
+            && crate::calendar::Impact::parse(impact).is_none()
+        {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "calendar.impact".into(),
+                message: format!("unknown impact \"{}\" (expected \"low\", \"medium\" or \"high\")", impact),
+            });
+        }
+        if let Some(url) = &calendar.url
+            && !matches!(Url::parse(url).map(|u| u.scheme().to_string()).as_deref(), Ok("http" | "https"))
+        {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "calendar.url".into(),
+                message: format!("\"{}\" is not an absolute http(s) URL", url),
+            });
+        }
+    }
+
+    if let Some(weather) = &cfg.weather {
+        if !(-90.0..=90.0).contains(&weather.latitude) {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "weather.latitude".into(),
+                message: format!("{} is not a latitude (-90 to 90)", weather.latitude),
+            });
+        }
+        if !(-180.0..=180.0).contains(&weather.longitude) {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "weather.longitude".into(),
+                message: format!("{} is not a longitude (-180 to 180)", weather.longitude),
+            });
+        }
+        if let Some(units) = &weather.units
+            && !matches!(units.as_str(), "metric" | "imperial")
+        {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "weather.units".into(),
+                message: format!("unknown units \"{}\" (expected \"metric\" or \"imperial\")", units),
+            });
+        }
+        if let Some(url) = &weather.url
+            && !matches!(Url::parse(url).map(|u| u.scheme().to_string()).as_deref(), Ok("http" | "https"))
+        {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "weather.url".into(),
+                message: format!("\"{}\" is not an absolute http(s) URL", url),
+            });
+        }
+    }
+
+    if let Some(briefing) = &cfg.briefing {
+        if !matches!(Url::parse(&briefing.api_base).map(|u| u.scheme().to_string()).as_deref(), Ok("http" | "https")) {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "briefing.api_base".into(),
+                message: format!("\"{}\" is not an absolute http(s) URL", briefing.api_base),
+            });
+        }
+        if briefing.model.trim().is_empty() {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "briefing.model".into(),
+                message: "model must not be empty".into(),
+            });
+        }
+        if briefing.top == Some(0) {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "briefing.top".into(),
+                message: "top must be at least 1".into(),
+            });
+        }
+    }
+
+    if let Some(digest) = &cfg.digest {
+        if let Some(format) = &digest.format
+            && DigestFormat::parse(format).is_none()
+        {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "digest.format".into(),
+                message: format!("unknown format \"{}\" (expected \"markdown\" or \"html\")", format),
+            });
+        }
+        for (i, to) in digest.to.iter().enumerate() {
+            if !to.contains('@') {
+                issues.push(Issue {
+                    severity: Severity::Error,
+                    location: format!("digest.to[{}]", i),
+                    message: format!("\"{}\" is not an email address", to),
+                });
+            }
+        }
+        if !digest.to.is_empty() && blank(&digest.smtp) && blank(&digest.sendmail) {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "digest.to".into(),
+                message: "recipients need a way to send mail; set smtp or sendmail".into(),
+            });
+        }
+        if digest.to.is_empty() && !(blank(&digest.smtp) && blank(&digest.sendmail)) {
+            issues.push(Issue {
+                severity: Severity::Warning,
+                location: "digest.to".into(),
+                message: "no recipients, so nothing is mailed".into(),
+            });
+        }
+        if let Some(smtp) = &digest.smtp
+            && smtp.rsplit_once(':').is_some_and(|(_, port)| port.parse::<u16>().is_err())
+        {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "digest.smtp".into(),
+                message: format!("\"{}\" is not host or host:port", smtp),
+            });
+        }
+    }
+
+    if let Some(scoring) = &cfg.scoring {
+        if scoring.half_life_hours.is_some_and(|h| !(h.is_finite() && h > 0.0)) {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "scoring.half_life_hours".into(),
+                message: "half_life_hours must be greater than 0".into(),
+            });
+        }
+        if scoring.points_weight.is_some_and(|w| !(w.is_finite() && w >= 0.0)) {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "scoring.points_weight".into(),
+                message: "points_weight must be a number of at least 0".into(),
+            });
+        }
+        for (field, zero) in [("window_hours", scoring.window_hours == Some(0)), ("top", scoring.top == Some(0))] {
+            if zero {
+                issues.push(Issue {
+                    severity: Severity::Error,
+                    location: format!("scoring.{}", field),
+                    message: format!("{} must be at least 1", field),
+                });
+            }
+        }
+        for (word, boost) in &scoring.keywords {
+            if word.trim().is_empty() || !boost.is_finite() {
+                issues.push(Issue {
+                    severity: Severity::Error,
+                    location: format!("scoring.keywords.{}", word),
+                    message: "keywords need a non-empty word and a finite number".into(),
+                });
+            }
+        }
+    }
+
+    if let Some(t) = &cfg.translation {
+        if !matches!(t.backend.as_str(), "deepl" | "libretranslate") {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "translation.backend".into(),
+                message: format!("unsupported translation backend \"{}\" (expected \"deepl\" or \"libretranslate\")", t.backend),
+            });
+        }
+        match &t.url {
+            Some(url) if !matches!(Url::parse(url).map(|u| u.scheme().to_string()).as_deref(), Ok("http" | "https")) => {
+                issues.push(Issue {
+                    severity: Severity::Error,
+                    location: "translation.url".into(),
+                    message: format!("\"{}\" is not an absolute http(s) URL", url),
+                });
+            }
+            None if t.backend == "libretranslate" => issues.push(Issue {
+                severity: Severity::Error,
+                location: "translation.url".into(),
+                message: "LibreTranslate needs the url of an instance".into(),
+            }),
+            _ => {}
+        }
+        if t.backend == "deepl" && blank(&t.api_key) && std::env::var_os("NEWS_CLI_TRANSLATE_KEY").is_none() {
+            issues.push(Issue {
+                severity: Severity::Warning,
+                location: "translation.api_key".into(),
+                message: "DeepL needs an API key; set api_key or $NEWS_CLI_TRANSLATE_KEY".into(),
+            });
+        }
+        let codes = t.target.iter().map(|c| ("target".to_string(), c));
+        let codes = codes.chain(t.languages.iter().enumerate().map(|(i, c)| (format!("languages[{}]", i), c)));
+        for (field, code) in codes {
+            if code.len() != 2 || !code.chars().all(|c| c.is_ascii_lowercase()) {
+                issues.push(Issue {
+                    severity: Severity::Error,
+                    location: format!("translation.{}", field),
+                    message: format!("\"{}\" is not a two-letter language code like \"de\"", code),
+                });
+            }
+        }
+        if let Some(display) = &t.display
+            && !matches!(display.as_str(), "alongside" | "instead")
+        {
+            issues.push(Issue {
+                severity: Severity::Error,
+                location: "translation.display".into(),
+                message: format!("unknown display \"{}\" (expected \"alongside\" or \"instead\")", display),
+            });
+        }
+    }
+
+    issues
+}
+
+fn check_limits(limits: &FetchLimits, loc: impl Fn(&str) -> String) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let numbers = [
+        ("connect_timeout_secs", limits.connect_timeout_secs),
+        ("timeout_secs", limits.timeout_secs),
+        ("max_bytes", limits.max_bytes),
+    ];
+    for (field, _) in numbers.iter().filter(|(_, v)| *v == Some(0)) {
+        issues.push(Issue {
+            severity: Severity::Error,
+            location: loc(field),
+            message: format!("{} must be at least 1", field),
+        });
+    }
+    if let Some(rps) = limits.per_host_rps
+        && !(rps.is_finite() && rps > 0.0)
+    {
+        issues.push(Issue {
+            severity: Severity::Error,
+            location: loc("per_host_rps"),
+            message: "per_host_rps must be a positive number".into(),
+        });
+    }
+    if let Some(ua) = &limits.user_agent
+        && (ua.trim().is_empty() || ua.chars().any(|c| c.is_control()))
+    {
+        issues.push(Issue {
+            severity: Severity::Error,
+            location: loc("user_agent"),
+            message: "user_agent must be non-empty text without control characters".into(),
+        });
+    }
+    issues
+}
+
+fn blank(value: &Option<String>) -> bool {
+    value.as_deref().is_none_or(|v| v.trim().is_empty())
+}
+
+fn check_feed_url(url: &str) -> Option<String> {
+    if Path::new(url).is_file() {
+        return None;
+    }
+    match Url::parse(url) {
+        Ok(u) => match u.scheme() {
+            "http" | "https" => None,
+            other => Some(format!("unsupported URL scheme \"{}\" (expected http or https)", other)),
+        },
+        Err(url::ParseError::RelativeUrlWithoutBase) => {
+            Some(format!("\"{}\" is neither an existing file nor an absolute http(s) URL", url))
+        }
+        Err(e) => Some(format!("invalid URL: {}", e)),
+    }
+}
+
+// 1-based line numbers of each `[[feeds]]` header, in order
+fn feed_header_lines(source: &str) -> Vec<usize> {
+    source
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| l.trim_start().starts_with("[[feeds]]"))
+        .map(|(n, _)| n + 1)
+        .collect()
+}
+
+// First part of a dotted (console) style spec that would be silently ignored, if any
+fn unknown_style_part(spec: &str) -> Option<&str> {
+    const NAMED: &[&str] = &[
+        "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white", "bright",
+        "bold", "dim", "underlined", "blink", "blink_fast", "reverse", "hidden",
+        "strikethrough",
+    ];
+    spec.split('.').find(|part| {
+        let color = part.strip_prefix("on_").unwrap_or(part);
+        let known = part.parse::<u8>().is_ok()
+            || color.parse::<u8>().is_ok()
+            || NAMED.contains(part)
+            || (part.starts_with("on_") && NAMED[..9].contains(&color));
+        !known
+    })
+}
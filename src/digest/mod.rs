@@ -0,0 +1,186 @@
+//! `news-cli digest`: what went into earlier digests, and mailing a new one (via an SMTP
+//! server or a sendmail-compatible command), so a cron job can deliver new stories once a
+//! day. Rendering the digest itself is up to the caller.
+pub mod smtp;
+
+use crate::config::DigestConfig;
+use crate::news::Story;
+use crate::util::atomic::write_atomic;
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::{fs, path::PathBuf};
+use time::{format_description::well_known::Rfc2822, OffsetDateTime};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+// Links are forgotten after this long; their feeds have moved on by then
+const KEEP_SECS: i64 = 90 * 86_400;
+
+/// `from` when none is configured.
+pub const DEFAULT_FROM: &str = "news-cli <news-cli@localhost>";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestFormat {
+    Markdown,
+    Html,
+}
+
+impl DigestFormat {
+    /// "md"/"markdown" or "html".
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "md" | "markdown" => Some(DigestFormat::Markdown),
+            "html" => Some(DigestFormat::Html),
+            _ => None,
+        }
+    }
+}
+
+/// Links already sent in a digest, with when; `digest.json` in the state directory.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DigestLog {
+    /// UNIX epoch seconds of the last digest.
+    #[serde(default)]
+    pub last: Option<i64>,
+    #[serde(default)]
+    sent: HashMap<String, i64>,
+}
+
+impl DigestLog {
+    pub fn load() -> Self {
+        if let Some(path) = digest_file_path()
+            && let Ok(contents) = fs::read_to_string(&path)
+            && let Ok(log) = serde_json::from_str::<DigestLog>(&contents)
+        {
+            return log;
+        }
+        DigestLog::default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = digest_file_path() {
+            let json = serde_json::to_string_pretty(self)?;
+            write_atomic(&path, json.as_bytes(), false)?;
+        }
+        Ok(())
+    }
+
+    /// The stories no earlier digest had.
+    pub fn unsent(&self, mut stories: Vec<Story>) -> Vec<Story> {
+        stories.retain(|s| !self.sent.contains_key(&s.link));
+        stories
+    }
+
+    /// `stories` went out at `now`; also forgets links older than 90 days.
+    pub fn record(&mut self, stories: &[Story], now: i64) {
+        self.sent.retain(|_, at| now - *at < KEEP_SECS);
+        for s in stories {
+            self.sent.insert(s.link.clone(), now);
+        }
+        self.last = Some(now);
+    }
+}
+
+fn digest_file_path() -> Option<PathBuf> {
+    crate::paths::state_dir().map(|d| d.join("digest.json"))
+}
+
+/// The digest as an RFC 5322 message from `cfg.from` to `cfg.to`, sent at `now`.
+pub fn message(cfg: &DigestConfig, subject: &str, body: &str, format: DigestFormat, now: OffsetDateTime) -> Result<String> {
+    let content_type = match format {
+        DigestFormat::Markdown => "text/plain",
+        DigestFormat::Html => "text/html",
+    };
+    let mut msg = format!(
+        "From: {}\r\n\
+         To: {}\r\n\
+         Subject: {}\r\n\
+         Date: {}\r\n\
+         Message-ID: <{}.{}@news-cli>\r\n\
+         MIME-Version: 1.0\r\n\
+         Content-Type: {}; charset=utf-8\r\n\
+         Content-Transfer-Encoding: 8bit\r\n\
+         \r\n",
+        mailbox_header(cfg.from()),
+        cfg.to.join(", "),
+        header_text(subject),
+        now.format(&Rfc2822)?,
+        now.unix_timestamp(),
+        std::process::id(),
+        content_type,
+    );
+    for line in body.lines() {
+        msg.push_str(line);
+        msg.push_str("\r\n");
+    }
+    Ok(msg)
+}
+
+/// Deliver `message` to `cfg.to` through `cfg.smtp`, else `cfg.sendmail`.
+pub async fn send(cfg: &DigestConfig, message: &str) -> Result<()> {
+    if cfg.to.is_empty() {
+        bail!("no recipients (digest.to)");
+    }
+    if let Some(server) = cfg.smtp.as_deref() {
+        let password = cfg
+            .smtp_password
+            .clone()
+            .filter(|p| !p.is_empty())
+            .or_else(|| std::env::var("NEWS_CLI_SMTP_PASSWORD").ok());
+        let login = match (cfg.smtp_user.as_deref(), password.as_deref()) {
+            (Some(user), Some(password)) => Some((user, password)),
+            (Some(_), None) => bail!("no SMTP password (smtp_password or $NEWS_CLI_SMTP_PASSWORD)"),
+            (None, _) => None,
+        };
+        return smtp::send(server.trim(), login, address(cfg.from()), &cfg.to, message).await;
+    }
+    let Some(cmd) = cfg.sendmail.as_deref() else { bail!("no mail transport (digest.smtp or digest.sendmail)") };
+    let mut parts = cmd.split_whitespace();
+    let Some(program) = parts.next() else { bail!("digest.sendmail is empty") };
+    let mut child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .with_context(|| format!("failed to run {}", program))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(message.as_bytes()).await?;
+    }
+    let status = child.wait().await?;
+    if !status.success() {
+        bail!("{} exited with {}", program, status);
+    }
+    Ok(())
+}
+
+// "Name <addr@host>" -> "addr@host"
+fn address(mailbox: &str) -> &str {
+    match (mailbox.rfind('<'), mailbox.rfind('>')) {
+        (Some(open), Some(close)) if open < close => &mailbox[open + 1..close],
+        _ => mailbox.trim(),
+    }
+}
+
+// The display name may need encoding, the address never does
+fn mailbox_header(mailbox: &str) -> String {
+    match mailbox.rfind('<') {
+        Some(open) if !mailbox[..open].trim().is_empty() => {
+            format!("{} {}", header_text(mailbox[..open].trim()), &mailbox[open..])
+        }
+        _ => header_text(mailbox),
+    }
+}
+
+// Headers are ASCII; anything else goes in an RFC 2047 encoded word
+fn header_text(s: &str) -> String {
+    let s = s.replace(['\r', '\n'], " ");
+    if s.is_ascii() {
+        s
+    } else {
+        format!("=?utf-8?B?{}?=", STANDARD.encode(s))
+    }
+}
@@ -0,0 +1,152 @@
+//! Just enough SMTP (RFC 5321) to hand one message to a mail server: TLS from the start on
+//! port 465, otherwise STARTTLS when the server offers it, and AUTH PLAIN.
+use crate::util::tls;
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use std::net::IpAddr;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::pki_types::ServerName;
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+trait Stream: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Stream for T {}
+
+/// Send `message` from `from` to `to` through `server` (`host` or `host:port`, default port
+/// 587), logging in with `login` (user, password) if given. A password is only sent over TLS,
+/// or to a server on this machine.
+pub(super) async fn send(server: &str, login: Option<(&str, &str)>, from: &str, to: &[String], message: &str) -> Result<()> {
+    tokio::time::timeout(TIMEOUT, deliver(server, login, from, to, message))
+        .await
+        .map_err(|_| anyhow!("SMTP server timed out"))?
+}
+
+async fn deliver(server: &str, login: Option<(&str, &str)>, from: &str, to: &[String], message: &str) -> Result<()> {
+    let (host, port) = match server.rsplit_once(':') {
+        Some((h, p)) => (h, p.parse::<u16>().context("invalid port in digest.smtp")?),
+        None => (server, 587),
+    };
+    if host.is_empty() {
+        bail!("no SMTP server configured");
+    }
+    let tcp = TcpStream::connect((host, port))
+        .await
+        .with_context(|| format!("failed to connect to {}:{}", host, port))?;
+    if port == 465 {
+        let tls = tls::connector()?.connect(ServerName::try_from(host.to_string())?, tcp).await?;
+        session(tls, host, true, login, from, to, message).await
+    } else {
+        session(tcp, host, false, login, from, to, message).await
+    }
+}
+
+/// The conversation with `host` over `stream` (already TLS when `secure`), from its greeting
+/// to QUIT.
+pub async fn session<S: AsyncRead + AsyncWrite + Unpin + Send + 'static>(
+    stream: S,
+    host: &str,
+    mut secure: bool,
+    login: Option<(&str, &str)>,
+    from: &str,
+    to: &[String],
+    message: &str,
+) -> Result<()> {
+    let mut smtp = Connection::new(Box::new(stream));
+    smtp.reply(220).await?;
+    let mut features = smtp.command("EHLO localhost", 250).await?;
+    if !secure && features.iter().any(|f| f.eq_ignore_ascii_case("STARTTLS")) {
+        smtp.command("STARTTLS", 220).await?;
+        smtp = smtp.start_tls(host).await?;
+        secure = true;
+        features = smtp.command("EHLO localhost", 250).await?;
+    }
+    if let Some((user, password)) = login {
+        if !secure && !is_loopback(host) {
+            bail!("{} offers no TLS; not sending the password in the clear", host);
+        }
+        if !features.iter().any(|f| f.to_ascii_uppercase().starts_with("AUTH")) {
+            bail!("{} doesn't accept logins", host);
+        }
+        let token = STANDARD.encode(format!("\0{}\0{}", user, password));
+        smtp.command(&format!("AUTH PLAIN {}", token), 235).await.context("login failed")?;
+    }
+    smtp.command(&format!("MAIL FROM:<{}>", from), 250).await?;
+    for rcpt in to {
+        smtp.command(&format!("RCPT TO:<{}>", rcpt.trim()), 250).await?;
+    }
+    smtp.command("DATA", 354).await?;
+    smtp.write(&dot_stuffed(message)).await?;
+    smtp.reply(250).await?;
+    let _ = smtp.command("QUIT", 221).await;
+    Ok(())
+}
+
+fn is_loopback(host: &str) -> bool {
+    host.eq_ignore_ascii_case("localhost") || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+// CRLF line endings, a '.' doubled at the start of a line, and the terminating "."
+fn dot_stuffed(message: &str) -> String {
+    let mut out = String::with_capacity(message.len() + 5);
+    for line in message.lines() {
+        if line.starts_with('.') {
+            out.push('.');
+        }
+        out.push_str(line);
+        out.push_str("\r\n");
+    }
+    out.push_str(".\r\n");
+    out
+}
+
+struct Connection {
+    stream: BufReader<Box<dyn Stream>>,
+}
+
+impl Connection {
+    fn new(stream: Box<dyn Stream>) -> Self {
+        Connection { stream: BufReader::new(stream) }
+    }
+
+    async fn start_tls(self, host: &str) -> Result<Self> {
+        let name = ServerName::try_from(host.to_string())?;
+        let tls = tls::connector()?.connect(name, self.stream.into_inner()).await?;
+        Ok(Connection::new(Box::new(tls)))
+    }
+
+    async fn write(&mut self, data: &str) -> Result<()> {
+        let stream = self.stream.get_mut();
+        stream.write_all(data.as_bytes()).await?;
+        stream.flush().await?;
+        Ok(())
+    }
+
+    /// Send `command` and read the reply, which must be in `expected`'s class (2xx, 3xx).
+    async fn command(&mut self, command: &str, expected: u16) -> Result<Vec<String>> {
+        self.write(&format!("{}\r\n", command)).await?;
+        self.reply(expected).await
+    }
+
+    /// Read one (possibly multi-line) reply; returns its lines without the codes.
+    async fn reply(&mut self, expected: u16) -> Result<Vec<String>> {
+        let mut lines = Vec::new();
+        loop {
+            let mut buf = Vec::new();
+            if self.stream.read_until(b'\n', &mut buf).await? == 0 {
+                bail!("connection closed by server");
+            }
+            let line = String::from_utf8_lossy(&buf).trim_end().to_string();
+            let code: u16 = line.get(..3).and_then(|c| c.parse().ok()).ok_or_else(|| anyhow!("bad reply: {}", line))?;
+            let last = line.as_bytes().get(3) != Some(&b'-');
+            lines.push(line.get(4..).unwrap_or("").to_string());
+            if last {
+                if code / 100 != expected / 100 {
+                    bail!("{}", line);
+                }
+                return Ok(lines);
+            }
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::process::Command;
+
+/// Let the user edit `text` in $VISUAL (else $EDITOR) and return the result; `None` when
+/// neither is set. The editor gets the terminal until it exits.
+pub fn edit(text: &str) -> Result<Option<String>> {
+    let Some(cmd) = ["VISUAL", "EDITOR"].iter().find_map(|v| std::env::var(v).ok().filter(|c| !c.trim().is_empty())) else {
+        return Ok(None);
+    };
+    let path = std::env::temp_dir().join(format!("news-cli-note-{}.txt", std::process::id()));
+    fs::write(&path, text).context("failed to write the note for the editor")?;
+    // "code --wait", "emacsclient -t": the file goes last
+    let mut parts = cmd.split_whitespace();
+    let program = parts.next().unwrap_or_default();
+    let status = Command::new(program).args(parts).arg(&path).status();
+    let edited = fs::read_to_string(&path);
+    let _ = fs::remove_file(&path);
+    let status = status.with_context(|| format!("failed to start {}", program))?;
+    if !status.success() {
+        bail!("{} exited with {}", program, status);
+    }
+    Ok(Some(edited.context("failed to read the edited note")?))
+}
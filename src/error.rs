@@ -0,0 +1,40 @@
+//! The library's error type. Its variants tell callers what kind of failure they face, so
+//! they can degrade (show cached or partial results after a network error) or give up (a
+//! broken config) instead of treating every failure alike.
+use std::io;
+
+/// What went wrong; see [`is_transient`](Error::is_transient) for which errors to work around.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// A request failed, timed out or was answered with an error.
+    #[error(transparent)]
+    Network(#[from] reqwest::Error),
+    /// A response grew past its size limit (in bytes).
+    #[error("feed too large (>{0} bytes)")]
+    TooLarge(usize),
+    /// Content that couldn't be read: a feed, JSON from an API, a date.
+    #[error("{0}")]
+    Parse(String),
+    /// The configuration is unreadable or invalid; nothing works until it is fixed.
+    #[error("{0}")]
+    Config(String),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+impl Error {
+    /// Network trouble that may be gone next time; what was fetched or cached before is
+    /// still worth showing.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Error::Network(_) | Error::TooLarge(_))
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Parse(e.to_string())
+    }
+}
+
+/// `Result` with [`Error`].
+pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -1,62 +1,192 @@
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
-use std::{env, fs, path::PathBuf};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use crate::util::atomic::{backup_path, with_lock, write_atomic};
+use std::{fs, path::{Path, PathBuf}};
+use time::OffsetDateTime;
 
+/// History shared between the interactive UI and its signal handler.
+pub type SharedHistory = Arc<Mutex<SeenStories>>;
+
+/// Links the user has already seen, persisted as `seen_stories.json` in the state directory.
+/// Seen means listed once (no more [NEW] badge); read, a subset, means opened or marked read.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SeenStories {
     seen_links: HashSet<String>,
+    /// When each link was marked seen (UNIX seconds); links saved before this was recorded
+    /// have no entry.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    seen_at: HashMap<String, i64>,
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    read_links: HashSet<String>,
+}
+
+/// Portable form of the history written by `news-cli history export`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryExport {
+    /// Always "news-cli-history", so other JSON isn't imported by mistake.
+    pub format: String,
+    pub version: u32,
+    /// UNIX seconds
+    pub exported_at: i64,
+    pub seen: Vec<ExportedLink>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedLink {
+    pub link: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seen_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub read: bool,
 }
 
+const EXPORT_FORMAT: &str = "news-cli-history";
+
 impl SeenStories {
     pub fn load() -> Self {
+        Self::load_with_warnings().0
+    }
+
+    /// [`SeenStories::load`], also returning what went wrong on the way: a failed migration
+    /// of the legacy file, or a restore from the backup.
+    pub fn load_with_warnings() -> (Self, Vec<String>) {
+        let mut warnings = Vec::new();
+        if let Err(e) = migrate_legacy_history() {
+            warnings.push(e.to_string());
+        }
         if let Some(path) = history_file_path() {
-            if path.is_file() {
-                if let Ok(contents) = fs::read_to_string(&path) {
-                    if let Ok(seen) = serde_json::from_str::<SeenStories>(&contents) {
-                        return seen;
-                    }
+            if let Some(seen) = read_history(&path) {
+                return (seen, warnings);
+            }
+            // A corrupt or missing file falls back to the previous save
+            let bak = backup_path(&path);
+            if let Some(seen) = read_history(&bak) {
+                if path.is_file() {
+                    warnings.push(format!("History file {} is unreadable; restored from backup", path.display()));
                 }
+                return (seen, warnings);
             }
         }
         // Return empty history if file doesn't exist or can't be read
-        SeenStories::default()
+        (SeenStories::default(), warnings)
     }
 
+    /// Atomic save; the previous version is kept as `seen_stories.json.bak`. Links saved
+    /// meanwhile by another instance (two terminals, `watch` next to the menu) are merged
+    /// in rather than overwritten; saves are serialized with a lock file.
     pub fn save(&self) -> Result<()> {
         if let Some(path) = history_file_path() {
-            // Ensure parent directory exists
-            if let Some(parent) = path.parent() {
-                fs::create_dir_all(parent)?;
-            }
-            let json = serde_json::to_string_pretty(self)?;
-            fs::write(&path, json)?;
+            with_lock(&path, || {
+                let mut merged = read_history(&path).or_else(|| read_history(&backup_path(&path))).unwrap_or_default();
+                merged.merge(self);
+                let json = serde_json::to_string_pretty(&merged)?;
+                write_atomic(&path, json.as_bytes(), true)
+            })?;
         }
         Ok(())
     }
 
     pub fn mark_as_seen(&mut self, link: &str) {
-        self.seen_links.insert(link.to_string());
+        if self.seen_links.insert(link.to_string()) {
+            self.seen_at.insert(link.to_string(), OffsetDateTime::now_utc().unix_timestamp());
+        }
+    }
+
+    /// Opened or marked read; implies seen.
+    pub fn mark_as_read(&mut self, link: &str) {
+        self.mark_as_seen(link);
+        self.read_links.insert(link.to_string());
+    }
+
+    /// Add everything `other` has seen or read; where both have a time for a link, the newer
+    /// wins. Returns how many links were new here.
+    pub fn merge(&mut self, other: &SeenStories) -> usize {
+        let before = self.seen_links.len();
+        self.seen_links.extend(other.seen_links.iter().cloned());
+        self.read_links.extend(other.read_links.iter().cloned());
+        for (link, &at) in &other.seen_at {
+            let mine = self.seen_at.entry(link.clone()).or_insert(at);
+            *mine = (*mine).max(at);
+        }
+        self.seen_links.len() - before
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen_links.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen_links.is_empty()
+    }
+
+    /// The history in portable form, links sorted.
+    pub fn export(&self) -> HistoryExport {
+        let mut links: Vec<&String> = self.seen_links.iter().collect();
+        links.sort();
+        HistoryExport {
+            format: EXPORT_FORMAT.into(),
+            version: 1,
+            exported_at: OffsetDateTime::now_utc().unix_timestamp(),
+            seen: links
+                .into_iter()
+                .map(|l| ExportedLink {
+                    link: l.clone(),
+                    seen_at: self.seen_at.get(l).copied(),
+                    read: self.read_links.contains(l),
+                })
+                .collect(),
+        }
+    }
+
+    /// Read an export (or a copied `seen_stories.json`) back into a history.
+    pub fn from_export(json: &str) -> Result<SeenStories> {
+        if let Ok(export) = serde_json::from_str::<HistoryExport>(json) {
+            if export.format != EXPORT_FORMAT {
+                bail!("not a news-cli history export (format \"{}\")", export.format);
+            }
+            if export.version > 1 {
+                bail!("history export version {} is newer than this news-cli supports", export.version);
+            }
+            let mut history = SeenStories::default();
+            for e in export.seen {
+                if let Some(at) = e.seen_at {
+                    history.seen_at.insert(e.link.clone(), at);
+                }
+                if e.read {
+                    history.read_links.insert(e.link.clone());
+                }
+                history.seen_links.insert(e.link);
+            }
+            return Ok(history);
+        }
+        serde_json::from_str::<SeenStories>(json).map_err(|e| anyhow::anyhow!("not a news-cli history file: {}", e))
     }
 
     pub fn is_seen(&self, link: &str) -> bool {
         self.seen_links.contains(link)
     }
+
+    pub fn is_read(&self, link: &str) -> bool {
+        self.read_links.contains(link)
+    }
+}
+
+fn read_history(path: &Path) -> Option<SeenStories> {
+    let contents = fs::read_to_string(path).ok()?;
+    serde_json::from_str::<SeenStories>(&contents).ok()
 }
 
 fn history_file_path() -> Option<PathBuf> {
-    if let Ok(xdg) = env::var("XDG_CONFIG_HOME") {
-        let mut p = PathBuf::from(xdg);
-        p.push("news-cli");
-        p.push("seen_stories.json");
-        return Some(p);
-    }
-    if let Ok(home) = env::var("HOME") {
-        let mut p = PathBuf::from(home);
-        p.push(".config");
-        p.push("news-cli");
-        p.push("seen_stories.json");
-        return Some(p);
-    }
-    None
+    crate::paths::state_dir().map(|d| d.join("seen_stories.json"))
+}
+
+// Older versions kept history next to config.toml
+fn migrate_legacy_history() -> Result<()> {
+    let (Some(legacy_dir), Some(current)) = (crate::paths::config_dir(), history_file_path()) else {
+        return Ok(());
+    };
+    crate::paths::migrate(&legacy_dir.join("seen_stories.json"), &current)
+        .map_err(|e| anyhow!("Failed to migrate history to {}: {}", current.display(), e))
 }
@@ -0,0 +1,126 @@
+//! UI text in the user's language. Messages live in one catalog per locale (`en.toml`,
+//! `de.toml` next to this file, compiled in); the locale is `language` from config.toml,
+//! else the first of LC_ALL, LC_MESSAGES and LANG that is set. Anything a catalog lacks
+//! falls back to English.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+/// Locales with a catalog; the first is the fallback.
+pub const LOCALES: &[&str] = &["en", "de"];
+
+const CATALOGS: &[(&str, &str)] = &[("en", include_str!("en.toml")), ("de", include_str!("de.toml"))];
+
+static LOCALE: OnceLock<&'static str> = OnceLock::new();
+static MESSAGES: OnceLock<Catalogs> = OnceLock::new();
+
+struct Catalogs {
+    messages: HashMap<&'static str, HashMap<String, String>>,
+    // One message per catalog that didn't parse
+    broken: Vec<String>,
+}
+
+/// `tr!("menu.news")` is the message for the current locale. With arguments, `{0}`, `{1}`,
+/// … in it are replaced by them: `tr!("news.source_prompt", source)`.
+#[macro_export]
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::i18n::t($key)
+    };
+    ($key:expr, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::fill($crate::i18n::t($key), &[$(&$arg as &dyn ::std::fmt::Display),+])
+    };
+}
+
+/// Pick the locale: `language` from the config if set, else the environment. Call once at
+/// startup, before anything is shown; later calls are ignored. Returns a message for each
+/// catalog that is broken; its messages fall back to English.
+pub fn init(language: Option<&str>) -> Vec<String> {
+    let _ = LOCALE.set(language.and_then(supported).unwrap_or_else(from_env));
+    MESSAGES.get_or_init(load).broken.clone()
+}
+
+/// The current locale, e.g. "de".
+pub fn locale() -> &'static str {
+    LOCALE.get_or_init(from_env)
+}
+
+/// The message `key` (e.g. "menu.news") in the current locale; the key itself if no catalog
+/// has it.
+pub fn t(key: &'static str) -> &'static str {
+    let messages = &MESSAGES.get_or_init(load).messages;
+    [locale(), LOCALES[0]]
+        .iter()
+        .find_map(|l| messages.get(l).and_then(|m| m.get(key)))
+        .map_or(key, String::as_str)
+}
+
+/// Replace `{0}`, `{1}`, … in `template` with `args`; other braces are kept.
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let arg = after
+            .find('}')
+            .and_then(|close| Some((after[..close].parse::<usize>().ok()?, close)))
+            .and_then(|(n, close)| Some((args.get(n)?, close)));
+        match arg {
+            Some((value, close)) => {
+                out.push_str(&value.to_string());
+                rest = &after[close + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The locale with a catalog for `language`, e.g. "de" for "de_DE.UTF-8".
+pub fn supported(language: &str) -> Option<&'static str> {
+    // "de_DE.UTF-8", "de-AT", "de" all mean German
+    let code = language.split(['_', '-', '.', '@']).next()?.to_ascii_lowercase();
+    LOCALES.iter().copied().find(|l| *l == code)
+}
+
+fn from_env() -> &'static str {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|v| !v.is_empty())
+        .and_then(|v| supported(&v))
+        .unwrap_or(LOCALES[0])
+}
+
+// Catalog sections become key prefixes: `news = "News"` under `[menu]` is "menu.news"
+fn load() -> Catalogs {
+    let mut all = HashMap::new();
+    let mut broken = Vec::new();
+    for (locale, source) in CATALOGS {
+        let mut messages = HashMap::new();
+        match source.parse::<toml::Table>() {
+            Ok(table) => flatten("", &table, &mut messages),
+            Err(e) => broken.push(format!("Broken {} message catalog: {}", locale, e)),
+        }
+        all.insert(*locale, messages);
+    }
+    Catalogs { messages: all, broken }
+}
+
+fn flatten(prefix: &str, table: &toml::Table, out: &mut HashMap<String, String>) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match value {
+            toml::Value::String(s) => {
+                out.insert(key, s.clone());
+            }
+            toml::Value::Table(t) => flatten(&key, t, out),
+            _ => {}
+        }
+    }
+}
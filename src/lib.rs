@@ -0,0 +1,94 @@
+//! Core of news-cli: feed fetching, link normalization and dedupe, and configuration.
+//!
+//! What the reader keeps between runs lives here too: read history, bookmarks, the reading
+//! queue, muted topics, story tags, keyword alerts, reading statistics, folded news list
+//! sections, named snapshots of a fetch and secrets kept out of the config.
+//!
+//! Around the stories sit story ranking and topic grouping, sync with a feed reader, LLM
+//! briefings, translation, mailed digests, an economic calendar, the weather and a catalog of
+//! curated feeds. UI message catalogs and list navigation round it out.
+//!
+//! The `news-cli` binary is the interactive UI on top of this.
+//!
+//! ```no_run
+//! # async fn demo() -> anyhow::Result<()> {
+//! use news_cli::{NewsClient, SeenStories};
+//!
+//! let cfg = news_cli::config::load(None)?; // same config.toml the CLI uses
+//! let mut history = SeenStories::load();
+//! for story in NewsClient::new(&cfg)?.fetch(&history).await? {
+//!     if story.is_new {
+//!         println!("[{}] {}", story.source, story.title);
+//!         history.mark_as_seen(&story.link);
+//!     }
+//! }
+//! history.save()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Programs with their own screen, such as a ratatui dashboard, use
+//! [`NewsClient::fetch_all`] or [`NewsClient::updates`] instead: they print nothing, handing
+//! back [`Diagnostic`](news::Diagnostic)s for failed feeds, and their futures are `Send`, so
+//! fetching can run on its own task while the UI keeps drawing.
+//!
+//! ```no_run
+//! # fn show_problem(_: &str) {}
+//! # fn add_stories(_: &str, _: Vec<news_cli::Story>) {}
+//! # async fn demo() -> anyhow::Result<()> {
+//! use futures_util::StreamExt;
+//! use news_cli::{NewsClient, SeenStories};
+//!
+//! let cfg = news_cli::config::load(None)?;
+//! let client = NewsClient::new(&cfg)?;
+//! let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+//! tokio::spawn(async move {
+//!     let history = SeenStories::load();
+//!     let mut updates = std::pin::pin!(client.updates(&history));
+//!     while let Some(update) = updates.next().await {
+//!         let _ = tx.send(update);
+//!     }
+//! });
+//! // In the UI's event loop: each feed's stories as soon as it is done
+//! while let Some(update) = rx.recv().await {
+//!     for problem in &update.diagnostics {
+//!         show_problem(&problem.to_string());
+//!     }
+//!     add_stories(&update.feed, update.stories);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+pub mod alerts;
+pub mod bookmarks;
+pub mod briefing;
+pub mod calendar;
+pub mod catalog;
+pub mod collapsed;
+pub mod config;
+pub mod digest;
+pub mod error;
+pub mod history;
+pub mod i18n;
+pub mod linkcheck;
+pub mod menu;
+pub mod mute;
+pub mod news;
+pub mod paths;
+pub mod queue;
+pub mod reading;
+pub mod scoring;
+pub mod secrets;
+pub mod sink;
+pub mod snapshot;
+pub mod sync;
+pub mod tags;
+pub mod topics;
+pub mod translate;
+pub mod util;
+pub mod weather;
+
+pub use config::{Feed, RuntimeConfig};
+pub use error::Error;
+pub use history::SeenStories;
+pub use news::{NewsClient, Story};
@@ -0,0 +1,157 @@
+//! Where a story link really leads, for `confirm_open`: its redirects followed with HEAD
+//! requests (only to http(s), at most [`MAX_REDIRECTS`]), and signs that the site isn't the
+//! one it looks like: another site at the end, international lookalike letters, an IP address.
+use crate::config::FetchLimits;
+use crate::news::{client_builder, strict};
+use crate::tr;
+use reqwest::header::LOCATION;
+use reqwest::redirect::Policy;
+use std::time::Duration;
+use url::{Host, Url};
+
+/// Redirects followed before giving up on a link.
+pub const MAX_REDIRECTS: usize = 5;
+
+/// What [`check`] found out about a link.
+#[derive(Debug, Clone)]
+pub struct Checked {
+    /// The link, then every URL it redirected to.
+    pub hops: Vec<Url>,
+    /// Why it mustn't be opened: a redirect away from http(s), or too many.
+    pub refused: Option<String>,
+    /// Reasons to look twice before opening it; empty for an ordinary link.
+    pub warnings: Vec<String>,
+}
+
+impl Checked {
+    /// The last URL the link led to.
+    pub fn destination(&self) -> &Url {
+        self.hops.last().expect("hops start with the link")
+    }
+
+    /// Redirects followed.
+    pub fn redirects(&self) -> usize {
+        self.hops.len() - 1
+    }
+}
+
+/// Follow `link`'s redirects and look at where it ends up. Failing to reach it is a warning,
+/// not an error: the link may still open in a browser that can.
+pub async fn check(limits: &FetchLimits, link: &Url) -> Checked {
+    let mut checked = Checked { hops: vec![link.clone()], refused: None, warnings: Vec::new() };
+    if !matches!(link.scheme(), "http" | "https") {
+        checked.refused = Some(tr!("open.scheme", link.scheme()));
+        return checked;
+    }
+    // The redirects are followed here, one hop at a time; strict mode still keeps the
+    // requests off private addresses
+    let client = client_builder(limits)
+        .redirect(Policy::none())
+        .timeout(Duration::from_secs(limits.timeout_secs.unwrap_or(20)).min(Duration::from_secs(10)))
+        .build();
+    let client = match client {
+        Ok(c) => c,
+        Err(e) => {
+            checked.warnings.push(tr!("open.check_failed", e));
+            return checked;
+        }
+    };
+    loop {
+        let current = checked.destination().clone();
+        // The client only keeps host names off private addresses
+        if let (Some(true), Err(e)) = (limits.strict, strict::check_url(&current)) {
+            checked.warnings.push(tr!("open.check_failed", e));
+            break;
+        }
+        let response = match client.head(current.clone()).send().await {
+            Ok(r) => r,
+            Err(e) => {
+                checked.warnings.push(tr!("open.check_failed", without_url(e)));
+                break;
+            }
+        };
+        if !response.status().is_redirection() {
+            break;
+        }
+        let location = response.headers().get(LOCATION).and_then(|v| v.to_str().ok());
+        let Some(next) = location.and_then(|l| current.join(l).ok()) else { break };
+        let scheme = next.scheme().to_string();
+        checked.hops.push(next);
+        if !matches!(scheme.as_str(), "http" | "https") {
+            checked.refused = Some(tr!("open.scheme", scheme));
+            break;
+        }
+        if checked.redirects() > MAX_REDIRECTS {
+            checked.refused = Some(tr!("open.too_many", MAX_REDIRECTS));
+            break;
+        }
+    }
+    checked.warnings.extend(host_warnings(link, checked.destination()));
+    checked
+}
+
+/// Warnings about the hosts of `link` and where it `ended` up: another site than the link
+/// names, international characters (worse: mixed with Latin, as in "pаypal.com" with a
+/// Cyrillic "а"), or an IP address instead of a name.
+pub fn host_warnings(link: &Url, ended: &Url) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let site = |u: &Url| u.host_str().map(|h| h.trim_start_matches("www.").to_ascii_lowercase());
+    if site(link) != site(ended) {
+        warnings.push(tr!("open.other_site", display_host(ended)));
+    }
+    for url in if link == ended { vec![link] } else { vec![link, ended] } {
+        match url.host() {
+            Some(Host::Ipv4(ip)) => warnings.push(tr!("open.ip_address", ip)),
+            Some(Host::Ipv6(ip)) => warnings.push(tr!("open.ip_address", ip)),
+            Some(Host::Domain(domain)) if domain.split('.').any(|l| l.starts_with("xn--")) => {
+                let shown = display_host(url);
+                let mixed = shown.split('.').map(scripts).find(|s| s.len() > 1);
+                warnings.push(match mixed {
+                    Some(scripts) => tr!("open.mixed_scripts", shown, scripts.join(" + "), domain),
+                    None => tr!("open.international", shown, domain),
+                });
+            }
+            _ => {}
+        }
+    }
+    warnings.dedup();
+    warnings
+}
+
+/// `url`'s host as people read it: international names decoded from punycode.
+pub fn display_host(url: &Url) -> String {
+    let host = url.host_str().unwrap_or_default();
+    let (unicode, result) = idna::domain_to_unicode(host);
+    match result {
+        Ok(()) => unicode,
+        Err(_) => host.to_string(),
+    }
+}
+
+// The alphabets of lookalike letters `label` uses, in a fixed order
+fn scripts(label: &str) -> Vec<&'static str> {
+    let mut found = Vec::new();
+    for (name, is) in [
+        ("Latin", (|c: char| c.is_ascii_alphabetic() || ('\u{c0}'..='\u{24f}').contains(&c)) as fn(char) -> bool),
+        ("Greek", |c| ('\u{370}'..='\u{3ff}').contains(&c)),
+        ("Cyrillic", |c| ('\u{400}'..='\u{52f}').contains(&c)),
+        ("Armenian", |c| ('\u{530}'..='\u{58f}').contains(&c)),
+    ] {
+        if label.chars().any(is) {
+            found.push(name);
+        }
+    }
+    found
+}
+
+// reqwest puts the URL in its message; the screen already shows it
+fn without_url(e: reqwest::Error) -> String {
+    let e = e.without_url();
+    let mut message = e.to_string();
+    let mut source = std::error::Error::source(&e);
+    while let Some(s) = source {
+        message = format!("{}: {}", message, s);
+        source = s.source();
+    }
+    message
+}
@@ -1,75 +1,311 @@
-mod config;
-mod history;
-mod news;
+mod app;
+mod cli;
+mod clipboard;
+mod commands;
+mod editor;
 mod stats;
 mod open_url;
+mod player;
+mod reload;
+mod shutdown;
+mod theme;
+mod tui;
 mod ui;
-mod util;
+mod wizard;
 
 use anyhow::Result;
+use news_cli::{config, history, i18n, snapshot, tr};
 use std::env;
+use std::sync::{Arc, Mutex};
 use console::Term;
 
+// Main menu entries; their labels depend on the locale
+#[derive(Clone, Copy)]
+enum Screen {
+    News,
+    Top,
+    CatchUp,
+    Stats,
+    Morning,
+    Calendar,
+    Briefing,
+    Alerts,
+    Tags,
+    Queue,
+    Reading,
+    Settings,
+    Catalog,
+    Quit,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Clear terminal at startup for a clean UI
-    let _ = Term::stdout().clear_screen();
-    // Parse a minimal CLI: optional --feeds <path>
-    let mut args = env::args().skip(1);
-    let mut feeds_override: Option<String> = None;
-    while let Some(arg) = args.next() {
-        match arg.as_str() {
-            "--feeds" => {
-                if let Some(p) = args.next() { feeds_override = Some(p); }
-            }
-            "-h" | "--help" => {
-                print_help();
-                return Ok(());
-            }
-            _ => {}
+    theme::honor_no_color();
+    let cli = cli::parse(env::args().skip(1))?;
+    match &cli.command {
+        cli::Command::Help => {
+            cli::print_help();
+            return Ok(());
+        }
+        // Must run before config::load, which refuses invalid configs
+        cli::Command::ConfigCheck { path } => {
+            return commands::config_check::run(path.as_ref().map(std::path::PathBuf::from));
+        }
+        // History files don't depend on the config
+        cli::Command::HistoryExport { path } => return commands::history::export(path.as_deref()),
+        cli::Command::HistoryImport { path } => return commands::history::import(path),
+        // Secrets are needed to load a config that refers to them
+        cli::Command::SecretSet { name } => return commands::secret::set(cli.feeds_override.as_deref(), name),
+        cli::Command::SecretRemove { name } => {
+            return commands::secret::remove(cli.feeds_override.as_deref(), name);
+        }
+        _ => {}
+    }
+
+    // First run: offer to create a config instead of silently using the built-in feeds
+    if matches!(cli.command, cli::Command::Interactive)
+        && cli.feeds_override.is_none()
+        && cli.fixture_dir.is_none()
+        && Term::stdout().is_term()
+        && let Some(path) = config::default_config_path()
+        && !path.exists()
+    {
+        wizard::run(&path)?;
+    }
+
+    let mut overrides = cli.overrides;
+    let mut feeds_override = cli.feeds_override;
+    // A team's shared config on a server is read from its downloaded copy
+    if let Some(url) = feeds_override.clone().filter(|f| config::remote::is_remote(f)) {
+        let remote = config::remote::fetch(&url, &cli.verify, cli.fixture_http).await?;
+        if let Some(e) = &remote.offline {
+            eprintln!("{}", tr!("config.remote_offline", url, e));
+        }
+        // Nothing vouches for it, so it stays off the local machine and network (last, so
+        // neither it nor a --set turns that off)
+        if !cli.verify.is_pinned() {
+            overrides.push(config::overrides::Override {
+                key: "fetch.strict".into(),
+                value: "true".into(),
+                origin: format!("--feeds {} (not pinned)", url),
+            });
+        }
+        feeds_override = Some(remote.path.display().to_string());
+    }
+    config::overrides::init(overrides);
+    let mut cfg = config::load(feeds_override.clone())?;
+    if let Some(dir) = &cli.fixture_dir {
+        cfg.feeds = config::fixture_feeds(std::path::Path::new(dir))?;
+    }
+    theme::init(&cfg.theme);
+    let mut warnings = i18n::init(cfg.language.as_deref());
+    let (mut history, more) = history::SeenStories::load_with_warnings();
+    warnings.extend(more);
+    for w in warnings {
+        eprintln!("{}", w);
+    }
+
+    // `snapshot view` browses saved stories in the News screen instead of the main menu
+    let mut viewing = None;
+    match cli.command {
+        cli::Command::Fetch { to, only_new, mark_seen } => {
+            return commands::fetch::run(&cfg, &mut history, &to, only_new, mark_seen).await;
+        }
+        cli::Command::Watch { to, interval_secs } => {
+            return commands::watch::run(&cfg, &mut history, &to, interval_secs).await;
         }
+        cli::Command::Doctor => return commands::doctor::run(&cfg).await,
+        cli::Command::Briefing => return commands::briefing::run(&cfg, &history).await,
+        cli::Command::Export { format, since_secs, only } => {
+            return commands::export::run(&cfg, &history, format, since_secs, only).await;
+        }
+        cli::Command::Digest { format, to, out } => {
+            return commands::digest::run(&cfg, &history, format, &to, out).await;
+        }
+        cli::Command::Serve { addr } => return commands::serve::run(&cfg, addr.as_deref()).await,
+        cli::Command::ImportUrls { path } => {
+            return commands::import_urls::run(&cfg, feeds_override.as_deref(), &path).await;
+        }
+        cli::Command::SnapshotSave { name } => return commands::snapshot::save(&cfg, &history, &name).await,
+        cli::Command::SnapshotList => return commands::snapshot::list(&cfg),
+        cli::Command::SnapshotView { name } => viewing = Some(snapshot::load(&name)?),
+        cli::Command::Interactive
+        | cli::Command::Help
+        | cli::Command::ConfigCheck { .. }
+        | cli::Command::HistoryExport { .. }
+        | cli::Command::HistoryImport { .. }
+        | cli::Command::SecretSet { .. }
+        | cli::Command::SecretRemove { .. } => {}
     }
 
-    let cfg = config::load(feeds_override)?;
-    let mut history = history::SeenStories::load();
+    let history: history::SharedHistory = Arc::new(Mutex::new(history));
+    let app = app::AppContext::new(&cfg)?;
+    shutdown::install(history.clone());
+
+    // Clear terminal at startup for a clean UI
+    let _ = Term::stdout().clear_screen();
 
+    // A session that ended on the News screen reopens there instead of the main menu
+    let mut resume = cfg.restore_session && viewing.is_none() && tui::session::start();
+    // A fixture directory or snapshot replaces what the config lists, so only watch without
+    if cli.fixture_dir.is_none() && viewing.is_none() {
+        reload::watch(&cfg);
+    }
+    // Fetch while the main menu is up, so entering News (or Stats) is instant
+    if viewing.is_none() {
+        prefetch(&cfg, &app, &history);
+    }
     loop {
-        let items = vec!["News", "Stats", "Quit"];
-        let sel = ui::prompt_menu(
-            "Main Menu (b = back/quit)",
-            &items,
-            Some(0),
-            cfg.header.as_deref(),
-        )?;
+        if let Some(fresh) = reload::take(&cfg) {
+            cfg = fresh;
+            // What was fetched for the old config is discarded, not shown
+            app.cancel_prefetch();
+            prefetch(&cfg, &app, &history);
+        }
+        if let Some(snapshot) = viewing.take() {
+            let _crumb = ui::enter(&snapshot.name);
+            tui::view_snapshot(&cfg, &history, snapshot).await?;
+            break;
+        }
+        if std::mem::take(&mut resume) {
+            let _crumb = ui::enter(tr!("menu.news"));
+            if tui::run(&cfg, &app, &history).await? {
+                break;
+            }
+            continue;
+        }
+        // Rebuilt each time round: the alert count changes after a fetch.
+        // Calendar, Briefing and Alerts are opt-in: only offered once configured; Tags and
+        // Queue once used; Settings and the feed catalog with a config file to write to
+        let mut items = vec![
+            (Screen::News, tr!("menu.news").to_string()),
+            (Screen::Top, tr!("menu.top").to_string()),
+            (Screen::CatchUp, tr!("menu.catch_up").to_string()),
+            (Screen::Stats, tr!("menu.stats").to_string()),
+            (Screen::Morning, tr!("menu.morning").to_string()),
+        ];
+        if cfg.calendar.is_some() {
+            items.push((Screen::Calendar, tr!("menu.calendar").to_string()));
+        }
+        if cfg.briefing.is_some() {
+            items.push((Screen::Briefing, tr!("menu.briefing").to_string()));
+        }
+        if let Some(label) = tui::alerts::menu_label(&cfg) {
+            items.push((Screen::Alerts, label));
+        }
+        if let Some(label) = tui::tags::menu_label() {
+            items.push((Screen::Tags, label));
+        }
+        if let Some(label) = tui::queue::menu_label() {
+            items.push((Screen::Queue, label));
+        }
+        items.push((Screen::Reading, tr!("menu.reading_stats").to_string()));
+        if let Some(label) = tui::settings::menu_label(&cfg) {
+            items.push((Screen::Settings, label));
+        }
+        if let Some(label) = tui::catalog::menu_label(&cfg) {
+            items.push((Screen::Catalog, label));
+        }
+        items.push((Screen::Quit, tr!("menu.quit").to_string()));
+        let labels: Vec<&str> = items.iter().map(|(_, label)| label.as_str()).collect();
+        let sel = ui::prompt_menu(tr!("menu.title"), &labels, Some(0), cfg.header.as_deref())?;
         match sel {
             ui::MenuChoice::Quit => break,
             ui::MenuChoice::Back => break,
-            ui::MenuChoice::Index(0) => {
-                let (story_links, quit) = news::run(&cfg, &history).await?;
-                // Mark all fetched stories as seen
-                for link in story_links {
-                    history.mark_as_seen(&link);
+            ui::MenuChoice::Index(i) => {
+                let Some((screen, label)) = items.get(i) else { continue };
+                // "Alerts (3 new)" is just Alerts on the breadcrumb
+                let _crumb = ui::enter(label.split(" (").next().unwrap_or(label));
+                match screen {
+                    Screen::News => {
+                        let quit = tui::run(&cfg, &app, &history).await?;
+                        if quit { break; }
+                    }
+                    Screen::Top => {
+                        if tui::top::show(&cfg, &app, &history).await? {
+                            break;
+                        }
+                    }
+                    Screen::CatchUp => {
+                        if tui::catchup::show(&cfg, &app, &history).await? {
+                            break;
+                        }
+                    }
+                    Screen::Stats => {
+                        if stats::run(&cfg, &app).await? {
+                            break;
+                        }
+                    }
+                    Screen::Morning => {
+                        if tui::morning::show(&cfg, &app, &history).await? {
+                            break;
+                        }
+                    }
+                    Screen::Calendar => {
+                        if let Some(ccfg) = &cfg.calendar
+                            && tui::calendar::show(&cfg, &app, ccfg).await?
+                        {
+                            break;
+                        }
+                    }
+                    Screen::Briefing => {
+                        if let Some(bcfg) = &cfg.briefing
+                            && tui::briefing::show(&cfg, &app, bcfg, &history).await?
+                        {
+                            break;
+                        }
+                    }
+                    Screen::Reading => {
+                        if tui::reading::show(&cfg)? {
+                            break;
+                        }
+                    }
+                    Screen::Alerts => {
+                        if tui::alerts::show(&cfg, &history)? {
+                            break;
+                        }
+                    }
+                    Screen::Tags => {
+                        if tui::tags::show(&cfg, &history)? {
+                            break;
+                        }
+                    }
+                    Screen::Queue => {
+                        if tui::queue::show(&cfg, &history)? {
+                            break;
+                        }
+                    }
+                    Screen::Settings => {
+                        if tui::settings::show(&mut cfg)? {
+                            break;
+                        }
+                    }
+                    Screen::Catalog => {
+                        if tui::catalog::show(&cfg).await? {
+                            break;
+                        }
+                    }
+                    Screen::Quit => break,
                 }
-                if quit { break; }
             }
-            ui::MenuChoice::Index(1) => {
-                stats::run(&cfg).await?;
-            }
-            ui::MenuChoice::Index(2) => break,
             _ => {}
         }
     }
 
+    app.cancel_prefetch();
+    tui::flush_sync().await;
+    tui::session::save();
     // Save history on clean exit
-    if let Err(e) = history.save() {
-        eprintln!("Failed to save history: {}", e);
+    if let Err(e) = history.lock().unwrap().save() {
+        eprintln!("{}", tr!("history.save_failed", e));
     }
 
     Ok(())
 }
 
-fn print_help() {
-    println!("news-cli");
-    println!("Usage: news-cli [--feeds <path>]");
-    println!("  --feeds <path>   Path to a config.toml (feeds list) or a local RSS/Atom XML file");
+fn prefetch(cfg: &config::RuntimeConfig, app: &app::AppContext, history: &history::SharedHistory) {
+    tui::prefetch(cfg, app, history);
+    if cfg.stats.prefetch == Some(true) {
+        stats::prefetch(cfg, app);
+    }
 }
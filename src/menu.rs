@@ -0,0 +1,413 @@
+//! Arrow-key navigation of numbered lists: selection, type-ahead filter, "go to N", Tab
+//! jumps between sections and the scrolling viewport. Keys come from and frames go to a
+//! [`Terminal`], so the same logic runs against the real terminal or scripted keys.
+use crate::tr;
+use crate::util::sanitize::sanitize_for_terminal;
+use crate::util::width::{display_width, isolate, truncate_to_width};
+use console::{Key, Style};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use std::io;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuChoice {
+    Back,
+    Quit,
+    Index(usize),
+    /// An action key (e.g. 'i' for details) pressed on an item.
+    Action(char, usize),
+    /// New content arrived while waiting ([`Input::Refresh`]); the caller may rebuild the list.
+    Refresh,
+}
+
+/// Highlighted item and type-ahead filter of an arrow-key list.
+#[derive(Debug, Clone, Default)]
+pub struct ListPosition {
+    pub selected: Option<usize>,
+    pub filter: Option<String>,
+}
+
+/// What the list waits for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    Key(Key),
+    /// The terminal was resized: draw again.
+    Redraw,
+    /// The content changed: stop with [`MenuChoice::Refresh`].
+    Refresh,
+}
+
+/// Where a list reads keys and draws itself.
+pub trait Terminal {
+    /// Rows and columns.
+    fn size(&self) -> (usize, usize);
+    fn next_input(&mut self) -> io::Result<Input>;
+    /// Replace the screen with `lines`.
+    fn draw(&mut self, lines: &[String]) -> io::Result<()>;
+    /// The '?' overlay for this list; returns once it is closed.
+    fn show_help(&mut self, _list: &List) -> io::Result<()> {
+        Ok(())
+    }
+    /// Called whenever the highlight or filter changes.
+    fn moved(&mut self, _position: &ListPosition) {}
+}
+
+/// Styles of the highlighted row and of a pinned section header.
+#[derive(Debug, Clone)]
+pub struct Styles {
+    pub selection: Style,
+    pub header: Style,
+}
+
+impl Default for Styles {
+    fn default() -> Self {
+        Styles { selection: Style::new().cyan(), header: Style::new().bold() }
+    }
+}
+
+/// A list as shown: the lines above it, its items and what they allow.
+pub struct List<'a> {
+    pub prompt: &'a str,
+    pub items: &'a [&'a str],
+    pub header: Option<&'a str>,
+    /// The breadcrumb line under the header.
+    pub crumb: Option<String>,
+    /// Indices of the items that head a section.
+    pub sections: Option<&'a [usize]>,
+    /// Keys that act on the highlighted item, with what they do.
+    pub actions: &'a [(char, &'a str)],
+    pub styles: Styles,
+}
+
+impl List<'_> {
+    /// Whether Tab, Shift-Tab and 's' move between sections.
+    pub fn has_sections(&self) -> bool {
+        self.sections.is_some_and(|s| !s.is_empty())
+    }
+}
+
+/// Footer hint for the action keys a list accepts, e.g. ", 'i' = details".
+pub fn action_help(actions: &[(char, &str)]) -> String {
+    actions
+        .iter()
+        .map(|(k, label)| tr!("list.action", k, label))
+        .collect()
+}
+
+/// Share `budget` rows among sections that have `(unread, available)` items: each gets up to
+/// `floor` first, then the rest go one by one to the section with the most unread items not
+/// yet shown (then the most left over), never past what it has. A quiet section takes only
+/// what it needs and leaves the space to busy ones.
+pub fn share_rows(budget: usize, floor: usize, sections: &[(usize, usize)]) -> Vec<usize> {
+    let mut rows: Vec<usize> = sections.iter().map(|&(_, available)| available.min(floor)).collect();
+    let mut left = budget.saturating_sub(rows.iter().sum());
+    while left > 0 {
+        let next = (0..sections.len())
+            .filter(|&i| rows[i] < sections[i].1)
+            .max_by_key(|&i| (sections[i].0.saturating_sub(rows[i]), sections[i].1 - rows[i], std::cmp::Reverse(i)));
+        let Some(i) = next else { break };
+        rows[i] += 1;
+        left -= 1;
+    }
+    rows
+}
+
+/// Let the user move through `list` until they choose. `sel` is the highlighted item and
+/// `filter` the type-ahead filter (`Some` while one is typed); both are left where the user
+/// was.
+pub fn navigate(
+    term: &mut impl Terminal,
+    list: &List,
+    sel: &mut usize,
+    filter: &mut Option<String>,
+) -> io::Result<MenuChoice> {
+    let items = list.items;
+    let header_indices = list.sections;
+    let mut top: usize = 0;
+    // Digits typed so far for a "go to item N" jump
+    let mut jump = String::new();
+    let plain: Vec<String> = items.iter().map(|s| sanitize_for_terminal(s)).collect();
+    let matcher = SkimMatcherV2::default();
+    let section_help = if list.has_sections() { tr!("list.section_help") } else { "" };
+    let help = tr!("list.arrow_help", section_help, action_help(list.actions));
+    loop {
+        // Items still shown, in original order; section headers stay if anything under them matches
+        let visible: Vec<usize> = match filter.as_deref() {
+            None | Some("") => (0..items.len()).collect(),
+            Some(f) => filter_items(&plain, f, header_indices.unwrap_or(&[]), &matcher),
+        };
+        // `pos` is the selection's place in `visible`; snap to the nearest match at or after `sel`
+        let mut pos = visible.iter().position(|&i| i >= *sel).unwrap_or(visible.len().saturating_sub(1));
+        if let Some(&i) = visible.get(pos) {
+            *sel = i;
+        }
+        term.moved(&ListPosition { selected: Some(*sel), filter: filter.clone().filter(|f| !f.is_empty()) });
+
+        let status = match &*filter {
+            Some(f) => tr!("list.filter_status", f),
+            None if !jump.is_empty() => tr!("list.jump_status", jump),
+            None => help.clone(),
+        };
+        let (rows, cols) = term.size();
+        let (rows, cols) = (rows.max(1), cols.max(1));
+        // header + breadcrumb + prompt + help, each of which may wrap
+        let chrome: Vec<&str> = [list.header.unwrap_or(""), list.crumb.as_deref().unwrap_or(""), list.prompt, &status]
+            .into_iter()
+            .filter(|l| !l.is_empty())
+            .collect();
+        let mut reserved: usize = chrome
+            .iter()
+            .map(|l| display_width(l).div_ceil(cols).max(1))
+            .sum();
+        // Too small for the full chrome: keep one prompt row and as many items as fit
+        let compact = rows < reserved + 3;
+        if compact {
+            reserved = 1;
+        }
+        let mut max_visible: usize = rows.saturating_sub(reserved).max(1).min(visible.len().max(1));
+        let sections: &[usize] = header_indices.unwrap_or(&[]);
+        // Once the list scrolls, spend a row on the position line and, with sections, one on
+        // a pinned copy of the current section header
+        let scrolling = !compact && visible.len() > max_visible;
+        let pin = scrolling && !sections.is_empty();
+        if scrolling {
+            max_visible = max_visible.saturating_sub(1 + usize::from(pin)).max(1);
+        }
+
+        // keep selection in viewport
+        if pos < top {
+            top = pos;
+        }
+        if pos >= top + max_visible {
+            top = pos + 1 - max_visible;
+        }
+        top = top.min(visible.len().saturating_sub(max_visible));
+
+        let mut lines: Vec<String> = Vec::with_capacity(max_visible + 5);
+        if compact {
+            lines.push(truncate_to_width(list.prompt, cols));
+        } else {
+            lines.extend(list.header.map(String::from));
+            lines.extend(list.crumb.clone());
+            lines.push(list.prompt.to_string());
+        }
+        if visible.is_empty() {
+            lines.push(format!("  {}", tr!("list.no_matches")));
+        }
+        let mut shown = max_visible;
+        if pin && let Some(&first) = visible.get(top) {
+            match sections.iter().rev().find(|&&h| h <= first) {
+                // The section's header scrolled off: repeat it above the first row
+                Some(&h) if h != first => {
+                    let label = isolate(&truncate_to_width(items[h], cols.saturating_sub(2)));
+                    lines.push(format!("  {}", list.styles.header.apply_to(label)));
+                }
+                // The first row is a header itself (or precedes all sections): use the row for an item
+                _ => shown += 1,
+            }
+        }
+        for &i in visible.iter().skip(top).take(shown) {
+            // One row per item: cut long labels so the "> " marker and numbers stay aligned
+            let number = format!("{}: ", i + 1);
+            // Isolated, so a right-to-left label can't pull the marker or number to its side
+            let label = isolate(&truncate_to_width(items[i], cols.saturating_sub(2 + number.len())));
+            if i == *sel {
+                let selected = format!("> {}{}", number, label);
+                lines.push(list.styles.selection.apply_to(selected).to_string());
+            } else {
+                lines.push(format!("  {}{}", number, label));
+            }
+        }
+        if scrolling {
+            let position = match sections.iter().rposition(|&h| h <= *sel) {
+                Some(n) => tr!("list.position_section", pos + 1, visible.len(), n + 1, sections.len()),
+                None => tr!("list.position", pos + 1, visible.len()),
+            };
+            lines.push(console::style(position).dim().to_string());
+        }
+        if !compact || filter.is_some() || !jump.is_empty() {
+            lines.push(status);
+        }
+        term.draw(&lines)?;
+
+        let key = match term.next_input()? {
+            Input::Key(key) => key,
+            Input::Redraw => continue,
+            Input::Refresh => return Ok(MenuChoice::Refresh),
+        };
+        let last = visible.len().saturating_sub(1);
+        if filter.is_none() {
+            match key {
+                Key::Char(c) if c.is_ascii_digit() => {
+                    jump.push(c);
+                    // Follow the number as it's typed; it's shown (not opened) until Enter
+                    if let Ok(n) = jump.parse::<usize>()
+                        && (1..=items.len()).contains(&n)
+                    {
+                        *sel = n - 1;
+                    }
+                    continue;
+                }
+                Key::Backspace if !jump.is_empty() => {
+                    jump.pop();
+                    continue;
+                }
+                Key::Escape if !jump.is_empty() => {
+                    jump.clear();
+                    continue;
+                }
+                Key::Enter => {}
+                _ => jump.clear(),
+            }
+        }
+        match key {
+            Key::ArrowUp => {
+                pos = pos.saturating_sub(1);
+            }
+            Key::ArrowDown => {
+                pos = (pos + 1).min(last);
+            }
+            Key::Home => {
+                pos = 0;
+            }
+            Key::End => {
+                pos = last;
+            }
+            Key::PageUp => {
+                let step: usize = max_visible.saturating_sub(1).max(1);
+                pos = pos.saturating_sub(step);
+            }
+            Key::PageDown => {
+                let step: usize = max_visible.saturating_sub(1).max(1);
+                pos = (pos + step).min(last);
+            }
+            Key::Tab => {
+                if let Some(hidx) = header_indices {
+                    // first visible header strictly after the selection, wrapping to the first
+                    let headers: Vec<usize> = (0..visible.len())
+                        .filter(|p| hidx.contains(&visible[*p]))
+                        .collect();
+                    if let Some(&next) = headers.iter().find(|&&p| p > pos).or(headers.first()) {
+                        pos = next;
+                    }
+                }
+            }
+            Key::BackTab => {
+                if let Some(hidx) = header_indices {
+                    // the current section's header, or the previous one when already on it; wraps
+                    let headers: Vec<usize> = (0..visible.len())
+                        .filter(|p| hidx.contains(&visible[*p]))
+                        .collect();
+                    if let Some(&prev) = headers.iter().rev().find(|&&p| p < pos).or(headers.last()) {
+                        pos = prev;
+                    }
+                }
+            }
+            Key::Enter => {
+                if visible.is_empty() {
+                    continue;
+                }
+                return Ok(MenuChoice::Index(*sel));
+            }
+            Key::Escape if filter.is_some() => {
+                *filter = None;
+            }
+            Key::Backspace if filter.is_some() => {
+                if let Some(f) = filter.as_mut()
+                    && f.pop().is_none()
+                {
+                    *filter = None;
+                }
+            }
+            Key::CtrlC => {
+                return Ok(MenuChoice::Quit);
+            }
+            Key::Char(c) if filter.is_some() && !c.is_control() => {
+                filter.get_or_insert_with(String::new).push(c);
+                // Start each narrowed list at its first match
+                top = 0;
+                *sel = 0;
+                continue;
+            }
+            Key::Char('/') => {
+                *filter = Some(String::new());
+            }
+            Key::Char('?') => {
+                term.show_help(list)?;
+                continue;
+            }
+            Key::Char('q') | Key::Char('Q') => {
+                return Ok(MenuChoice::Quit);
+            }
+            Key::Char('b') | Key::Char('B') | Key::Escape => {
+                return Ok(MenuChoice::Back);
+            }
+            Key::Char('s') | Key::Char('S') if !section_help.is_empty() => {
+                if let Some(h) = pick_section(term, &plain, sections)? {
+                    // A filter may hide the header; the snap at the top of the loop finds the next match
+                    *sel = h;
+                    continue;
+                }
+            }
+            // Left arrow is 'z' (fold the section) in lists that offer it
+            Key::ArrowLeft if !visible.is_empty() && list.actions.iter().any(|(k, _)| *k == 'z') => {
+                return Ok(MenuChoice::Action('z', *sel));
+            }
+            Key::Char(c)
+                if !visible.is_empty() && list.actions.iter().any(|(k, _)| *k == c.to_ascii_lowercase()) =>
+            {
+                return Ok(MenuChoice::Action(c.to_ascii_lowercase(), *sel));
+            }
+            _ => {}
+        }
+        if let Some(&i) = visible.get(pos) {
+            *sel = i;
+        }
+    }
+}
+
+const SECTION_KEYS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Quick-jump overlay: one key per section header. Returns the chosen header's item index.
+fn pick_section(term: &mut impl Terminal, plain: &[String], sections: &[usize]) -> io::Result<Option<usize>> {
+    loop {
+        let cols = term.size().1.max(1);
+        let mut lines = vec![tr!("list.jump_to_source").to_string()];
+        for (key, &h) in SECTION_KEYS.chars().zip(sections) {
+            lines.push(format!("  {}) {}", key, truncate_to_width(&plain[h], cols.saturating_sub(5))));
+        }
+        term.draw(&lines)?;
+        match term.next_input()? {
+            Input::Key(Key::Char(c)) => {
+                if let Some(n) = SECTION_KEYS.find(c)
+                    && let Some(&h) = sections.get(n)
+                {
+                    return Ok(Some(h));
+                }
+            }
+            Input::Key(Key::Escape | Key::CtrlC | Key::Backspace) => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+// Fuzzy (fzf-style) match against the plain-text labels. A matching story keeps its section
+// header visible so the list still reads as grouped.
+fn filter_items(
+    plain: &[String],
+    pattern: &str,
+    header_indices: &[usize],
+    matcher: &SkimMatcherV2,
+) -> Vec<usize> {
+    let mut keep: Vec<bool> = plain
+        .iter()
+        .map(|label| matcher.fuzzy_match(label, pattern).is_some())
+        .collect();
+    for (n, &h) in header_indices.iter().enumerate() {
+        let end = header_indices.get(n + 1).copied().unwrap_or(plain.len());
+        if keep.get(h + 1..end).is_some_and(|section| section.contains(&true)) {
+            keep[h] = true;
+        }
+    }
+    (0..plain.len()).filter(|&i| keep[i]).collect()
+}
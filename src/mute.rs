@@ -0,0 +1,58 @@
+use crate::news::Story;
+use crate::reading::keywords;
+use crate::util::atomic::write_atomic;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+/// Headline words the user never wants to triage again ('m' in Catch-up), persisted as
+/// `muted.json` in the state directory. A story is muted if its title has one of them as a
+/// word (see [`keywords`]).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct MutedTopics {
+    topics: Vec<String>,
+}
+
+impl MutedTopics {
+    pub fn load() -> Self {
+        if let Some(path) = muted_file_path()
+            && let Ok(contents) = fs::read_to_string(&path)
+            && let Ok(m) = serde_json::from_str::<MutedTopics>(&contents)
+        {
+            return m;
+        }
+        MutedTopics::default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = muted_file_path() {
+            let json = serde_json::to_string_pretty(self)?;
+            write_atomic(&path, json.as_bytes(), true)?;
+        }
+        Ok(())
+    }
+
+    /// In the order they were muted.
+    pub fn topics(&self) -> &[String] {
+        &self.topics
+    }
+
+    /// Mute `topic` (case-insensitive); returns whether it was new.
+    pub fn add(&mut self, topic: &str) -> bool {
+        let topic = topic.trim().to_lowercase();
+        if topic.is_empty() || self.topics.contains(&topic) {
+            return false;
+        }
+        self.topics.push(topic);
+        true
+    }
+
+    /// Whether `story`'s title mentions a muted topic.
+    pub fn matches(&self, story: &Story) -> bool {
+        !self.topics.is_empty() && keywords(&story.title).iter().any(|w| self.topics.contains(w))
+    }
+}
+
+fn muted_file_path() -> Option<PathBuf> {
+    crate::paths::state_dir().map(|d| d.join("muted.json"))
+}
@@ -0,0 +1,26 @@
+use super::model::Story;
+use crate::util::atomic::write_atomic;
+use anyhow::Result;
+use std::{fs, path::PathBuf};
+
+/// Stories from the last successful fetch, used when every feed fails (e.g. offline).
+pub fn load() -> Vec<Story> {
+    if let Some(path) = cache_file_path()
+        && let Ok(contents) = fs::read_to_string(&path)
+        && let Ok(stories) = serde_json::from_str::<Vec<Story>>(&contents)
+    {
+        return stories;
+    }
+    Vec::new()
+}
+
+pub fn save(stories: &[Story]) -> Result<()> {
+    if let Some(path) = cache_file_path() {
+        write_atomic(&path, serde_json::to_string(stories)?.as_bytes(), false)?;
+    }
+    Ok(())
+}
+
+fn cache_file_path() -> Option<PathBuf> {
+    crate::paths::cache_dir().map(|d| d.join("stories.json"))
+}
@@ -0,0 +1,64 @@
+//! Problems met while fetching, returned to the caller instead of printed, so programs
+//! embedding the fetcher decide where (and whether) they show up; and progress reports, for
+//! showing how a fetch is going while it runs.
+use crate::tr;
+use std::fmt;
+
+/// Something that went wrong with one feed, or the fetch as a whole. None of them stop the
+/// other feeds; `Display` gives the message the command-line tools print.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// No provider handles the feed's `kind`.
+    UnknownKind { feed: String, kind: String },
+    /// Not fetched: the server asked (Retry-After) to wait until `until`, formatted for display.
+    BackingOff { feed: String, until: String },
+    /// The feed failed; its stories are missing.
+    Failed { feed: String, error: String },
+    /// The feed was fetched, but not all of it went well (one repository of several, the
+    /// cached copy); `message` names the feed.
+    Warning { feed: String, message: String },
+    /// `feeds.json` couldn't be updated.
+    Status(String),
+    /// The `[translation]` backend failed; stories are untranslated.
+    Translation(String),
+}
+
+impl Diagnostic {
+    /// The feed concerned, if any.
+    pub fn feed(&self) -> Option<&str> {
+        match self {
+            Diagnostic::UnknownKind { feed, .. }
+            | Diagnostic::BackingOff { feed, .. }
+            | Diagnostic::Failed { feed, .. }
+            | Diagnostic::Warning { feed, .. } => Some(feed),
+            Diagnostic::Status(_) | Diagnostic::Translation(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Diagnostic::UnknownKind { feed, kind } => tr!("fetch.unknown_kind", kind, feed),
+            Diagnostic::BackingOff { feed, until } => tr!("fetch.backing_off", feed, until),
+            Diagnostic::Failed { feed, error } => tr!("fetch.failed", feed, error),
+            Diagnostic::Warning { message, .. } => message.clone(),
+            Diagnostic::Status(error) => tr!("fetch.status_failed", error),
+            Diagnostic::Translation(error) => tr!("fetch.translation_failed", error),
+        };
+        f.write_str(&message)
+    }
+}
+
+/// How one feed of a running fetch is going, sent to the channel given to
+/// [`NewsClient::report_progress`](crate::news::NewsClient::report_progress).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedProgress {
+    /// To be fetched once the feeds before it are done; sent for every feed at the start.
+    Queued(String),
+    Fetching(String),
+    /// Done, with how many stories it listed.
+    Fetched { feed: String, stories: usize },
+    /// Failed, or skipped (unknown kind, backing off); why.
+    Failed { feed: String, error: String },
+}
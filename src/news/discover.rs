@@ -0,0 +1,108 @@
+//! Telling a web page from a feed, and finding the feeds a page names: a common mistake is
+//! configuring a site's home page (`https://example.com/`) instead of its feed. Also the
+//! URLs in a shared list of sites, for `import-urls`.
+use regex::Regex;
+use std::sync::OnceLock;
+use url::Url;
+
+/// Whether a response is a web page: served as HTML, or starting like one whatever the
+/// server calls it.
+pub fn looks_like_html(content_type: Option<&str>, body: &[u8]) -> bool {
+    let media = content_type.and_then(|t| t.split(';').next()).map(|t| t.trim().to_ascii_lowercase());
+    if matches!(media.as_deref(), Some("text/html" | "application/xhtml+xml")) {
+        return true;
+    }
+    let start = String::from_utf8_lossy(&body[..body.len().min(512)]).to_ascii_lowercase();
+    let start = start.trim_start_matches('\u{feff}').trim_start();
+    start.starts_with("<!doctype html") || start.starts_with("<html")
+}
+
+/// The feeds `html` advertises with `<link rel="alternate">` (RSS, Atom or JSON Feed), in
+/// page order, resolved against the page's URL.
+pub fn feed_links(html: &str, base: &Url) -> Vec<Url> {
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    static ATTR: OnceLock<Regex> = OnceLock::new();
+    let link = LINK.get_or_init(|| Regex::new(r"(?is)<link\b[^>]*>").expect("valid regex"));
+    let attr = ATTR.get_or_init(|| {
+        Regex::new(r#"(?is)([a-z-]+)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#).expect("valid regex")
+    });
+    let mut found: Vec<Url> = Vec::new();
+    for tag in link.find_iter(html) {
+        let (mut rel, mut kind, mut href) = (String::new(), String::new(), None);
+        for c in attr.captures_iter(tag.as_str()) {
+            let value = c.get(2).or(c.get(3)).or(c.get(4)).map_or("", |m| m.as_str());
+            match c[1].to_ascii_lowercase().as_str() {
+                "rel" => rel = value.to_ascii_lowercase(),
+                "type" => kind = value.trim().to_ascii_lowercase(),
+                "href" => href = Some(crate::util::html::decode_entities(value.trim())),
+                _ => {}
+            }
+        }
+        let is_feed = matches!(
+            kind.as_str(),
+            "application/rss+xml" | "application/atom+xml" | "application/feed+json" | "application/json"
+        );
+        if !rel.split_whitespace().any(|r| r == "alternate") || !is_feed {
+            continue;
+        }
+        if let Some(url) = href.and_then(|h| base.join(&h).ok())
+            && !found.contains(&url)
+        {
+            found.push(url);
+        }
+    }
+    found
+}
+
+/// Why a web page isn't a feed, naming the first feed it advertises if there is one.
+pub fn not_a_feed(body: &[u8], base: Option<&Url>) -> String {
+    let html = String::from_utf8_lossy(body);
+    match base.and_then(|b| feed_links(&html, b).into_iter().next()) {
+        Some(feed) => format!("this looks like a web page, not a feed; the page names a feed at {}", feed),
+        None => "this looks like a web page, not a feed (use the site's RSS or Atom link)".into(),
+    }
+}
+
+/// The `<title>` of a page, as text.
+pub fn page_title(html: &str) -> Option<String> {
+    static TITLE: OnceLock<Regex> = OnceLock::new();
+    let re = TITLE.get_or_init(|| Regex::new(r"(?is)<title\b[^>]*>(.*?)</title>").expect("valid regex"));
+    let title = crate::util::sanitize::html_to_text(re.captures(html)?.get(1)?.as_str());
+    (!title.is_empty()).then_some(title)
+}
+
+/// The http(s) URLs in a list as friends share them: one per line, bare or as a Markdown
+/// list item, link (`[Title](url)`, whose text comes along) or `<url>`. Lines starting
+/// with `#` are comments (or Markdown headings); repeats are dropped.
+pub fn listed_urls(text: &str) -> Vec<(Url, Option<String>)> {
+    static MARKDOWN: OnceLock<Regex> = OnceLock::new();
+    static BARE: OnceLock<Regex> = OnceLock::new();
+    let markdown = MARKDOWN.get_or_init(|| Regex::new(r"\[([^\]]*)\]\(\s*<?(https?://[^\s)>]+)>?[^)]*\)").expect("valid regex"));
+    let bare = BARE.get_or_init(|| Regex::new(r#"https?://[^\s<>"'\]\)]+"#).expect("valid regex"));
+    let mut found: Vec<(Url, Option<String>)> = Vec::new();
+    let mut add = |url: &str, title: Option<String>| {
+        // Sentence punctuation after a bare URL isn't part of it
+        let url = url.trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        if let Ok(url) = Url::parse(url)
+            && !found.iter().any(|(u, _)| *u == url)
+        {
+            found.push((url, title));
+        }
+    };
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let links: Vec<_> = markdown.captures_iter(line).collect();
+        if links.is_empty() {
+            for m in bare.find_iter(line) {
+                add(m.as_str(), None);
+            }
+        }
+        for c in links {
+            let title = c[1].trim();
+            add(&c[2], (!title.is_empty()).then(|| title.to_string()));
+        }
+    }
+    found
+}
@@ -0,0 +1,48 @@
+//! DNS answers kept for a few minutes and shared by every client, so a refresh doesn't
+//! look up each feed's host again.
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+const TTL: Duration = Duration::from_secs(300);
+
+// Host -> when it was looked up and what it resolved to
+type Entries = HashMap<String, (Instant, Vec<SocketAddr>)>;
+
+#[derive(Default)]
+pub(crate) struct DnsCache {
+    // Shared with lookups still in flight
+    entries: Arc<Mutex<Entries>>,
+}
+
+/// The process-wide cache.
+pub(crate) fn shared() -> Arc<DnsCache> {
+    static CACHE: OnceLock<Arc<DnsCache>> = OnceLock::new();
+    CACHE.get_or_init(Arc::default).clone()
+}
+
+impl DnsCache {
+    fn get(&self, host: &str) -> Option<Vec<SocketAddr>> {
+        let entries = self.entries.lock().unwrap();
+        entries.get(host).filter(|(at, _)| at.elapsed() < TTL).map(|(_, addrs)| addrs.clone())
+    }
+}
+
+impl Resolve for DnsCache {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        if let Some(addrs) = self.get(&host) {
+            return Box::pin(async move { Ok(Box::new(addrs.into_iter()) as Addrs) });
+        }
+        let entries = self.entries.clone();
+        Box::pin(async move {
+            // Port 0: reqwest fills in the URL's port
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            // Failures aren't cached, so the next refresh tries again
+            entries.lock().unwrap().insert(host, (Instant::now(), addrs.clone()));
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
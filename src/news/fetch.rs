@@ -1,146 +1,343 @@
+use super::diagnostic::{Diagnostic, FeedProgress};
 use super::model::Story;
-use crate::config::Feed;
+use super::provider::{FetchContext, ProviderRegistry, SourceProvider};
+use super::status::{self, Outcome, Transfer};
+use super::strict;
+use crate::config::{Feed, FetchLimits, RuntimeConfig, TranslationConfig};
+use crate::util::dates::DateDisplay;
 use crate::history::SeenStories;
-use anyhow::Result;
-use feed_rs::parser;
-use futures_util::StreamExt;
-use reqwest::Client;
-use std::{fs, path::Path, time::Duration};
+use crate::util::sanitize::html_to_text;
+use crate::util::tracking::strip_tracking_params;
+use crate::error::{Error, Result};
+use futures_util::{stream, Stream, StreamExt};
+use reqwest::{Client, ClientBuilder, Response};
+use std::sync::Arc;
+use std::time::Duration;
+use time::OffsetDateTime;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
 use url::Url;
 
-pub async fn collect_stories(feeds: &[Feed], history: &SeenStories) -> Result<Vec<Story>> {
-    let client = Client::builder()
-        .user_agent("news-cli/0.1")
-        .gzip(true)
-        .connect_timeout(Duration::from_secs(5))
-        .timeout(Duration::from_secs(20))
-        .build()?;
-
-    let mut all: Vec<Story> = Vec::new();
-
-    // Fetch sequentially for simplicity; can be optimized later with concurrency
-    for f in feeds {
-        let source_name = f.name.clone();
-        if Path::new(&f.url).is_file() {
-            // Local XML file
-            match fs::read(&f.url) {
-                Ok(bytes) => {
-                    if bytes.len() > max_feed_bytes() {
-                        eprintln!("Feed too large ({} bytes): {}", bytes.len(), f.url);
-                        continue;
-                    }
-                    match parser::parse(&bytes[..]) {
-                        Ok(feed) => push_entries(&mut all, feed, &source_name, None, history),
-                        Err(err) => eprintln!("Failed to parse feed {}: {}", f.url, err),
-                    }
-                }
-                Err(err) => eprintln!("failed to read file feed {}: {}", f.url, err),
+/// Fetches a set of feeds (URLs or local files) and turns their entries into [`Story`] values
+/// with normalized, tracking-free, deduplicated links.
+///
+/// ```no_run
+/// # async fn demo() -> anyhow::Result<()> {
+/// use news_cli::{NewsClient, SeenStories};
+///
+/// let cfg = news_cli::config::load(None)?;
+/// let history = SeenStories::load();
+/// let stories = NewsClient::new(&cfg)?.fetch(&history).await?;
+/// let unread = stories.iter().filter(|s| s.is_new).count();
+/// println!("{} unread", unread);
+/// # Ok(())
+/// # }
+/// ```
+pub struct NewsClient {
+    ctx: FetchContext,
+    feeds: Vec<Feed>,
+    tracking_params: Vec<String>,
+    translation: Option<TranslationConfig>,
+    // The clock feed schedules (`days`, `hours`) are read on
+    dates: DateDisplay,
+    providers: ProviderRegistry,
+    progress: Option<UnboundedSender<FeedProgress>>,
+    // Leave out lead images (`data_saver`)
+    no_images: bool,
+}
+
+impl NewsClient {
+    /// Client for the feeds and link rules in `cfg`, with the built-in providers.
+    pub fn new(cfg: &RuntimeConfig) -> Result<Self> {
+        Ok(Self::with_context(cfg, FetchContext::new(cfg.fetch.clone())?.with_data_saver(cfg.data_saver)))
+    }
+
+    /// Like [`new`](Self::new), but fetching through `ctx`, so a long-running program can
+    /// keep one HTTP client (and its open connections) across fetches. With `data_saver`,
+    /// `ctx` should be [`with_data_saver`](FetchContext::with_data_saver) too.
+    pub fn with_context(cfg: &RuntimeConfig, ctx: FetchContext) -> Self {
+        NewsClient {
+            ctx,
+            feeds: cfg.feeds.clone(),
+            tracking_params: cfg.tracking_params.clone(),
+            translation: cfg.translation.clone(),
+            dates: cfg.dates.clone(),
+            providers: ProviderRegistry::default(),
+            progress: None,
+            no_images: cfg.data_saver,
+        }
+    }
+
+    /// Handle feeds of another `kind` (or replace a built-in provider).
+    pub fn register<P: SourceProvider + 'static>(&mut self, provider: P) {
+        self.providers.register(provider);
+    }
+
+    /// Send how each feed is going to `progress` while fetching, e.g. for a progress list.
+    /// A closed channel is ignored.
+    pub fn report_progress(&mut self, progress: UnboundedSender<FeedProgress>) {
+        self.progress = Some(progress);
+    }
+
+    pub fn feeds(&self) -> &[Feed] {
+        &self.feeds
+    }
+
+    /// Fetch every feed. `is_new` is set from `history`, which is not modified, and
+    /// `language` detected (plus `translation` filled in with a `[translation]` section).
+    /// Feeds that fail, like translation, are skipped and reported in
+    /// [`diagnostics`](Fetched::diagnostics); nothing is printed. How each feed went is
+    /// recorded in [`status`](super::status). Feeds outside their `days` / `hours` are dormant
+    /// and not fetched at all.
+    pub async fn fetch_all(&self, history: &SeenStories) -> Fetched {
+        self.fetch_all_cancellable(history, &CancellationToken::new()).await
+    }
+
+    /// [`fetch_all`](Self::fetch_all) that stops early once `cancel` is cancelled, e.g. by a
+    /// keypress: the feeds done by then are returned, deduplicated but not translated, with
+    /// [`cancelled`](Fetched::cancelled) set. The feed being fetched is dropped mid-request.
+    pub async fn fetch_all_cancellable(&self, history: &SeenStories, cancel: &CancellationToken) -> Fetched {
+        let mut all: Vec<Story> = Vec::new();
+        let mut diagnostics = Vec::new();
+        let mut cancelled = false;
+        let mut updates = std::pin::pin!(self.updates(history));
+        loop {
+            let next = tokio::select! {
+                biased;
+                _ = cancel.cancelled() => None,
+                next = updates.next() => Some(next),
+            };
+            let Some(next) = next else {
+                cancelled = true;
+                break;
+            };
+            let Some(mut update) = next else { break };
+            all.append(&mut update.stories);
+            diagnostics.append(&mut update.diagnostics);
+        }
+
+        // Dedupe by link
+        all.sort_by(|a, b| a.link.cmp(&b.link));
+        all.dedup_by(|a, b| a.link == b.link);
+
+        crate::translate::detect_all(&mut all);
+        if let Some(t) = self.translation.as_ref().filter(|_| !cancelled) {
+            let translated = tokio::select! {
+                biased;
+                _ = cancel.cancelled() => None,
+                result = crate::translate::apply(t, &mut all) => Some(result),
+            };
+            match translated {
+                Some(Ok(())) => {}
+                Some(Err(err)) => diagnostics.push(Diagnostic::Translation(format!("{:#}", err))),
+                None => cancelled = true,
             }
-        } else {
-            // Remote URL
-            let base = Url::parse(&f.url).ok();
-            match client.get(&f.url).send().await {
-                Ok(resp) => {
-                    // Stream with a max size limit
-                    let mut stream = resp.bytes_stream();
-                    let mut buf: Vec<u8> = Vec::new();
-                    let mut total: usize = 0;
-                    let max = max_feed_bytes();
-                    while let Some(chunk) = stream.next().await {
-                        match chunk {
-                            Ok(c) => {
-                                total += c.len();
-                                if total > max {
-                                    eprintln!("Feed too large (>{} bytes): {}", max, f.url);
-                                    buf.clear();
-                                    break;
-                                }
-                                buf.extend_from_slice(&c);
-                            }
-                            Err(err) => {
-                                eprintln!("Failed to read body {}: {}", f.url, err);
-                                buf.clear();
-                                break;
+        }
+
+        Fetched { stories: all, diagnostics, cancelled }
+    }
+
+    /// Fetch the feeds one after another, yielding each as it is done, for screens that
+    /// show stories while the rest load. Stories are as [`fetch_all`](Self::fetch_all)
+    /// returns them, except not yet deduplicated across feeds, language-detected or
+    /// translated. Every feed's status is recorded as it completes.
+    pub fn updates<'a>(&'a self, history: &'a SeenStories) -> impl Stream<Item = FeedUpdate> + Send + 'a {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let statuses = status::load();
+        let feeds: Vec<(&Feed, Option<i64>)> = self
+            .feeds
+            .iter()
+            .filter(|f| f.is_active(&self.dates, now))
+            .map(|f| (f, statuses.get(&f.name).and_then(|s| s.backing_off(now))))
+            .collect();
+        for (f, _) in &feeds {
+            self.report(FeedProgress::Queued(f.name.clone()));
+        }
+        stream::iter(feeds).then(move |(f, backing_off)| self.fetch_feed(f, backing_off, history, now))
+    }
+
+    /// [`fetch_all`](Self::fetch_all) for command-line programs: the diagnostics are printed
+    /// to stderr, and only setup failures are errors.
+    pub async fn fetch(&self, history: &SeenStories) -> Result<Vec<Story>> {
+        let fetched = self.fetch_all(history).await;
+        for d in &fetched.diagnostics {
+            eprintln!("{}", d);
+        }
+        Ok(fetched.stories)
+    }
+
+    fn report(&self, progress: FeedProgress) {
+        if let Some(tx) = &self.progress {
+            let _ = tx.send(progress);
+        }
+    }
+
+    async fn fetch_feed(&self, f: &Feed, backing_off: Option<i64>, history: &SeenStories, now: i64) -> FeedUpdate {
+        self.report(FeedProgress::Fetching(f.name.clone()));
+        let update = self.fetch_feed_items(f, backing_off, history, now).await;
+        let failure = update.diagnostics.iter().find_map(|d| match d {
+            Diagnostic::UnknownKind { kind, .. } => Some(format!("unknown kind \"{}\"", kind)),
+            Diagnostic::BackingOff { until, .. } => Some(format!("the server asked to wait until {}", until)),
+            Diagnostic::Failed { error, .. } => Some(error.clone()),
+            _ => None,
+        });
+        self.report(match failure {
+            Some(error) => FeedProgress::Failed { feed: f.name.clone(), error },
+            None => FeedProgress::Fetched { feed: f.name.clone(), stories: update.stories.len() },
+        });
+        update
+    }
+
+    async fn fetch_feed_items(&self, f: &Feed, backing_off: Option<i64>, history: &SeenStories, now: i64) -> FeedUpdate {
+        let mut update = FeedUpdate { feed: f.name.clone(), stories: Vec::new(), diagnostics: Vec::new() };
+        if self.providers.get(f.kind()).is_none() {
+            update.diagnostics.push(Diagnostic::UnknownKind { feed: f.name.clone(), kind: f.kind().to_string() });
+            return update;
+        }
+        // A server that answered 429/503 with Retry-After is left alone until then
+        if let Some(until) = backing_off {
+            let until = self.dates.format(until).unwrap_or_default();
+            update.diagnostics.push(Diagnostic::BackingOff { feed: f.name.clone(), until });
+            return update;
+        }
+        let (outcome, transfer) = match self.ctx.for_feed(f) {
+            Err(err) => {
+                let error = format!("{:#}", err);
+                update.diagnostics.push(Diagnostic::Failed { feed: f.name.clone(), error: error.clone() });
+                (Outcome::Failed(error), Transfer::default())
+            }
+            Ok(ctx) => {
+                let result = self.providers.fetch(&ctx, f).await;
+                for message in ctx.warnings() {
+                    update.diagnostics.push(Diagnostic::Warning { feed: f.name.clone(), message });
+                }
+                match result {
+                    Ok(items) => {
+                        let newest = items.iter().filter_map(|s| s.published).max();
+                        for mut story in items {
+                            let Some(link) = normalize_link(&story.link, &self.tracking_params) else { continue };
+                            // Standardize source label to the configured feed name so ordering and
+                            // labels match the configuration.
+                            story.source = f.name.clone();
+                            // "AT&amp;T", "It&#8217;s" and stray <b> tags, so titles show
+                            // and compare as text
+                            story.title = html_to_text(&story.title);
+                            story.is_new = !history.is_seen(&link);
+                            story.link = link;
+                            if self.no_images {
+                                story.image = None;
                             }
+                            update.stories.push(story);
                         }
+                        (Outcome::Fetched(newest), ctx.transfer())
                     }
-                    if buf.is_empty() { continue; }
-                    match parser::parse(&buf[..]) {
-                        Ok(feed) => push_entries(&mut all, feed, &source_name, base.as_ref(), history),
-                        Err(err) => eprintln!("Failed to parse feed {}: {}", f.url, err),
+                    Err(err) => {
+                        let error = format!("{:#}", err);
+                        update.diagnostics.push(Diagnostic::Failed { feed: f.name.clone(), error: error.clone() });
+                        let outcome = match ctx.retry_after() {
+                            Some(until) => Outcome::Throttled(error, until),
+                            None => Outcome::Failed(error),
+                        };
+                        (outcome, ctx.transfer())
                     }
                 }
-                Err(err) => eprintln!("Failed to fetch {}: {}", f.url, err),
             }
+        };
+        if let Err(err) = status::record(now, &[(f.name.clone(), outcome, transfer)]) {
+            update.diagnostics.push(Diagnostic::Status(err.to_string()));
         }
+        update
     }
+}
 
-    // Dedupe by link
-    all.sort_by(|a, b| a.link.cmp(&b.link));
-    all.dedup_by(|a, b| a.link == b.link);
+/// One feed's part of a fetch, as [`NewsClient::updates`] yields it.
+#[derive(Debug, Clone)]
+pub struct FeedUpdate {
+    /// The feed's configured name.
+    pub feed: String,
+    pub stories: Vec<Story>,
+    pub diagnostics: Vec<Diagnostic>,
+}
 
-    Ok(all)
+/// What [`NewsClient::fetch_all`] brings back.
+#[derive(Debug, Clone, Default)]
+pub struct Fetched {
+    /// Deduplicated by link, in link order.
+    pub stories: Vec<Story>,
+    /// Everything that went wrong, in feed order; empty if nothing did.
+    pub diagnostics: Vec<Diagnostic>,
+    /// Stopped early (see [`NewsClient::fetch_all_cancellable`]), so feeds may be missing.
+    pub cancelled: bool,
 }
 
-fn push_entries(
-    all: &mut Vec<Story>,
-    feed: feed_rs::model::Feed,
-    fallback_source: &str,
-    base: Option<&Url>,
-    history: &SeenStories,
-) {
-    // Standardize source label to the configured feed name (fallback_source)
-    // so ordering and labels match the configuration.
-    let source = fallback_source.to_string();
-    for entry in feed.entries.into_iter() {
-        let title = entry
-            .title
-            .as_ref()
-            .map(|t| t.content.clone())
-            .unwrap_or_else(|| "(untitled)".into());
-
-        let raw_link = entry
-            .links
-            .iter()
-            .find(|l| l.rel.as_deref().unwrap_or("") == "alternate")
-            .or_else(|| entry.links.first())
-            .map(|l| l.href.clone())
-            .unwrap_or_else(|| String::from(""));
-
-        if let Some(normalized) = normalize_link(&raw_link, base) {
-            // Prefer published, fallback to updated; store as UNIX epoch seconds
-            let when: Option<i64> = entry
-                .published
-                .map(|d| d.timestamp())
-                .or_else(|| entry.updated.map(|d| d.timestamp()));
-            let is_new = !history.is_seen(&normalized);
-            all.push(Story { 
-                title, 
-                link: normalized, 
-                source: source.clone(),
-                is_new,
-                published: when,
-            });
-        }
-    }
+/// Fetch every feed in `cfg` without printing anything (see [`NewsClient::fetch_all`]).
+pub async fn fetch_all(cfg: &RuntimeConfig, history: &SeenStories) -> Result<Fetched> {
+    Ok(NewsClient::new(cfg)?.fetch_all(history).await)
 }
 
-fn normalize_link(candidate: &str, base: Option<&Url>) -> Option<String> {
+/// One-shot convenience for [`NewsClient::fetch`].
+pub async fn collect_stories(cfg: &RuntimeConfig, history: &SeenStories) -> Result<Vec<Story>> {
+    NewsClient::new(cfg)?.fetch(history).await
+}
+
+pub(crate) fn normalize_link(candidate: &str, tracking_params: &[String]) -> Option<String> {
     if candidate.trim().is_empty() { return None; }
-    let resolved = match Url::parse(candidate) {
-        Ok(u) => u,
-        Err(_) => {
-            let b = base?;
-            b.join(candidate).ok()?
-        }
-    };
+    let mut resolved = Url::parse(candidate.trim()).ok()?;
+    // Strip campaign junk so the same article always maps to the same history entry
+    strip_tracking_params(&mut resolved, tracking_params);
     match resolved.scheme() {
-        "http" | "https" => Some(resolved.into_string()),
+        // imap: newsletters, read in the detail view rather than a browser
+        "http" | "https" | "imap" => Some(resolved.into()),
         _ => None,
     }
 }
 
-fn max_feed_bytes() -> usize {
+pub const DEFAULT_USER_AGENT: &str = "news-cli/0.1";
+
+pub fn build_client() -> Result<Client> {
+    build_client_with(&FetchLimits::default())
+}
+
+/// Client with the timeouts and user agent of `limits` (defaults: 5s connect, 20s total).
+/// Bodies may come gzip or Brotli compressed, and HTTPS servers that offer HTTP/2 get it.
+/// Host lookups go through a cache shared by all clients. With `strict`, private addresses
+/// and long redirect chains are refused (see [`strict`](super::strict)).
+pub fn build_client_with(limits: &FetchLimits) -> Result<Client> {
+    Ok(client_builder(limits).build()?)
+}
+
+/// The builder [`build_client_with`] finishes, for clients that need more settings.
+pub(crate) fn client_builder(limits: &FetchLimits) -> ClientBuilder {
+    let builder = Client::builder()
+        .user_agent(limits.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT))
+        .gzip(true)
+        .brotli(true)
+        .connect_timeout(Duration::from_secs(limits.connect_timeout_secs.unwrap_or(5)))
+        .timeout(Duration::from_secs(limits.timeout_secs.unwrap_or(20)));
+    match limits.strict == Some(true) {
+        true => builder
+            .dns_resolver(Arc::new(strict::PublicOnly(super::dns::shared())))
+            .redirect(strict::redirect_policy()),
+        false => builder.dns_resolver(super::dns::shared()),
+    }
+}
+
+/// Stream a response body, giving up once it grows past `max` bytes.
+pub async fn read_body_capped(resp: Response, max: usize) -> Result<Vec<u8>> {
+    let mut stream = resp.bytes_stream();
+    let mut buf: Vec<u8> = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let c = chunk?;
+        if buf.len() + c.len() > max {
+            return Err(Error::TooLarge(max));
+        }
+        buf.extend_from_slice(&c);
+    }
+    Ok(buf)
+}
+
+/// Default body cap; `max_bytes` in `[fetch]` or on a feed overrides it.
+pub fn max_feed_bytes() -> usize {
     // 5 MB cap
     5 * 1024 * 1024
 }
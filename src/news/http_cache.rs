@@ -0,0 +1,71 @@
+//! Feed bodies kept with their `ETag` / `Last-Modified`, so the next fetch can ask "changed
+//! since?" and a 304 Not Modified costs no download. Stored under `feeds/` in the cache
+//! directory, one body and one validators file per feed URL.
+use crate::util::atomic::write_atomic;
+use reqwest::header::{HeaderMap, DATE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+
+#[derive(Serialize, Deserialize)]
+struct Validators {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+/// The last body fetched from a URL.
+pub(crate) struct Cached {
+    validators: Validators,
+    pub(crate) body: Vec<u8>,
+}
+
+impl Cached {
+    pub(crate) fn load(url: &str) -> Option<Cached> {
+        let (meta, body) = paths(url)?;
+        let validators: Validators = serde_json::from_str(&fs::read_to_string(meta).ok()?).ok()?;
+        // Another URL with the same hash
+        if validators.url != url {
+            return None;
+        }
+        Some(Cached { validators, body: fs::read(body).ok()? })
+    }
+
+    /// `request`, asking for the body only if it changed since this copy.
+    pub(crate) fn condition(&self, mut request: RequestBuilder) -> RequestBuilder {
+        if let Some(etag) = &self.validators.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(date) = &self.validators.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, date);
+        }
+        request
+    }
+}
+
+/// Keep `body` as fetched from `url` if its response `headers` give something to ask with
+/// next time; otherwise forget any earlier copy. With `always`, a response without `ETag`
+/// or `Last-Modified` is kept too, asked for next time as modified since its `Date`.
+pub(crate) fn save(url: &str, headers: &HeaderMap, body: &[u8], always: bool) -> io::Result<()> {
+    let Some((meta, body_path)) = paths(url) else { return Ok(()) };
+    let header = |name| headers.get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+    let mut validators = Validators { url: url.to_string(), etag: header(ETAG), last_modified: header(LAST_MODIFIED) };
+    if always && validators.etag.is_none() && validators.last_modified.is_none() {
+        validators.last_modified = header(DATE);
+    }
+    if validators.etag.is_none() && validators.last_modified.is_none() {
+        let _ = fs::remove_file(meta);
+        let _ = fs::remove_file(body_path);
+        return Ok(());
+    }
+    // The body first: validators without it are never read
+    write_atomic(&body_path, body, false)?;
+    write_atomic(&meta, serde_json::to_string(&validators)?.as_bytes(), false)
+}
+
+fn paths(url: &str) -> Option<(PathBuf, PathBuf)> {
+    // FNV-1a, so the file names stay the same across builds
+    let hash = url.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |h, b| (h ^ b as u64).wrapping_mul(0x0100_0000_01b3));
+    let dir = crate::paths::cache_dir()?.join("feeds");
+    Some((dir.join(format!("{:016x}.json", hash)), dir.join(format!("{:016x}.body", hash))))
+}
@@ -1,12 +1,48 @@
 use serde::{Deserialize, Serialize};
 
+/// One feed entry, as shown in the UI and written by the sinks.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Story {
     pub title: String,
+    /// Absolute http(s) URL with tracking parameters removed; the identity used for history.
     pub link: String,
+    /// Configured name of the feed it came from.
     pub source: String,
+    /// Not in the history at fetch time.
     #[serde(default)]
     pub is_new: bool,
+    /// UNIX epoch seconds (published, else updated).
     #[serde(default)]
     pub published: Option<i64>,
+    #[serde(default)]
+    pub author: Option<String>,
+    /// Feed-provided summary/description (may contain HTML).
+    #[serde(default)]
+    pub summary: Option<String>,
+    /// Video length in seconds, for sources that know it.
+    #[serde(default)]
+    pub duration: Option<u64>,
+    /// Hacker News / Reddit points, for sources that publish them.
+    #[serde(default)]
+    pub points: Option<u64>,
+    /// Full article HTML when the feed embeds it (RSS `content:encoded`, Atom `<content>`).
+    #[serde(default)]
+    pub content: Option<String>,
+    /// Lead image or thumbnail URL (enclosure, `media:thumbnail`, first `<img>` of the content).
+    #[serde(default)]
+    pub image: Option<String>,
+    /// Detected language of the title and summary (ISO 639-1, e.g. "de"), when confident.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Machine translation into the `[translation]` target language.
+    #[serde(default)]
+    pub translation: Option<Translation>,
+}
+
+/// Translated title and (plain-text) summary of a [`Story`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Translation {
+    pub title: String,
+    #[serde(default)]
+    pub summary: Option<String>,
 }
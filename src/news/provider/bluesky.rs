@@ -0,0 +1,128 @@
+use super::{instance_url, post_title, FetchContext, SourceProvider};
+use crate::config::Feed;
+use crate::news::Story;
+use anyhow::{bail, Result};
+use futures_util::future::BoxFuture;
+use serde::Deserialize;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use url::Url;
+
+// Unauthenticated read-only AppView run by Bluesky
+const PUBLIC_APPVIEW: &str = "https://public.api.bsky.app/";
+
+/// Public posts from Bluesky. `account` is a handle or DID, or the `at://` URI of a list
+/// (`at://did:plc:.../app.bsky.graph.list/...`) to follow its members' posts.
+pub struct BlueskyProvider;
+
+#[derive(Deserialize)]
+struct FeedResponse {
+    feed: Vec<FeedItem>,
+}
+
+#[derive(Deserialize)]
+struct FeedItem {
+    post: Post,
+    reason: Option<Reason>,
+}
+
+#[derive(Deserialize)]
+struct Reason {
+    #[serde(rename = "$type")]
+    kind: String,
+    by: Option<Author>,
+}
+
+#[derive(Deserialize)]
+struct Post {
+    uri: String,
+    author: Author,
+    record: Record,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Author {
+    handle: String,
+    display_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Record {
+    #[serde(default)]
+    text: String,
+    created_at: Option<String>,
+}
+
+impl SourceProvider for BlueskyProvider {
+    fn kind(&self) -> &'static str {
+        "bluesky"
+    }
+
+    fn fetch<'a>(&'a self, ctx: &'a FetchContext, feed: &'a Feed) -> BoxFuture<'a, Result<Vec<Story>>> {
+        Box::pin(async move {
+            let base = match feed.instance.as_deref() {
+                Some(i) if !i.trim().is_empty() => instance_url(i)?,
+                _ => Url::parse(PUBLIC_APPVIEW)?,
+            };
+            let account = feed.account.as_deref().unwrap_or("").trim().trim_start_matches('@');
+            if account.is_empty() {
+                bail!("no account configured");
+            }
+            let mut url;
+            if account.starts_with("at://") {
+                url = base.join("xrpc/app.bsky.feed.getListFeed")?;
+                url.query_pairs_mut().append_pair("list", account).append_pair("limit", "50");
+            } else {
+                url = base.join("xrpc/app.bsky.feed.getAuthorFeed")?;
+                url.query_pairs_mut()
+                    .append_pair("actor", account)
+                    .append_pair("limit", "50")
+                    .append_pair("filter", "posts_no_replies");
+            }
+            let resp = ctx.send(ctx.client.get(url)).await?.error_for_status()?;
+            let body: FeedResponse = ctx.json(resp).await?;
+            Ok(body.feed.into_iter().filter_map(to_story).collect())
+        })
+    }
+}
+
+fn to_story(item: FeedItem) -> Option<Story> {
+    let post = item.post;
+    // at://<did>/app.bsky.feed.post/<rkey> -> https://bsky.app/profile/<handle>/post/<rkey>
+    let rkey = post.uri.rsplit('/').next().filter(|k| !k.is_empty())?;
+    let link = format!("https://bsky.app/profile/{}/post/{}", post.author.handle, rkey);
+    let text = post.record.text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let reposter = item
+        .reason
+        .filter(|r| r.kind.ends_with("#reasonRepost"))
+        .and_then(|r| r.by)
+        .map(|a| a.handle);
+    let title = post_title(&text);
+    let name = match post.author.display_name.as_deref().map(str::trim) {
+        Some(d) if !d.is_empty() => format!("{} (@{})", d, post.author.handle),
+        _ => format!("@{}", post.author.handle),
+    };
+    Some(Story {
+        title: match reposter {
+            Some(r) => format!("{} (reposted by @{})", title, r),
+            None => title,
+        },
+        link,
+        source: String::new(),
+        is_new: false,
+        published: post
+            .record
+            .created_at
+            .and_then(|t| OffsetDateTime::parse(&t, &Rfc3339).ok())
+            .map(|d| d.unix_timestamp()),
+        author: Some(name),
+        summary: (!text.is_empty()).then_some(text),
+        duration: None,
+        points: None,
+        content: None,
+        image: None,
+        language: None,
+        translation: None,
+    })
+}
@@ -0,0 +1,111 @@
+use super::{FetchContext, SourceProvider};
+use crate::config::Feed;
+use crate::news::Story;
+use anyhow::{anyhow, bail, Context, Result};
+use futures_util::future::BoxFuture;
+use serde::Deserialize;
+use std::process::Stdio;
+use std::time::Duration;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use tokio::process::Command;
+
+/// Runs `command` and reads stories from its stdout: a JSON array, or one JSON object per
+/// line, with `title`, `link` (or `url`) and optional `published` (RFC 3339 or epoch
+/// seconds), `author`, `summary`, `content` (full HTML), `image` (URL), `duration`
+/// (seconds) and `points` (votes, used by the Top Stories ranking).
+pub struct CommandProvider;
+
+#[derive(Deserialize)]
+struct Item {
+    title: String,
+    #[serde(alias = "url")]
+    link: String,
+    #[serde(default)]
+    published: Option<When>,
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    duration: Option<u64>,
+    #[serde(default)]
+    points: Option<u64>,
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    image: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum When {
+    Epoch(i64),
+    Text(String),
+}
+
+impl SourceProvider for CommandProvider {
+    fn kind(&self) -> &'static str {
+        "command"
+    }
+
+    fn fetch<'a>(&'a self, ctx: &'a FetchContext, feed: &'a Feed) -> BoxFuture<'a, Result<Vec<Story>>> {
+        Box::pin(async move {
+            let cmd = feed.command.as_deref().unwrap_or("");
+            let mut parts = cmd.split_whitespace();
+            let Some(program) = parts.next() else { bail!("no command configured") };
+            let child = Command::new(program)
+                .args(parts)
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::inherit())
+                .kill_on_drop(true)
+                .spawn()
+                .with_context(|| format!("failed to run {}", program))?;
+            let limit = Duration::from_secs(ctx.limits.timeout_secs.unwrap_or(30));
+            let out = tokio::time::timeout(limit, child.wait_with_output())
+                .await
+                .map_err(|_| anyhow!("{} timed out", program))??;
+            if !out.status.success() {
+                bail!("{} exited with {}", program, out.status);
+            }
+            if out.stdout.len() > ctx.max_bytes() {
+                bail!("output too large ({} bytes)", out.stdout.len());
+            }
+            parse_output(&out.stdout)
+        })
+    }
+}
+
+fn parse_output(stdout: &[u8]) -> Result<Vec<Story>> {
+    let text = std::str::from_utf8(stdout).context("output is not UTF-8")?;
+    let items: Vec<Item> = if text.trim_start().starts_with('[') {
+        serde_json::from_str(text).context("invalid JSON array")?
+    } else {
+        text.lines()
+            .filter(|l| !l.trim().is_empty())
+            .enumerate()
+            .map(|(n, l)| serde_json::from_str(l).with_context(|| format!("invalid JSON on line {}", n + 1)))
+            .collect::<Result<_>>()?
+    };
+    Ok(items
+        .into_iter()
+        .map(|it| Story {
+            title: it.title,
+            link: it.link,
+            source: String::new(),
+            is_new: false,
+            published: it.published.and_then(|w| match w {
+                When::Epoch(s) => Some(s),
+                When::Text(t) => OffsetDateTime::parse(&t, &Rfc3339).ok().map(|d| d.unix_timestamp()),
+            }),
+            author: it.author,
+            summary: it.summary,
+            duration: it.duration,
+            points: it.points,
+            content: it.content,
+            image: it.image,
+            language: None,
+            translation: None,
+        })
+        .collect())
+}
@@ -0,0 +1,186 @@
+use super::{instance_url, FetchContext, SourceProvider};
+use crate::config::Feed;
+use crate::news::Story;
+use anyhow::{bail, Result};
+use futures_util::future::{join_all, BoxFuture};
+use reqwest::{Client, RequestBuilder};
+use serde::Deserialize;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use url::Url;
+
+const API: &str = "https://api.github.com/";
+
+/// GitHub releases or notifications. `account` lists repositories (`owner/repo`, separated
+/// by spaces or commas) to follow their releases, or is `notifications` for the token
+/// owner's unread notifications. `token` (or `$GITHUB_TOKEN`) is required for
+/// notifications and raises the API rate limit for releases. `instance` is the API of a
+/// GitHub Enterprise server (e.g. `github.example.com/api/v3`), api.github.com by default.
+pub struct GithubProvider;
+
+#[derive(Deserialize)]
+struct Release {
+    name: Option<String>,
+    tag_name: String,
+    html_url: String,
+    published_at: Option<String>,
+    body: Option<String>,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+    author: Option<User>,
+}
+
+#[derive(Deserialize)]
+struct User {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct Notification {
+    subject: Subject,
+    repository: Repository,
+    updated_at: String,
+    reason: String,
+}
+
+#[derive(Deserialize)]
+struct Subject {
+    title: String,
+    url: Option<String>,
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+#[derive(Deserialize)]
+struct Repository {
+    full_name: String,
+    html_url: String,
+}
+
+impl SourceProvider for GithubProvider {
+    fn kind(&self) -> &'static str {
+        "github"
+    }
+
+    fn fetch<'a>(&'a self, ctx: &'a FetchContext, feed: &'a Feed) -> BoxFuture<'a, Result<Vec<Story>>> {
+        Box::pin(async move {
+            let token = feed
+                .token
+                .clone()
+                .filter(|t| !t.trim().is_empty())
+                .or_else(|| std::env::var("GITHUB_TOKEN").ok().filter(|t| !t.is_empty()));
+            let api = match feed.instance.as_deref() {
+                Some(i) if !i.trim().is_empty() => instance_url(i)?,
+                _ => Url::parse(API)?,
+            };
+            let account = feed.account.as_deref().unwrap_or("").trim();
+            if account == "notifications" {
+                let Some(token) = token else { bail!("notifications need a token (token or $GITHUB_TOKEN)") };
+                return notifications(ctx, &api, &token).await;
+            }
+            let repos: Vec<&str> = account.split([',', ' ']).filter(|r| !r.is_empty()).collect();
+            if repos.is_empty() {
+                bail!("no repositories configured");
+            }
+            let results = join_all(repos.iter().map(|r| releases(ctx, &api, token.as_deref(), r))).await;
+            let mut stories = Vec::new();
+            for (repo, result) in repos.iter().zip(results) {
+                match result {
+                    Ok(mut s) => stories.append(&mut s),
+                    // One renamed or deleted repo shouldn't hide the others
+                    Err(e) => ctx.warn(format!("Failed to fetch releases of {}: {:#}", repo, e)),
+                }
+            }
+            Ok(stories)
+        })
+    }
+}
+
+fn get(client: &Client, token: Option<&str>, url: Url) -> RequestBuilder {
+    let req = client
+        .get(url)
+        .header("Accept", "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28");
+    match token {
+        Some(t) => req.bearer_auth(t.trim()),
+        None => req,
+    }
+}
+
+async fn releases(ctx: &FetchContext, api: &Url, token: Option<&str>, repo: &str) -> Result<Vec<Story>> {
+    if repo.split('/').filter(|p| !p.is_empty()).count() != 2 {
+        bail!("expected owner/repo");
+    }
+    let url = api.join(&format!("repos/{}/releases?per_page=10", repo))?;
+    let list: Vec<Release> = ctx.json(ctx.send(get(&ctx.client, token, url)).await?.error_for_status()?).await?;
+    Ok(list
+        .into_iter()
+        .filter(|r| !r.draft)
+        .map(|r| {
+            let name = r.name.filter(|n| !n.trim().is_empty()).unwrap_or(r.tag_name);
+            let pre = if r.prerelease { " (pre-release)" } else { "" };
+            Story {
+                title: format!("{} {} released{}", repo, name.trim(), pre),
+                link: r.html_url,
+                source: String::new(),
+                is_new: false,
+                published: r.published_at.as_deref().and_then(parse_time),
+                author: r.author.map(|a| a.login),
+                summary: r.body.filter(|b| !b.trim().is_empty()),
+                duration: None,
+                points: None,
+                content: None,
+                image: None,
+                language: None,
+                translation: None,
+            }
+        })
+        .collect())
+}
+
+async fn notifications(ctx: &FetchContext, api: &Url, token: &str) -> Result<Vec<Story>> {
+    let url = api.join("notifications?per_page=50")?;
+    let resp = ctx.send(get(&ctx.client, Some(token), url)).await?.error_for_status()?;
+    let list: Vec<Notification> = ctx.json(resp).await?;
+    Ok(list
+        .into_iter()
+        .map(|n| Story {
+            title: format!("[{}] {}", n.repository.full_name, n.subject.title),
+            link: web_link(api, &n),
+            source: String::new(),
+            is_new: false,
+            published: parse_time(&n.updated_at),
+            author: None,
+            summary: Some(format!("{} ({})", n.subject.kind, n.reason.replace('_', " "))),
+            duration: None,
+            points: None,
+            content: None,
+            image: None,
+            language: None,
+            translation: None,
+        })
+        .collect())
+}
+
+// Notifications point at API URLs; issues, PRs and commits map onto their web pages, on the
+// server the repository's page is on
+fn web_link(api: &Url, n: &Notification) -> String {
+    let repo = &n.repository;
+    let Some(path) = n.subject.url.as_deref().and_then(|u| u.strip_prefix(&format!("{}repos/", api))) else {
+        return repo.html_url.clone();
+    };
+    // https://github.com/ for https://github.com/owner/repo
+    let Some(web) = repo.html_url.strip_suffix(&repo.full_name) else { return repo.html_url.clone() };
+    match n.subject.kind.as_str() {
+        "Issue" => format!("{}{}", web, path),
+        "PullRequest" => format!("{}{}", web, path.replacen("/pulls/", "/pull/", 1)),
+        "Commit" => format!("{}{}", web, path.replacen("/commits/", "/commit/", 1)),
+        "Release" => format!("{}/releases", repo.html_url),
+        _ => repo.html_url.clone(),
+    }
+}
+
+fn parse_time(s: &str) -> Option<i64> {
+    OffsetDateTime::parse(s, &Rfc3339).ok().map(|d| d.unix_timestamp())
+}
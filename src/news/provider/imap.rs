@@ -0,0 +1,245 @@
+use super::{FetchContext, SourceProvider};
+use crate::config::Feed;
+use crate::news::{strict, Story};
+use crate::util::tls;
+use anyhow::{anyhow, bail, Context, Result};
+use futures_util::future::BoxFuture;
+use mail_parser::MessageParser;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::pki_types::ServerName;
+use url::Url;
+
+// Newest unread messages to show per fetch
+const MAX_MESSAGES: usize = 30;
+// Longest response line accepted; message bodies come as literals, not lines
+const MAX_LINE: usize = 64 * 1024;
+
+/// Unread mail in one folder of an IMAP mailbox, e.g. a "Newsletters" folder. `instance`
+/// is the server (`host`, `host:port` or `[ipv6]:port`, TLS only, port 993 by default),
+/// `account` the user name, `token` the password (default `$NEWS_CLI_IMAP_PASSWORD`) and
+/// `folder` the mailbox (default `INBOX`). The folder is opened read-only, so nothing is
+/// marked read.
+///
+/// Stories link to an `imap://` URL (RFC 5092); their text is in `summary`.
+pub struct ImapProvider;
+
+impl SourceProvider for ImapProvider {
+    fn kind(&self) -> &'static str {
+        "imap"
+    }
+
+    fn fetch<'a>(&'a self, ctx: &'a FetchContext, feed: &'a Feed) -> BoxFuture<'a, Result<Vec<Story>>> {
+        Box::pin(async move {
+            let limit = Duration::from_secs(ctx.limits.timeout_secs.unwrap_or(60));
+            let strict = ctx.limits.strict == Some(true);
+            tokio::time::timeout(limit, fetch_unread(feed, ctx.max_bytes(), strict))
+                .await
+                .map_err(|_| anyhow!("IMAP server timed out"))?
+        })
+    }
+}
+
+// With `strict`, only to a public address of the server
+async fn fetch_unread(feed: &Feed, max_bytes: usize, strict: bool) -> Result<Vec<Story>> {
+    let (host, port) = server(feed)?;
+    let password = feed
+        .token
+        .clone()
+        .filter(|t| !t.is_empty())
+        .or_else(|| std::env::var("NEWS_CLI_IMAP_PASSWORD").ok())
+        .context("no password (token or $NEWS_CLI_IMAP_PASSWORD)")?;
+    let tcp = match strict {
+        true => TcpStream::connect(&*strict::public_addrs(host, port).await.map_err(anyhow::Error::msg)?).await,
+        false => TcpStream::connect((host, port)).await,
+    };
+    let tcp = tcp.with_context(|| format!("failed to connect to {}:{}", host, port))?;
+    let tls = tls::connector()?.connect(ServerName::try_from(host.to_string())?, tcp).await?;
+    session(tls, feed, &password, max_bytes).await
+}
+
+/// The conversation for `feed` over `stream` (already TLS), from the greeting to LOGOUT: the
+/// newest unread messages of its folder, none of them larger than `max_bytes`.
+pub async fn session<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: S,
+    feed: &Feed,
+    password: &str,
+    max_bytes: usize,
+) -> Result<Vec<Story>> {
+    let (host, port) = server(feed)?;
+    let user = feed.account.as_deref().unwrap_or("").trim();
+    let folder = feed.folder.as_deref().unwrap_or("INBOX");
+
+    let mut imap = Connection::new(stream, max_bytes).await?;
+    imap.run(&format!("LOGIN {} {}", quote(user)?, quote(password)?))
+        .await
+        .context("login failed")?;
+    let selected = imap.run(&format!("EXAMINE {}", quote(folder)?)).await?;
+    let uid_validity = selected
+        .iter()
+        .find_map(|r| r.text.split("[UIDVALIDITY ").nth(1)?.split(']').next()?.parse::<u32>().ok())
+        .unwrap_or(0);
+    let found = imap.run("UID SEARCH UNSEEN").await?;
+    let mut uids: Vec<u32> = found
+        .iter()
+        .filter_map(|r| r.text.strip_prefix("SEARCH"))
+        .flat_map(|rest| rest.split_whitespace().filter_map(|n| n.parse().ok()))
+        .collect();
+    uids.sort_unstable();
+    let newest = &uids[uids.len().saturating_sub(MAX_MESSAGES)..];
+
+    let mut base = match host.contains(':') {
+        true => Url::parse(&format!("imap://[{}]/", host))?,
+        false => Url::parse(&format!("imap://{}/", host))?,
+    };
+    let _ = base.set_username(user);
+    if port != 993 {
+        let _ = base.set_port(Some(port));
+    }
+    let mut stories = Vec::new();
+    for uid in newest {
+        let resp = imap.run(&format!("UID FETCH {} BODY.PEEK[]", uid)).await?;
+        let Some(raw) = resp.into_iter().find_map(|r| r.literals.into_iter().next()) else { continue };
+        let Some(msg) = MessageParser::default().parse(&raw) else { continue };
+        let mut link = base.clone();
+        link.set_path(&format!("{};UIDVALIDITY={}/;UID={}", folder, uid_validity, uid));
+        let author = msg.from().and_then(|a| a.first()).and_then(|a| {
+            a.name()
+                .map(str::to_string)
+                .or_else(|| a.address().map(str::to_string))
+        });
+        let summary = msg
+            .body_text(0)
+            .map(|t| t.into_owned())
+            .filter(|t| !t.trim().is_empty());
+        stories.push(Story {
+            title: msg.subject().unwrap_or("(no subject)").to_string(),
+            link: link.into(),
+            source: String::new(),
+            is_new: false,
+            published: msg.date().map(|d| d.to_timestamp()),
+            author,
+            summary,
+            duration: None,
+            points: None,
+            content: None,
+            image: None,
+            language: None,
+            translation: None,
+        });
+    }
+    let _ = imap.run("LOGOUT").await;
+    Ok(stories)
+}
+
+// The feed's `instance` as host and port: `host`, `host:port`, `[ipv6]`, `[ipv6]:port`, or a
+// bare IPv6 address on the default port
+fn server(feed: &Feed) -> Result<(&str, u16)> {
+    let server = feed.instance.as_deref().unwrap_or("").trim();
+    let port = |p: &str| p.parse::<u16>().context("invalid port in instance");
+    let (host, port) = match server.strip_prefix('[') {
+        Some(rest) => match rest.split_once(']') {
+            Some((h, "")) => (h, 993),
+            Some((h, p)) => (h, port(p.strip_prefix(':').unwrap_or(p))?),
+            None => bail!("missing ] in instance"),
+        },
+        None => match server.rsplit_once(':') {
+            Some((h, p)) if !h.contains(':') => (h, port(p)?),
+            _ => (server, 993),
+        },
+    };
+    if host.is_empty() {
+        bail!("no IMAP server configured");
+    }
+    Ok((host, port))
+}
+
+// IMAP quoted string; the server sees the exact bytes between the quotes. CR and LF would end
+// the command there and start another, and a quoted string can't hold them (or NUL) anyway.
+fn quote(s: &str) -> Result<String> {
+    if s.contains(['\r', '\n', '\0']) {
+        bail!("IMAP user name, password and folder can't contain line breaks");
+    }
+    Ok(format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"")))
+}
+
+/// One untagged `* ...` response and the literals (`{n}` blocks) it carried.
+struct Untagged {
+    text: String,
+    literals: Vec<Vec<u8>>,
+}
+
+struct Connection<S> {
+    stream: BufReader<S>,
+    next_tag: u32,
+    // Largest message accepted
+    max_bytes: usize,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> Connection<S> {
+    /// Wait for the server's greeting on `stream`.
+    async fn new(stream: S, max_bytes: usize) -> Result<Self> {
+        let mut conn = Connection { stream: BufReader::new(stream), next_tag: 1, max_bytes };
+        let greeting = conn.read_line().await?;
+        if !greeting.starts_with("* OK") {
+            bail!("unexpected greeting: {}", greeting.trim_end());
+        }
+        Ok(conn)
+    }
+
+    /// Send one command and collect its untagged responses; a NO/BAD completion is an error.
+    async fn run(&mut self, command: &str) -> Result<Vec<Untagged>> {
+        let tag = format!("a{}", self.next_tag);
+        self.next_tag += 1;
+        let stream = self.stream.get_mut();
+        stream.write_all(format!("{} {}\r\n", tag, command).as_bytes()).await?;
+        stream.flush().await?;
+
+        let mut responses = Vec::new();
+        loop {
+            let mut line = self.read_line().await?;
+            if let Some(status) = line.strip_prefix(&format!("{} ", tag)) {
+                if status.starts_with("OK") {
+                    return Ok(responses);
+                }
+                bail!("{}", status.trim_end());
+            }
+            let mut literals = Vec::new();
+            let mut size = 0;
+            // A line ending in {n} is followed by n raw bytes, then the rest of the response
+            while let Some(len) = literal_len(&line) {
+                size += len;
+                if size > self.max_bytes {
+                    bail!("message too large ({} bytes)", size);
+                }
+                let mut buf = vec![0; len];
+                self.stream.read_exact(&mut buf).await?;
+                literals.push(buf);
+                line.push_str(&self.read_line().await?);
+                if line.len() > MAX_LINE {
+                    bail!("response line too long");
+                }
+            }
+            let text = line.trim_start_matches("* ").trim_end().to_string();
+            responses.push(Untagged { text, literals });
+        }
+    }
+
+    async fn read_line(&mut self) -> Result<String> {
+        let mut buf = Vec::new();
+        if (&mut self.stream).take(MAX_LINE as u64).read_until(b'\n', &mut buf).await? == 0 {
+            bail!("connection closed by server");
+        }
+        if !buf.ends_with(b"\n") && buf.len() == MAX_LINE {
+            bail!("response line too long");
+        }
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+}
+
+fn literal_len(line: &str) -> Option<usize> {
+    let head = line.trim_end().strip_suffix('}')?;
+    let start = head.rfind('{')?;
+    head[start + 1..].parse().ok()
+}
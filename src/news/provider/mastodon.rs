@@ -0,0 +1,121 @@
+use super::{instance_url, post_title, FetchContext, SourceProvider};
+use crate::config::Feed;
+use crate::news::Story;
+use crate::util::sanitize::html_to_text;
+use anyhow::{bail, Context, Result};
+use futures_util::future::BoxFuture;
+use serde::Deserialize;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use url::Url;
+
+/// Public posts from a Mastodon (or compatible) server. `account` is a user (`name` or
+/// `name@other.server`) or a hashtag (`#tag`); no login is needed for either.
+pub struct MastodonProvider;
+
+#[derive(Deserialize)]
+struct Account {
+    id: String,
+    acct: String,
+    #[serde(default)]
+    display_name: String,
+}
+
+#[derive(Deserialize)]
+struct Status {
+    created_at: String,
+    url: Option<String>,
+    uri: String,
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    spoiler_text: String,
+    account: Account,
+    reblog: Option<Box<Status>>,
+    #[serde(default)]
+    media_attachments: Vec<Attachment>,
+}
+
+#[derive(Deserialize)]
+struct Attachment {
+    #[serde(rename = "type")]
+    kind: String,
+    preview_url: Option<String>,
+}
+
+impl SourceProvider for MastodonProvider {
+    fn kind(&self) -> &'static str {
+        "mastodon"
+    }
+
+    fn fetch<'a>(&'a self, ctx: &'a FetchContext, feed: &'a Feed) -> BoxFuture<'a, Result<Vec<Story>>> {
+        Box::pin(async move {
+            let Some(instance) = feed.instance.as_deref() else { bail!("no instance configured") };
+            let base = instance_url(instance)?;
+            let account = feed.account.as_deref().unwrap_or("").trim();
+            let statuses: Vec<Status> = if let Some(tag) = account.strip_prefix('#') {
+                let mut url = base.join("api/v1/timelines/tag/")?.join(tag)?;
+                url.query_pairs_mut().append_pair("limit", "40");
+                get_json(ctx, url).await?
+            } else {
+                let acct = account.trim_start_matches('@');
+                if acct.is_empty() {
+                    bail!("no account configured");
+                }
+                let mut lookup = base.join("api/v1/accounts/lookup")?;
+                lookup.query_pairs_mut().append_pair("acct", acct);
+                let who: Account = get_json(ctx, lookup)
+                    .await
+                    .with_context(|| format!("account {} not found", acct))?;
+                let mut url = base.join(&format!("api/v1/accounts/{}/statuses", who.id))?;
+                url.query_pairs_mut()
+                    .append_pair("limit", "40")
+                    .append_pair("exclude_replies", "true");
+                get_json(ctx, url).await?
+            };
+            Ok(statuses.into_iter().map(to_story).collect())
+        })
+    }
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(ctx: &FetchContext, url: Url) -> Result<T> {
+    let resp = ctx.send(ctx.client.get(url)).await?.error_for_status()?;
+    Ok(ctx.json(resp).await?)
+}
+
+fn to_story(status: Status) -> Story {
+    // A boost shows the original post, credited to its author
+    let booster = status.reblog.is_some().then(|| status.account.acct.clone());
+    let post = match status.reblog {
+        Some(original) => *original,
+        None => status,
+    };
+    let text = html_to_text(&post.content);
+    let title = if !post.spoiler_text.trim().is_empty() {
+        format!("CW: {}", post.spoiler_text.trim())
+    } else {
+        post_title(&text)
+    };
+    let name = if post.account.display_name.trim().is_empty() {
+        format!("@{}", post.account.acct)
+    } else {
+        format!("{} (@{})", post.account.display_name.trim(), post.account.acct)
+    };
+    Story {
+        title: match booster {
+            Some(b) => format!("{} (boosted by @{})", title, b),
+            None => title,
+        },
+        link: post.url.unwrap_or(post.uri),
+        source: String::new(),
+        is_new: false,
+        published: OffsetDateTime::parse(&post.created_at, &Rfc3339).ok().map(|d| d.unix_timestamp()),
+        author: Some(name),
+        summary: (!text.is_empty()).then_some(text),
+        duration: None,
+        points: None,
+        content: None,
+        image: post.media_attachments.iter().find(|a| a.kind == "image").and_then(|a| a.preview_url.clone()),
+        language: None,
+        translation: None,
+    }
+}
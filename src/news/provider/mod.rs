@@ -0,0 +1,281 @@
+//! Source providers turn one `[[feeds]]` entry into stories. The entry's `kind` picks the
+//! provider (`rss` when omitted); new source types only need a [`SourceProvider`] impl.
+mod bluesky;
+mod command;
+mod github;
+pub mod imap;
+mod mastodon;
+mod rss;
+mod youtube;
+
+pub use bluesky::BlueskyProvider;
+pub use command::CommandProvider;
+pub use github::GithubProvider;
+pub use imap::ImapProvider;
+pub use mastodon::MastodonProvider;
+pub use rss::RssProvider;
+pub use youtube::YoutubeProvider;
+
+use crate::config::{Feed, FetchLimits, DATA_SAVER_MAX_BYTES};
+use crate::news::ratelimit::{self, HostLimiter};
+use crate::news::status::Transfer;
+use crate::news::strict;
+use crate::util::width::{display_width, truncate_to_width};
+use crate::news::{build_client_with, max_feed_bytes, read_body_capped, Story, DEFAULT_USER_AGENT};
+use anyhow::{bail, Context, Result};
+use futures_util::future::BoxFuture;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use url::Url;
+
+/// Shared resources handed to every provider call. Clones share the client's connection
+/// pool and the per-host pacing.
+#[derive(Clone)]
+pub struct FetchContext {
+    /// Built with `limits`' timeouts and user agent
+    pub client: Client,
+    /// The feed's limits over `[fetch]`
+    pub limits: FetchLimits,
+    // Shared by every feed's context, so hosts are paced across feeds
+    hosts: Arc<HostLimiter>,
+    // Until when (UNIX seconds) a 429 or 503 response asked this feed to wait
+    retry_after: Arc<Mutex<Option<i64>>>,
+    // What this feed's requests cost so far
+    transfer: Arc<Mutex<Transfer>>,
+    // Problems that didn't fail the feed
+    warnings: Arc<Mutex<Vec<String>>>,
+    data_saver: bool,
+}
+
+impl FetchContext {
+    pub fn new(limits: FetchLimits) -> crate::error::Result<Self> {
+        let hosts = Arc::new(HostLimiter::new(&limits));
+        Ok(FetchContext {
+            client: build_client_with(&limits)?,
+            limits,
+            hosts,
+            retry_after: Arc::default(),
+            transfer: Arc::default(),
+            warnings: Arc::default(),
+            data_saver: false,
+        })
+    }
+
+    /// With `on`, bodies are capped at [`DATA_SAVER_MAX_BYTES`] and feeds kept for
+    /// conditional requests even when the server sends no validators (see `data_saver`).
+    pub fn with_data_saver(mut self, on: bool) -> Self {
+        self.data_saver = on;
+        self
+    }
+
+    /// Whether this context saves data (see [`with_data_saver`](Self::with_data_saver)).
+    pub fn data_saver(&self) -> bool {
+        self.data_saver
+    }
+
+    /// Why `[fetch] strict = true` in these limits won't fetch `feed`, if it won't (see
+    /// [`strict::check_feed`]).
+    pub fn check_strict(&self, feed: &Feed) -> Result<()> {
+        match self.limits.strict {
+            Some(true) => strict::check_feed(feed).map_err(anyhow::Error::msg),
+            _ => Ok(()),
+        }
+    }
+
+    /// Context for `feed`'s own limits; the client is shared unless it needs other
+    /// timeouts or user agent.
+    pub fn for_feed(&self, feed: &Feed) -> crate::error::Result<FetchContext> {
+        let limits = feed.limits().or(&self.limits);
+        let same_client = limits.connect_timeout_secs == self.limits.connect_timeout_secs
+            && limits.timeout_secs == self.limits.timeout_secs
+            && limits.user_agent == self.limits.user_agent;
+        let client = if same_client { self.client.clone() } else { build_client_with(&limits)? };
+        Ok(FetchContext {
+            client,
+            limits,
+            hosts: self.hosts.clone(),
+            retry_after: Arc::default(),
+            transfer: Arc::default(),
+            warnings: Arc::default(),
+            data_saver: self.data_saver,
+        })
+    }
+
+    /// Send `request` (built on [`client`](Self::client)) once its host's rate limit (and
+    /// with `respect_robots` its Crawl-delay) allows. A 429 or 503 with a Retry-After is
+    /// noted for [`retry_after`](Self::retry_after), the request in [`transfer`](Self::transfer).
+    /// Providers should send everything through here, and read bodies with
+    /// [`read_body`](Self::read_body) or [`json`](Self::json).
+    pub async fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let request = request.build()?;
+        if self.limits.respect_robots == Some(true) {
+            self.read_robots(request.url()).await;
+        }
+        self.wait_turn(request.url()).await;
+        let started = Instant::now();
+        let response = self.client.execute(request).await;
+        let mut transfer = self.transfer.lock().unwrap();
+        transfer.requests += 1;
+        transfer.millis += started.elapsed().as_millis() as u64;
+        let response = response?;
+        transfer.version = Some(format!("{:?}", response.version()));
+        if response.status() == StatusCode::NOT_MODIFIED {
+            transfer.cache_hits += 1;
+        }
+        drop(transfer);
+        if matches!(response.status(), StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE)
+            && let Some(value) = response.headers().get(RETRY_AFTER).and_then(|v| v.to_str().ok())
+            && let Some(until) = ratelimit::retry_after(value, time::OffsetDateTime::now_utc().unix_timestamp())
+        {
+            *self.retry_after.lock().unwrap() = Some(until);
+        }
+        Ok(response)
+    }
+
+    /// Read `response`'s body, up to `max` bytes, counting it in [`transfer`](Self::transfer).
+    pub async fn read_body(&self, response: Response, max: usize) -> crate::error::Result<Vec<u8>> {
+        let started = Instant::now();
+        let body = read_body_capped(response, max).await;
+        let mut transfer = self.transfer.lock().unwrap();
+        transfer.millis += started.elapsed().as_millis() as u64;
+        if let Ok(body) = &body {
+            transfer.bytes += body.len() as u64;
+        }
+        body
+    }
+
+    /// Read `response`'s body (up to [`max_bytes`](Self::max_bytes)) as JSON.
+    pub async fn json<T: serde::de::DeserializeOwned>(&self, response: Response) -> crate::error::Result<T> {
+        Ok(serde_json::from_slice(&self.read_body(response, self.max_bytes()).await?)?)
+    }
+
+    /// What this feed's requests cost so far.
+    pub fn transfer(&self) -> Transfer {
+        self.transfer.lock().unwrap().clone()
+    }
+
+    /// Report a problem that doesn't fail the feed (one of several sources failing, the
+    /// cache not written); the caller gets it as a [`Diagnostic::Warning`](crate::news::Diagnostic).
+    /// Providers must not print.
+    pub fn warn(&self, message: impl Into<String>) {
+        self.warnings.lock().unwrap().push(message.into());
+    }
+
+    /// What [`warn`](Self::warn) was told so far.
+    pub fn warnings(&self) -> Vec<String> {
+        self.warnings.lock().unwrap().clone()
+    }
+
+    /// Until when (UNIX epoch seconds) a server asked not to be sent this feed's requests.
+    pub fn retry_after(&self) -> Option<i64> {
+        *self.retry_after.lock().unwrap()
+    }
+
+    // The first request to a host reads its robots.txt; a missing or unreadable one asks
+    // for nothing
+    async fn read_robots(&self, url: &Url) {
+        let (Some(host), Ok(robots)) = (url.host_str(), url.join("/robots.txt")) else { return };
+        self.hosts
+            .read_robots_once(host, || async {
+                self.wait_turn(&robots).await;
+                let response = self.client.get(robots).send().await.ok()?.error_for_status().ok()?;
+                let text = response.text().await.ok()?;
+                ratelimit::crawl_delay(&text, self.limits.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT))
+            })
+            .await;
+    }
+
+    /// Wait until a request to `url` may start, for callers that send it themselves.
+    pub async fn wait_turn(&self, url: &Url) {
+        self.hosts.wait(url).await;
+    }
+
+    /// Largest body to accept from the feed.
+    pub fn max_bytes(&self) -> usize {
+        let max = self.limits.max_bytes.map_or_else(max_feed_bytes, |b| b as usize);
+        match self.data_saver {
+            true => max.min(DATA_SAVER_MAX_BYTES as usize),
+            false => max,
+        }
+    }
+}
+
+pub trait SourceProvider: Send + Sync {
+    /// The `kind = "..."` value that selects this provider.
+    fn kind(&self) -> &'static str;
+    /// Fetch the feed's current items. Links must be absolute; `source` and `is_new` are
+    /// filled in (and links normalized) by the caller.
+    fn fetch<'a>(&'a self, ctx: &'a FetchContext, feed: &'a Feed) -> BoxFuture<'a, Result<Vec<Story>>>;
+}
+
+/// Providers by kind. `default()` has the built-in ones.
+pub struct ProviderRegistry {
+    providers: Vec<Box<dyn SourceProvider>>,
+}
+
+impl Default for ProviderRegistry {
+    fn default() -> Self {
+        let mut r = ProviderRegistry { providers: Vec::new() };
+        r.register(RssProvider);
+        r.register(CommandProvider);
+        r.register(MastodonProvider);
+        r.register(BlueskyProvider);
+        r.register(YoutubeProvider);
+        r.register(GithubProvider);
+        r.register(ImapProvider);
+        r
+    }
+}
+
+impl ProviderRegistry {
+    /// Add a provider, replacing any existing one for the same kind.
+    pub fn register<P: SourceProvider + 'static>(&mut self, provider: P) {
+        self.providers.retain(|p| p.kind() != provider.kind());
+        self.providers.push(Box::new(provider));
+    }
+
+    /// Fetch `feed` with the provider for its kind, unless strict mode in `ctx` refuses it
+    /// (see [`FetchContext::check_strict`]). Refreshes, previews and `doctor` all fetch
+    /// through here, so strict mode holds for each of them.
+    pub async fn fetch(&self, ctx: &FetchContext, feed: &Feed) -> Result<Vec<Story>> {
+        let Some(provider) = self.get(feed.kind()) else { bail!("unknown feed kind \"{}\"", feed.kind()) };
+        ctx.check_strict(feed)?;
+        provider.fetch(ctx, feed).await
+    }
+
+    pub fn get(&self, kind: &str) -> Option<&dyn SourceProvider> {
+        self.providers.iter().find(|p| p.kind() == kind).map(|p| p.as_ref())
+    }
+
+    pub fn kinds(&self) -> Vec<&'static str> {
+        self.providers.iter().map(|p| p.kind()).collect()
+    }
+}
+
+// Social posts have no headline: use the first sentence, capped (in terminal columns, so
+// CJK posts aren't twice as wide) so sinks get a sane title
+fn post_title(text: &str) -> String {
+    const MAX_WIDTH: usize = 140;
+    if text.is_empty() {
+        return "(no text)".into();
+    }
+    let end = text.find(". ").map(|i| i + 1).unwrap_or(text.len());
+    let first = &text[..end];
+    if display_width(first) <= MAX_WIDTH {
+        return first.to_string();
+    }
+    let cut = truncate_to_width(first, MAX_WIDTH);
+    match cut.strip_suffix('…') {
+        Some(rest) => format!("{}…", rest.trim_end()),
+        None => cut,
+    }
+}
+
+// "mastodon.social" is accepted as shorthand for "https://mastodon.social/"
+fn instance_url(instance: &str) -> Result<Url> {
+    let instance = instance.trim().trim_end_matches('/');
+    let full = if instance.contains("://") { format!("{}/", instance) } else { format!("https://{}/", instance) };
+    Url::parse(&full).with_context(|| format!("invalid instance \"{}\"", instance))
+}
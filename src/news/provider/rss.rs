@@ -0,0 +1,182 @@
+use super::{FetchContext, SourceProvider};
+use crate::config::Feed;
+use crate::news::discover;
+use crate::news::http_cache::{self, Cached};
+use crate::news::Story;
+use crate::tr;
+use crate::util::html;
+use anyhow::{anyhow, bail, Context, Result};
+use feed_rs::parser;
+use futures_util::future::BoxFuture;
+use regex::Regex;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::StatusCode;
+use std::sync::OnceLock;
+use std::{fs, path::Path};
+use url::Url;
+
+/// RSS/Atom/JSON Feed from a URL or a local file (the default kind).
+pub struct RssProvider;
+
+impl SourceProvider for RssProvider {
+    fn kind(&self) -> &'static str {
+        "rss"
+    }
+
+    fn fetch<'a>(&'a self, ctx: &'a FetchContext, feed: &'a Feed) -> BoxFuture<'a, Result<Vec<Story>>> {
+        Box::pin(async move {
+            if Path::new(&feed.url).is_file() {
+                // Local XML file
+                let bytes = fs::read(&feed.url).context("failed to read file feed")?;
+                if bytes.len() > ctx.max_bytes() {
+                    bail!("feed too large ({} bytes; raise max_bytes)", bytes.len());
+                }
+                return parse_checked(&bytes, None, None);
+            }
+            // Remote URL, asked for only if it changed since the cached copy
+            let base = Url::parse(&feed.url).ok();
+            let cached = Cached::load(&feed.url);
+            let mut request = ctx.client.get(&feed.url);
+            if let Some(cached) = &cached {
+                request = cached.condition(request);
+            }
+            let resp = ctx.send(request).await?;
+            if resp.status() == StatusCode::NOT_MODIFIED
+                && let Some(cached) = cached
+            {
+                return parse(&cached.body, base.as_ref());
+            }
+            // An error page isn't an empty feed
+            let resp = resp.error_for_status()?;
+            let headers = resp.headers().clone();
+            let buf = ctx
+                .read_body(resp, ctx.max_bytes())
+                .await
+                .context("failed to read body (raise max_bytes for large feeds)")?;
+            if buf.is_empty() {
+                return Ok(Vec::new());
+            }
+            let content_type = headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok());
+            let stories = parse_checked(&buf, content_type, base.as_ref())?;
+            if let Err(e) = http_cache::save(&feed.url, &headers, &buf, ctx.data_saver()) {
+                ctx.warn(tr!("fetch.cache_failed", feed.name, e));
+            }
+            Ok(stories)
+        })
+    }
+}
+
+// [`parse`], but a web page where a feed was expected fails with why (someone configured
+// the site's home page) and where the page says its feed is
+fn parse_checked(bytes: &[u8], content_type: Option<&str>, base: Option<&Url>) -> Result<Vec<Story>> {
+    parse(bytes, base).map_err(|e| match discover::looks_like_html(content_type, bytes) {
+        true => anyhow!("{}", discover::not_a_feed(bytes, base)),
+        false => e,
+    })
+}
+
+/// Parse a feed document; relative entry links are resolved against `base`.
+pub fn parse(bytes: &[u8], base: Option<&Url>) -> Result<Vec<Story>> {
+    let feed = parser::parse(bytes).map_err(|e| anyhow!("failed to parse feed: {}", e))?;
+    let mut stories = Vec::new();
+    for entry in feed.entries.into_iter() {
+        let title = entry
+            .title
+            .as_ref()
+            .map(|t| t.content.clone())
+            .unwrap_or_else(|| "(untitled)".into());
+
+        let raw_link = entry
+            .links
+            .iter()
+            .find(|l| l.rel.as_deref().unwrap_or("") == "alternate")
+            .or_else(|| entry.links.first())
+            .map(|l| l.href.clone())
+            .unwrap_or_else(|| String::from(""));
+        if raw_link.trim().is_empty() {
+            continue;
+        }
+        let link = match Url::parse(&raw_link) {
+            Ok(u) => u,
+            Err(_) => match base.and_then(|b| b.join(&raw_link).ok()) {
+                Some(u) => u,
+                None => continue,
+            },
+        };
+
+        // Prefer published, fallback to updated; store as UNIX epoch seconds
+        let published: Option<i64> = entry
+            .published
+            .map(|d| d.timestamp())
+            .or_else(|| entry.updated.map(|d| d.timestamp()));
+        let author = entry.authors.iter().find_map(author_name);
+        // Media RSS (YouTube, podcasts) puts the description in <media:group>
+        let summary = entry
+            .summary
+            .as_ref()
+            .or_else(|| entry.media.iter().find_map(|m| m.description.as_ref()))
+            .map(|t| t.content.clone());
+        // content:encoded / Atom <content>: the whole article, for the detail view
+        let content = entry.content.and_then(|c| c.body).filter(|b| !b.trim().is_empty());
+        let image = lead_image(&entry.media, &link)
+            .or_else(|| content.as_deref().and_then(|c| html::first_image(c, Some(&link))))
+            .or_else(|| summary.as_deref().and_then(|s| html::first_image(s, Some(&link))));
+        let points = summary.as_deref().and_then(hn_points);
+        stories.push(Story {
+            title,
+            link: link.into(),
+            source: String::new(),
+            is_new: false,
+            published,
+            author,
+            summary,
+            duration: None,
+            points,
+            content,
+            image,
+            language: None,
+            translation: None,
+        });
+    }
+    Ok(stories)
+}
+
+// hnrss.org and similar aggregators put "Points: 123" in the description
+fn hn_points(summary: &str) -> Option<u64> {
+    static POINTS: OnceLock<Regex> = OnceLock::new();
+    let re = POINTS.get_or_init(|| Regex::new(r"\bPoints: (\d+)").expect("valid regex"));
+    re.captures(summary)?[1].parse().ok()
+}
+
+// media:thumbnail, else an image enclosure / media:content (feed-rs maps both to media)
+fn lead_image(media: &[feed_rs::model::MediaObject], link: &Url) -> Option<String> {
+    let thumbnail = media.iter().flat_map(|m| &m.thumbnails).map(|t| t.image.uri.clone()).next();
+    let content = || {
+        media.iter().flat_map(|m| &m.content).find_map(|c| {
+            let url = c.url.as_ref()?;
+            let is_image = match &c.content_type {
+                Some(t) => t.to_string().starts_with("image/"),
+                None => html::looks_like_image(url.path()),
+            };
+            is_image.then(|| url.to_string())
+        })
+    };
+    let uri = thumbnail.or_else(content)?;
+    link.join(&uri).ok().filter(|u| matches!(u.scheme(), "http" | "https")).map(String::from)
+}
+
+// RSS <author> is "email (Name)"; feed-rs keeps the text in `email` under a placeholder name.
+fn author_name(p: &feed_rs::model::Person) -> Option<String> {
+    if let Some(email) = p.email.as_deref() {
+        if let Some((_, rest)) = email.split_once('(')
+            && let Some(name) = rest.strip_suffix(')')
+            && !name.trim().is_empty()
+        {
+            return Some(name.trim().to_string());
+        }
+        if p.name == "author" {
+            return Some(email.trim().to_string()).filter(|e| !e.is_empty());
+        }
+    }
+    Some(p.name.trim().to_string()).filter(|n| !n.is_empty())
+}
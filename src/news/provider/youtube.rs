@@ -0,0 +1,62 @@
+use super::{rss, FetchContext, SourceProvider};
+use crate::config::Feed;
+use crate::news::{max_feed_bytes, Story};
+use anyhow::{bail, Context, Result};
+use futures_util::future::{join_all, BoxFuture};
+use regex::Regex;
+use url::Url;
+
+/// Uploads of a YouTube channel via its public RSS feed. `account` is the channel ID
+/// (`UC...`) or handle (`@name`). The feed has no durations, so each video's watch page
+/// is read for its length.
+pub struct YoutubeProvider;
+
+impl SourceProvider for YoutubeProvider {
+    fn kind(&self) -> &'static str {
+        "youtube"
+    }
+
+    fn fetch<'a>(&'a self, ctx: &'a FetchContext, feed: &'a Feed) -> BoxFuture<'a, Result<Vec<Story>>> {
+        Box::pin(async move {
+            let account = feed.account.as_deref().unwrap_or("").trim();
+            let channel_id = if account.starts_with("UC") {
+                account.to_string()
+            } else if account.starts_with('@') {
+                resolve_handle(ctx, account).await?
+            } else {
+                bail!("account must be a channel ID (UC...) or a handle (@name)");
+            };
+            let mut url = Url::parse("https://www.youtube.com/feeds/videos.xml")?;
+            url.query_pairs_mut().append_pair("channel_id", &channel_id);
+            let resp = ctx.send(ctx.client.get(url)).await?.error_for_status()?;
+            let body = ctx.read_body(resp, ctx.max_bytes()).await?;
+            let mut stories = rss::parse(&body, None)?;
+            let lengths = join_all(stories.iter().map(|s| video_length(ctx, &s.link))).await;
+            for (story, length) in stories.iter_mut().zip(lengths) {
+                story.duration = length;
+            }
+            Ok(stories)
+        })
+    }
+}
+
+// Handles have no feed of their own; the channel page names the channel ID
+async fn resolve_handle(ctx: &FetchContext, handle: &str) -> Result<String> {
+    let url = Url::parse("https://www.youtube.com/")?.join(handle)?;
+    let resp = ctx.send(ctx.client.get(url)).await?.error_for_status()?;
+    let page = String::from_utf8_lossy(&ctx.read_body(resp, max_feed_bytes()).await?).into_owned();
+    let re = Regex::new(r#"youtube\.com/channel/(UC[\w-]{22})|"externalId":"(UC[\w-]{22})""#).expect("valid regex");
+    re.captures(&page)
+        .and_then(|c| c.get(1).or_else(|| c.get(2)))
+        .map(|m| m.as_str().to_string())
+        .with_context(|| format!("could not find the channel ID for {}", handle))
+}
+
+// Best effort: a missing length only hides it in the listing
+async fn video_length(ctx: &FetchContext, link: &str) -> Option<u64> {
+    let resp = ctx.send(ctx.client.get(link)).await.ok()?.error_for_status().ok()?;
+    let page = ctx.read_body(resp, max_feed_bytes()).await.ok()?;
+    let page = String::from_utf8_lossy(&page);
+    let re = Regex::new(r#""lengthSeconds":"(\d+)""#).expect("valid regex");
+    re.captures(&page)?.get(1)?.as_str().parse().ok()
+}
@@ -0,0 +1,139 @@
+//! Politeness towards servers that host several feeds (e.g. many hnrss.org queries):
+//! requests to one host start at most `per_host_rps` times a second, each delayed by up to
+//! `jitter_ms` more so they don't arrive in lockstep. With `respect_robots`, a host's
+//! robots.txt `Crawl-delay` slows that down further; a `Retry-After` on a 429 or 503 keeps
+//! the feed from being fetched again too soon (see [`status`](super::status)).
+use crate::config::FetchLimits;
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::hash::BuildHasher;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+use url::Url;
+
+// `per_host_rps` when `[fetch]` doesn't set it
+const DEFAULT_PER_HOST_RPS: f64 = 2.0;
+// Longest Crawl-delay honored, so one host can't stall a whole fetch
+const MAX_CRAWL_DELAY: Duration = Duration::from_secs(60);
+// Longest Retry-After honored
+const MAX_RETRY_AFTER_SECS: i64 = 86_400;
+
+/// When each host may be asked next. Shared by every request of one fetch.
+#[derive(Debug)]
+pub(crate) struct HostLimiter {
+    interval: Duration,
+    jitter: Duration,
+    next: Mutex<HashMap<String, Instant>>,
+    // Hosts whose robots.txt asked for more time between requests
+    crawl_delays: Mutex<HashMap<String, Duration>>,
+    // Hosts whose robots.txt was looked at; held while one is read, so requests wait for it
+    robots_read: futures_util::lock::Mutex<HashSet<String>>,
+}
+
+impl HostLimiter {
+    pub(crate) fn new(limits: &FetchLimits) -> Self {
+        let rps = limits.per_host_rps.filter(|r| r.is_finite() && *r > 0.0).unwrap_or(DEFAULT_PER_HOST_RPS);
+        HostLimiter {
+            interval: Duration::from_secs_f64(1.0 / rps),
+            jitter: Duration::from_millis(limits.jitter_ms.unwrap_or(0)),
+            next: Mutex::new(HashMap::new()),
+            crawl_delays: Mutex::new(HashMap::new()),
+            robots_read: futures_util::lock::Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// The first time `host` comes up, run `read` (which fetches its robots.txt) and keep
+    /// at least the Crawl-delay it returns (capped at a minute) between requests to `host`,
+    /// counting from now. Callers meanwhile wait for it to finish.
+    pub(crate) async fn read_robots_once<F>(&self, host: &str, read: impl FnOnce() -> F)
+    where
+        F: Future<Output = Option<Duration>>,
+    {
+        let mut read_hosts = self.robots_read.lock().await;
+        if !read_hosts.insert(host.to_string()) {
+            return;
+        }
+        let Some(delay) = read().await.map(|d| d.min(MAX_CRAWL_DELAY)) else { return };
+        self.crawl_delays.lock().unwrap().insert(host.to_string(), delay);
+        let mut next = self.next.lock().unwrap();
+        let after_robots = Instant::now() + delay;
+        if next.get(host).is_none_or(|t| *t < after_robots) {
+            next.insert(host.to_string(), after_robots);
+        }
+    }
+
+    /// Wait until a request to `url`'s host may start. Slots are handed out in call order,
+    /// so concurrent callers queue up instead of all firing at once.
+    pub(crate) async fn wait(&self, url: &Url) {
+        let Some(host) = url.host_str() else { return };
+        let start = {
+            let mut next = self.next.lock().unwrap();
+            let now = Instant::now();
+            let slot = next.get(host).copied().filter(|t| *t > now).unwrap_or(now) + self.random_jitter();
+            let interval = self.crawl_delays.lock().unwrap().get(host).map_or(self.interval, |d| self.interval.max(*d));
+            next.insert(host.to_string(), slot + interval);
+            slot
+        };
+        tokio::time::sleep_until(start).await;
+    }
+
+    fn random_jitter(&self) -> Duration {
+        if self.jitter.is_zero() {
+            return Duration::ZERO;
+        }
+        // A fresh RandomState is randomly seeded; good enough to spread requests out
+        let r = RandomState::new().hash_one(Instant::now());
+        self.jitter.mul_f64((r % 1000) as f64 / 1000.0)
+    }
+}
+
+/// The `Crawl-delay` robots.txt asks of `user_agent` ("news-cli/0.1" is matched as
+/// "news-cli"): from the group naming it if there is one, else from the `*` group.
+pub fn crawl_delay(robots: &str, user_agent: &str) -> Option<Duration> {
+    let token = user_agent.split('/').next().unwrap_or(user_agent).trim().to_lowercase();
+    let (mut named, mut ours, mut anyone) = (false, None, None);
+    // Agents of the group being read; a new group starts at a User-agent after other lines
+    let mut agents: Vec<String> = Vec::new();
+    let mut in_rules = false;
+    for line in robots.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let Some((key, value)) = line.split_once(':') else { continue };
+        let value = value.trim();
+        match key.trim().to_lowercase().as_str() {
+            "user-agent" => {
+                if in_rules {
+                    agents.clear();
+                    in_rules = false;
+                }
+                let agent = value.to_lowercase();
+                named |= agent != "*" && !agent.is_empty() && token.starts_with(agent.as_str());
+                agents.push(agent);
+            }
+            "crawl-delay" => {
+                in_rules = true;
+                let Some(delay) = value.parse::<f64>().ok().filter(|d| d.is_finite() && *d >= 0.0) else { continue };
+                let delay = Duration::from_secs_f64(delay);
+                if agents.iter().any(|a| !a.is_empty() && a != "*" && token.starts_with(a.as_str())) {
+                    ours = Some(delay);
+                } else if agents.iter().any(|a| a == "*") {
+                    anyone = Some(delay);
+                }
+            }
+            _ => in_rules = true,
+        }
+    }
+    if named { ours } else { anyone }
+}
+
+/// When a `Retry-After` header sent at `now` (UNIX epoch seconds) allows asking again:
+/// either a number of seconds or an HTTP date. At most a day ahead.
+pub fn retry_after(value: &str, now: i64) -> Option<i64> {
+    let value = value.trim();
+    let until = match value.parse::<i64>() {
+        Ok(secs) => now.saturating_add(secs.max(0)),
+        Err(_) => chrono::DateTime::parse_from_rfc2822(value).ok()?.timestamp(),
+    };
+    Some(until.clamp(now, now + MAX_RETRY_AFTER_SECS))
+}
@@ -0,0 +1,124 @@
+//! How each feed's fetches went, persisted as `feeds.json` in the state directory, so the
+//! News screen can show feeds that stopped working or stopped publishing long ago, and
+//! `doctor` what they cost to download.
+use crate::error::Result;
+use crate::util::atomic::{with_lock, write_atomic};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::{fs, path::{Path, PathBuf}};
+
+/// One feed's record (times in UNIX seconds).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeedStatus {
+    /// The last fetch that worked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_success: Option<i64>,
+    /// The last fetch that failed, and why.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_failure: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// When the newest item the feed ever listed was published.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub newest_item: Option<i64>,
+    /// The server asked (Retry-After) not to be asked again before this.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<i64>,
+    /// The network use of the last fetch that sent requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_transfer: Option<Transfer>,
+    /// Bytes downloaded by every fetch so far.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub total_bytes: u64,
+}
+
+/// Network use of one fetch of a feed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Transfer {
+    pub requests: u32,
+    /// Response bodies read, after decompression.
+    pub bytes: u64,
+    /// Time spent sending requests and reading responses, not waiting for the host's turn.
+    pub millis: u64,
+    /// Requests answered 304 Not Modified, so the cached copy was used.
+    pub cache_hits: u32,
+    /// The protocol of the last response, e.g. "HTTP/2.0".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+fn is_zero(n: &u64) -> bool {
+    *n == 0
+}
+
+impl FeedStatus {
+    /// Whether the most recent fetch failed.
+    pub fn failing(&self) -> bool {
+        self.last_failure > self.last_success
+    }
+
+    /// When the feed may be fetched again, if the server asked to wait past `now`.
+    pub fn backing_off(&self, now: i64) -> Option<i64> {
+        self.retry_after.filter(|t| *t > now)
+    }
+}
+
+/// What fetching a feed came to.
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    /// Fetched; the publication time of its newest item, if any had one.
+    Fetched(Option<i64>),
+    Failed(String),
+    /// Failed, and the server asked not to be asked again before the given time.
+    Throttled(String, i64),
+}
+
+/// Every feed's status, by feed name; empty if nothing was recorded yet.
+pub fn load() -> BTreeMap<String, FeedStatus> {
+    status_file_path().and_then(|p| read(&p)).unwrap_or_default()
+}
+
+/// Note the outcome of fetching the named feeds at `now`, and what it transferred. Other
+/// feeds' records are kept; saves from several processes are serialized with a lock file.
+pub fn record(now: i64, outcomes: &[(String, Outcome, Transfer)]) -> Result<()> {
+    let Some(path) = status_file_path() else { return Ok(()) };
+    with_lock(&path, || {
+        let mut all = read(&path).unwrap_or_default();
+        for (feed, outcome, transfer) in outcomes {
+            let status = all.entry(feed.clone()).or_default();
+            // File feeds and feeds failing before a request keep the last real transfer
+            if transfer.requests > 0 {
+                status.total_bytes += transfer.bytes;
+                status.last_transfer = Some(transfer.clone());
+            }
+            match outcome {
+                Outcome::Fetched(newest) => {
+                    status.last_success = Some(now);
+                    status.error = None;
+                    status.newest_item = status.newest_item.max(*newest);
+                    status.retry_after = None;
+                }
+                Outcome::Failed(error) => {
+                    status.last_failure = Some(now);
+                    status.error = Some(error.clone());
+                }
+                Outcome::Throttled(error, until) => {
+                    status.last_failure = Some(now);
+                    status.error = Some(error.clone());
+                    status.retry_after = Some(*until);
+                }
+            }
+        }
+        let json = serde_json::to_string_pretty(&all)?;
+        write_atomic(&path, json.as_bytes(), false)
+    })?;
+    Ok(())
+}
+
+fn read(path: &Path) -> Option<BTreeMap<String, FeedStatus>> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn status_file_path() -> Option<PathBuf> {
+    crate::paths::state_dir().map(|d| d.join("feeds.json"))
+}
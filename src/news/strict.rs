@@ -0,0 +1,132 @@
+//! `[fetch] strict = true`, for running configs from elsewhere (a colleague's snippet, a
+//! shared feed list) without letting them reach into the local machine or network: hosts
+//! that are or resolve to private, loopback or link-local addresses are refused, as are
+//! local file feeds, `kind = "command"` and redirect chains longer than [`MAX_REDIRECTS`].
+use super::dns::DnsCache;
+use crate::config::Feed;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::redirect::Policy;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::Path;
+use std::sync::Arc;
+use url::{Host, Url};
+
+/// Redirects followed per request in strict mode (reqwest's default is 10).
+pub const MAX_REDIRECTS: usize = 3;
+
+/// Whether `ip` is on the local machine or network rather than the internet: loopback,
+/// private (10/8, 172.16/12, 192.168/16, fc00::/7), link-local (169.254/16, fe80::/10),
+/// carrier-grade NAT (100.64/10), benchmarking (198.18/15), multicast (224/4, ff00::/8),
+/// reserved (240/4, which holds broadcast), unspecified, or an IPv6 address standing for
+/// one of those IPv4 ones (mapped `::ffff:a.b.c.d`, compatible `::a.b.c.d`, NAT64
+/// `64:ff9b::a.b.c.d`).
+pub fn is_private(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            v4.is_loopback()
+                || v4.is_private()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_multicast()
+                || (a == 100 && (64..128).contains(&b))
+                || (a == 198 && (b & 0xfe) == 18)
+                || a >= 240
+                || a == 0
+        }
+        IpAddr::V6(v6) => {
+            let s = v6.segments();
+            let v4 = || Ipv4Addr::from((u32::from(s[6]) << 16) | u32::from(s[7]));
+            match s {
+                _ if v6.is_loopback() || v6.is_unspecified() => true,
+                [0, 0, 0, 0, 0, 0 | 0xffff, ..] | [0x64, 0xff9b, 0, 0, 0, 0, ..] => is_private(IpAddr::V4(v4())),
+                [first, ..] => (first & 0xfe00) == 0xfc00 || (first & 0xffc0) == 0xfe80 || v6.is_multicast(),
+            }
+        }
+    }
+}
+
+/// Why strict mode won't fetch `feed`, if it won't: a local file or `file:` URL, a command,
+/// or a URL or instance naming a private address outright (names are checked when
+/// they resolve).
+pub fn check_feed(feed: &Feed) -> Result<(), String> {
+    if feed.kind() == "command" {
+        return Err("command feeds are off with [fetch] strict = true".into());
+    }
+    for target in [Some(feed.url.as_str()), feed.instance.as_deref()].into_iter().flatten() {
+        let target = target.trim();
+        if target.is_empty() {
+            continue;
+        }
+        // Instances may be given without a scheme ("mastodon.social")
+        let url = match Url::parse(target) {
+            Ok(url) => url,
+            Err(_) if feed.kind() == "rss" && Path::new(target).exists() => {
+                return Err(format!("{} is a local file; [fetch] strict = true only fetches http(s) URLs", target));
+            }
+            Err(_) => match Url::parse(&format!("https://{}", target)) {
+                Ok(url) => url,
+                Err(_) => continue,
+            },
+        };
+        check_url(&url)?;
+    }
+    Ok(())
+}
+
+/// Why strict mode won't request `url`, if it won't: not http(s), or a private address.
+pub fn check_url(url: &Url) -> Result<(), String> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("{}: only http(s) URLs are fetched with [fetch] strict = true", url));
+    }
+    let ip = match url.host() {
+        Some(Host::Ipv4(ip)) => IpAddr::V4(ip),
+        Some(Host::Ipv6(ip)) => IpAddr::V6(ip),
+        _ => return Ok(()),
+    };
+    match is_private(ip) {
+        true => Err(format!("{} is a private or local address (blocked by [fetch] strict = true)", ip)),
+        false => Ok(()),
+    }
+}
+
+/// Follows at most [`MAX_REDIRECTS`] redirects, and none to a URL [`check_url`] refuses.
+pub(crate) fn redirect_policy() -> Policy {
+    Policy::custom(|attempt| {
+        if attempt.previous().len() > MAX_REDIRECTS {
+            let error = format!("more than {} redirects (blocked by [fetch] strict = true)", MAX_REDIRECTS);
+            return attempt.error(error);
+        }
+        match check_url(attempt.url()) {
+            Ok(()) => attempt.follow(),
+            Err(e) => attempt.error(format!("redirected to {}", e)),
+        }
+    })
+}
+
+/// `host`'s public addresses, with `port`, for connections made without a strict client
+/// (IMAP); an error when it has none.
+pub async fn public_addrs(host: &str, port: u16) -> Result<Vec<SocketAddr>, String> {
+    let name: Name = host.parse().map_err(|_| format!("invalid host name {}", host))?;
+    let addrs = PublicOnly(super::dns::shared()).resolve(name).await.map_err(|e| e.to_string())?;
+    Ok(addrs.map(|a| SocketAddr::new(a.ip(), port)).collect())
+}
+
+/// Resolves through the shared cache, but keeps only public addresses; a name with none
+/// fails to resolve.
+pub(crate) struct PublicOnly(pub(crate) Arc<DnsCache>);
+
+impl Resolve for PublicOnly {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        let lookup = self.0.resolve(name);
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = lookup.await?.filter(|a| !is_private(a.ip())).collect();
+            if addrs.is_empty() {
+                let error = format!("{} resolves only to private or local addresses (blocked by [fetch] strict = true)", host);
+                return Err(error.into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
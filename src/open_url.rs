@@ -1,24 +1,59 @@
+use news_cli::config::RuntimeConfig;
+use news_cli::util::tracking::strip_tracking_params;
 use anyhow::{bail, Result};
 use std::process::Command;
 use url::Url;
 
-pub fn open_url(url: &str) -> Result<()> {
+pub fn open_url(cfg: &RuntimeConfig, url: &str) -> Result<()> {
     // Validate scheme strictly
-    let u = Url::parse(url)?;
+    let mut u = Url::parse(url)?;
     match u.scheme() {
         "http" | "https" => {}
         _ => bail!("unsupported URL scheme"),
     }
+    // Links from older history/caches may predate stripping; never hand tracking params to the browser
+    strip_tracking_params(&mut u, &cfg.tracking_params);
+    let url = u.as_str();
 
-    // Try using the system default
+    // A configured browser command wins over the system default
+    if let Some(cmd) = cfg.open_command.as_deref() {
+        let mut parts = cmd.split_whitespace();
+        if let Some(program) = parts.next() {
+            let mut args: Vec<String> = parts.map(|p| p.replace("{url}", url)).collect();
+            if !cmd.contains("{url}") {
+                args.push(url.to_string());
+            }
+            Command::new(program).args(&args).spawn()?;
+            return Ok(());
+        }
+    }
+
+    // Try using the system default (ShellExecute on Windows, `open` on macOS, xdg-open & co. elsewhere)
     if open::that(url).is_ok() {
         return Ok(());
     }
+    fallback_launch(url);
+    Ok(())
+}
+
+#[cfg(windows)]
+fn fallback_launch(url: &str) {
+    // explorer.exe hands the URL to the default browser without going through cmd, whose
+    // `start` would expand %VAR% and treat `&`, `^` and `|` in it as syntax
+    let _ = Command::new("explorer.exe").arg(url).spawn();
+}
+
+#[cfg(target_os = "macos")]
+fn fallback_launch(url: &str) {
+    let _ = Command::new("open").arg(url).spawn();
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn fallback_launch(url: &str) {
     // Fallback: try firefox directly
     let _ = Command::new("firefox")
         .arg("--new-tab")
         .arg("--")
         .arg(url)
         .spawn();
-    Ok(())
 }
@@ -0,0 +1,67 @@
+use directories::ProjectDirs;
+use std::{env, fs, io, path::{Path, PathBuf}};
+
+const APP: &str = "news-cli";
+
+/// Directory holding config.toml.
+///
+/// `$NEWS_CLI_CONFIG_DIR` overrides everything, then `$XDG_CONFIG_HOME/news-cli`; on Unix we
+/// then use `~/.config/news-cli` (also on macOS, where existing setups live). Otherwise the
+/// platform default applies, e.g. `%APPDATA%\news-cli\config` on Windows.
+pub fn config_dir() -> Option<PathBuf> {
+    resolve("NEWS_CLI_CONFIG_DIR", "XDG_CONFIG_HOME", &[".config"], |d| {
+        d.config_dir().to_path_buf()
+    })
+}
+
+/// User state that isn't configuration (seen-story history): `$NEWS_CLI_STATE_DIR`,
+/// `$XDG_STATE_HOME/news-cli`, or `~/.local/state/news-cli`.
+pub fn state_dir() -> Option<PathBuf> {
+    resolve("NEWS_CLI_STATE_DIR", "XDG_STATE_HOME", &[".local", "state"], |d| {
+        d.state_dir().unwrap_or(d.data_local_dir()).to_path_buf()
+    })
+}
+
+/// Disposable data (fetched stories): `$NEWS_CLI_CACHE_DIR`, `$XDG_CACHE_HOME/news-cli`,
+/// or `~/.cache/news-cli`.
+pub fn cache_dir() -> Option<PathBuf> {
+    resolve("NEWS_CLI_CACHE_DIR", "XDG_CACHE_HOME", &[".cache"], |d| {
+        d.cache_dir().to_path_buf()
+    })
+}
+
+fn resolve(
+    override_var: &str,
+    xdg_var: &str,
+    home_relative: &[&str],
+    platform: fn(&ProjectDirs) -> PathBuf,
+) -> Option<PathBuf> {
+    if let Ok(dir) = env::var(override_var) {
+        return Some(PathBuf::from(dir));
+    }
+    if let Ok(xdg) = env::var(xdg_var) {
+        return Some(PathBuf::from(xdg).join(APP));
+    }
+    if !cfg!(windows) && let Ok(home) = env::var("HOME") {
+        let mut p = PathBuf::from(home);
+        p.extend(home_relative);
+        return Some(p.join(APP));
+    }
+    ProjectDirs::from("", "", APP).map(|d| platform(&d))
+}
+
+/// Move a file from where older versions kept it, unless the new location already exists.
+pub fn migrate(legacy: &Path, current: &Path) -> io::Result<()> {
+    if current.exists() || !legacy.is_file() {
+        return Ok(());
+    }
+    if let Some(parent) = current.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // rename fails across filesystems (e.g. a separate /home/.cache mount); fall back to copy
+    if fs::rename(legacy, current).is_err() {
+        fs::copy(legacy, current)?;
+        fs::remove_file(legacy)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use news_cli::config::RuntimeConfig;
+use anyhow::{bail, Context, Result};
+use std::process::{Command, Stdio};
+use url::Url;
+
+/// Links a video player can stream (YouTube watch pages and shorts).
+pub fn is_video(link: &str) -> bool {
+    let Ok(u) = Url::parse(link) else { return false };
+    match u.host_str().unwrap_or("").trim_start_matches("www.").trim_start_matches("m.") {
+        "youtube.com" => u.path() == "/watch" || u.path().starts_with("/shorts/"),
+        "youtu.be" => true,
+        _ => false,
+    }
+}
+
+/// Start the configured player (default mpv) on `url` in the background.
+pub fn play(cfg: &RuntimeConfig, url: &str) -> Result<()> {
+    let cmd = cfg.player.as_deref().unwrap_or("mpv");
+    let mut parts = cmd.split_whitespace();
+    let Some(program) = parts.next() else { bail!("player command is empty") };
+    let mut args: Vec<String> = parts.map(|p| p.replace("{url}", url)).collect();
+    if !cmd.contains("{url}") {
+        args.push(url.to_string());
+    }
+    // The player's own output would scribble over the menu
+    Command::new(program)
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("failed to start {} (install mpv and yt-dlp, or set player)", program))?;
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use crate::news::Story;
+use crate::util::atomic::write_atomic;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Queued {
+    pub story: Story,
+    /// UNIX epoch seconds
+    pub queued_at: i64,
+}
+
+/// Stories to read next, in the order the user wants them ('l' in the story lists); each
+/// leaves the queue once opened. Unlike bookmarks, a to-do list rather than an archive.
+/// Persisted as `queue.json` in the state directory.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Queue {
+    items: Vec<Queued>,
+}
+
+impl Queue {
+    pub fn load() -> Self {
+        if let Some(path) = queue_file_path()
+            && let Ok(contents) = fs::read_to_string(&path)
+            && let Ok(q) = serde_json::from_str::<Queue>(&contents)
+        {
+            return q;
+        }
+        Queue::default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = queue_file_path() {
+            let json = serde_json::to_string_pretty(self)?;
+            write_atomic(&path, json.as_bytes(), false)?;
+        }
+        Ok(())
+    }
+
+    /// Next to read first.
+    pub fn items(&self) -> &[Queued] {
+        &self.items
+    }
+
+    /// Where the story with `link` is in the queue (0 = next), if it is queued.
+    pub fn position(&self, link: &str) -> Option<usize> {
+        self.items.iter().position(|q| q.story.link == link)
+    }
+
+    /// Add `story` at the end, or take it out if it is queued; returns whether it is queued
+    /// afterwards.
+    pub fn toggle(&mut self, story: &Story) -> bool {
+        if self.remove(&story.link) {
+            return false;
+        }
+        self.items.push(Queued { story: story.clone(), queued_at: time::OffsetDateTime::now_utc().unix_timestamp() });
+        true
+    }
+
+    /// Take the story with `link` out; returns whether it was queued.
+    pub fn remove(&mut self, link: &str) -> bool {
+        let before = self.items.len();
+        self.items.retain(|q| q.story.link != link);
+        self.items.len() != before
+    }
+
+    /// Move the story at `index` one place towards the front (`up`) or the back; returns its
+    /// new index.
+    pub fn shift(&mut self, index: usize, up: bool) -> usize {
+        if index >= self.items.len() {
+            return index;
+        }
+        let target = match up {
+            true => index.saturating_sub(1),
+            false => (index + 1).min(self.items.len().saturating_sub(1)),
+        };
+        self.items.swap(index, target);
+        target
+    }
+}
+
+fn queue_file_path() -> Option<PathBuf> {
+    crate::paths::state_dir().map(|d| d.join("queue.json"))
+}
@@ -0,0 +1,115 @@
+use crate::news::Story;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// One story the user opened, appended to `reading_log.jsonl` in the state directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenEvent {
+    /// UNIX epoch seconds
+    pub at: i64,
+    /// Local UTC offset in seconds when it happened, so time of day survives travel
+    #[serde(default)]
+    pub offset: i32,
+    pub source: String,
+    pub title: String,
+    pub link: String,
+}
+
+/// Append an open of `story` to the log.
+pub fn record(story: &Story) -> Result<()> {
+    let Some(path) = log_file_path() else { return Ok(()) };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let now = chrono::Local::now();
+    let event = OpenEvent {
+        at: now.timestamp(),
+        offset: now.offset().local_minus_utc(),
+        source: story.source.clone(),
+        title: story.title.clone(),
+        link: story.link.clone(),
+    };
+    let mut line = serde_json::to_string(&event)?;
+    line.push('\n');
+    OpenOptions::new().create(true).append(true).open(&path)?.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Every logged open, oldest first. Lines that don't parse (e.g. a torn write) are skipped.
+pub fn load() -> Vec<OpenEvent> {
+    let Some(contents) = log_file_path().and_then(|p| fs::read_to_string(p).ok()) else {
+        return Vec::new();
+    };
+    contents.lines().filter_map(|l| serde_json::from_str(l).ok()).collect()
+}
+
+/// Aggregates over the opens in one period.
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub opens: usize,
+    /// Most opened first
+    pub per_source: Vec<(String, usize)>,
+    /// Monday first, in the local time of each open
+    pub per_weekday: [usize; 7],
+    pub per_hour: [usize; 24],
+    /// Most frequent title keywords, most read first
+    pub topics: Vec<(String, usize)>,
+}
+
+/// Summarize the opens at or after `since` (all of them for `None`), keeping the
+/// `top` most frequent topics.
+pub fn summarize(events: &[OpenEvent], since: Option<i64>, top: usize) -> Summary {
+    let mut summary = Summary::default();
+    let mut sources: HashMap<&str, usize> = HashMap::new();
+    let mut topics: HashMap<String, usize> = HashMap::new();
+    for e in events.iter().filter(|e| since.is_none_or(|s| e.at >= s)) {
+        summary.opens += 1;
+        *sources.entry(e.source.as_str()).or_default() += 1;
+        let local = e.at + i64::from(e.offset);
+        let days = local.div_euclid(86_400);
+        // 1970-01-01 was a Thursday
+        summary.per_weekday[(days + 3).rem_euclid(7) as usize] += 1;
+        summary.per_hour[(local.rem_euclid(86_400) / 3600) as usize] += 1;
+        for word in keywords(&e.title) {
+            *topics.entry(word).or_default() += 1;
+        }
+    }
+    summary.per_source = ranked(sources.into_iter().map(|(k, v)| (k.to_string(), v)).collect(), usize::MAX);
+    summary.topics = ranked(topics.into_iter().collect(), top);
+    summary
+}
+
+// By count, then name, so ties render in a stable order
+fn ranked(mut counts: Vec<(String, usize)>, top: usize) -> Vec<(String, usize)> {
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.truncate(top);
+    counts
+}
+
+// Words common enough in headlines to say nothing about the topic
+const STOPWORDS: &[&str] = &[
+    "about", "after", "again", "against", "amid", "back", "been", "before", "being", "between", "could",
+    "does", "down", "from", "have", "here", "into", "just", "more", "most", "much", "new", "news", "over",
+    "says", "said", "than", "that", "their", "them", "then", "there", "these", "they", "this", "those",
+    "what", "when", "where", "which", "while", "will", "with", "would", "your", "year", "years",
+];
+
+/// Lowercased title words of four or more letters, minus stopwords; each counted once per title.
+pub fn keywords(title: &str) -> Vec<String> {
+    let mut words: Vec<String> = title
+        .split(|c: char| !c.is_alphanumeric() && c != '-')
+        .map(|w| w.trim_matches('-').to_lowercase())
+        .filter(|w| w.chars().count() >= 4 && !w.chars().all(|c| c.is_numeric()) && !STOPWORDS.contains(&w.as_str()))
+        .collect();
+    words.sort();
+    words.dedup();
+    words
+}
+
+fn log_file_path() -> Option<PathBuf> {
+    crate::paths::state_dir().map(|d| d.join("reading_log.jsonl"))
+}
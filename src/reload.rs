@@ -0,0 +1,87 @@
+//! Live reload of config.toml while the interactive UI runs: a watcher notices the file (or
+//! one it includes) change and wakes the screen, and the main menu and News screen switch to
+//! the new config at their next redraw. A config that no longer loads is reported and the old
+//! one kept.
+use crate::{theme, ui};
+use news_cli::config::{self, RuntimeConfig};
+use news_cli::tr;
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+static CHANGED: AtomicBool = AtomicBool::new(false);
+// Watching stops when the watcher is dropped
+static WATCHER: Mutex<Option<RecommendedWatcher>> = Mutex::new(None);
+
+/// Start watching the file `cfg` was read from and the files it includes (and new `.toml`
+/// files next to those, for a `feeds.d/*.toml`); nothing to watch for the built-in defaults
+/// or a single feed. Failing to watch only means no live reload.
+pub fn watch(cfg: &RuntimeConfig) {
+    let Some(path) = cfg.path.clone() else { return };
+    let Some(name) = path.file_name().map(|n| n.to_os_string()) else { return };
+    // Editors often save by replacing the file, which a watch on the file itself would miss
+    let dir_of = |p: &Path| match p.parent() {
+        Some(d) if !d.as_os_str().is_empty() => d.to_path_buf(),
+        _ => Path::new(".").to_path_buf(),
+    };
+    let dir = dir_of(&path);
+    let mut names: HashSet<OsString> = HashSet::from([name]);
+    let mut include_dirs: Vec<PathBuf> = Vec::new();
+    for file in &cfg.included {
+        names.extend(file.file_name().map(|n| n.to_os_string()));
+        let d = dir_of(file);
+        if d != dir && !include_dirs.contains(&d) {
+            include_dirs.push(d);
+        }
+    }
+    let canonical: Vec<PathBuf> = include_dirs.iter().filter_map(|d| d.canonicalize().ok()).collect();
+    let watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+        if let Ok(event) = res
+            && !event.kind.is_access()
+            && event.paths.iter().any(|p| {
+                p.file_name().is_some_and(|n| names.contains(n))
+                    || (p.extension().is_some_and(|e| e == "toml")
+                        && p.parent().and_then(|d| d.canonicalize().ok()).is_some_and(|d| canonical.contains(&d)))
+            })
+        {
+            CHANGED.store(true, Ordering::SeqCst);
+            ui::notify_refresh();
+        }
+    });
+    let watched = watcher.and_then(|mut w| {
+        w.watch(&dir, RecursiveMode::NonRecursive)?;
+        for d in &include_dirs {
+            w.watch(d, RecursiveMode::NonRecursive)?;
+        }
+        Ok(w)
+    });
+    match watched {
+        Ok(w) => *WATCHER.lock().unwrap() = Some(w),
+        Err(e) => eprintln!("{}", tr!("config.watch_failed", path.display(), e)),
+    }
+}
+
+/// The config as the file now reads, if it changed since the last call. The theme follows
+/// at once; the UI language stays until the next start.
+pub fn take(cfg: &RuntimeConfig) -> Option<RuntimeConfig> {
+    if !CHANGED.swap(false, Ordering::SeqCst) {
+        return None;
+    }
+    let path = cfg.path.as_ref()?;
+    match config::load_file(path) {
+        Ok(fresh) => {
+            theme::init(&fresh.theme);
+            if fresh.included != cfg.included {
+                watch(&fresh);
+            }
+            Some(fresh)
+        }
+        Err(e) => {
+            eprintln!("{}", tr!("config.reload_failed", e));
+            None
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Ranking stories across feeds for the Top Stories screen and the Morning briefing
+//! (`[scoring]` in config.toml): feed weight, keyword boosts and Hacker News / Reddit
+//! points, decayed by age.
+use crate::config::RuntimeConfig;
+use crate::news::Story;
+use crate::util::sanitize::html_to_text;
+use std::cmp::Ordering;
+
+const DEFAULT_HALF_LIFE_HOURS: f64 = 12.0;
+const DEFAULT_WINDOW_HOURS: u64 = 24;
+const DEFAULT_TOP: usize = 20;
+
+/// A story and the score it was ranked by.
+#[derive(Debug, Clone)]
+pub struct Scored {
+    pub score: f64,
+    pub story: Story,
+}
+
+/// Score of `story` at `now` (UNIX epoch seconds):
+/// `weight × max(0, 1 + keyword boosts + points_weight × ln(1 + points)) × ½^(age / half-life)`.
+/// Undated stories can't be placed in time and score as if just published.
+pub fn score(cfg: &RuntimeConfig, story: &Story, now: i64) -> f64 {
+    let scoring = &cfg.scoring;
+    let weight = cfg.feeds.iter().find(|f| f.name == story.source).and_then(|f| f.weight).unwrap_or(1.0);
+    let text = match &story.summary {
+        Some(summary) => format!("{}\n{}", story.title, html_to_text(summary)),
+        None => story.title.clone(),
+    }
+    .to_lowercase();
+    let boost: f64 = scoring
+        .keywords
+        .iter()
+        .filter(|(word, _)| text.contains(word.to_lowercase().as_str()))
+        .map(|(_, b)| b)
+        .sum();
+    let points = story.points.map_or(0.0, |p| (p as f64).ln_1p()) * scoring.points_weight.unwrap_or(1.0);
+    let age_hours = story.published.map_or(0.0, |p| (now - p).max(0) as f64 / 3600.0);
+    let decay = 0.5_f64.powf(age_hours / scoring.half_life_hours.unwrap_or(DEFAULT_HALF_LIFE_HOURS));
+    weight * (1.0 + boost + points).max(0.0) * decay
+}
+
+/// Stories about the same event (see [`crate::topics`]), best first.
+#[derive(Debug, Clone)]
+pub struct Topic {
+    /// What the titles share, e.g. "ECB decision"
+    pub label: String,
+    pub stories: Vec<Scored>,
+}
+
+impl Topic {
+    /// The best story's score.
+    pub fn score(&self) -> f64 {
+        self.stories.first().map_or(0.0, |s| s.score)
+    }
+}
+
+/// The best `top` stories published within the last `window_hours`, highest score first.
+/// Stories scoring 0 (weight 0, or buried by negative keywords) are left out.
+pub fn rank(cfg: &RuntimeConfig, stories: &[Story], now: i64) -> Vec<Scored> {
+    let mut ranked = rank_all(cfg, stories, now);
+    ranked.truncate(cfg.scoring.top.unwrap_or(DEFAULT_TOP));
+    ranked
+}
+
+/// Like [`rank`], but stories about the same event share a place, ranked by the best of
+/// them; with `group_topics = false` every topic is a single story.
+pub fn rank_topics(cfg: &RuntimeConfig, stories: &[Story], now: i64) -> Vec<Topic> {
+    let ranked = rank_all(cfg, stories, now);
+    let groups = if cfg.scoring.group_topics.unwrap_or(true) {
+        let titles: Vec<&str> = ranked.iter().map(|s| s.story.title.as_str()).collect();
+        crate::topics::cluster(&titles)
+    } else {
+        (0..ranked.len()).map(|i| vec![i]).collect()
+    };
+    groups
+        .into_iter()
+        .take(cfg.scoring.top.unwrap_or(DEFAULT_TOP))
+        .map(|group| {
+            let titles: Vec<&str> = group.iter().map(|&i| ranked[i].story.title.as_str()).collect();
+            Topic { label: crate::topics::label(&titles), stories: group.iter().map(|&i| ranked[i].clone()).collect() }
+        })
+        .collect()
+}
+
+/// The best `per` stories of each feed category (the `category` of their feed) published
+/// within the last `window_hours`, for the Morning briefing. Categories come in the order of
+/// their first feed in the config, stories of feeds without one last under `None`.
+pub fn top_by_category(cfg: &RuntimeConfig, stories: &[Story], now: i64, per: usize) -> Vec<(Option<String>, Vec<Scored>)> {
+    let category = |source: &str| -> Option<String> {
+        let feed = cfg.feeds.iter().find(|f| f.name == source)?;
+        Some(feed.category.as_deref()?.trim().to_lowercase()).filter(|c| !c.is_empty())
+    };
+    let mut order: Vec<Option<String>> = Vec::new();
+    for feed in &cfg.feeds {
+        let c = category(&feed.name);
+        if c.is_some() && !order.contains(&c) {
+            order.push(c);
+        }
+    }
+    order.push(None);
+    let ranked = rank_all(cfg, stories, now);
+    order
+        .into_iter()
+        .map(|c| {
+            let best: Vec<Scored> = ranked.iter().filter(|s| category(&s.story.source) == c).take(per).cloned().collect();
+            (c, best)
+        })
+        .filter(|(_, best)| !best.is_empty())
+        .collect()
+}
+
+fn rank_all(cfg: &RuntimeConfig, stories: &[Story], now: i64) -> Vec<Scored> {
+    let window = i64::try_from(cfg.scoring.window_hours.unwrap_or(DEFAULT_WINDOW_HOURS)).unwrap_or(i64::MAX);
+    let cutoff = now.saturating_sub(window.saturating_mul(3600));
+    let mut ranked: Vec<Scored> = stories
+        .iter()
+        .filter(|s| s.published.is_none_or(|p| p >= cutoff))
+        .map(|s| Scored { score: score(cfg, s, now), story: s.clone() })
+        .filter(|s| s.score > 0.0)
+        .collect();
+    // Ties keep fetch order
+    ranked.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+    ranked
+}
@@ -0,0 +1,180 @@
+//! API keys and passwords kept out of config.toml. A value written as `"secret:<name>"`
+//! (e.g. `token = "secret:miniflux"`) is looked up when the config loads: in the system
+//! keyring (default), or with `[secrets] store = "file"` in `secrets.age` in the config
+//! directory, encrypted with a passphrase (age/scrypt). `news-cli secret set <name>` stores
+//! one.
+use crate::config::{AppConfig, SecretsConfig};
+use crate::error::{Error, Result};
+use crate::util::atomic::write_atomic;
+use age::secrecy::SecretString;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Prefix marking a config value as a secret's name.
+pub const PREFIX: &str = "secret:";
+
+const SERVICE: &str = "news-cli";
+
+// The file store's passphrase, asked for at most once per run
+static PASSPHRASE: OnceLock<String> = OnceLock::new();
+
+/// Where secrets are kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Store {
+    /// The platform's keyring (Secret Service, macOS Keychain, Windows Credential Manager)
+    Keyring,
+    /// An age-encrypted JSON map of name to value
+    File(PathBuf),
+}
+
+impl Store {
+    /// The store `[secrets]` picks; an unknown `store` is rejected by validation.
+    pub fn from_config(cfg: Option<&SecretsConfig>) -> Result<Store> {
+        match cfg.and_then(|c| c.store.as_deref()) {
+            None | Some("keyring") => Ok(Store::Keyring),
+            Some("file") => crate::paths::config_dir()
+                .map(|d| Store::File(d.join("secrets.age")))
+                .ok_or_else(|| Error::Config("no config directory for secrets.age".into())),
+            Some(other) => Err(Error::Config(format!("unknown secrets store \"{}\"", other))),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Result<Option<String>> {
+        match self {
+            Store::Keyring => match entry(name)?.get_password() {
+                Ok(value) => Ok(Some(value)),
+                Err(keyring::Error::NoEntry) => Ok(None),
+                Err(e) => Err(keyring_error(e)),
+            },
+            Store::File(path) => Ok(read_file(path)?.remove(name)),
+        }
+    }
+
+    pub fn set(&self, name: &str, value: &str) -> Result<()> {
+        match self {
+            Store::Keyring => entry(name)?.set_password(value).map_err(keyring_error),
+            Store::File(path) => {
+                let mut all = read_file(path)?;
+                all.insert(name.to_string(), value.to_string());
+                write_file(path, &all)
+            }
+        }
+    }
+
+    /// Returns whether there was such a secret.
+    pub fn remove(&self, name: &str) -> Result<bool> {
+        match self {
+            Store::Keyring => match entry(name)?.delete_credential() {
+                Ok(()) => Ok(true),
+                Err(keyring::Error::NoEntry) => Ok(false),
+                Err(e) => Err(keyring_error(e)),
+            },
+            Store::File(path) => {
+                let mut all = read_file(path)?;
+                let found = all.remove(name).is_some();
+                if found {
+                    write_file(path, &all)?;
+                }
+                Ok(found)
+            }
+        }
+    }
+}
+
+/// Names become keyring entries and map keys: letters, digits, '-', '_' and '.'.
+pub fn valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Replace every `"secret:<name>"` value in `cfg` by the secret.
+pub(crate) fn resolve_config(cfg: &mut AppConfig) -> Result<()> {
+    let mut fields: Vec<(String, &mut Option<String>)> = Vec::new();
+    for (i, f) in cfg.feeds.iter_mut().enumerate() {
+        fields.push((format!("feeds[{}].token", i), &mut f.token));
+    }
+    if let Some(s) = cfg.sync.as_mut() {
+        fields.push(("sync.token".into(), &mut s.token));
+    }
+    if let Some(b) = cfg.briefing.as_mut() {
+        fields.push(("briefing.api_key".into(), &mut b.api_key));
+    }
+    if let Some(t) = cfg.translation.as_mut() {
+        fields.push(("translation.api_key".into(), &mut t.api_key));
+    }
+    if let Some(d) = cfg.digest.as_mut() {
+        fields.push(("digest.smtp_password".into(), &mut d.smtp_password));
+    }
+    // Only open the store (and maybe ask for a passphrase) if something is in it
+    if !fields.iter().any(|(_, v)| v.as_deref().is_some_and(|v| v.starts_with(PREFIX))) {
+        return Ok(());
+    }
+    let store = Store::from_config(cfg.secrets.as_ref())?;
+    for (location, value) in fields {
+        let Some(name) = value.as_deref().and_then(|v| v.strip_prefix(PREFIX)) else { continue };
+        let secret = store.get(name)?.ok_or_else(|| {
+            Error::Config(format!(
+                "{}: no secret \"{}\" (add it with `news-cli secret set {}`)",
+                location, name, name
+            ))
+        })?;
+        *value = Some(secret);
+    }
+    Ok(())
+}
+
+fn entry(name: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(SERVICE, name).map_err(keyring_error)
+}
+
+fn keyring_error(e: keyring::Error) -> Error {
+    Error::Config(format!("keyring: {} (or use [secrets] store = \"file\")", e))
+}
+
+// $NEWS_CLI_SECRETS_PASSPHRASE, else asked for on a terminal; twice when it is `new`, i.e.
+// secrets.age is about to be created, so a typo doesn't lock the file for good
+fn passphrase(new: bool) -> Result<SecretString> {
+    if let Some(p) = PASSPHRASE.get() {
+        return Ok(SecretString::from(p.clone()));
+    }
+    let p = match std::env::var("NEWS_CLI_SECRETS_PASSPHRASE") {
+        Ok(p) if !p.is_empty() => p,
+        _ if console::Term::stderr().is_term() => {
+            let p = ask("Passphrase for secrets.age")?;
+            if new && ask("Repeat the passphrase")? != p {
+                return Err(Error::Config("the passphrases differ; secrets.age not created".into()));
+            }
+            p
+        }
+        _ => return Err(Error::Config("secrets.age is locked; set $NEWS_CLI_SECRETS_PASSPHRASE".into())),
+    };
+    Ok(SecretString::from(PASSPHRASE.get_or_init(|| p).clone()))
+}
+
+fn ask(prompt: &str) -> Result<String> {
+    dialoguer::Password::new()
+        .with_prompt(prompt)
+        .interact()
+        .map_err(|e| Error::Config(format!("no passphrase for secrets.age: {}", e)))
+}
+
+fn read_file(path: &Path) -> Result<BTreeMap<String, String>> {
+    let encrypted = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let identity = age::scrypt::Identity::new(passphrase(false)?);
+    let json = age::decrypt(&identity, &encrypted)
+        .map_err(|e| Error::Config(format!("can't decrypt {}: {}", path.display(), e)))?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+fn write_file(path: &Path, all: &BTreeMap<String, String>) -> Result<()> {
+    let recipient = age::scrypt::Recipient::new(passphrase(!path.exists())?);
+    let encrypted = age::encrypt(&recipient, &serde_json::to_vec(all)?)
+        .map_err(|e| Error::Config(format!("can't encrypt {}: {}", path.display(), e)))?;
+    write_atomic(path, &encrypted, false)?;
+    Ok(())
+}
@@ -0,0 +1,88 @@
+//! Ctrl-C / SIGTERM handling for the interactive UI: persist history and the session, put
+//! the terminal back the way we found it, and exit.
+use news_cli::history::SharedHistory;
+use news_cli::tr;
+use console::Term;
+use std::sync::PoisonError;
+
+/// Spawn the signal watcher. Must be called from within the tokio runtime.
+pub fn install(history: SharedHistory) {
+    let saved_mode = tty::save();
+    tokio::spawn(async move {
+        let code = wait_for_signal().await;
+        // The UI thread may be blocked in a raw-mode key read; undo that before we leave
+        tty::restore(&saved_mode);
+        let term = Term::stdout();
+        let _ = term.show_cursor();
+        let _ = term.write_line("\x1b[0m");
+        // Save even if a panic poisoned the lock; the set itself is still consistent
+        let h = history.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Err(e) = h.save() {
+            eprintln!("{}", tr!("history.save_failed", e));
+        }
+        crate::tui::session::save();
+        std::process::exit(code);
+    });
+}
+
+/// Resolves with the conventional exit status (128 + signal number).
+#[cfg(unix)]
+async fn wait_for_signal() -> i32 {
+    use tokio::signal::unix::{signal, SignalKind};
+    let mut term = match signal(SignalKind::terminate()) {
+        Ok(s) => s,
+        Err(_) => {
+            let _ = tokio::signal::ctrl_c().await;
+            return 130;
+        }
+    };
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => 130,
+        _ = term.recv() => 143,
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_signal() -> i32 {
+    let _ = tokio::signal::ctrl_c().await;
+    130
+}
+
+#[cfg(unix)]
+mod tty {
+    use std::mem::MaybeUninit;
+
+    pub struct Saved(Option<libc::termios>);
+
+    pub fn save() -> Saved {
+        let mut t = MaybeUninit::<libc::termios>::uninit();
+        // SAFETY: tcgetattr fully initializes `t` when it returns 0
+        unsafe {
+            if libc::tcgetattr(libc::STDIN_FILENO, t.as_mut_ptr()) == 0 {
+                Saved(Some(t.assume_init()))
+            } else {
+                Saved(None)
+            }
+        }
+    }
+
+    pub fn restore(saved: &Saved) {
+        if let Some(t) = &saved.0 {
+            // SAFETY: `t` came from a successful tcgetattr on the same fd
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, t);
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod tty {
+    pub struct Saved;
+
+    pub fn save() -> Saved {
+        Saved
+    }
+
+    pub fn restore(_: &Saved) {}
+}
@@ -0,0 +1,49 @@
+use super::{Document, Sink};
+use crate::news::Story;
+use crate::util::atomic::write_atomic;
+use anyhow::{Context, Result};
+use futures_util::future::BoxFuture;
+use std::{fs::OpenOptions, io::Write, path::PathBuf};
+
+/// Appends stories as JSON lines, building up an archive across runs; a document replaces
+/// the file.
+pub struct FileSink {
+    path: PathBuf,
+}
+
+impl FileSink {
+    pub fn new(path: &str) -> Self {
+        FileSink { path: PathBuf::from(path) }
+    }
+}
+
+impl Sink for FileSink {
+    fn label(&self) -> String {
+        format!("file:{}", self.path.display())
+    }
+
+    fn emit<'a>(&'a mut self, stories: &'a [Story]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut out = String::new();
+            for s in stories {
+                out.push_str(&serde_json::to_string(s)?);
+                out.push('\n');
+            }
+            let mut f = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .with_context(|| format!("failed to open {}", self.path.display()))?;
+            f.write_all(out.as_bytes())?;
+            Ok(())
+        })
+    }
+
+    fn emit_document<'a>(&'a mut self, doc: &'a Document) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            write_atomic(&self.path, doc.body.as_bytes(), false)
+                .with_context(|| format!("failed to write {}", self.path.display()))?;
+            Ok(())
+        })
+    }
+}
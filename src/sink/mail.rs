@@ -0,0 +1,46 @@
+use super::{Document, Sink};
+use crate::config::DigestConfig;
+use crate::digest::{self, DigestFormat};
+use crate::news::Story;
+use crate::util::sanitize::sanitize_for_terminal;
+use anyhow::{Context, Result};
+use futures_util::future::BoxFuture;
+use time::OffsetDateTime;
+
+/// Mails to `[digest] to` through the configured transport: a document as one message, a
+/// batch of stories as a plain list.
+pub struct MailSink {
+    cfg: DigestConfig,
+}
+
+impl MailSink {
+    pub fn new(cfg: &DigestConfig) -> Self {
+        MailSink { cfg: cfg.clone() }
+    }
+
+    async fn send(&self, title: &str, body: &str, format: DigestFormat) -> Result<()> {
+        let message = digest::message(&self.cfg, title, body, format, OffsetDateTime::now_utc())?;
+        digest::send(&self.cfg, &message).await.context("failed to mail the digest")
+    }
+}
+
+impl Sink for MailSink {
+    fn label(&self) -> String {
+        format!("mail:{}", self.cfg.to.join(","))
+    }
+
+    fn emit<'a>(&'a mut self, stories: &'a [Story]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let title = format!("{}: {} new stories", self.cfg.subject(), stories.len());
+            let mut body = String::new();
+            for s in stories {
+                body.push_str(&format!("[{}] {}\n    {}\n", sanitize_for_terminal(&s.source), sanitize_for_terminal(&s.title), s.link));
+            }
+            self.send(&title, &body, DigestFormat::Markdown).await
+        })
+    }
+
+    fn emit_document<'a>(&'a mut self, doc: &'a Document) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move { self.send(&doc.title, &doc.body, doc.format).await })
+    }
+}
@@ -0,0 +1,83 @@
+use super::{Document, Sink};
+use crate::news::Story;
+use crate::util::sanitize::sanitize_for_terminal;
+use anyhow::{Context, Result};
+use futures_util::future::BoxFuture;
+use std::{fs, path::PathBuf, process};
+use time::{format_description::well_known::Rfc2822, OffsetDateTime};
+
+/// Delivers one plain-text message per story, or one for a document, into a Maildir (tmp/
+/// then renamed into new/).
+pub struct MaildirSink {
+    root: PathBuf,
+    counter: u64,
+}
+
+impl MaildirSink {
+    pub fn new(dir: &str) -> Result<Self> {
+        let root = PathBuf::from(dir);
+        for sub in ["tmp", "new", "cur"] {
+            fs::create_dir_all(root.join(sub))
+                .with_context(|| format!("failed to create maildir {}", root.display()))?;
+        }
+        Ok(MaildirSink { root, counter: 0 })
+    }
+
+    fn deliver_story(&mut self, story: &Story) -> Result<()> {
+        let subject = format!("[{}] {}", sanitize_for_terminal(&story.source), sanitize_for_terminal(&story.title));
+        let body = format!("{}\r\n\r\n{}\r\n", sanitize_for_terminal(&story.title), story.link);
+        self.deliver(&subject, story.published, "text/plain", &body)
+    }
+
+    fn deliver(&mut self, subject: &str, published: Option<i64>, content_type: &str, body: &str) -> Result<()> {
+        let now = OffsetDateTime::now_utc();
+        self.counter += 1;
+        // Unique name per the Maildir convention: time.pid_counter.host
+        let name = format!("{}.{}_{}.news-cli", now.unix_timestamp(), process::id(), self.counter);
+        let date = published
+            .and_then(|ts| OffsetDateTime::from_unix_timestamp(ts).ok())
+            .unwrap_or(now)
+            .format(&Rfc2822)?;
+        let msg = format!(
+            "From: news-cli <news-cli@localhost>\r\n\
+             Subject: {}\r\n\
+             Date: {}\r\n\
+             Message-ID: <{}@news-cli>\r\n\
+             MIME-Version: 1.0\r\n\
+             Content-Type: {}; charset=utf-8\r\n\
+             \r\n\
+             {}",
+            subject.replace(['\r', '\n'], " "),
+            date,
+            name,
+            content_type,
+            body,
+        );
+        let tmp = self.root.join("tmp").join(&name);
+        fs::write(&tmp, msg)?;
+        fs::rename(&tmp, self.root.join("new").join(&name))?;
+        Ok(())
+    }
+}
+
+impl Sink for MaildirSink {
+    fn label(&self) -> String {
+        format!("maildir:{}", self.root.display())
+    }
+
+    fn emit<'a>(&'a mut self, stories: &'a [Story]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            for s in stories {
+                self.deliver_story(s)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn emit_document<'a>(&'a mut self, doc: &'a Document) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let body: String = doc.body.lines().map(|l| format!("{}\r\n", l)).collect();
+            self.deliver(&sanitize_for_terminal(&doc.title), None, doc.content_type(), &body)
+        })
+    }
+}
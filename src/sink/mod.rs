@@ -0,0 +1,121 @@
+mod file;
+mod mail;
+mod maildir;
+mod notify;
+mod stdout;
+mod webhook;
+
+pub use mail::MailSink;
+
+use crate::digest::DigestFormat;
+use crate::news::Story;
+use anyhow::{bail, Result};
+use futures_util::future::BoxFuture;
+
+/// A rendered document delivered whole, such as a digest.
+pub struct Document {
+    pub title: String,
+    pub body: String,
+    pub format: DigestFormat,
+    /// Stories the document covers
+    pub stories: usize,
+}
+
+impl Document {
+    /// `{"title", "format", "body"}`, as the json and webhook sinks send it.
+    pub fn to_json(&self) -> serde_json::Value {
+        let format = match self.format {
+            DigestFormat::Markdown => "markdown",
+            DigestFormat::Html => "html",
+        };
+        serde_json::json!({ "title": self.title, "format": format, "body": self.body })
+    }
+
+    /// The MIME type of `body`.
+    pub fn content_type(&self) -> &'static str {
+        match self.format {
+            DigestFormat::Markdown => "text/plain",
+            DigestFormat::Html => "text/html",
+        }
+    }
+}
+
+/// Destination for stories produced by the non-interactive commands.
+pub trait Sink: Send {
+    /// Short label used when reporting failures.
+    fn label(&self) -> String;
+    /// Deliver one batch of stories (one fetch cycle). Never called with an empty batch.
+    fn emit<'a>(&'a mut self, stories: &'a [Story]) -> BoxFuture<'a, Result<()>>;
+    /// Deliver `doc` as one piece: printed, written to a file (replacing it), one message.
+    fn emit_document<'a>(&'a mut self, doc: &'a Document) -> BoxFuture<'a, Result<()>>;
+}
+
+/// All sinks selected via `--to`; every batch is written to each of them.
+#[derive(Default)]
+pub struct SinkSet {
+    sinks: Vec<Box<dyn Sink>>,
+}
+
+impl SinkSet {
+    /// Build from `--to` specs; with no specs, stories go to stdout as plain text.
+    pub fn from_specs(specs: &[String]) -> Result<Self> {
+        let mut sinks: Vec<Box<dyn Sink>> = Vec::new();
+        for spec in specs {
+            sinks.push(parse_spec(spec)?);
+        }
+        if sinks.is_empty() {
+            sinks.push(Box::new(stdout::PlainSink));
+        }
+        Ok(SinkSet { sinks })
+    }
+
+    /// Add a sink no `--to` spec names, such as the digest's [`MailSink`].
+    pub fn push(&mut self, sink: Box<dyn Sink>) {
+        self.sinks.push(sink);
+    }
+
+    /// Deliver `doc` to every sink; a failing sink doesn't stop the others. Returns what
+    /// failed, one message per sink.
+    pub async fn emit_document(&mut self, doc: &Document) -> Vec<String> {
+        let mut failed = Vec::new();
+        for sink in self.sinks.iter_mut() {
+            if let Err(e) = sink.emit_document(doc).await {
+                failed.push(format!("Sink {} failed: {:#}", sink.label(), e));
+            }
+        }
+        failed
+    }
+
+    /// Write to every sink; a failing sink doesn't stop the others. Returns what failed, one
+    /// message per sink.
+    pub async fn emit(&mut self, stories: &[Story]) -> Vec<String> {
+        let mut failed = Vec::new();
+        if stories.is_empty() { return failed; }
+        for sink in self.sinks.iter_mut() {
+            if let Err(e) = sink.emit(stories).await {
+                failed.push(format!("Sink {} failed: {:#}", sink.label(), e));
+            }
+        }
+        failed
+    }
+}
+
+fn parse_spec(spec: &str) -> Result<Box<dyn Sink>> {
+    let (kind, arg) = match spec.split_once(':') {
+        Some((k, a)) => (k, Some(a)),
+        None => (spec, None),
+    };
+    let sink: Box<dyn Sink> = match (kind, arg) {
+        ("plain", None) => Box::new(stdout::PlainSink),
+        ("json", None) => Box::new(stdout::JsonSink),
+        ("file", Some(path)) if !path.is_empty() => Box::new(file::FileSink::new(path)),
+        ("maildir", Some(dir)) if !dir.is_empty() => Box::new(maildir::MaildirSink::new(dir)?),
+        ("webhook", Some(url)) => Box::new(webhook::WebhookSink::new(url)?),
+        ("notify", None) => Box::new(notify::NotifySink),
+        _ => bail!(
+            "unknown sink '{}' (expected plain, json, file:<path>, maildir:<dir>, webhook:<url>, notify)",
+            spec
+        ),
+    };
+    Ok(sink)
+}
@@ -0,0 +1,44 @@
+use super::{Document, Sink};
+use crate::news::Story;
+use crate::util::sanitize::sanitize_for_terminal;
+use anyhow::{bail, Result};
+use futures_util::future::BoxFuture;
+use std::process::Command;
+
+/// Desktop notification through `notify-send`, listing the first few titles.
+pub struct NotifySink;
+
+impl Sink for NotifySink {
+    fn label(&self) -> String {
+        "notify".into()
+    }
+
+    fn emit<'a>(&'a mut self, stories: &'a [Story]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let summary = format!("news-cli: {} new stories", stories.len());
+            let mut body: Vec<String> = stories
+                .iter()
+                .take(5)
+                .map(|s| format!("{}: {}", sanitize_for_terminal(&s.source), sanitize_for_terminal(&s.title)))
+                .collect();
+            if stories.len() > 5 {
+                body.push(format!("… and {} more", stories.len() - 5));
+            }
+            notify(&summary, &body.join("\n"))
+        })
+    }
+
+    fn emit_document<'a>(&'a mut self, doc: &'a Document) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            notify(&format!("news-cli: {}", sanitize_for_terminal(&doc.title)), &format!("{} new stories", doc.stories))
+        })
+    }
+}
+
+fn notify(summary: &str, body: &str) -> Result<()> {
+    let status = Command::new("notify-send").arg("--app-name=news-cli").arg("--").arg(summary).arg(body).status()?;
+    if !status.success() {
+        bail!("notify-send exited with {}", status);
+    }
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use super::{Document, Sink};
+use crate::news::Story;
+use crate::util::sanitize::sanitize_for_terminal;
+use anyhow::Result;
+use futures_util::future::BoxFuture;
+
+pub struct PlainSink;
+
+impl Sink for PlainSink {
+    fn label(&self) -> String {
+        "plain".into()
+    }
+
+    fn emit<'a>(&'a mut self, stories: &'a [Story]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            for s in stories {
+                println!("[{}] {}", sanitize_for_terminal(&s.source), sanitize_for_terminal(&s.title));
+                println!("    {}", s.link);
+            }
+            Ok(())
+        })
+    }
+
+    fn emit_document<'a>(&'a mut self, doc: &'a Document) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            print!("{}", doc.body);
+            Ok(())
+        })
+    }
+}
+
+/// JSON Lines: one story object per line, so `watch` output can be piped into jq.
+pub struct JsonSink;
+
+impl Sink for JsonSink {
+    fn label(&self) -> String {
+        "json".into()
+    }
+
+    fn emit<'a>(&'a mut self, stories: &'a [Story]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            for s in stories {
+                println!("{}", serde_json::to_string(s)?);
+            }
+            Ok(())
+        })
+    }
+
+    fn emit_document<'a>(&'a mut self, doc: &'a Document) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            println!("{}", serde_json::to_string(&doc.to_json())?);
+            Ok(())
+        })
+    }
+}
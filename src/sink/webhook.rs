@@ -0,0 +1,58 @@
+use super::{Document, Sink};
+use crate::news::Story;
+use anyhow::{bail, Result};
+use futures_util::future::BoxFuture;
+use reqwest::Client;
+use std::time::Duration;
+use url::Url;
+
+/// POSTs each batch as a JSON array of stories, and a document as one JSON object.
+pub struct WebhookSink {
+    client: Client,
+    url: Url,
+}
+
+impl WebhookSink {
+    pub fn new(url: &str) -> Result<Self> {
+        let url = Url::parse(url)?;
+        match url.scheme() {
+            "http" | "https" => {}
+            _ => bail!("webhook URL must be http(s): {}", url),
+        }
+        let client = Client::builder()
+            .user_agent("news-cli/0.1")
+            .timeout(Duration::from_secs(20))
+            .build()?;
+        Ok(WebhookSink { client, url })
+    }
+}
+
+impl Sink for WebhookSink {
+    fn label(&self) -> String {
+        format!("webhook:{}", self.url)
+    }
+
+    fn emit<'a>(&'a mut self, stories: &'a [Story]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            self.client
+                .post(self.url.clone())
+                .json(stories)
+                .send()
+                .await?
+                .error_for_status()?;
+            Ok(())
+        })
+    }
+
+    fn emit_document<'a>(&'a mut self, doc: &'a Document) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            self.client
+                .post(self.url.clone())
+                .json(&doc.to_json())
+                .send()
+                .await?
+                .error_for_status()?;
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,75 @@
+//! Named copies of a whole fetch ("election-night"), kept under `snapshots/` in the state
+//! directory so the stories can be browsed again later, exactly as they were listed.
+use crate::error::{Error, Result};
+use crate::news::Story;
+use crate::util::atomic::write_atomic;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub name: String,
+    /// When the stories were fetched (UNIX epoch seconds)
+    pub taken_at: i64,
+    /// With their `is_new` as it was then
+    pub stories: Vec<Story>,
+}
+
+/// What [`list`] knows about a snapshot without keeping its stories.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub name: String,
+    pub taken_at: i64,
+    pub stories: usize,
+}
+
+/// Names become file names: letters, digits, '-', '_' and '.', not starting with '.'.
+pub fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && name.chars().all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Save `stories` as `name`, replacing a snapshot of that name; returns where it went.
+pub fn save(name: &str, taken_at: i64, stories: &[Story]) -> Result<PathBuf> {
+    let path = path_for(name)?;
+    let snapshot = Snapshot { name: name.to_string(), taken_at, stories: stories.to_vec() };
+    write_atomic(&path, serde_json::to_string(&snapshot)?.as_bytes(), false)?;
+    Ok(path)
+}
+
+pub fn load(name: &str) -> Result<Snapshot> {
+    let path = path_for(name)?;
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| Error::Parse(format!("no snapshot \"{}\" ({}: {})", name, path.display(), e)))?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Every saved snapshot, oldest first; unreadable files are skipped.
+pub fn list() -> Vec<Summary> {
+    let Some(dir) = snapshots_dir() else { return Vec::new() };
+    let Ok(entries) = fs::read_dir(dir) else { return Vec::new() };
+    let mut all: Vec<Summary> = entries
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|x| x == "json"))
+        .filter_map(|e| serde_json::from_str::<Snapshot>(&fs::read_to_string(e.path()).ok()?).ok())
+        .map(|s| Summary { name: s.name, taken_at: s.taken_at, stories: s.stories.len() })
+        .collect();
+    all.sort_by(|a, b| (a.taken_at, &a.name).cmp(&(b.taken_at, &b.name)));
+    all
+}
+
+fn path_for(name: &str) -> Result<PathBuf> {
+    if !valid_name(name) {
+        return Err(Error::Parse(format!(
+            "invalid snapshot name \"{}\" (use letters, digits, '-', '_' and '.')",
+            name
+        )));
+    }
+    let dir = snapshots_dir().ok_or_else(|| Error::Parse("no state directory for snapshots".into()))?;
+    Ok(dir.join(format!("{}.json", name)))
+}
+
+fn snapshots_dir() -> Option<PathBuf> {
+    crate::paths::state_dir().map(|d| d.join("snapshots"))
+}
@@ -1,93 +1,569 @@
 use anyhow::Result;
-use console::{style, Term};
+use console::{style, Key, Term};
 use futures_util::future::join_all;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use time::OffsetDateTime;
+use tokio::task::AbortHandle;
 
-use crate::config::{RuntimeConfig, StatsConfig};
+use crate::app::AppContext;
+use crate::ui::{self, read_key_or_resize};
+use news_cli::config::{FxConfig, RuntimeConfig, StatsConfig, StatsGroup, STATS_ITEMS};
+use news_cli::tr;
+use news_cli::util::atomic::write_atomic;
+use news_cli::util::chart::{line_chart, Mark};
 
-pub async fn run(cfg: &RuntimeConfig) -> Result<()> {
-    let term = Term::stdout();
-    let _ = term.clear_screen();
+// `stats.cache_minutes` when unset
+const DEFAULT_CACHE_MINUTES: u64 = 60;
 
-    let client = Client::builder()
-        .user_agent("news-cli/0.1 stats")
-        .gzip(true)
-        .build()?;
+// Indicator readings, kept in stats.json in the cache directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum Reading {
+    // A percentage, the date it was observed and the observation before it
+    Rate {
+        value: f64,
+        date: Option<String>,
+        #[serde(default)]
+        previous: Option<f64>,
+    },
+    // (period, value), oldest first
+    Points(Vec<(String, String)>),
+    // An exchange rate (quote currency per unit of the base), like `Rate`
+    Quote { value: f64, date: Option<String>, previous: Option<f64> },
+    Prices(Vec<CoinPrice>),
+    Curve {
+        points: Vec<(String, Option<f64>)>,
+        date: Option<String>,
+        // Each maturity's observation before, in the order of `points`
+        #[serde(default)]
+        previous: Vec<Option<f64>>,
+    },
+}
 
-    // Fetch in parallel
-    let pol = fetch_boc_latest_number(&client, "V39079"); // Target for the overnight rate
-    let cpi = fetch_boc_latest_number(&client, "STATIC_TOTALCPICHANGE"); // Total CPI, % change over 1 year ago
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CoinPrice {
+    // CoinGecko id
+    coin: String,
+    price: Option<f64>,
+    // Percent over the last 24 hours
+    change_24h: Option<f64>,
+    // When CoinGecko last updated it (UNIX seconds)
+    updated: Option<i64>,
+}
 
-    let yields = fetch_yield_curve(&client, &cfg.stats).await;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Cached {
+    // When it was fetched (UNIX seconds)
+    fetched: i64,
+    reading: Reading,
+}
 
-    let (policy_rate, inflation) = futures_util::join!(pol, cpi);
+// What an indicator is fetched from
+#[derive(Debug, Clone)]
+enum Source {
+    Boc(&'static str),
+    Statcan(String),
+    // (label, series id) per maturity
+    Curve(Vec<(String, String)>),
+    // An exchange rate, base and quote: from BoC if either is CAD, else ECB
+    Fx(String, String),
+    // CoinGecko ids and the currency to price them in
+    Crypto(Vec<String>, String),
+    // A StatsCan series without its config key set; never fetched
+    Unconfigured(&'static str),
+}
 
-    // Display
-    println!("Key Stats (q = quit, b = back)");
-    println!("");
-    match policy_rate {
-        Ok(Some(v)) => println!("- Policy rate (BoC): {:.2}%", v),
-        Ok(None) => println!("- Policy rate (BoC): N/A"),
-        Err(e) => println!("- Policy rate (BoC): error: {}", e),
+struct Indicator {
+    // What `stats.layout` and `stats.labels` call it
+    name: &'static str,
+    // Cache key; includes the series so a config change isn't served old values
+    key: String,
+    label: &'static str,
+    // Label when showing several periods
+    recent_label: &'static str,
+    source: Source,
+    // From `stats.labels`; replaces both of the above
+    custom_label: Option<String>,
+    // Set on the first indicator of a `stats.layout` group: its title, if any
+    group: Option<Option<String>>,
+}
+
+impl Indicator {
+    fn new(name: &'static str, key: String, label: &'static str, recent_label: &'static str, source: Source) -> Self {
+        Indicator { name, key, label, recent_label, source, custom_label: None, group: None }
     }
-    match inflation {
-        Ok(Some(v)) => println!("- Inflation YoY (CPI, BoC): {:.2}%", v),
-        Ok(None) => println!("- Inflation YoY (BoC): N/A"),
-        Err(e) => println!("- Inflation YoY (BoC): error: {}", e),
+
+    fn label(&self) -> String {
+        self.custom_label.clone().unwrap_or_else(|| tr!(self.label).to_string())
     }
+}
 
-    // Population (StatsCan) last 4 quarters, if configured
-    if let Some(vec_id) = cfg.stats.statscan_population_vector.as_ref() {
-        match fetch_statcan_last_n(&client, vec_id, 4).await {
-            Ok(Some(points)) => {
-                println!("- Population (StatsCan, last 4q):");
-                for (period, val) in points {
-                    println!("  {}: {}", period, val);
+#[derive(Default)]
+struct Slot {
+    value: Option<Cached>,
+    loading: bool,
+    // Why the last load gave nothing; None for "no data"
+    problem: Option<Option<String>>,
+}
+
+// Indicators' state, shared with the loads running in the background
+struct Board {
+    slots: Vec<Slot>,
+    cache: HashMap<String, Cached>,
+    // Whether the screen is up; loads finishing meanwhile redraw it, but not other screens
+    shown: bool,
+    loads: Vec<AbortHandle>,
+}
+
+/// The Stats screen's loads, started before it is opened (see [`prefetch`]).
+pub struct Prefetched {
+    indicators: Arc<Vec<Indicator>>,
+    board: Arc<Mutex<Board>>,
+}
+
+impl Prefetched {
+    /// Stop the loads still running; what arrived is in the cache file.
+    pub fn cancel(&self) {
+        for load in self.board.lock().unwrap().loads.drain(..) {
+            load.abort();
+        }
+    }
+}
+
+/// Start loading the values the Stats screen would (those missing from the cache or older
+/// than `stats.cache_minutes`), for its first visit to take.
+pub fn prefetch(cfg: &RuntimeConfig, app: &AppContext) {
+    let (indicators, board, stale) = prepare(cfg);
+    start_loads(app.client(), &indicators, &board, &stale);
+    app.set_stats_prefetch(Prefetched { indicators, board });
+}
+
+/// Indicators from the cache right away (fetching those missing or older than
+/// `stats.cache_minutes` in the background); 'r' fetches all again. Returns `true` if the
+/// user quit.
+pub async fn run(cfg: &RuntimeConfig, app: &AppContext) -> Result<bool> {
+    let term = Term::stdout();
+    // Loads started at launch carry on where they are
+    let (indicators, board) = match app.take_stats_prefetch() {
+        Some(Prefetched { indicators, board }) => (indicators, board),
+        None => {
+            let (indicators, board, stale) = prepare(cfg);
+            start_loads(app.client(), &indicators, &board, &stale);
+            (indicators, board)
+        }
+    };
+    board.lock().unwrap().shown = true;
+    let quit = loop {
+        let _ = term.clear_screen();
+        render(cfg, &indicators, &board.lock().unwrap());
+        match read_key_or_resize(&term)? {
+            None => {}
+            Some(Key::Char('r') | Key::Char('R')) => {
+                let all: Vec<usize> = (0..indicators.len()).collect();
+                start_loads(app.client(), &indicators, &board, &all);
+            }
+            Some(Key::Char('q') | Key::Char('Q') | Key::CtrlC) => break true,
+            Some(_) => break false,
+        }
+    };
+    board.lock().unwrap().shown = false;
+    Ok(quit)
+}
+
+/// One line per cached indicator for the Morning briefing: "Policy rate (BoC): 2.75% (▼ 0.25
+/// ...)", an exchange rate or coin price each, the latest period of a series. The yield
+/// curve is left to the Stats screen; nothing is fetched, so an indicator never loaded there
+/// (or by `stats.prefetch`) is missing.
+pub fn key_lines(cfg: &RuntimeConfig) -> Vec<String> {
+    let cache = load_cache();
+    let mut out = Vec::new();
+    for indicator in indicators(&cfg.stats) {
+        let Some(cached) = cache.get(&indicator.key) else { continue };
+        match (&cached.reading, &indicator.source) {
+            (Reading::Rate { value, previous, .. }, _) => {
+                let change = previous.map(|p| format!(" {}", change(*value, p, 2, "%"))).unwrap_or_default();
+                out.push(format!("{}: {:.2}%{}", indicator.label(), value, change));
+            }
+            (Reading::Quote { value, previous, .. }, Source::Fx(base, quote)) => {
+                let change = previous.map(|p| format!(" {}", change(*value, p, 4, ""))).unwrap_or_default();
+                out.push(format!("{}/{}: {:.4}{}", base, quote, value, change));
+            }
+            (Reading::Prices(prices), Source::Crypto(_, currency)) => {
+                for CoinPrice { coin, price, change_24h, .. } in prices {
+                    let Some(price) = price else { continue };
+                    let change = change_24h.map(|c| format!(" ({})", tr!("stats.change_24h", percent_arrow(c)))).unwrap_or_default();
+                    out.push(format!("{}: {:.2} {}{}", coin, price, currency.to_uppercase(), change));
+                }
+            }
+            (Reading::Points(points), _) => {
+                if let Some((period, value)) = points.last() {
+                    out.push(format!("{}: {} ({})", indicator.label(), value, period));
                 }
             }
-            Ok(None) => println!("- Population (StatsCan): N/A"),
-            Err(e) => println!("- Population (StatsCan): error: {}", e),
+            _ => {}
         }
-    } else {
-        println!("- Population (StatsCan): not configured (add stats.statscan_population_vector)");
     }
+    out
+}
+
+// The indicators, a board with their cached values, and which of those need loading
+fn prepare(cfg: &RuntimeConfig) -> (Arc<Vec<Indicator>>, Arc<Mutex<Board>>, Vec<usize>) {
+    let indicators: Arc<Vec<Indicator>> = Arc::new(indicators(&cfg.stats));
+    let cache = load_cache();
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    let ttl = i64::try_from(cfg.slowed(cfg.stats.cache_minutes.unwrap_or(DEFAULT_CACHE_MINUTES))).unwrap_or(i64::MAX).saturating_mul(60);
+    let slots: Vec<Slot> = indicators
+        .iter()
+        .map(|i| Slot { value: cache.get(&i.key).cloned(), ..Slot::default() })
+        .collect();
+    let stale: Vec<usize> = (0..slots.len())
+        .filter(|&i| slots[i].value.as_ref().is_none_or(|c| now.saturating_sub(c.fetched) >= ttl))
+        .collect();
+    let board = Arc::new(Mutex::new(Board { slots, cache, shown: false, loads: Vec::new() }));
+    (indicators, board, stale)
+}
+
+// What the screen shows, in order
+fn indicators(stats: &StatsConfig) -> Vec<Indicator> {
+    let mut out = vec![
+        // Target for the overnight rate
+        Indicator::new("policy_rate", "policy_rate".into(), "stats.policy_rate", "", Source::Boc("V39079")),
+        // Total CPI, % change over 1 year ago
+        Indicator::new("inflation", "inflation".into(), "stats.inflation", "", Source::Boc("STATIC_TOTALCPICHANGE")),
+    ];
+    let vectors = [
+        ("population", "stats.population", "stats.population_recent", "stats.statscan_population_vector", &stats.statscan_population_vector),
+        ("housing", "stats.housing", "stats.housing_recent", "stats.housing_starts_vector", &stats.housing_starts_vector),
+    ];
+    for (name, label, recent_label, config_key, vector) in vectors {
+        let (key, source) = match vector {
+            Some(v) => (format!("{}:{}", name, v), Source::Statcan(v.clone())),
+            None => (name.to_string(), Source::Unconfigured(config_key)),
+        };
+        out.push(Indicator::new(name, key, label, recent_label, source));
+    }
+    let pairs = yield_series(stats);
+    let ids: Vec<&str> = pairs.iter().map(|(_, id)| id.as_str()).collect();
+    out.push(Indicator::new("yield_curve", format!("yields:{}", ids.join(",")), "stats.yield_curve", "", Source::Curve(pairs)));
+    for pair in stats.fx.iter().flat_map(|fx| &fx.pairs) {
+        // Checked by validate
+        let Some((base, quote)) = FxConfig::parse_pair(pair) else { continue };
+        out.push(Indicator::new("fx", format!("fx:{}/{}", base, quote), "stats.fx", "", Source::Fx(base, quote)));
+    }
+    if let Some(crypto) = &stats.crypto
+        && !crypto.coins.is_empty()
+    {
+        let currency = crypto.currency();
+        let key = format!("crypto:{}:{}", currency, crypto.coins.join(","));
+        out.push(Indicator::new("crypto", key, "stats.crypto", "", Source::Crypto(crypto.coins.clone(), currency)));
+    }
+    arrange(stats, out)
+}
+
+// `all` in the order and groups of `stats.layout`, leaving out what it doesn't list, with
+// `stats.labels` applied. Without a layout, everything in one group.
+fn arrange(stats: &StatsConfig, all: Vec<Indicator>) -> Vec<Indicator> {
+    let everything = [StatsGroup { title: None, items: STATS_ITEMS.iter().map(|s| s.to_string()).collect() }];
+    let groups = stats.layout.as_deref().unwrap_or(&everything);
+    let mut pool: Vec<Option<Indicator>> = all.into_iter().map(Some).collect();
+    let mut out = Vec::new();
+    for group in groups {
+        let mut title = Some(group.title.clone());
+        for item in &group.items {
+            // All of an item's indicators (e.g. each fx pair), in config order
+            for slot in pool.iter_mut().filter(|s| s.as_ref().is_some_and(|i| i.name == item.as_str())) {
+                let Some(mut indicator) = slot.take() else { continue };
+                indicator.custom_label = stats.labels.get(item).cloned();
+                indicator.group = title.take();
+                out.push(indicator);
+            }
+        }
+    }
+    out
+}
 
-    // Housing starts (StatsCan/CMHC) last 4 periods, if configured
-    if let Some(vec_id) = cfg.stats.housing_starts_vector.as_ref() {
-        match fetch_statcan_last_n(&client, vec_id, 4).await {
-            Ok(Some(points)) => {
-                println!("- Housing starts (StatsCan/CMHC, last 4):");
+// Fetch `which` indicators in the background (those not already loading), updating the
+// board and the cache file as each arrives
+fn start_loads(client: &Client, indicators: &Arc<Vec<Indicator>>, board: &Arc<Mutex<Board>>, which: &[usize]) {
+    let mut started = false;
+    {
+        let mut b = board.lock().unwrap();
+        let idle = !b.slots.iter().any(|s| s.loading);
+        for &i in which {
+            let slot = &mut b.slots[i];
+            if slot.loading || matches!(indicators[i].source, Source::Unconfigured(_)) {
+                continue;
+            }
+            slot.loading = true;
+            started = true;
+            let (client, indicators, board) = (client.clone(), indicators.clone(), board.clone());
+            let load = tokio::spawn(async move {
+                let indicator = &indicators[i];
+                let loaded = fetch(&client, &indicator.source).await;
+                let mut b = board.lock().unwrap();
+                let b = &mut *b;
+                let slot = &mut b.slots[i];
+                slot.loading = false;
+                match loaded {
+                    Ok(Some(reading)) => {
+                        let cached = Cached { fetched: OffsetDateTime::now_utc().unix_timestamp(), reading };
+                        slot.value = Some(cached.clone());
+                        slot.problem = None;
+                        b.cache.insert(indicator.key.clone(), cached);
+                        if let Err(e) = save_cache(&b.cache) {
+                            eprintln!("{}", tr!("stats.cache_failed", e));
+                        }
+                    }
+                    Ok(None) => slot.problem = Some(None),
+                    Err(e) => slot.problem = Some(Some(e.to_string())),
+                }
+                if b.shown {
+                    ui::notify_refresh();
+                }
+            });
+            b.loads.retain(|l| !l.is_finished());
+            b.loads.push(load.abort_handle());
+        }
+        if !idle {
+            // A spinner is already turning
+            return;
+        }
+    }
+    if started {
+        // Redraw now and then so the spinners turn
+        let board = board.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_millis(150)).await;
+                let b = board.lock().unwrap();
+                if !b.slots.iter().any(|s| s.loading) {
+                    break;
+                }
+                if b.shown {
+                    ui::notify_refresh();
+                }
+            }
+        });
+    }
+}
+
+async fn fetch(client: &Client, source: &Source) -> Result<Option<Reading>> {
+    Ok(match source {
+        Source::Boc(series) => {
+            fetch_boc_latest_number(client, series).await?.map(|(value, date, previous)| Reading::Rate { value, date, previous })
+        }
+        Source::Statcan(vector) => fetch_statcan_last_n(client, vector, 4).await?.map(Reading::Points),
+        Source::Curve(pairs) => {
+            let (points, date, previous) = fetch_yield_curve(client, pairs).await;
+            // Not one maturity loaded: nothing worth caching
+            points.iter().any(|(_, v)| v.is_some()).then_some(Reading::Curve { points, date, previous })
+        }
+        Source::Fx(base, quote) => fetch_fx(client, base, quote).await?.map(|(value, date, previous)| Reading::Quote { value, date, previous }),
+        Source::Crypto(coins, currency) => fetch_crypto(client, coins, currency).await?.map(Reading::Prices),
+        Source::Unconfigured(_) => None,
+    })
+}
+
+fn render(cfg: &RuntimeConfig, indicators: &[Indicator], board: &Board) {
+    if let Some(b) = crate::ui::breadcrumb() {
+        println!("{}", b);
+    }
+    println!("{}", tr!("stats.title"));
+    println!();
+    let spinner = ui::spinner();
+    let mut oldest: Option<i64> = None;
+    for (i, (indicator, slot)) in indicators.iter().zip(&board.slots).enumerate() {
+        if let Some(title) = &indicator.group {
+            if i > 0 {
+                println!();
+            }
+            if let Some(title) = title {
+                println!("{}", style(title).bold());
+            }
+        }
+        let label = match &indicator.source {
+            Source::Fx(base, quote) => format!("{}/{}", base, quote),
+            _ => indicator.label(),
+        };
+        let busy = if slot.loading { format!(" {}", spinner) } else { String::new() };
+        let problem = match &slot.problem {
+            _ if slot.loading => None,
+            Some(Some(e)) => Some(tr!("stats.error", e)),
+            Some(None) => Some(tr!("stats.na").to_string()),
+            None => None,
+        };
+        let curve = matches!(indicator.source, Source::Curve(_) | Source::Crypto(..));
+        // Multi-line sections get a heading, after a blank line unless a group starts there
+        let spaced = i > 0 && indicator.group.is_none();
+        let first_fx = i == 0 || indicator.group.is_some() || !matches!(indicators[i - 1].source, Source::Fx(..));
+        if matches!(indicator.source, Source::Fx(..)) && first_fx {
+            if spaced {
+                println!();
+            }
+            println!("{}", indicator.label());
+        }
+        if curve {
+            if spaced {
+                println!();
+            }
+            // Without a value the spinner shows on the line below
+            println!("{}{}", label, if slot.value.is_some() { busy.as_str() } else { "" });
+        }
+        if let Source::Unconfigured(key) = indicator.source {
+            println!("- {}: {}", label, tr!("stats.not_configured", key));
+            continue;
+        }
+        let Some(cached) = &slot.value else {
+            // Nothing fetched yet: the spinner or why there is no value
+            let shown = problem.unwrap_or_else(|| spinner.to_string());
+            if curve {
+                println!("- {}", shown);
+            } else {
+                println!("- {}: {}", label, shown);
+            }
+            continue;
+        };
+        oldest = Some(oldest.map_or(cached.fetched, |o| o.min(cached.fetched)));
+        match &cached.reading {
+            Reading::Rate { value, date, previous } => {
+                let change = previous.map(|p| format!(" {}", change(*value, p, 2, "%"))).unwrap_or_default();
+                println!("- {}: {:.2}%{}{}{}", label, value, change, as_of(date.as_deref()), busy)
+            }
+            Reading::Quote { value, date, previous } => {
+                let change = previous.map(|p| format!(" {}", change(*value, p, 4, ""))).unwrap_or_default();
+                println!("- {}: {:.4}{}{}{}", label, value, change, as_of(date.as_deref()), busy)
+            }
+            Reading::Prices(prices) => {
+                let Source::Crypto(_, currency) = &indicator.source else { continue };
+                for CoinPrice { coin, price, change_24h, updated } in prices {
+                    let Some(price) = price else {
+                        println!("- {}: {}", coin, tr!("stats.na"));
+                        continue;
+                    };
+                    let change = change_24h.map(|c| format!(" ({})", tr!("stats.change_24h", percent_arrow(c)))).unwrap_or_default();
+                    let updated = updated.and_then(|ts| cfg.dates.format(ts));
+                    println!(
+                        "- {}: {:.2} {}{}{}",
+                        coin,
+                        price,
+                        currency.to_uppercase(),
+                        change,
+                        as_of(updated.as_deref())
+                    );
+                }
+            }
+            Reading::Points(points) => {
+                let recent = indicator.custom_label.clone().unwrap_or_else(|| tr!(indicator.recent_label).to_string());
+                println!("- {}:{}", recent, busy);
+                let mut before: Option<f64> = None;
                 for (period, val) in points {
-                    println!("  {}: {}", period, val);
+                    let n = val.parse::<f64>().ok();
+                    let change = n.zip(before).map(|(n, b)| format!(" {}", change(n, b, 0, ""))).unwrap_or_default();
+                    println!("  {}: {}{}", period, val, change);
+                    before = n;
+                }
+            }
+            Reading::Curve { points, date, previous } => {
+                render_yield_curve(points);
+                let changes: Vec<String> = points
+                    .iter()
+                    .zip(previous)
+                    .filter_map(|((label, v), p)| Some(format!("{} {}", label, arrow(v.zip(*p)?, 2))))
+                    .collect();
+                if !changes.is_empty() {
+                    println!("  {}", tr!("stats.curve_change", changes.join(" | ")));
+                }
+                if date.is_some() {
+                    println!(" {}", as_of(date.as_deref()));
                 }
             }
-            Ok(None) => println!("- Housing starts: N/A"),
-            Err(e) => println!("- Housing starts: error: {}", e),
         }
-    } else {
-        println!("- Housing starts: not configured (add stats.housing_starts_vector)");
+        // The cached value stays; say the refresh didn't work
+        if let Some(problem) = problem {
+            println!("  {}", style(tr!("stats.refresh_failed", problem)).dim());
+        }
     }
 
-    // Yield curve
-    println!("");
-    println!("Yield Curve (BoC):");
-    render_yield_curve_line(&yields);
+    println!();
+    if let Some(at) = oldest.and_then(|ts| cfg.dates.format(ts)) {
+        println!("{}", style(tr!("stats.updated", at)).dim());
+    }
+    print!("{}", tr!("stats.footer"));
+    let _ = std::io::Write::flush(&mut std::io::stdout());
+}
 
-    // Wait for user to go back or quit
-    println!("");
-    println!("Press Enter to return, 'q' to quit.");
-    match term.read_key()? {
-        console::Key::Char('q') | console::Key::Char('Q') => std::process::exit(0),
-        _ => {}
+// "(▼ 0.25, -5.3% from 4.75%)": the move from `previous` to `value`, colored by direction;
+// `unit` follows the previous value
+fn change(value: f64, previous: f64, decimals: usize, unit: &str) -> String {
+    let from = format!("{:.*}{}", decimals, previous, unit);
+    if value == previous {
+        return format!("({})", tr!("stats.unchanged", from));
+    }
+    let mut moved = arrow((value, previous), decimals);
+    if previous != 0.0 {
+        moved = format!("{}, {:+.1}%", moved, (value - previous) / previous.abs() * 100.0);
     }
+    format!("({})", tr!("stats.change_from", moved, from))
+}
 
+// "▲ 0.05" in the theme's positive color, "▼ 0.25" in its negative one, "= 0.00" unchanged
+fn arrow((value, previous): (f64, f64), decimals: usize) -> String {
+    let theme = crate::theme::current();
+    let delta = format!("{:.*}", decimals, (value - previous).abs());
+    if value > previous {
+        theme.positive.apply_to(format!("▲ {}", delta)).to_string()
+    } else if value < previous {
+        theme.negative.apply_to(format!("▼ {}", delta)).to_string()
+    } else {
+        format!("= {}", delta)
+    }
+}
+
+// "▲ 1.2%" in the theme's positive color, "▼ 1.2%" in its negative one
+fn percent_arrow(change: f64) -> String {
+    let theme = crate::theme::current();
+    let magnitude = format!("{:.1}%", change.abs());
+    if change > 0.0 {
+        theme.positive.apply_to(format!("▲ {}", magnitude)).to_string()
+    } else if change < 0.0 {
+        theme.negative.apply_to(format!("▼ {}", magnitude)).to_string()
+    } else {
+        format!("= {}", magnitude)
+    }
+}
+
+// " (as of 2024-10-23)", or nothing without a date
+fn as_of(date: Option<&str>) -> String {
+    date.map(|d| format!(" {}", tr!("stats.as_of", d))).unwrap_or_default()
+}
+
+fn load_cache() -> HashMap<String, Cached> {
+    cache_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_cache(cache: &HashMap<String, Cached>) -> Result<()> {
+    if let Some(path) = cache_path() {
+        write_atomic(&path, serde_json::to_string(cache)?.as_bytes(), false)?;
+    }
     Ok(())
 }
 
-async fn fetch_boc_latest_number(client: &Client, series: &str) -> Result<Option<f64>> {
+fn cache_path() -> Option<PathBuf> {
+    news_cli::paths::cache_dir().map(|d| d.join("stats.json"))
+}
+
+// The latest observation: value, date and the value observed before it
+async fn fetch_boc_latest_number(client: &Client, series: &str) -> Result<Option<(f64, Option<String>, Option<f64>)>> {
     let url = format!(
-        "https://www.bankofcanada.ca/valet/observations/{}?recent=1",
+        "https://www.bankofcanada.ca/valet/observations/{}?recent=2",
         series
     );
     let text = client.get(url).send().await?.text().await?;
@@ -95,26 +571,24 @@ async fn fetch_boc_latest_number(client: &Client, series: &str) -> Result<Option
     let obs = v.get("observations").and_then(|x| x.as_array());
     let Some(arr) = obs else { return Ok(None) };
     let Some(obj) = arr.last().and_then(|x| x.as_object()) else { return Ok(None) };
+    let Some(value) = observation_value(obj, series) else { return Ok(None) };
+    let date = obj.get("d").and_then(|x| x.as_str()).map(str::to_string);
+    // Observations come oldest first
+    let previous = arr.len().checked_sub(2).and_then(|i| arr[i].as_object()).and_then(|o| observation_value(o, series));
+    Ok(Some((value, date, previous)))
+}
+
+fn observation_value(obj: &serde_json::Map<String, Value>, series: &str) -> Option<f64> {
+    // {"v": "1.23"} or a bare "1.23"
+    let parse = |s: &str| s.parse::<f64>().ok();
+    let number = |val: &Value| {
+        val.get("v").and_then(|x| x.as_str()).and_then(parse).or_else(|| val.as_str().and_then(parse))
+    };
     // Prefer direct field by series id
-    if let Some(val) = obj.get(series) {
-        if let Some(s) = val.get("v").and_then(|x| x.as_str()) {
-            if let Ok(n) = s.parse::<f64>() { return Ok(Some(n)); }
-        }
-        if let Some(s) = val.as_str() {
-            if let Ok(n) = s.parse::<f64>() { return Ok(Some(n)); }
-        }
-    }
-    // Fallback: scan values except the date field 'd'
-    for (k, val) in obj.iter() {
-        if k == "d" { continue; }
-        if let Some(s) = val.get("v").and_then(|x| x.as_str()) {
-            if let Ok(n) = s.parse::<f64>() { return Ok(Some(n)); }
-        }
-        if let Some(s) = val.as_str() {
-            if let Ok(n) = s.parse::<f64>() { return Ok(Some(n)); }
-        }
-    }
-    Ok(None)
+    obj.get(series)
+        .and_then(number)
+        // Fallback: scan values except the date field 'd'
+        .or_else(|| obj.iter().filter(|(k, _)| *k != "d").find_map(|(_, val)| number(val)))
 }
 
 async fn fetch_statcan_last_n(client: &Client, vector: &str, n: usize) -> Result<Option<Vec<(String, String)>>> {
@@ -122,7 +596,7 @@ async fn fetch_statcan_last_n(client: &Client, vector: &str, n: usize) -> Result
     // Vector IDs are numeric; strip any leading 'v'/'V' prefix from config values
     let vec_id_str = vector.trim_start_matches(|c: char| c == 'v' || c == 'V');
     let vec_id: u64 = vec_id_str.parse()
-        .map_err(|_| anyhow::anyhow!("invalid StatsCan vector id: {}", vector))?;
+        .map_err(|_| anyhow::anyhow!(tr!("stats.invalid_vector", vector)))?;
 
     let url = "https://www150.statcan.gc.ca/t1/wds/rest/getDataFromVectorsAndLatestNPeriods";
     let body = serde_json::json!([{"vectorId": vec_id, "latestN": n}]);
@@ -143,13 +617,106 @@ async fn fetch_statcan_last_n(client: &Client, vector: &str, n: usize) -> Result
                 };
                 out.push((period, val));
             }
+            // Oldest first, so each period compares with the one above it (refPer is ISO)
+            out.sort_by(|a, b| a.0.cmp(&b.0));
             return Ok(Some(out));
         }
     }
     Ok(None)
 }
 
-async fn fetch_yield_curve(client: &Client, stats: &StatsConfig) -> Vec<(String, Option<f64>)> {
+// `quote` per `base`, as (latest, its date, the one before). Pairs with CAD come from the
+// BoC's FX series (CAD per unit of a currency); others from the ECB's reference rates
+// (units per EUR), crossed when neither currency is the euro.
+async fn fetch_fx(client: &Client, base: &str, quote: &str) -> Result<Option<(f64, Option<String>, Option<f64>)>> {
+    let (per_base, per_quote) = if base == "CAD" || quote == "CAD" {
+        let series = |c: &str| format!("FX{}CAD", c);
+        let (b, q) = futures_util::join!(
+            async { if base == "CAD" { Ok(Some(ONE)) } else { fetch_boc_latest_number(client, &series(base)).await } },
+            async { if quote == "CAD" { Ok(Some(ONE)) } else { fetch_boc_latest_number(client, &series(quote)).await } },
+        );
+        (b?, q?)
+    } else {
+        let (b, q) = futures_util::join!(
+            async { if base == "EUR" { Ok(Some(ONE)) } else { fetch_ecb_rate(client, base).await } },
+            async { if quote == "EUR" { Ok(Some(ONE)) } else { fetch_ecb_rate(client, quote).await } },
+        );
+        // ECB rates are units per EUR: the other way round from BoC's
+        (q?, b?)
+    };
+    let (Some(b), Some(q)) = (per_base, per_quote) else { return Ok(None) };
+    let previous = b.2.zip(q.2).map(|(pb, pq)| pb / pq);
+    // The earlier date when the two series are a day apart
+    let date = match (b.1, q.1) {
+        (Some(db), Some(dq)) => Some(db.min(dq)),
+        (db, dq) => db.or(dq),
+    };
+    Ok(Some((b.0 / q.0, date, previous)))
+}
+
+// The reference currency of a pair against itself: 1, with no date of its own
+const ONE: (f64, Option<String>, Option<f64>) = (1.0, None, Some(1.0));
+
+// Each coin's price in `currency` from CoinGecko's simple price API; None if it knows none
+// of them
+async fn fetch_crypto(
+    client: &Client,
+    coins: &[String],
+    currency: &str,
+) -> Result<Option<Vec<CoinPrice>>> {
+    let url = url::Url::parse_with_params(
+        "https://api.coingecko.com/api/v3/simple/price",
+        [
+            ("ids", coins.join(",").as_str()),
+            ("vs_currencies", currency),
+            ("include_24hr_change", "true"),
+            ("include_last_updated_at", "true"),
+        ],
+    )?;
+    let v: Value = client.get(url).send().await?.error_for_status()?.json().await?;
+    let prices: Vec<_> = coins
+        .iter()
+        .map(|coin| {
+            let quote = v.get(coin.as_str());
+            let field = |name: &str| quote.and_then(|q| q.get(name));
+            CoinPrice {
+                coin: coin.clone(),
+                price: field(currency).and_then(Value::as_f64),
+                change_24h: field(&format!("{}_24h_change", currency)).and_then(Value::as_f64),
+                updated: field("last_updated_at").and_then(Value::as_i64),
+            }
+        })
+        .collect();
+    Ok(prices.iter().any(|p| p.price.is_some()).then_some(prices))
+}
+
+// Units of `currency` per EUR from the ECB's daily reference rates: latest, its date and
+// the one before
+async fn fetch_ecb_rate(client: &Client, currency: &str) -> Result<Option<(f64, Option<String>, Option<f64>)>> {
+    let url = format!(
+        "https://data-api.ecb.europa.eu/service/data/EXR/D.{}.EUR.SP00.A?lastNObservations=2&format=csvdata",
+        currency
+    );
+    let text = client.get(url).send().await?.error_for_status()?.text().await?;
+    let mut lines = text.lines();
+    let Some(header) = lines.next() else { return Ok(None) };
+    let column = |name: &str| header.split(',').position(|h| h.trim() == name);
+    let (Some(period), Some(value)) = (column("TIME_PERIOD"), column("OBS_VALUE")) else { return Ok(None) };
+    // (date, rate), oldest first
+    let mut rows: Vec<(String, f64)> = lines
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            Some((fields.get(period)?.to_string(), fields.get(value)?.parse().ok()?))
+        })
+        .collect();
+    rows.sort_by(|a, b| a.0.cmp(&b.0));
+    let Some((date, latest)) = rows.last().cloned() else { return Ok(None) };
+    let previous = rows.len().checked_sub(2).map(|i| rows[i].1);
+    Ok(Some((latest, Some(date), previous)))
+}
+
+// (label, series id) per maturity, shortest first unless configured
+fn yield_series(stats: &StatsConfig) -> Vec<(String, String)> {
     let default_series: Vec<(String, String)> = vec![
         ("3M".to_string(), "TB.CDN.90D.MID".to_string()),  // 3-month T-bill mid-rate
         ("2Y".to_string(), "BD.CDN.2YR.DQ.YLD".to_string()),  // GoC 2-year benchmark bond yield
@@ -165,43 +732,59 @@ async fn fetch_yield_curve(client: &Client, stats: &StatsConfig) -> Vec<(String,
         }
         None => default_series,
     };
+    pairs
+}
 
+// Each maturity's latest yield, the most recent observation date among them and each
+// maturity's yield before
+async fn fetch_yield_curve(
+    client: &Client,
+    pairs: &[(String, String)],
+) -> (Vec<(String, Option<f64>)>, Option<String>, Vec<Option<f64>>) {
     let futs = pairs.iter().map(|(_label, id)| fetch_boc_latest_number(client, id));
     let vals = join_all(futs).await;
     let mut out: Vec<(String, Option<f64>)> = Vec::new();
-    for ((label, _), v) in pairs.into_iter().zip(vals.into_iter()) {
-        out.push((label, v.ok().flatten()));
+    let mut date: Option<String> = None;
+    let mut previous: Vec<Option<f64>> = Vec::new();
+    for ((label, _), v) in pairs.iter().zip(vals) {
+        let v = v.ok().flatten();
+        // ISO dates compare as strings
+        date = date.max(v.as_ref().and_then(|(_, d, _)| d.clone()));
+        out.push((label.clone(), v.as_ref().map(|(n, _, _)| *n)));
+        previous.push(v.and_then(|(_, _, p)| p));
     }
-    out
+    (out, date, previous)
 }
 
-fn render_yield_curve_line(data: &[(String, Option<f64>)]) {
-    if data.is_empty() {
-        println!("(no yield data)");
+// A chart of yield by maturity, sized to the terminal; stretches where a longer maturity
+// yields less (an inverted curve) in the theme's negative color
+fn render_yield_curve(data: &[(String, Option<f64>)]) {
+    if data.iter().all(|(_, v)| v.is_none()) {
+        println!("{}", tr!("stats.no_yield_data"));
         return;
     }
-    // Build a single line with inversion coloring against previous point
-    let mut prev: Option<f64> = None;
-    let mut parts: Vec<String> = Vec::new();
-    for (label, val) in data.iter() {
-        match (val, prev) {
-            (Some(v), Some(p)) => {
-                let s = if *v < p { // inverted relative to previous maturity
-                    format!("{}: {}%", label, style(format!("{:.2}", v)).red())
-                } else {
-                    format!("{}: {}%", label, style(format!("{:.2}", v)).green())
-                };
-                parts.push(s);
-                prev = Some(*v);
-            }
-            (Some(v), None) => {
-                parts.push(format!("{}: {}%", label, style(format!("{:.2}", v)).green()));
-                prev = Some(*v);
-            }
-            (None, _) => {
-                parts.push(format!("{}: N/A", label));
-            }
-        }
+    let theme = crate::theme::current();
+    let (rows, cols) = Term::stdout().size();
+    let labels: Vec<String> = data
+        .iter()
+        .map(|(label, v)| match v {
+            Some(v) => format!("{} {:.2}%", label, v),
+            None => format!("{} {}", label, tr!("stats.na")),
+        })
+        .collect();
+    let values: Vec<Option<f64>> = data.iter().map(|(_, v)| *v).collect();
+    let inverted = |i: usize| {
+        let next = values[i + 1..].iter().flatten().next();
+        matches!((values[i], next), (Some(v), Some(n)) if *n < v)
+    };
+    let paint = |mark: Mark, ch: char| match mark {
+        Mark::Segment(i) if inverted(i) => theme.negative.apply_to(ch).to_string(),
+        Mark::Segment(_) => ch.to_string(),
+        Mark::Point(_) => style(ch).bold().to_string(),
+    };
+    let width = (cols as usize).clamp(20, 100) - 2;
+    let height = (rows as usize / 4).clamp(4, 10);
+    for line in line_chart(&labels, &values, width, height, 2, paint) {
+        println!("  {}", line);
     }
-    println!("- {}", parts.join(" | "));
 }
@@ -0,0 +1,163 @@
+use super::{RemoteState, SyncBackend};
+use crate::config::FetchLimits;
+use crate::news::{build_client_with, normalize_link, strict, Story};
+use anyhow::{bail, Result};
+use futures_util::future::BoxFuture;
+use reqwest::{Client, Method, RequestBuilder};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use url::Url;
+
+// Recent entries compared per pull; older ones are unlikely to still be in any feed
+const PULL_LIMIT: usize = 500;
+
+/// Miniflux REST API (`/v1`), authenticated with an API key from its settings page.
+pub struct MinifluxBackend {
+    client: Client,
+    base: Url,
+    token: String,
+    tracking_params: Vec<String>,
+    // Normalized link -> (entry id, starred), from the last pull
+    entries: Mutex<HashMap<String, (i64, bool)>>,
+}
+
+#[derive(Deserialize)]
+struct EntriesPage {
+    entries: Vec<Entry>,
+}
+
+#[derive(Deserialize)]
+struct Entry {
+    id: i64,
+    url: String,
+    title: String,
+    status: String,
+    starred: bool,
+    published_at: Option<String>,
+    #[serde(default)]
+    author: String,
+    #[serde(default)]
+    content: String,
+    feed: EntryFeed,
+}
+
+#[derive(Deserialize)]
+struct EntryFeed {
+    title: String,
+}
+
+impl MinifluxBackend {
+    /// The client follows `[fetch]`, so strict mode applies to the server too.
+    pub fn new(url: &str, token: &str, tracking_params: &[String], limits: &FetchLimits) -> Result<Self> {
+        let base = Url::parse(&format!("{}/", url.trim_end_matches('/')))?;
+        if !matches!(base.scheme(), "http" | "https") {
+            bail!("sync url must be http(s): {}", url);
+        }
+        // The client's resolver only sees host names, not addresses
+        if limits.strict == Some(true) {
+            strict::check_url(&base).map_err(anyhow::Error::msg)?;
+        }
+        Ok(MinifluxBackend {
+            client: build_client_with(limits)?,
+            base,
+            token: token.to_string(),
+            tracking_params: tracking_params.to_vec(),
+            entries: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn request(&self, method: Method, path: &str) -> Result<RequestBuilder> {
+        Ok(self
+            .client
+            .request(method, self.base.join(path)?)
+            .header("X-Auth-Token", &self.token))
+    }
+}
+
+impl SyncBackend for MinifluxBackend {
+    fn name(&self) -> &'static str {
+        "miniflux"
+    }
+
+    fn pull(&self) -> BoxFuture<'_, Result<RemoteState>> {
+        Box::pin(async move {
+            let limit = PULL_LIMIT.to_string();
+            let page: EntriesPage = self
+                .request(Method::GET, "v1/entries")?
+                .query(&[("limit", limit.as_str()), ("order", "published_at"), ("direction", "desc")])
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            let mut state = RemoteState::default();
+            let mut entries = HashMap::new();
+            for e in page.entries {
+                let Some(link) = normalize_link(&e.url, &self.tracking_params) else { continue };
+                entries.insert(link.clone(), (e.id, e.starred));
+                if e.status == "read" {
+                    state.read.push(link.clone());
+                }
+                if e.starred {
+                    state.starred.push(Story {
+                        title: e.title,
+                        link,
+                        source: e.feed.title,
+                        is_new: false,
+                        published: e
+                            .published_at
+                            .and_then(|t| OffsetDateTime::parse(&t, &Rfc3339).ok())
+                            .map(|d| d.unix_timestamp()),
+                        author: Some(e.author).filter(|a| !a.is_empty()),
+                        summary: Some(e.content).filter(|c| !c.is_empty()),
+                        duration: None,
+                        points: None,
+                        content: None,
+                        image: None,
+                        language: None,
+                        translation: None,
+                    });
+                }
+            }
+            *self.entries.lock().unwrap() = entries;
+            Ok(state)
+        })
+    }
+
+    fn mark_read<'a>(&'a self, links: &'a [String]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let ids: Vec<i64> = {
+                let entries = self.entries.lock().unwrap();
+                links.iter().filter_map(|l| entries.get(l).map(|(id, _)| *id)).collect()
+            };
+            if ids.is_empty() {
+                return Ok(());
+            }
+            self.request(Method::PUT, "v1/entries")?
+                .json(&json!({ "entry_ids": ids, "status": "read" }))
+                .send()
+                .await?
+                .error_for_status()?;
+            Ok(())
+        })
+    }
+
+    fn set_starred<'a>(&'a self, link: &'a str, starred: bool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let Some((id, current)) = self.entries.lock().unwrap().get(link).copied() else { return Ok(()) };
+            if current == starred {
+                return Ok(());
+            }
+            // Miniflux only offers a toggle
+            self.request(Method::PUT, &format!("v1/entries/{}/bookmark", id))?
+                .send()
+                .await?
+                .error_for_status()?;
+            self.entries.lock().unwrap().insert(link.to_string(), (id, starred));
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,43 @@
+//! Read and starred state shared with a self-hosted feed reader, so stories read on the web
+//! reader don't show as new here and vice versa. The local history stays in use alongside
+//! (it also covers feeds the reader doesn't subscribe to).
+mod miniflux;
+
+pub use miniflux::MinifluxBackend;
+
+use crate::config::RuntimeConfig;
+use crate::news::Story;
+use anyhow::{bail, Result};
+use futures_util::future::BoxFuture;
+use std::sync::Arc;
+
+/// What the reader knows, keyed by normalized link like the local history.
+#[derive(Debug, Default)]
+pub struct RemoteState {
+    pub read: Vec<String>,
+    pub starred: Vec<Story>,
+}
+
+pub trait SyncBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+    /// Current read/starred entries; also refreshes the link-to-entry mapping used by pushes.
+    fn pull(&self) -> BoxFuture<'_, Result<RemoteState>>;
+    /// Mark stories read; links the reader doesn't have are skipped.
+    fn mark_read<'a>(&'a self, links: &'a [String]) -> BoxFuture<'a, Result<()>>;
+    fn set_starred<'a>(&'a self, link: &'a str, starred: bool) -> BoxFuture<'a, Result<()>>;
+}
+
+/// The backend configured in `[sync]`, if any.
+pub fn connect(cfg: &RuntimeConfig) -> Result<Option<Arc<dyn SyncBackend>>> {
+    let Some(sync) = &cfg.sync else { return Ok(None) };
+    let token = sync
+        .token
+        .clone()
+        .filter(|t| !t.is_empty())
+        .or_else(|| std::env::var("NEWS_CLI_SYNC_TOKEN").ok().filter(|t| !t.is_empty()));
+    let Some(token) = token else { bail!("sync: no API token (token or $NEWS_CLI_SYNC_TOKEN)") };
+    match sync.backend.as_str() {
+        "miniflux" => Ok(Some(Arc::new(MinifluxBackend::new(&sync.url, &token, &cfg.tracking_params, &cfg.fetch)?))),
+        other => bail!("sync: unsupported backend \"{}\"", other),
+    }
+}
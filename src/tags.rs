@@ -0,0 +1,129 @@
+use crate::news::Story;
+use crate::util::atomic::write_atomic;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tagged {
+    pub story: Story,
+    /// Lowercase, in the order given
+    pub tags: Vec<String>,
+    /// Why the story was kept, in the user's words
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// When the story was first tagged or annotated (UNIX epoch seconds)
+    pub tagged_at: i64,
+}
+
+/// Free-form tags ('t' in the story lists) and notes ('n' in the detail view) the user put on
+/// stories, persisted as `tags.json` in the state directory. Like bookmarks, the stories are
+/// kept whole, so they outlive the feeds that listed them.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Tags {
+    items: Vec<Tagged>,
+}
+
+impl Tags {
+    pub fn load() -> Self {
+        if let Some(path) = tags_file_path()
+            && let Ok(contents) = fs::read_to_string(&path)
+            && let Ok(t) = serde_json::from_str::<Tags>(&contents)
+        {
+            return t;
+        }
+        Tags::default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = tags_file_path() {
+            let json = serde_json::to_string_pretty(self)?;
+            write_atomic(&path, json.as_bytes(), true)?;
+        }
+        Ok(())
+    }
+
+    /// Oldest first.
+    pub fn items(&self) -> &[Tagged] {
+        &self.items
+    }
+
+    /// The tags on the story with `link`; empty if it has none.
+    pub fn of(&self, link: &str) -> &[String] {
+        self.items.iter().find(|t| t.story.link == link).map_or(&[], |t| &t.tags)
+    }
+
+    /// The note on the story with `link`, if it has one.
+    pub fn note(&self, link: &str) -> Option<&str> {
+        self.items.iter().find(|t| t.story.link == link).and_then(|t| t.note.as_deref())
+    }
+
+    /// Replace `story`'s tags with `tags` (see [`parse`]); none untags it.
+    pub fn set(&mut self, story: &Story, tags: Vec<String>) {
+        self.entry(story).tags = tags;
+        self.prune();
+    }
+
+    /// Replace `story`'s note; a blank one removes it.
+    pub fn set_note(&mut self, story: &Story, note: &str) {
+        let note = note.trim();
+        self.entry(story).note = (!note.is_empty()).then(|| note.to_string());
+        self.prune();
+    }
+
+    /// The stories with a note, oldest first.
+    pub fn noted(&self) -> Vec<&Tagged> {
+        self.items.iter().filter(|t| t.note.is_some()).collect()
+    }
+
+    fn entry(&mut self, story: &Story) -> &mut Tagged {
+        let i = match self.items.iter().position(|t| t.story.link == story.link) {
+            Some(i) => i,
+            None => {
+                self.items.push(Tagged {
+                    story: story.clone(),
+                    tags: Vec::new(),
+                    note: None,
+                    tagged_at: time::OffsetDateTime::now_utc().unix_timestamp(),
+                });
+                self.items.len() - 1
+            }
+        };
+        &mut self.items[i]
+    }
+
+    // Stories with neither tags nor a note are forgotten
+    fn prune(&mut self) {
+        self.items.retain(|t| !t.tags.is_empty() || t.note.is_some());
+    }
+
+    /// Every tag in use with how many stories carry it, by name.
+    pub fn names(&self) -> Vec<(String, usize)> {
+        let mut counts: std::collections::BTreeMap<&str, usize> = std::collections::BTreeMap::new();
+        for tag in self.items.iter().flat_map(|t| &t.tags) {
+            *counts.entry(tag).or_default() += 1;
+        }
+        counts.into_iter().map(|(tag, n)| (tag.to_string(), n)).collect()
+    }
+
+    /// The stories tagged `tag`, oldest first.
+    pub fn tagged(&self, tag: &str) -> Vec<&Tagged> {
+        self.items.iter().filter(|t| t.tags.iter().any(|x| x == tag)).collect()
+    }
+}
+
+/// Tags as typed, e.g. "rust, Async ,rust": comma-separated, trimmed and lowercased, without
+/// empty ones or repeats.
+pub fn parse(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(',').map(|t| t.trim().to_lowercase()) {
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+fn tags_file_path() -> Option<PathBuf> {
+    crate::paths::state_dir().map(|d| d.join("tags.json"))
+}
@@ -0,0 +1,85 @@
+use news_cli::config::ThemeConfig;
+use console::{Style, StyledObject};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// Resolved styles for the UI. Set from config via [`init`]; until then the defaults apply.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub header: Style,
+    pub new_badge: Style,
+    pub selection: Style,
+    pub positive: Style,
+    pub negative: Style,
+    feeds: HashMap<String, Style>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            header: Style::new().bold(),
+            new_badge: Style::new().green().bold(),
+            selection: Style::new().cyan(),
+            positive: Style::new().green(),
+            negative: Style::new().red(),
+            feeds: HashMap::new(),
+        }
+    }
+}
+
+impl Theme {
+    fn from_config(cfg: &ThemeConfig) -> Self {
+        let d = Theme::default();
+        let pick = |spec: &Option<String>, fallback: Style| {
+            spec.as_deref().map(Style::from_dotted_str).unwrap_or(fallback)
+        };
+        Theme {
+            header: pick(&cfg.header, d.header),
+            new_badge: pick(&cfg.new_badge, d.new_badge),
+            selection: pick(&cfg.selection, d.selection),
+            positive: pick(&cfg.positive, d.positive),
+            negative: pick(&cfg.negative, d.negative),
+            feeds: cfg
+                .feeds
+                .iter()
+                .map(|(name, spec)| (name.clone(), Style::from_dotted_str(spec)))
+                .collect(),
+        }
+    }
+
+    /// Section header style for a feed: its accent if configured, else the general header style.
+    pub fn feed_header<D>(&self, feed: &str, text: D) -> StyledObject<D> {
+        self.feeds.get(feed).unwrap_or(&self.header).apply_to(text)
+    }
+
+    pub fn new_badge(&self) -> StyledObject<&'static str> {
+        self.new_badge.apply_to("[NEW]")
+    }
+}
+
+// Leaked so screens can hold on to it; a new one only comes with a config reload
+static THEME: RwLock<Option<&'static Theme>> = RwLock::new(None);
+
+/// Turn colors off when NO_COLOR is set (https://no-color.org). Call before any output.
+pub fn honor_no_color() {
+    if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        console::set_colors_enabled(false);
+        console::set_colors_enabled_stderr(false);
+    }
+}
+
+/// Use `cfg`'s styles from now on; called at startup and again when the config is reloaded.
+pub fn init(cfg: &ThemeConfig) {
+    if cfg.no_color {
+        console::set_colors_enabled(false);
+        console::set_colors_enabled_stderr(false);
+    }
+    *THEME.write().unwrap() = Some(Box::leak(Box::new(Theme::from_config(cfg))));
+}
+
+pub fn current() -> &'static Theme {
+    if let Some(theme) = *THEME.read().unwrap() {
+        return theme;
+    }
+    THEME.write().unwrap().get_or_insert_with(|| Box::leak(Box::new(Theme::default())))
+}
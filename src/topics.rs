@@ -0,0 +1,96 @@
+//! Grouping stories about the same event across feeds ("5 stories about the ECB decision"),
+//! by how many title words they share.
+use std::collections::BTreeSet;
+
+/// Titles sharing at least this fraction of the shorter one's words are about the same thing...
+pub const SIMILARITY: f64 = 0.5;
+/// ...if that is at least this many words ("Apple shares fall", "Tesla shares fall" aren't).
+pub const MIN_SHARED: usize = 3;
+
+// Too common to say anything about a story
+const STOPWORDS: &[&str] = &[
+    "a", "about", "after", "all", "an", "and", "are", "as", "at", "be", "by", "can", "for", "from", "has", "have",
+    "how", "in", "into", "is", "it", "its", "new", "not", "of", "on", "or", "over", "says", "than", "that", "the",
+    "this", "to", "up", "was", "what", "when", "who", "why", "will", "with", "der", "die", "das", "und", "den",
+    "dem", "ein", "eine", "im", "ist", "mit", "von", "zu", "auf", "für", "nach",
+];
+
+/// The words of a title that identify its topic: lowercase, without punctuation, stopwords
+/// or a plural "s" ("rates" and "rate" match).
+pub fn words(title: &str) -> BTreeSet<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .map(|w| w.to_lowercase())
+        .filter(|w| !w.is_empty() && !STOPWORDS.contains(&w.as_str()))
+        .map(|w| match w.strip_suffix('s') {
+            Some(stem) if stem.chars().count() > 2 && !stem.ends_with('s') => stem.to_string(),
+            _ => w,
+        })
+        .collect()
+}
+
+/// Shared words over the words of the smaller set; 0 when either is empty.
+pub fn similarity(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    let smaller = a.len().min(b.len());
+    if smaller == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / smaller as f64
+}
+
+/// Groups of indices into `titles`, each in input order, ordered by their first member. A
+/// title joins a group when it shares [`MIN_SHARED`] words and [`SIMILARITY`] with any title
+/// already in it; titles like nothing else are groups of one.
+pub fn cluster(titles: &[&str]) -> Vec<Vec<usize>> {
+    let words: Vec<BTreeSet<String>> = titles.iter().map(|t| words(t)).collect();
+    let alike = |a: usize, b: usize| {
+        words[a].intersection(&words[b]).count() >= MIN_SHARED && similarity(&words[a], &words[b]) >= SIMILARITY
+    };
+    // Union-find over the pairs that are alike
+    let mut parent: Vec<usize> = (0..titles.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    for a in 0..titles.len() {
+        for b in a + 1..titles.len() {
+            if alike(a, b) {
+                let (ra, rb) = (root(&mut parent, a), root(&mut parent, b));
+                // The earlier story stays the root, so groups sort by their first member
+                parent[ra.max(rb)] = ra.min(rb);
+            }
+        }
+    }
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut group_of: Vec<Option<usize>> = vec![None; titles.len()];
+    for i in 0..titles.len() {
+        let r = root(&mut parent, i);
+        match group_of[r] {
+            Some(g) => groups[g].push(i),
+            None => {
+                group_of[r] = Some(groups.len());
+                groups.push(vec![i]);
+            }
+        }
+    }
+    groups
+}
+
+/// A short name for a group: the words of the first title that more than half of the titles
+/// share, in its order ("ECB decision"); the whole first title when there are none.
+pub fn label(titles: &[&str]) -> String {
+    let Some(first) = titles.first() else { return String::new() };
+    let sets: Vec<BTreeSet<String>> = titles.iter().map(|t| words(t)).collect();
+    let shared: Vec<&str> = first
+        .split_whitespace()
+        .filter(|w| {
+            let Some(key) = words(w).into_iter().next() else { return false };
+            sets.iter().filter(|s| s.contains(&key)).count() * 2 > titles.len()
+        })
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()))
+        .collect();
+    if shared.is_empty() { first.to_string() } else { shared.join(" ") }
+}
@@ -0,0 +1,211 @@
+//! Language detection for stories and optional machine translation of their titles and
+//! summaries through DeepL or a LibreTranslate instance (`[translation]` in config.toml).
+use crate::config::TranslationConfig;
+use crate::news::{Story, Translation};
+use crate::util::atomic::write_atomic;
+use crate::util::sanitize::html_to_text;
+use anyhow::{bail, Context, Result};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Duration;
+use std::{fs, path::PathBuf};
+
+const DEEPL_URL: &str = "https://api-free.deepl.com";
+// Summaries can be whole articles; only the start is worth paying to translate
+const MAX_SUMMARY_CHARS: usize = 1000;
+// DeepL takes at most 50 texts per request
+const BATCH: usize = 50;
+
+// whatlang's ISO 639-3 codes for the languages translation services commonly support
+const CODES: &[(&str, &str)] = &[
+    ("ara", "ar"), ("bul", "bg"), ("ces", "cs"), ("cmn", "zh"), ("dan", "da"), ("deu", "de"),
+    ("ell", "el"), ("eng", "en"), ("est", "et"), ("fin", "fi"), ("fra", "fr"), ("heb", "he"),
+    ("hin", "hi"), ("hun", "hu"), ("ind", "id"), ("ita", "it"), ("jpn", "ja"), ("kor", "ko"),
+    ("lav", "lv"), ("lit", "lt"), ("nld", "nl"), ("nob", "nb"), ("pol", "pl"), ("por", "pt"),
+    ("ron", "ro"), ("rus", "ru"), ("slk", "sk"), ("slv", "sl"), ("spa", "es"), ("swe", "sv"),
+    ("tur", "tr"), ("ukr", "uk"), ("vie", "vi"),
+];
+
+/// Two-letter code of the language `text` is written in, if whatlang is confident.
+pub fn detect(text: &str) -> Option<&'static str> {
+    let info = whatlang::detect(text).filter(|i| i.is_reliable())?;
+    CODES.iter().find(|(long, _)| *long == info.lang().code()).map(|(_, short)| *short)
+}
+
+/// Set `language` on every story from its title and summary.
+pub fn detect_all(stories: &mut [Story]) {
+    for s in stories {
+        let summary = s.summary.as_deref().map(html_to_text).unwrap_or_default();
+        s.language = detect(&format!("{} {}", s.title, summary)).map(String::from);
+    }
+}
+
+/// Translate the stories whose detected language `cfg` selects, filling `translation`.
+/// Earlier translations are reused from `translations.json` in the cache directory, so
+/// each story is sent once.
+pub async fn apply(cfg: &TranslationConfig, stories: &mut [Story]) -> Result<()> {
+    let mut cache = Cache::load();
+    let mut todo: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (i, s) in stories.iter_mut().enumerate() {
+        let Some(lang) = s.language.clone() else { continue };
+        if lang == cfg.target() || (!cfg.languages.is_empty() && !cfg.languages.contains(&lang)) {
+            continue;
+        }
+        match cache.get(&s.link, cfg.target(), &s.title) {
+            Some(t) => s.translation = Some(t),
+            None => todo.entry(lang).or_default().push(i),
+        }
+    }
+    let client = Client::builder()
+        .user_agent("news-cli/0.1")
+        .connect_timeout(Duration::from_secs(5))
+        .timeout(Duration::from_secs(60))
+        .build()?;
+    let mut result = Ok(());
+    'langs: for (lang, indices) in &todo {
+        for chunk in indices.chunks(BATCH / 2) {
+            // Title and summary of each story, in order
+            let mut texts = Vec::new();
+            for &i in chunk {
+                texts.push(stories[i].title.clone());
+                texts.push(summary_text(&stories[i]).unwrap_or_default());
+            }
+            let translated = match request(&client, cfg, lang, &texts).await {
+                Ok(t) => t,
+                Err(e) => {
+                    // Keep what was translated so far
+                    result = Err(e);
+                    break 'langs;
+                }
+            };
+            for (&i, pair) in chunk.iter().zip(translated.chunks(2)) {
+                let t = Translation {
+                    title: pair[0].trim().to_string(),
+                    summary: pair.get(1).map(|s| s.trim().to_string()).filter(|s| !s.is_empty()),
+                };
+                cache.insert(&stories[i], cfg.target(), t.clone());
+                stories[i].translation = Some(t);
+            }
+        }
+    }
+    let links: HashSet<&str> = stories.iter().map(|s| s.link.as_str()).collect();
+    cache.entries.retain(|link, _| links.contains(link.as_str()));
+    cache.save()?;
+    result
+}
+
+fn summary_text(story: &Story) -> Option<String> {
+    let text = html_to_text(story.summary.as_deref()?);
+    match text.char_indices().nth(MAX_SUMMARY_CHARS) {
+        Some((i, _)) => Some(format!("{}…", &text[..i])),
+        None => Some(text),
+    }
+}
+
+#[derive(Deserialize)]
+struct DeeplResponse {
+    translations: Vec<DeeplText>,
+}
+
+#[derive(Deserialize)]
+struct DeeplText {
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct LibreResponse {
+    #[serde(rename = "translatedText")]
+    translated_text: Vec<String>,
+}
+
+async fn request(client: &Client, cfg: &TranslationConfig, source: &str, texts: &[String]) -> Result<Vec<String>> {
+    let api_key = cfg
+        .api_key
+        .clone()
+        .filter(|k| !k.is_empty())
+        .or_else(|| std::env::var("NEWS_CLI_TRANSLATE_KEY").ok().filter(|k| !k.is_empty()));
+    let base = cfg.url.as_deref().unwrap_or(DEEPL_URL).trim_end_matches('/');
+    let (url, req) = if cfg.backend == "deepl" {
+        let url = format!("{}/v2/translate", base);
+        let mut req = client.post(&url).json(&json!({
+            "text": texts,
+            "source_lang": source.to_uppercase(),
+            "target_lang": cfg.target().to_uppercase(),
+        }));
+        if let Some(key) = api_key {
+            req = req.header("Authorization", format!("DeepL-Auth-Key {}", key));
+        }
+        (url, req)
+    } else {
+        let url = format!("{}/translate", base);
+        let mut body = json!({ "q": texts, "source": source, "target": cfg.target(), "format": "text" });
+        if let Some(key) = api_key {
+            body["api_key"] = json!(key);
+        }
+        (url.clone(), client.post(&url).json(&body))
+    };
+    let resp = req.send().await.with_context(|| format!("failed to reach {}", url))?;
+    let status = resp.status();
+    if !status.is_success() {
+        let detail = resp.text().await.unwrap_or_default();
+        bail!("{} returned {}: {}", url, status, detail.trim().chars().take(300).collect::<String>());
+    }
+    let translated: Vec<String> = if cfg.backend == "deepl" {
+        let r: DeeplResponse = resp.json().await.context("unexpected response from DeepL")?;
+        r.translations.into_iter().map(|t| t.text).collect()
+    } else {
+        let r: LibreResponse = resp.json().await.context("unexpected response from LibreTranslate")?;
+        r.translated_text
+    };
+    if translated.len() != texts.len() {
+        bail!("{} returned {} translations for {} texts", url, translated.len(), texts.len());
+    }
+    Ok(translated)
+}
+
+#[derive(Serialize, Deserialize)]
+struct Cached {
+    target: String,
+    // The title that was translated; a changed title is translated again
+    source_title: String,
+    translation: Translation,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct Cache {
+    entries: HashMap<String, Cached>,
+}
+
+impl Cache {
+    fn load() -> Self {
+        cache_file_path()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(path) = cache_file_path() {
+            write_atomic(&path, serde_json::to_string(self)?.as_bytes(), false)?;
+        }
+        Ok(())
+    }
+
+    fn get(&self, link: &str, target: &str, title: &str) -> Option<Translation> {
+        self.entries
+            .get(link)
+            .filter(|c| c.target == target && c.source_title == title)
+            .map(|c| c.translation.clone())
+    }
+
+    fn insert(&mut self, story: &Story, target: &str, translation: Translation) {
+        let cached = Cached { target: target.to_string(), source_title: story.title.clone(), translation };
+        self.entries.insert(story.link.clone(), cached);
+    }
+}
+
+fn cache_file_path() -> Option<PathBuf> {
+    crate::paths::cache_dir().map(|d| d.join("translations.json"))
+}
@@ -0,0 +1,99 @@
+use super::{detail, select_story, story_title, with_icon};
+use crate::ui::{prompt_index, MenuChoice};
+use anyhow::Result;
+use news_cli::alerts::AlertLog;
+use news_cli::config::RuntimeConfig;
+use news_cli::history::SharedHistory;
+use news_cli::tr;
+use news_cli::util::sanitize::sanitize_for_terminal;
+
+/// Main-menu entry, e.g. "Alerts (3 new)"; `None` when there is nothing to show.
+pub fn menu_label(cfg: &RuntimeConfig) -> Option<String> {
+    let log = AlertLog::load();
+    if cfg.alerts.is_empty() && log.hits().is_empty() {
+        return None;
+    }
+    Some(match log.unreviewed() {
+        0 => tr!("menu.alerts").to_string(),
+        n => tr!("menu.alerts_new", n),
+    })
+}
+
+/// Everything the configured alerts ever matched, one section per alert, newest first.
+/// Returns `true` if the user quit.
+pub fn show(cfg: &RuntimeConfig, history: &SharedHistory) -> Result<bool> {
+    let mut log = AlertLog::load();
+    log.mark_reviewed();
+    if let Err(e) = log.save() {
+        eprintln!("{}", tr!("alerts.save_failed", e));
+    }
+    let mut last: Option<usize> = None;
+    loop {
+        // Config order first, then rules that were removed but still have hits
+        let mut names: Vec<&str> = cfg.alerts.iter().map(|a| a.name.as_str()).collect();
+        for h in log.hits() {
+            if !names.contains(&h.alert.as_str()) {
+                names.push(&h.alert);
+            }
+        }
+        let theme = crate::theme::current();
+        let mut labels = Vec::new();
+        let mut header_indices = Vec::new();
+        // (alert, hit index) per label; headers have no hit
+        let mut index_map: Vec<(String, Option<usize>)> = Vec::new();
+        for name in names {
+            let mut hits: Vec<usize> = (0..log.hits().len()).filter(|&i| log.hits()[i].alert == name).collect();
+            hits.sort_by_key(|&i| std::cmp::Reverse(log.hits()[i].matched_at));
+            header_indices.push(labels.len());
+            labels.push(theme.feed_header(name, tr!("alerts.header", sanitize_for_terminal(name), hits.len())).to_string());
+            index_map.push((name.to_string(), None));
+            for i in hits {
+                let hit = &log.hits()[i];
+                let when = cfg.dates.format_as(hit.matched_at, "%Y-%m-%d").unwrap_or_default();
+                let title = story_title(cfg, &hit.story);
+                let source = with_icon(cfg, &hit.story.source, format!("[{}]", sanitize_for_terminal(&hit.story.source)));
+                labels.push(format!("  - {} {} ({})", source, title, when));
+                index_map.push((name.to_string(), Some(i)));
+            }
+        }
+        if labels.is_empty() {
+            return Ok(false);
+        }
+
+        match prompt_index(
+            "Alerts (b = back, q = quit). Everything that ever matched, newest first.",
+            &labels,
+            last,
+            cfg.header.as_deref(),
+            Some(&header_indices),
+            &[('i', "details"), ('c', "clear alert")],
+        )? {
+            MenuChoice::Back => return Ok(false),
+            MenuChoice::Quit => return Ok(true),
+            MenuChoice::Action('c', i) => {
+                log.clear(&index_map[i].0);
+                if let Err(e) = log.save() {
+                    eprintln!("{}", tr!("alerts.save_failed", e));
+                }
+                last = None;
+            }
+            MenuChoice::Action('i', i) => {
+                last = Some(i);
+                if let Some(hit) = index_map[i].1
+                    && detail::show(cfg, history, &log.hits()[hit].story)?
+                {
+                    return Ok(true);
+                }
+            }
+            MenuChoice::Index(i) => {
+                last = Some(i);
+                if let Some(hit) = index_map[i].1
+                    && select_story(cfg, history, &log.hits()[hit].story)?
+                {
+                    return Ok(true);
+                }
+            }
+            MenuChoice::Action(..) | MenuChoice::Refresh => {}
+        }
+    }
+}
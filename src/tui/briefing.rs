@@ -0,0 +1,71 @@
+use super::detail::wrap;
+use super::spawn_fetch;
+use crate::app::AppContext;
+use crate::ui::{self, read_key_or_resize};
+use anyhow::Result;
+use console::{style, Key, Term};
+use news_cli::briefing;
+use news_cli::config::{BriefingConfig, RuntimeConfig};
+use news_cli::history::SharedHistory;
+use news_cli::news::Story;
+use news_cli::tr;
+use news_cli::util::sanitize::sanitize_for_terminal;
+
+/// Generate and show a briefing of the current top stories. Returns `true` if the user quit.
+pub async fn show(cfg: &RuntimeConfig, app: &AppContext, bcfg: &BriefingConfig, history: &SharedHistory) -> Result<bool> {
+    let term = Term::stdout();
+    loop {
+        let generating = tr!("briefing.generating", sanitize_for_terminal(&bcfg.model));
+        let Some(fetched) = spawn_fetch(cfg, app, history).wait(&generating).await? else { return Ok(true) };
+        let stories = briefing::select(bcfg, &fetched.stories);
+        let text = briefing::generate(bcfg, &cfg.fetch, &stories).await.unwrap_or_else(|e| tr!("briefing.failed", format!("{:#}", e)));
+        loop {
+            term.clear_screen()?;
+            render(cfg, &text, &stories, &term);
+            let Some(key) = read_key_or_resize(&term)? else { continue };
+            match key {
+                Key::Char('?') => ui::show_keys(
+                    tr!("menu.briefing"),
+                    &[
+                        (tr!("menu.briefing"), ui::key_rows(&[("r", tr!("briefing.key_regenerate"))])),
+                        ui::general_keys(),
+                    ],
+                )?,
+                Key::Char('r') | Key::Char('R') => break,
+                Key::Char('b') | Key::Char('B') | Key::Escape => return Ok(false),
+                Key::Char('q') | Key::Char('Q') | Key::CtrlC => return Ok(true),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn render(cfg: &RuntimeConfig, text: &str, stories: &[Story], term: &Term) {
+    let width = (term.size().1 as usize).clamp(20, 200);
+    if let Some(h) = cfg.header.as_deref() {
+        println!("{}", h);
+    }
+    if let Some(b) = ui::breadcrumb() {
+        println!("{}", b);
+    }
+    println!("{}", style(tr!("briefing.title", stories.len())).bold());
+    println!();
+    for line in text.lines() {
+        let line = sanitize_for_terminal(line);
+        // Keep bullet continuations indented under their text
+        let (indent, body) = match line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+            Some(rest) => ("  ", rest),
+            None => ("", line.as_str()),
+        };
+        if body.is_empty() {
+            println!();
+            continue;
+        }
+        for (i, wrapped) in wrap(body, width - indent.len()).into_iter().enumerate() {
+            let lead = if indent.is_empty() { "" } else if i == 0 { "- " } else { indent };
+            println!("{}{}", lead, wrapped);
+        }
+    }
+    println!();
+    println!("{}", tr!("briefing.footer"));
+}
@@ -0,0 +1,120 @@
+use crate::app::AppContext;
+use crate::ui::{self, read_key_or_resize};
+use anyhow::Result;
+use console::{style, Key, Term};
+use news_cli::calendar::{self, Event, Impact};
+use news_cli::config::{CalendarConfig, RuntimeConfig};
+use news_cli::tr;
+use news_cli::util::sanitize::sanitize_for_terminal;
+use news_cli::util::width::truncate_to_width;
+
+/// Upcoming economic releases for the configured countries, by day. Returns `true` if the
+/// user quit.
+pub async fn show(cfg: &RuntimeConfig, app: &AppContext, ccfg: &CalendarConfig) -> Result<bool> {
+    let term = Term::stdout();
+    loop {
+        term.clear_screen()?;
+        println!("{}", tr!("calendar.loading"));
+        let lines = match calendar::fetch(app.client(), ccfg).await {
+            Ok(events) if events.is_empty() => vec![tr!("calendar.empty").to_string()],
+            Ok(events) => lines(cfg, &events),
+            Err(e) => vec![tr!("calendar.failed", format!("{:#}", e))],
+        };
+        let mut top = 0;
+        loop {
+            term.clear_screen()?;
+            let page = render(cfg, &lines, top, &term);
+            let Some(key) = read_key_or_resize(&term)? else { continue };
+            let last = lines.len().saturating_sub(page);
+            match key {
+                Key::ArrowDown | Key::Char('j') => top = (top + 1).min(last),
+                Key::ArrowUp | Key::Char('k') => top = top.saturating_sub(1),
+                Key::PageDown | Key::Char(' ') => top = (top + page).min(last),
+                Key::PageUp => top = top.saturating_sub(page),
+                Key::Char('?') => ui::show_keys(
+                    "Calendar",
+                    &[
+                        (
+                            "Calendar",
+                            ui::key_rows(&[("↑ / ↓, j / k", "scroll"), ("PgUp / PgDn", "scroll a page"), ("r", "fetch again")]),
+                        ),
+                        ui::general_keys(),
+                    ],
+                )?,
+                Key::Char('r') | Key::Char('R') => break,
+                Key::Char('b') | Key::Char('B') | Key::Escape => return Ok(false),
+                Key::Char('q') | Key::Char('Q') | Key::CtrlC => return Ok(true),
+                _ => {}
+            }
+        }
+    }
+}
+
+// A bold heading per day, then a line per event (see `event_line`)
+fn lines(cfg: &RuntimeConfig, events: &[Event]) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut day: Option<String> = None;
+    for e in events {
+        let this_day = cfg.dates.format_as(e.time, "%a %Y-%m-%d");
+        if this_day != day {
+            if day.is_some() {
+                out.push(String::new());
+            }
+            out.push(style(this_day.as_deref().unwrap_or("")).bold().to_string());
+            day = this_day;
+        }
+        out.push(event_line(cfg, e));
+    }
+    out
+}
+
+/// "  08:30  USD  !!!  CPI m/m  (previous 0.4%, forecast 0.3%)"
+pub(super) fn event_line(cfg: &RuntimeConfig, e: &Event) -> String {
+    let theme = crate::theme::current();
+    let impact = match e.impact {
+        Impact::High => theme.negative.apply_to("!!!").to_string(),
+        Impact::Medium => "!! ".to_string(),
+        Impact::Low => "!  ".to_string(),
+    };
+    let mut figures = Vec::new();
+    if let Some(p) = &e.previous {
+        figures.push(tr!("calendar.previous", sanitize_for_terminal(p)));
+    }
+    if let Some(f) = &e.forecast {
+        figures.push(tr!("calendar.forecast", sanitize_for_terminal(f)));
+    }
+    let figures = if figures.is_empty() { String::new() } else { format!("  ({})", figures.join(", ")) };
+    format!(
+        "  {}  {}  {}  {}{}",
+        cfg.dates.format_as(e.time, "%H:%M").unwrap_or_default(),
+        sanitize_for_terminal(&e.country),
+        impact,
+        sanitize_for_terminal(&e.title),
+        figures
+    )
+}
+
+// Draws the lines from `top` that fit; returns how many did
+fn render(cfg: &RuntimeConfig, lines: &[String], top: usize, term: &Term) -> usize {
+    let (rows, cols) = term.size();
+    let width = (cols as usize).clamp(20, 200);
+    let mut head: Vec<String> = Vec::new();
+    if let Some(h) = cfg.header.as_deref() {
+    head.push(h.to_string());
+    }
+    head.extend(ui::breadcrumb());
+    head.push(style(tr!("calendar.title")).bold().to_string());
+    head.push(String::new());
+    // A blank line and the footer below
+    let page = (rows as usize).saturating_sub(head.len() + 2).max(1);
+    let mut out = head;
+    out.extend(lines.iter().skip(top).take(page).map(|l| truncate_to_width(l, width)));
+    out.push(String::new());
+    let more = lines.len().saturating_sub(top + page);
+    let footer = if more > 0 { tr!("calendar.footer_more", more) } else { tr!("calendar.footer").to_string() };
+    out.push(footer);
+    // No newline after the last line: on a full screen it would scroll the first one away
+    print!("{}", out.join("\n"));
+    let _ = std::io::Write::flush(&mut std::io::stdout());
+    page
+}
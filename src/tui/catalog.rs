@@ -0,0 +1,152 @@
+use crate::ui::{self, prompt_index, read_key_or_resize, MenuChoice};
+use anyhow::Result;
+use console::{style, Key, Term};
+use news_cli::catalog::{self, Category, Entry};
+use news_cli::config::edit::ConfigFile;
+use news_cli::config::{Feed, RuntimeConfig};
+use news_cli::news::provider::{FetchContext, ProviderRegistry};
+use news_cli::news::Story;
+use news_cli::tr;
+use news_cli::util::sanitize::{html_to_text, sanitize_to_width};
+use news_cli::util::width::isolate;
+use std::path::Path;
+
+/// Main-menu entry; `None` without a config.toml to add feeds to.
+pub fn menu_label(cfg: &RuntimeConfig) -> Option<String> {
+    cfg.path.as_ref().map(|_| tr!("menu.catalog").to_string())
+}
+
+/// The built-in catalog by category: Enter previews a feed's latest headlines, 'a' adds it
+/// to config.toml (the News screen picks it up when the file is reloaded). Returns `true`
+/// if the user quit.
+pub async fn show(cfg: &RuntimeConfig) -> Result<bool> {
+    let Some(path) = cfg.path.clone() else { return Ok(false) };
+    let mut last: Option<usize> = None;
+    let mut status = String::new();
+    // Added from here; `cfg` only learns of them when the main menu reloads it
+    let mut added: Vec<&str> = Vec::new();
+    loop {
+        let theme = crate::theme::current();
+        let mut labels = Vec::new();
+        let mut header_indices = Vec::new();
+        let mut entries: Vec<Option<&Entry>> = Vec::new();
+        for category in Category::ALL {
+            header_indices.push(labels.len());
+            labels.push(theme.feed_header("", format!("== {} ==", label(category))).to_string());
+            entries.push(None);
+            for entry in catalog::in_category(category) {
+                let mark = match configured(cfg, &added, entry) {
+                    true => format!(" {}", style(tr!("catalog.in_config")).dim()),
+                    false => String::new(),
+                };
+                labels.push(format!("  {}{}  {}", entry.name, mark, style(entry.about).dim()));
+                entries.push(Some(entry));
+            }
+        }
+        let prompt = match status.is_empty() {
+            true => tr!("catalog.prompt").to_string(),
+            false => format!("{} {}", tr!("catalog.prompt"), status),
+        };
+        let actions = [('a', tr!("catalog.add"))];
+        let choice = prompt_index(&prompt, &labels, last, cfg.header.as_deref(), Some(&header_indices), &actions)?;
+        if !matches!(choice, MenuChoice::Refresh) {
+            status.clear();
+        }
+        let (i, preview) = match choice {
+            MenuChoice::Back => return Ok(false),
+            MenuChoice::Quit => return Ok(true),
+            MenuChoice::Index(i) => (i, true),
+            MenuChoice::Action('a', i) => (i, false),
+            MenuChoice::Action(..) | MenuChoice::Refresh => continue,
+        };
+        last = Some(i);
+        let Some(entry) = entries[i] else { continue };
+        if preview {
+            match show_preview(cfg, entry).await? {
+                Preview::Back => continue,
+                Preview::Quit => return Ok(true),
+                Preview::Add => {}
+            }
+        }
+        status = match configured(cfg, &added, entry) {
+            true => tr!("catalog.already", entry.name),
+            false => match add(&path, entry) {
+                Ok(()) => {
+                    added.push(entry.url);
+                    tr!("catalog.added", entry.name)
+                }
+                Err(e) => tr!("catalog.failed", e),
+            },
+        };
+    }
+}
+
+fn label(category: Category) -> &'static str {
+    match category {
+        Category::World => tr!("catalog.world"),
+        Category::Tech => tr!("catalog.tech"),
+        Category::Science => tr!("catalog.science"),
+        Category::Finance => tr!("catalog.finance"),
+    }
+}
+
+fn configured(cfg: &RuntimeConfig, added: &[&str], entry: &Entry) -> bool {
+    added.contains(&entry.url) || cfg.feeds.iter().any(|f| f.url.trim().trim_end_matches('/') == entry.url.trim_end_matches('/'))
+}
+
+fn add(path: &Path, entry: &Entry) -> news_cli::error::Result<()> {
+    let mut file = ConfigFile::open(path)?;
+    file.add_feed(entry.name, entry.url)?;
+    file.save()
+}
+
+enum Preview {
+    Back,
+    Quit,
+    Add,
+}
+
+// The feed's latest headlines, fetched now; 'a' adds it from here
+async fn show_preview(cfg: &RuntimeConfig, entry: &Entry) -> Result<Preview> {
+    let term = Term::stdout();
+    term.clear_screen()?;
+    println!("{}", tr!("catalog.loading", entry.name));
+    let feed = Feed { name: entry.name.into(), url: entry.url.into(), ..Feed::default() };
+    let lines = match fetch(cfg, &feed).await {
+        Ok(stories) if stories.is_empty() => vec![tr!("catalog.preview_empty").to_string()],
+        Ok(mut stories) => {
+            stories.sort_by_key(|s| std::cmp::Reverse(s.published));
+            let cols = term.size().1 as usize;
+            stories.iter().map(|s| format!("  - {}", isolate(&sanitize_to_width(&html_to_text(&s.title), cols.saturating_sub(4))))).collect()
+        }
+        Err(e) => vec![tr!("catalog.preview_failed", entry.name, format!("{:#}", e))],
+    };
+    loop {
+        term.clear_screen()?;
+        let rows = term.size().0 as usize;
+        println!("{}", style(entry.name).bold());
+        println!("{}", style(format!("{} · {}", entry.about, entry.url)).dim());
+        println!();
+        for line in lines.iter().take(rows.saturating_sub(5)) {
+            println!("{}", line);
+        }
+        println!();
+        println!("{}", tr!("catalog.preview_footer"));
+        let Some(key) = read_key_or_resize(&term)? else { continue };
+        match key {
+            Key::Char('a') | Key::Char('A') | Key::Enter => return Ok(Preview::Add),
+            Key::Char('?') => ui::show_keys(
+                "Feed catalog",
+                &[("Preview", ui::key_rows(&[("a / Enter", "add to config.toml"), ("b / Esc", "back to the catalog")])), ui::general_keys()],
+            )?,
+            Key::Char('b') | Key::Char('B') | Key::Escape => return Ok(Preview::Back),
+            Key::Char('q') | Key::Char('Q') | Key::CtrlC => return Ok(Preview::Quit),
+            _ => {}
+        }
+    }
+}
+
+async fn fetch(cfg: &RuntimeConfig, feed: &Feed) -> Result<Vec<Story>> {
+    let ctx = FetchContext::new(cfg.fetch.clone())?.with_data_saver(cfg.data_saver);
+    ProviderRegistry::default().fetch(&ctx, feed).await
+}
@@ -0,0 +1,188 @@
+use super::{keep, spawn_fetch, detail, list_title, mark_stories_read, select_story, with_icon};
+use crate::app::AppContext;
+use crate::ui::{self, read_key_or_resize};
+use anyhow::Result;
+use console::{style, Key, Term};
+use news_cli::bookmarks::Bookmarks;
+use news_cli::config::RuntimeConfig;
+use news_cli::history::SharedHistory;
+use news_cli::mute::MutedTopics;
+use news_cli::news::Story;
+use news_cli::tr;
+use news_cli::reading::keywords;
+use news_cli::util::sanitize::{html_to_text, sanitize_for_terminal};
+use news_cli::util::width::truncate_to_width;
+use std::collections::VecDeque;
+
+/// Unread stories one at a time, newest first, each dealt with by a single key: open,
+/// bookmark, skip or mute its topic. Everything dealt with is marked seen, so the next
+/// catch-up starts where this one stopped. Returns `true` if the user quit.
+pub async fn show(cfg: &RuntimeConfig, app: &AppContext, history: &SharedHistory) -> Result<bool> {
+    let term = Term::stdout();
+    let Some(fetched) = spawn_fetch(cfg, app, history).wait(tr!("news.fetching")).await? else { return Ok(true) };
+    let (stories, _) = keep(cfg, history, fetched);
+    let mut muted = MutedTopics::load();
+    let mut unread: Vec<Story> = stories.into_iter().filter(|s| s.is_new && !muted.matches(s)).collect();
+    unread.sort_by_key(|s| std::cmp::Reverse(s.published));
+    let mut queue = VecDeque::from(unread);
+    let mut total = queue.len();
+    let mut bookmarks = Bookmarks::load();
+    let mut status = String::new();
+    loop {
+        let Some(mut story) = queue.front().cloned() else {
+            return caught_up(cfg, &term, &status);
+        };
+        let position = tr!("catchup.position", total - queue.len() + 1, total);
+        term.clear_screen()?;
+        let mut foot = vec![tr!("catchup.footer").to_string()];
+        if !status.is_empty() {
+            foot.push(style(&status).dim().to_string());
+        }
+        render(cfg, &story, &position, &foot, &term);
+        status.clear();
+        let Some(key) = read_key_or_resize(&term)? else { continue };
+        match key {
+            Key::Char('o') | Key::Char('O') => {
+                queue.pop_front();
+                if select_story(cfg, history, &story)? {
+                    return Ok(true);
+                }
+            }
+            Key::Char('b') | Key::Char('B') => {
+                queue.pop_front();
+                if bookmarks.add(&story) {
+                    super::sync::push_starred(&story.link, true);
+                }
+                mark_stories_read(history, [&mut story]);
+                status = match bookmarks.save() {
+                    Err(e) => tr!("bookmarks.save_failed", e),
+                    Ok(()) => tr!("bookmarks.added").into(),
+                };
+            }
+            Key::Char('s') | Key::Char('S') | Key::Char(' ') => {
+                queue.pop_front();
+                mark_stories_read(history, [&mut story]);
+            }
+            Key::Char('m') | Key::Char('M') => {
+                let Some(topic) = pick_topic(cfg, &story, &position, &term)? else { continue };
+                muted.add(&topic);
+                if let Err(e) = muted.save() {
+                    status = tr!("catchup.mute_failed", e);
+                    continue;
+                }
+                let (mut gone, kept): (Vec<Story>, Vec<Story>) = queue.drain(..).partition(|s| muted.matches(s));
+                queue.extend(kept);
+                total -= gone.len();
+                mark_stories_read(history, gone.iter_mut());
+                status = tr!("catchup.muted", topic, gone.len());
+            }
+            Key::Char('i') | Key::Char('I') | Key::Enter if detail::show(cfg, history, &story)? => return Ok(true),
+            Key::Char('?') => ui::show_keys(tr!("menu.catch_up"), &keys())?,
+            Key::Escape => return Ok(false),
+            Key::Char('q') | Key::Char('Q') | Key::CtrlC => return Ok(true),
+            _ => {}
+        }
+    }
+}
+
+fn keys() -> Vec<ui::KeyGroup> {
+    vec![
+        (
+            tr!("menu.catch_up"),
+            ui::key_rows(&[
+                ("o", tr!("catchup.key_open")),
+                ("b", tr!("catchup.key_bookmark")),
+                ("s / Space", tr!("catchup.key_skip")),
+                ("m", tr!("catchup.key_mute")),
+                ("i / Enter", tr!("catchup.key_details")),
+                ("Esc", tr!("catchup.key_back")),
+            ]),
+        ),
+        ui::general_keys(),
+    ]
+}
+
+// Offer the title's keywords as 1-9; the chosen one, or None if cancelled
+fn pick_topic(cfg: &RuntimeConfig, story: &Story, position: &str, term: &Term) -> Result<Option<String>> {
+    let mut words = keywords(&story.title);
+    words.truncate(9);
+    if words.is_empty() {
+        term.clear_screen()?;
+        render(cfg, story, position, &[tr!("catchup.nothing_to_mute").into()], term);
+        while read_key_or_resize(term)?.is_none() {}
+        return Ok(None);
+    }
+    let choices: Vec<String> = words.iter().enumerate().map(|(i, w)| format!("{} = {}", i + 1, w)).collect();
+    let prompt = tr!("catchup.mute_prompt", choices.join(", "));
+    loop {
+        term.clear_screen()?;
+        render(cfg, story, position, &[sanitize_for_terminal(&prompt)], term);
+        match read_key_or_resize(term)? {
+            Some(Key::Char(c)) if c.is_ascii_digit() => {
+                let n = c.to_digit(10).unwrap_or(0) as usize;
+                if let Some(word) = n.checked_sub(1).and_then(|i| words.get(i)) {
+                    return Ok(Some(word.clone()));
+                }
+            }
+            Some(Key::Escape | Key::Char('b') | Key::Char('B') | Key::CtrlC) => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+// Title, source and date, then as much of the summary as fits above `foot`
+fn render(cfg: &RuntimeConfig, story: &Story, position: &str, foot: &[String], term: &Term) {
+    let (rows, cols) = term.size();
+    let width = (cols as usize).clamp(20, 200);
+    let mut head: Vec<String> = Vec::new();
+    if let Some(h) = cfg.header.as_deref() {
+        head.push(h.to_string());
+    }
+    head.extend(ui::breadcrumb());
+    head.push(style(position).dim().to_string());
+    head.push(String::new());
+    for line in detail::wrap(&list_title(cfg, story), width) {
+        head.push(style(sanitize_for_terminal(&line)).bold().to_string());
+    }
+    let source = sanitize_for_terminal(&story.source);
+    let source = crate::theme::current().feed_header(&story.source, with_icon(cfg, &story.source, source));
+    let date = story.published.and_then(|ts| cfg.dates.format_with_zone(ts));
+    head.push(format!("{}, {}", source, date.as_deref().unwrap_or(tr!("catchup.undated"))));
+    head.push(String::new());
+
+    let summary = story.summary.as_deref().or(story.content.as_deref()).map(html_to_text).unwrap_or_default();
+    let mut body = detail::wrap(&sanitize_for_terminal(&summary), width);
+    // The footer may wrap once
+    let fit = (rows as usize).saturating_sub(head.len() + 2 + foot.len());
+    if body.len() > fit {
+        body.truncate(fit);
+        if let Some(last) = body.last_mut() {
+            *last = truncate_to_width(&format!("{} …", last), width);
+        }
+    }
+    let mut lines: Vec<String> = head;
+    lines.extend(body);
+    lines.push(String::new());
+    lines.extend(foot.iter().cloned());
+    // No newline after the last line: on a full screen it would scroll the first one away
+    print!("{}", lines.join("\n"));
+    let _ = std::io::Write::flush(&mut std::io::stdout());
+}
+
+fn caught_up(cfg: &RuntimeConfig, term: &Term, status: &str) -> Result<bool> {
+    term.clear_screen()?;
+    if let Some(h) = cfg.header.as_deref() {
+        term.write_line(h)?;
+    }
+    if !status.is_empty() {
+        term.write_line(&style(status).dim().to_string())?;
+    }
+    term.write_line(tr!("catchup.done"))?;
+    loop {
+        match read_key_or_resize(term)? {
+            Some(Key::Char('q') | Key::Char('Q') | Key::CtrlC) => return Ok(true),
+            Some(_) => return Ok(false),
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! `confirm_open`: where a link leads, and a yes/no before handing an unusual one to the
+//! browser.
+use crate::ui::{self, read_key_or_resize};
+use anyhow::Result;
+use console::{style, Key, Term};
+use news_cli::config::{ConfirmOpen, RuntimeConfig};
+use news_cli::linkcheck::{self, Checked};
+use news_cli::tr;
+use news_cli::util::sanitize::sanitize_for_terminal;
+use news_cli::util::width::truncate_to_width;
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+/// Whether `link` may be opened: always with confirm_open = "off"; with "unusual" once its
+/// redirects are followed and nothing about it stands out, otherwise (and always with
+/// "always") when the user says so. Links that redirect away from http(s) or too often are
+/// never opened.
+pub(super) fn confirm_open(cfg: &RuntimeConfig, link: &str) -> Result<bool> {
+    if cfg.confirm_open == ConfirmOpen::Off {
+        return Ok(true);
+    }
+    // open_url refuses what doesn't parse, and says why
+    let Ok(url) = Url::parse(link) else { return Ok(true) };
+    let term = Term::stdout();
+    let Some(checked) = check(cfg, &term, &url)? else { return Ok(false) };
+    if checked.refused.is_none() && checked.warnings.is_empty() && cfg.confirm_open == ConfirmOpen::Unusual {
+        return Ok(true);
+    }
+    loop {
+        render(&term, &checked)?;
+        let Some(key) = read_key_or_resize(&term)? else { continue };
+        if checked.refused.is_some() {
+            return Ok(false);
+        }
+        match key {
+            Key::Enter | Key::Char('y') | Key::Char('Y') => return Ok(true),
+            Key::Char('n') | Key::Char('N') | Key::Escape | Key::CtrlC => return Ok(false),
+            _ => {}
+        }
+    }
+}
+
+// Follow the redirects in the background with a spinner; None if the user gave up waiting
+fn check(cfg: &RuntimeConfig, term: &Term, url: &Url) -> Result<Option<Checked>> {
+    let result = Arc::new(Mutex::new(None));
+    let shared = result.clone();
+    let limits = cfg.fetch.clone();
+    let url = url.clone();
+    tokio::spawn(async move {
+        let checked = linkcheck::check(&limits, &url).await;
+        *shared.lock().unwrap() = Some(checked);
+    });
+    loop {
+        if let Some(checked) = result.lock().unwrap().take() {
+            return Ok(Some(checked));
+        }
+        term.clear_line()?;
+        term.write_str(&format!("{} {}", ui::spinner(), tr!("open.checking")))?;
+        if let Some(Key::Escape | Key::CtrlC) = ui::read_key_until(term, || result.lock().unwrap().is_some())? {
+            term.clear_line()?;
+            return Ok(None);
+        }
+    }
+}
+
+fn render(term: &Term, checked: &Checked) -> Result<()> {
+    let cols = term.size().1 as usize;
+    term.clear_screen()?;
+    term.write_line(&style(tr!("open.title")).bold().to_string())?;
+    term.write_line("")?;
+    let link = sanitize_for_terminal(checked.hops[0].as_str());
+    term.write_line(&format!("  {}", truncate_to_width(&link, cols.saturating_sub(2))))?;
+    if checked.redirects() > 0 {
+        let destination = sanitize_for_terminal(checked.destination().as_str());
+        let leads_to = tr!("open.leads_to", truncate_to_width(&destination, cols / 2), checked.redirects());
+        term.write_line(&format!("  {}", style(leads_to).dim()))?;
+    }
+    term.write_line("")?;
+    for warning in &checked.warnings {
+        term.write_line(&style(format!("⚠ {}", sanitize_for_terminal(warning))).yellow().to_string())?;
+    }
+    if let Some(reason) = &checked.refused {
+        term.write_line(&style(tr!("open.refused", reason)).red().to_string())?;
+        term.write_line("")?;
+        term.write_line(tr!("open.any_key"))?;
+    } else {
+        term.write_line("")?;
+        term.write_line(tr!("open.keys"))?;
+    }
+    Ok(())
+}
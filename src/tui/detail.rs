@@ -0,0 +1,397 @@
+use news_cli::news::{Story, Translation};
+use news_cli::bookmarks::Bookmarks;
+use news_cli::queue::Queue;
+use news_cli::tags::Tags;
+use crate::clipboard;
+use crate::open_url::open_url;
+use super::image::{self, Preview};
+use news_cli::config::{RuntimeConfig, TranslationConfig};
+use news_cli::history::SharedHistory;
+use crate::ui::{self, read_key_or_resize};
+use news_cli::util::html;
+use news_cli::util::sanitize::{html_to_text, sanitize_for_terminal};
+use news_cli::util::width::{display_width, truncate_to_width};
+use news_cli::tr;
+use anyhow::Result;
+use console::{style, Key, Term};
+use unicode_width::UnicodeWidthChar;
+
+/// Full-screen detail view for one story. Returns `true` if the user quit.
+pub(super) fn show(cfg: &RuntimeConfig, history: &SharedHistory, story: &Story) -> Result<bool> {
+    let term = Term::stdout();
+    let mut bookmarks = Bookmarks::load();
+    let mut tags = Tags::load();
+    let mut queue = Queue::load();
+    let mut status = String::new();
+    let mut scroll = 0;
+    // Stories cached before `data_saver` was turned on may still name an image
+    let images = if cfg.data_saver { Some("off") } else { cfg.images.as_deref() };
+    let mut preview = match (image::protocol(images, &term), story.image.as_deref()) {
+        (Some(protocol), Some(url)) if url.starts_with("http") => Some(Preview::start(cfg, protocol, url)),
+        _ => None,
+    };
+    loop {
+        if let Some(p) = &preview {
+            print!("{}", p.clear());
+        }
+        term.clear_screen()?;
+        let marks = Marks {
+            bookmarked: bookmarks.contains(&story.link),
+            tags: tags.of(&story.link),
+            note: tags.note(&story.link),
+            queued: queue.position(&story.link),
+        };
+        let (max_scroll, page) = render(cfg, story, &marks, &status, scroll, preview.as_mut(), &term);
+        scroll = scroll.min(max_scroll);
+        status.clear();
+        let Some(key) = read_key_or_resize(&term)? else { continue };
+        // Kitty images outlive the screen; none may be left behind when the view closes
+        if let Some(p) = &preview {
+            print!("{}", p.clear());
+        }
+        match key {
+            Key::ArrowDown | Key::Char('j') if scroll < max_scroll => scroll += 1,
+            Key::ArrowUp | Key::Char('k') => scroll = scroll.saturating_sub(1),
+            Key::Char(' ') | Key::PageDown => scroll = (scroll + page).min(max_scroll),
+            Key::PageUp => scroll = scroll.saturating_sub(page),
+            Key::Home => scroll = 0,
+            Key::End => scroll = max_scroll,
+            Key::Char('o') | Key::Char('O') | Key::Enter | Key::Char('a') | Key::Char('A')
+                if !super::has_web_link(story) =>
+            {
+                status = tr!("detail.no_web_page").into();
+            }
+            Key::Char('o') | Key::Char('O') | Key::Enter => {
+                status = match super::open_story(cfg, history, story) {
+                    Ok(true) => tr!("detail.opened").into(),
+                    Ok(false) => tr!("detail.not_opened").into(),
+                    Err(e) => tr!("open.failed", format!("{:#}", e)),
+                };
+            }
+            Key::Char('a') | Key::Char('A') => {
+                status = match open_url(cfg, &cfg.archive_link(&story.link)) {
+                    Ok(()) => {
+                        super::mark_read(cfg, history, story);
+                        tr!("detail.archive_opened").into()
+                    }
+                    Err(e) => tr!("detail.archive_failed", format!("{:#}", e)),
+                };
+            }
+            Key::Char('p') | Key::Char('P') if crate::player::is_video(&story.link) => {
+                status = match super::play_story(cfg, history, story) {
+                    Ok(()) => tr!("detail.playing").into(),
+                    Err(e) => format!("{:#}", e),
+                };
+            }
+            Key::Char('c') | Key::Char('C') => {
+                clipboard::copy(&story.link);
+                status = tr!("detail.link_copied").into();
+            }
+            Key::Char('s') | Key::Char('S') => {
+                let saved = bookmarks.toggle(story);
+                super::sync::push_starred(&story.link, saved);
+                status = match bookmarks.save() {
+                    Err(e) => tr!("bookmarks.save_failed", e),
+                    Ok(()) if saved => tr!("bookmarks.added").into(),
+                    Ok(()) => tr!("bookmarks.removed").into(),
+                };
+            }
+            Key::Char('l') | Key::Char('L') => {
+                status = super::queue::toggle(story);
+                queue = Queue::load();
+            }
+            Key::Char('e') | Key::Char('E') => status = super::export::story(cfg, story, tags.note(&story.link)),
+            Key::Char('n') | Key::Char('N') => {
+                println!();
+                status = super::tags::edit_note(story)?;
+                tags = Tags::load();
+            }
+            Key::Char('?') => ui::show_keys(tr!("detail.title"), &keys(story))?,
+            Key::Char('b') | Key::Char('B') | Key::Escape => return Ok(false),
+            Key::Char('q') | Key::Char('Q') | Key::CtrlC => return Ok(true),
+            _ => {}
+        }
+    }
+}
+
+fn keys(story: &Story) -> Vec<ui::KeyGroup> {
+    let mut actions = Vec::new();
+    if super::has_web_link(story) {
+        actions.push(("o / Enter", tr!("detail.key_open")));
+        actions.push(("a", tr!("detail.key_archive")));
+    }
+    if crate::player::is_video(&story.link) {
+        actions.push(("p", tr!("detail.key_play")));
+    }
+    actions.extend([
+        ("c", tr!("detail.key_copy")),
+        ("s", tr!("detail.key_bookmark")),
+        ("l", tr!("detail.key_queue")),
+        ("n", tr!("detail.key_note")),
+        ("e", tr!("detail.key_export")),
+    ]);
+    vec![
+        (
+            tr!("detail.scrolling"),
+            ui::key_rows(&[
+                ("↑ / ↓, k / j", tr!("detail.key_line")),
+                ("Space / PgDn, PgUp", tr!("detail.key_page")),
+                ("Home / End", tr!("detail.key_top_end")),
+            ]),
+        ),
+        (tr!("detail.title"), ui::key_rows(&actions)),
+        ui::general_keys(),
+    ]
+}
+
+/// What the user attached to the story, listed under its metadata.
+struct Marks<'a> {
+    bookmarked: bool,
+    tags: &'a [String],
+    note: Option<&'a str>,
+    /// Place in the reading queue (0 = next)
+    queued: Option<usize>,
+}
+
+/// Draw the view with the body scrolled down `scroll` lines; returns how far it can scroll
+/// and how many body lines fit on screen. A loaded lead image sits above the body and
+/// scrolls away with it; until then (or without one) the metadata lists its URL.
+fn render(
+    cfg: &RuntimeConfig,
+    story: &Story,
+    marks: &Marks,
+    status: &str,
+    scroll: usize,
+    mut preview: Option<&mut Preview>,
+    term: &Term,
+) -> (usize, usize) {
+    let (rows, cols) = term.size();
+    let width = (cols as usize).clamp(20, 200);
+    let mut head: Vec<String> = Vec::new();
+    if let Some(h) = cfg.header.as_deref() {
+        head.push(h.to_string());
+    }
+    head.extend(ui::breadcrumb());
+    let translation = story.translation.as_ref().zip(cfg.translation.as_ref());
+    let title = match translation {
+        Some((t, tc)) if tc.instead() => &t.title,
+        _ => &story.title,
+    };
+    for line in wrap(title, width) {
+        head.push(style(sanitize_for_terminal(&line)).bold().to_string());
+    }
+    if let Some((t, tc)) = translation
+        && !tc.instead()
+    {
+        for line in wrap(&format!("{}: {}", tc.target(), t.title), width) {
+            head.push(style(sanitize_for_terminal(&line)).dim().to_string());
+        }
+    }
+    head.push(String::new());
+    let source = sanitize_for_terminal(&story.source);
+    let paywalled = if cfg.is_paywalled(&story.source) { tr!("detail.paywalled") } else { "" };
+    let source = crate::theme::current().feed_header(&story.source, source);
+    head.push(format!("{}{}{}", label(tr!("detail.source")), source, paywalled));
+    if let Some(a) = &story.author {
+        head.push(format!("{}{}", label(tr!("detail.author")), sanitize_for_terminal(a)));
+    }
+    let date = story.published.and_then(|ts| cfg.dates.format_with_zone(ts));
+    head.push(format!("{}{}", label(tr!("detail.date")), date.as_deref().unwrap_or(tr!("detail.unknown_date"))));
+    if let Some(secs) = story.duration {
+        head.push(format!("{}{}", label(tr!("detail.length")), super::format_length(secs)));
+    }
+    head.push(format!("{}{}", label(tr!("detail.link")), sanitize_for_terminal(&story.link)));
+    if let Some((_, tc)) = translation
+        && tc.instead()
+    {
+        let original = tr!("detail.original", story.language.as_deref().unwrap_or("?"), story.title);
+        head.push(truncate_to_width(&sanitize_for_terminal(&original), width));
+    }
+    let image_box = preview.as_ref().and_then(|p| p.size(width as u16, (rows / 3).clamp(4, 16)));
+    if let Some(url) = &story.image
+        && image_box.is_none()
+    {
+        head.push(format!("{}{}", label(tr!("detail.image")), sanitize_for_terminal(url)));
+    }
+    if marks.bookmarked {
+        head.push(format!("{}{}", label(""), style(tr!("detail.bookmarked")).yellow()));
+    }
+    if let Some(place) = marks.queued {
+        head.push(format!("{}{}", label(""), style(tr!("detail.queued", place + 1)).yellow()));
+    }
+    if !marks.tags.is_empty() {
+        head.push(format!("{}{}", label(tr!("detail.tags")), sanitize_for_terminal(&marks.tags.join(", "))));
+    }
+    if let Some(note) = marks.note {
+        let lines = note.lines().flat_map(|l| wrap(l, width.saturating_sub(9).max(10)));
+        for (i, line) in lines.enumerate() {
+            let label = label(if i == 0 { tr!("detail.note") } else { "" });
+            head.push(format!("{}{}", label, sanitize_for_terminal(&line)));
+        }
+    }
+
+    let mut body = body_lines(story, translation, width);
+    if let Some((_, image_rows)) = image_box {
+        body.splice(0..0, std::iter::repeat_n(String::new(), usize::from(image_rows) + 1));
+    }
+    let bookmark_label = if marks.bookmarked { tr!("detail.unbookmark") } else { tr!("detail.bookmark") };
+    let open = if super::has_web_link(story) { tr!("detail.help_open") } else { "" };
+    let play = if crate::player::is_video(&story.link) { tr!("detail.help_play") } else { "" };
+    let keys_help = tr!("detail.help", open, play, bookmark_label);
+    let mut foot = vec![String::new()];
+    // Body lines that fit between the metadata and the key help and status as they wrap,
+    // with room for the scroll percentage the help may gain
+    let screen_cols = usize::from(cols).max(1);
+    let help_rows = display_width(&format!("{}{}", tr!("detail.scroll", 100), keys_help)).div_ceil(screen_cols);
+    let status_rows = display_width(status).div_ceil(screen_cols);
+    let fit = (rows as usize).saturating_sub(head.len() + 1 + help_rows + status_rows);
+    let (shown, max_scroll) = if body.len() <= fit {
+        (&body[..], 0)
+    } else {
+        let max_scroll = body.len() - fit.max(1);
+        let start = scroll.min(max_scroll);
+        let end = (start + fit.max(1)).min(body.len());
+        (&body[start..end], max_scroll)
+    };
+    let scroll_help = match (scroll.min(max_scroll) * 100).checked_div(max_scroll) {
+        Some(percent) => tr!("detail.scroll", percent),
+        None => String::new(),
+    };
+    foot.push(format!("{}{}", scroll_help, keys_help));
+    if !status.is_empty() {
+        foot.push(style(status).dim().to_string());
+    }
+    // No newline after the last line: on a full screen it would scroll the first one away
+    let lines: Vec<&str> = head.iter().chain(shown).chain(&foot).map(String::as_str).collect();
+    print!("{}", lines.join("\n"));
+    let _ = std::io::Write::flush(&mut std::io::stdout());
+    // Drawn only in full, so not once the body has scrolled
+    if let (Some(p), Some((cols, image_rows))) = (preview.as_mut(), image_box)
+        && scroll == 0
+    {
+        print!("\x1b7\x1b[{};1H{}\x1b8", head.len() + 2, p.escape(cols, image_rows));
+        let _ = std::io::Write::flush(&mut std::io::stdout());
+    }
+    (max_scroll, fit.max(1))
+}
+
+// `text` padded to line up the metadata values after it
+fn label(text: &str) -> String {
+    format!("{}{}", text, " ".repeat(9usize.saturating_sub(display_width(text)).max(1)))
+}
+
+// The article (full content when the feed has it, else the summary), each block after a blank line
+// A translated summary replaces the original with display = "instead" (full content stays
+// as it is) and otherwise follows it.
+fn body_lines(story: &Story, translation: Option<(&Translation, &TranslationConfig)>, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let translated = translation.and_then(|(t, tc)| Some((t.summary.as_deref()?, tc)));
+    let replace_summary = story.content.is_none() && translated.is_some_and(|(_, tc)| tc.instead());
+    if let Some(content) = &story.content {
+        let base = url::Url::parse(&story.link).ok();
+        for block in html::to_text(content, base.as_ref()).split("\n\n") {
+            lines.push(String::new());
+            for line in block.lines() {
+                // Sanitize word by word so preformatted spacing survives
+                let clean: Vec<String> = line.split(' ').map(sanitize_for_terminal).collect();
+                push_wrapped(&mut lines, &clean.join(" "), width);
+            }
+        }
+    } else if !replace_summary {
+        // Plain-text bodies (mail) keep their paragraphs; HTML comes out as one block
+        let summary = story.summary.as_deref().unwrap_or("").replace("\r\n", "\n");
+        for paragraph in summary.split("\n\n").map(html_to_text).filter(|p| !p.is_empty()) {
+            lines.push(String::new());
+            for line in wrap(&paragraph, width) {
+                lines.push(sanitize_for_terminal(&line));
+            }
+        }
+    }
+    if let Some((text, tc)) = translated {
+        lines.push(String::new());
+        if !replace_summary {
+            let lang = story.language.as_deref().unwrap_or("?");
+            lines.push(style(tr!("detail.translation", lang, tc.target())).dim().to_string());
+        }
+        for line in wrap(text, width) {
+            lines.push(sanitize_for_terminal(&line));
+        }
+    }
+    lines
+}
+
+// Wrap one line of rendered HTML, keeping quote/list prefixes as a hanging indent and
+// preformatted lines as they are
+fn push_wrapped(lines: &mut Vec<String>, line: &str, width: usize) {
+    if line.starts_with("    ") || line.starts_with(">     ") {
+        lines.push(truncate_to_width(line, width));
+        return;
+    }
+    let quote_len = line.len() - line.trim_start_matches("> ").len();
+    let (quote, rest) = line.split_at(quote_len);
+    let marker_len = list_marker_len(rest);
+    let (marker, text) = rest.split_at(marker_len);
+    let lead = display_width(quote) + display_width(marker);
+    for (i, wrapped) in wrap(text, width.saturating_sub(lead).max(10)).into_iter().enumerate() {
+        let prefix = if i == 0 { marker.to_string() } else { " ".repeat(marker.len()) };
+        lines.push(format!("{}{}{}", quote, prefix, wrapped));
+    }
+}
+
+// Length of a leading "- " or "12. " (after list indentation), else 0
+fn list_marker_len(s: &str) -> usize {
+    let indent = s.len() - s.trim_start_matches(' ').len();
+    let rest = &s[indent..];
+    if rest.starts_with("- ") {
+        return indent + 2;
+    }
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits > 0 && rest[digits..].starts_with(". ") {
+        return indent + digits + 2;
+    }
+    0
+}
+
+// Greedy word wrap by display width; words wider than the line are hard-split.
+pub(super) fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_width = 0;
+    for word in text.split_whitespace() {
+        let mut word_width = display_width(word);
+        let mut word = word.to_string();
+        while word_width > width {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0;
+            }
+            let mut head = String::new();
+            let mut head_width = 0;
+            for ch in word.chars() {
+                let w = ch.width().unwrap_or(0);
+                if head_width + w > width {
+                    break;
+                }
+                head.push(ch);
+                head_width += w;
+            }
+            word = word[head.len()..].to_string();
+            word_width -= head_width;
+            lines.push(head);
+        }
+        if !line.is_empty() && line_width + 1 + word_width > width {
+            lines.push(std::mem::take(&mut line));
+            line_width = 0;
+        }
+        if !line.is_empty() {
+            line.push(' ');
+            line_width += 1;
+        }
+        line.push_str(&word);
+        line_width += word_width;
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
@@ -0,0 +1,171 @@
+//! 'e' in the News list and the story view: the list or story as a self-contained HTML page
+//! for printing or keeping, in `export_dir`, plus a PDF of it when `pdf_command` is set.
+use crate::commands::serve::escape;
+use anyhow::{bail, Context, Result};
+use news_cli::config::RuntimeConfig;
+use news_cli::news::Story;
+use news_cli::tr;
+use news_cli::util::atomic::write_atomic;
+use news_cli::util::html;
+use news_cli::util::sanitize::{html_to_text, sanitize_for_terminal};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use time::OffsetDateTime;
+
+const STYLE: &str = "body{font-family:Georgia,serif;max-width:40em;margin:2em auto;padding:0 1em;line-height:1.5}\
+     h1{font-size:1.6em}h2{font-size:1.2em;border-bottom:1px solid #ccc;margin-top:1.5em}\
+     li{margin:.4em 0}small,.meta{color:#666;font-size:.9em}blockquote{border-left:3px solid #ccc;margin-left:0;padding-left:1em}\
+     pre{white-space:pre-wrap}img{max-width:100%}@media print{a{color:inherit;text-decoration:none}}";
+
+/// Save the list `sections` (each a source and its stories, as shown) under `title`; returns
+/// the message for the screen.
+pub(super) fn list(cfg: &RuntimeConfig, title: &str, sections: &[(&str, Vec<&Story>)]) -> String {
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    let when = cfg.dates.format_with_zone(now).unwrap_or_default();
+    let mut body = format!("<h1>{}</h1>\n<p class=\"meta\">{}</p>\n", escape(title), escape(&when));
+    for (source, stories) in sections {
+        body.push_str(&format!("<h2>{}</h2>\n<ul>\n", escape(source)));
+        for s in stories {
+            let meta: Vec<String> = [s.author.clone(), s.published.and_then(|ts| cfg.dates.format(ts))]
+                .into_iter()
+                .flatten()
+                .collect();
+            body.push_str(&format!(
+                "<li><a href=\"{}\">{}</a> <small>{}</small></li>\n",
+                escape(&s.link),
+                escape(&s.title),
+                escape(&meta.join(" · "))
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+    let stem = format!("news-{}", cfg.dates.format_as(now, "%Y-%m-%d-%H%M").unwrap_or_default());
+    saved(save(cfg, &stem, &page(title, &body)))
+}
+
+/// Save one story as the story view shows it: metadata, the user's note, the lead image and
+/// the article (or summary) with its translation. Returns the message for the screen.
+pub(super) fn story(cfg: &RuntimeConfig, story: &Story, note: Option<&str>) -> String {
+    let mut body = format!("<h1>{}</h1>\n<p class=\"meta\">", escape(&story.title));
+    let mut meta = vec![escape(&story.source)];
+    meta.extend(story.author.as_deref().map(escape));
+    meta.extend(story.published.and_then(|ts| cfg.dates.format_with_zone(ts)).map(|d| escape(&d)));
+    body.push_str(&meta.join(" · "));
+    body.push_str(&format!("<br><a href=\"{0}\">{0}</a></p>\n", escape(&story.link)));
+    if let Some(note) = note {
+        body.push_str(&format!("<blockquote>{}</blockquote>\n", lines_html(note)));
+    }
+    if let Some(url) = story.image.as_deref().filter(|u| u.starts_with("https://") || u.starts_with("http://")) {
+        body.push_str(&format!("<p><img src=\"{}\" alt=\"\"></p>\n", escape(url)));
+    }
+    // As plain text: the feed's markup (and any scripts in it) stays out of the page
+    if let Some(content) = &story.content {
+        let base = url::Url::parse(&story.link).ok();
+        for block in html::to_text(content, base.as_ref()).split("\n\n").filter(|b| !b.trim().is_empty()) {
+            match block.starts_with("    ") {
+                true => body.push_str(&format!("<pre>{}</pre>\n", escape(block))),
+                false => body.push_str(&format!("<p>{}</p>\n", lines_html(block))),
+            }
+        }
+    } else if let Some(summary) = &story.summary {
+        for paragraph in summary.replace("\r\n", "\n").split("\n\n").map(html_to_text).filter(|p| !p.is_empty()) {
+            body.push_str(&format!("<p>{}</p>\n", escape(&paragraph)));
+        }
+    }
+    if let (Some(t), Some(tc)) = (&story.translation, &cfg.translation) {
+        let lang = story.language.as_deref().unwrap_or("?");
+        body.push_str(&format!("<h2>{} → {}: {}</h2>\n", escape(lang), escape(tc.target()), escape(&t.title)));
+        if let Some(summary) = &t.summary {
+            body.push_str(&format!("<p>{}</p>\n", escape(summary)));
+        }
+    }
+    saved(save(cfg, &slug(&story.title), &page(&story.title, &body)))
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!doctype html>\n<html><head><meta charset=\"utf-8\"><title>{}</title><style>{}</style></head><body>\n{}</body></html>\n",
+        escape(title),
+        STYLE,
+        body
+    )
+}
+
+// Line breaks kept, for notes and quoted or listed lines of an article
+fn lines_html(text: &str) -> String {
+    text.lines().map(escape).collect::<Vec<_>>().join("<br>\n")
+}
+
+fn saved(result: Result<Vec<PathBuf>>) -> String {
+    match result {
+        Ok(files) => {
+            let names: Vec<String> = files.iter().map(|f| f.display().to_string()).collect();
+            tr!("export.saved", sanitize_for_terminal(&names.join(", ")))
+        }
+        Err(e) => tr!("export.failed", sanitize_for_terminal(&format!("{:#}", e))),
+    }
+}
+
+// Write `<stem>.html` to the export folder, and `<stem>.pdf` from it with `pdf_command`
+fn save(cfg: &RuntimeConfig, stem: &str, html: &str) -> Result<Vec<PathBuf>> {
+    let dir = export_dir(cfg);
+    std::fs::create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let html_path = dir.join(format!("{}.html", stem));
+    write_atomic(&html_path, html.as_bytes(), false).with_context(|| format!("failed to write {}", html_path.display()))?;
+    let mut files = vec![html_path.clone()];
+    if let Some(cmd) = cfg.pdf_command.as_deref() {
+        let pdf_path = dir.join(format!("{}.pdf", stem));
+        to_pdf(cmd, &html_path, &pdf_path)?;
+        files.push(pdf_path);
+    }
+    Ok(files)
+}
+
+fn export_dir(cfg: &RuntimeConfig) -> PathBuf {
+    if let Some(dir) = &cfg.export_dir {
+        return PathBuf::from(dir);
+    }
+    directories::UserDirs::new()
+        .and_then(|d| d.download_dir().map(Path::to_path_buf))
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+fn to_pdf(cmd: &str, html: &Path, pdf: &Path) -> Result<()> {
+    let (html, pdf) = (html.display().to_string(), pdf.display().to_string());
+    let mut parts = cmd.split_whitespace();
+    let Some(program) = parts.next() else { bail!("pdf_command is empty") };
+    let mut args: Vec<String> = parts.map(|p| p.replace("{html}", &html).replace("{pdf}", &pdf)).collect();
+    if !cmd.contains("{html}") && !cmd.contains("{pdf}") {
+        args.extend([html, pdf]);
+    }
+    let output = Command::new(program)
+        .args(&args)
+        .output()
+        .with_context(|| format!("failed to start {} (install it, or change pdf_command)", program))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().rfind(|l| !l.trim().is_empty()).unwrap_or("").trim().to_string();
+        bail!("{} failed ({}): {}", program, output.status, reason);
+    }
+    Ok(())
+}
+
+// "Central bank holds rates steady!" as "central-bank-holds-rates-steady"
+fn slug(title: &str) -> String {
+    let words: Vec<String> = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let mut slug = String::new();
+    for w in words {
+        if slug.chars().count() + w.chars().count() > 60 {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(&w);
+    }
+    if slug.is_empty() { "story".to_string() } else { slug }
+}
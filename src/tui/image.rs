@@ -0,0 +1,242 @@
+//! Lead images in the detail view, drawn with the terminal's graphics protocol (kitty,
+//! iTerm2 inline images or sixel). Other terminals get the image URL as text.
+use crate::ui;
+use anyhow::{bail, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use console::Term;
+use image::{imageops::FilterType, DynamicImage, ImageFormat, ImageReader, Limits, RgbaImage};
+use news_cli::config::{FetchLimits, RuntimeConfig};
+use news_cli::news::{build_client_with, max_feed_bytes, read_body_capped};
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write as _;
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Protocol {
+    Kitty,
+    Iterm,
+    Sixel,
+}
+
+/// The protocol for the `images` setting; "auto" (the default) guesses from the environment.
+pub(super) fn protocol(setting: Option<&str>, term: &Term) -> Option<Protocol> {
+    match setting.unwrap_or("auto") {
+        "off" => None,
+        "kitty" => Some(Protocol::Kitty),
+        "iterm" => Some(Protocol::Iterm),
+        "sixel" => Some(Protocol::Sixel),
+        _ if term.is_term() => detect(),
+        _ => None,
+    }
+}
+
+fn detect() -> Option<Protocol> {
+    let term = env::var("TERM").unwrap_or_default();
+    let program = env::var("TERM_PROGRAM").unwrap_or_default();
+    // tmux and screen drop graphics escapes unless passthrough is set up
+    if env::var_os("TMUX").is_some() || term.starts_with("screen") || term.starts_with("tmux") {
+        return None;
+    }
+    if term == "xterm-kitty" || term == "xterm-ghostty" || program == "ghostty" {
+        Some(Protocol::Kitty)
+    } else if program == "iTerm.app" || program == "WezTerm" || env::var("LC_TERMINAL").is_ok_and(|t| t == "iTerm2") {
+        Some(Protocol::Iterm)
+    } else if term.starts_with("foot") || term.starts_with("mlterm") || term.contains("sixel") || program == "contour" {
+        Some(Protocol::Sixel)
+    } else {
+        None
+    }
+}
+
+enum State {
+    Loading,
+    Ready(DynamicImage),
+    Failed,
+}
+
+/// An image being downloaded in the background; the view redraws when it arrives.
+pub(super) struct Preview {
+    protocol: Protocol,
+    state: Arc<Mutex<State>>,
+    // Escape sequence for the last box size, so redraws don't re-encode
+    encoded: Option<((u16, u16), String)>,
+}
+
+impl Preview {
+    pub(super) fn start(cfg: &RuntimeConfig, protocol: Protocol, url: &str) -> Preview {
+        let state = Arc::new(Mutex::new(State::Loading));
+        let shared = state.clone();
+        let limits = cfg.fetch.clone();
+        let url = url.to_string();
+        tokio::spawn(async move {
+            let loaded = fetch(&limits, &url).await;
+            *shared.lock().unwrap() = match loaded {
+                Ok(img) => State::Ready(img),
+                Err(_) => State::Failed,
+            };
+            ui::notify_refresh();
+        });
+        Preview { protocol, state, encoded: None }
+    }
+
+    /// Columns and rows the image takes at most `cols` wide and `rows` high; None until it
+    /// has loaded (or if it failed to).
+    pub(super) fn size(&self, cols: u16, rows: u16) -> Option<(u16, u16)> {
+        let State::Ready(img) = &*self.state.lock().unwrap() else { return None };
+        let (cell_w, cell_h) = cell_size();
+        let scale = f64::min(
+            f64::from(cols) * f64::from(cell_w) / f64::from(img.width()),
+            f64::from(rows) * f64::from(cell_h) / f64::from(img.height()),
+        );
+        let w = (f64::from(img.width()) * scale / f64::from(cell_w)).round() as u16;
+        let h = (f64::from(img.height()) * scale / f64::from(cell_h)).round() as u16;
+        Some((w.clamp(1, cols), h.clamp(1, rows)))
+    }
+
+    /// Escape sequence drawing the image into a box of `cols`×`rows` cells at the cursor.
+    pub(super) fn escape(&mut self, cols: u16, rows: u16) -> &str {
+        if self.encoded.as_ref().is_none_or(|(size, _)| *size != (cols, rows)) {
+            let seq = match &*self.state.lock().unwrap() {
+                State::Ready(img) => encode(self.protocol, img, cols, rows),
+                _ => String::new(),
+            };
+            self.encoded = Some(((cols, rows), seq));
+        }
+        self.encoded.as_ref().map_or("", |(_, seq)| seq)
+    }
+
+    /// Escape sequence removing drawn images; kitty keeps them across a screen clear.
+    pub(super) fn clear(&self) -> &'static str {
+        match self.protocol {
+            Protocol::Kitty => "\x1b_Ga=d,d=A,q=2\x1b\\",
+            _ => "",
+        }
+    }
+}
+
+async fn fetch(limits: &FetchLimits, url: &str) -> Result<DynamicImage> {
+    let client = build_client_with(limits)?;
+    let resp = client.get(url).send().await?.error_for_status()?;
+    let cap = limits.max_bytes.map_or(max_feed_bytes(), |b| b as usize);
+    let bytes = read_body_capped(resp, cap).await?;
+    let mut reader = ImageReader::new(Cursor::new(bytes)).with_guessed_format()?;
+    if reader.format().is_none() {
+        bail!("not an image");
+    }
+    let mut decode_limits = Limits::default();
+    decode_limits.max_image_width = Some(8192);
+    decode_limits.max_image_height = Some(8192);
+    reader.limits(decode_limits);
+    // Nothing needs more pixels than a large terminal shows
+    Ok(reader.decode()?.thumbnail(1600, 1600))
+}
+
+fn encode(protocol: Protocol, img: &DynamicImage, cols: u16, rows: u16) -> String {
+    let (cell_w, cell_h) = cell_size();
+    let fitted = img.resize(u32::from(cols) * cell_w, u32::from(rows) * cell_h, FilterType::Triangle);
+    match protocol {
+        Protocol::Kitty => kitty(&png(&fitted), cols, rows),
+        Protocol::Iterm => {
+            let data = png(&fitted);
+            format!(
+                "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+                data.len(),
+                cols,
+                rows,
+                STANDARD.encode(&data)
+            )
+        }
+        Protocol::Sixel => sixel(&fitted.to_rgba8()),
+    }
+}
+
+fn png(img: &DynamicImage) -> Vec<u8> {
+    let mut out = Cursor::new(Vec::new());
+    // Writing to memory only fails on an unsupported color type, which to_rgba8 rules out
+    let _ = DynamicImage::ImageRgba8(img.to_rgba8()).write_to(&mut out, ImageFormat::Png);
+    out.into_inner()
+}
+
+// Transmit-and-display in 4096-byte base64 chunks; q=2 keeps the terminal from replying
+// and C=1 leaves the cursor where it was
+fn kitty(png: &[u8], cols: u16, rows: u16) -> String {
+    let data = STANDARD.encode(png);
+    let chunks: Vec<&[u8]> = data.as_bytes().chunks(4096).collect();
+    let mut out = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+        if i == 0 {
+            let _ = write!(out, "\x1b_Ga=T,f=100,q=2,C=1,c={},r={},m={};{}\x1b\\", cols, rows, more, chunk);
+        } else {
+            let _ = write!(out, "\x1b_Gm={};{}\x1b\\", more, chunk);
+        }
+    }
+    out
+}
+
+// Sixel with the 6×6×6 color cube as palette; transparent pixels are left unpainted
+fn sixel(img: &RgbaImage) -> String {
+    let (w, h) = img.dimensions();
+    let level = |v: u8| (u16::from(v) * 5 + 127) / 255;
+    let index = |x: u32, y: u32| {
+        let p = img.get_pixel(x, y).0;
+        (p[3] >= 128).then(|| (level(p[0]) * 36 + level(p[1]) * 6 + level(p[2])) as usize)
+    };
+    let mut out = format!("\x1bP0;1;0q\"1;1;{};{}", w, h);
+    for c in 0..216 {
+        let pct = |l: usize| l * 100 / 5;
+        let _ = write!(out, "#{};2;{};{};{}", c, pct(c / 36), pct(c / 6 % 6), pct(c % 6));
+    }
+    for band in (0..h).step_by(6) {
+        // Per color, the six-pixel column bits across the band
+        let mut rows: BTreeMap<usize, Vec<u8>> = BTreeMap::new();
+        for x in 0..w {
+            for dy in 0..6.min(h - band) {
+                if let Some(c) = index(x, band + dy) {
+                    rows.entry(c).or_insert_with(|| vec![0; w as usize])[x as usize] |= 1 << dy;
+                }
+            }
+        }
+        for (c, bits) in &rows {
+            let _ = write!(out, "#{}", c);
+            let mut x = 0;
+            while x < bits.len() {
+                let run = bits[x..].iter().take_while(|&&b| b == bits[x]).count();
+                let ch = char::from(63 + bits[x]);
+                if run > 3 {
+                    let _ = write!(out, "!{}{}", run, ch);
+                } else {
+                    out.extend(std::iter::repeat_n(ch, run));
+                }
+                x += run;
+            }
+            out.push('$');
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+// Pixel size of one character cell, from the tty; 8×16 where it doesn't say
+#[cfg(unix)]
+fn cell_size() -> (u32, u32) {
+    let mut ws = std::mem::MaybeUninit::<libc::winsize>::zeroed();
+    // SAFETY: TIOCGWINSZ fills the winsize struct, which is zero-initialized if it fails
+    let ws = unsafe {
+        libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, ws.as_mut_ptr());
+        ws.assume_init()
+    };
+    if ws.ws_xpixel == 0 || ws.ws_ypixel == 0 || ws.ws_col == 0 || ws.ws_row == 0 {
+        return (8, 16);
+    }
+    (u32::from(ws.ws_xpixel / ws.ws_col).max(1), u32::from(ws.ws_ypixel / ws.ws_row).max(1))
+}
+
+#[cfg(not(unix))]
+fn cell_size() -> (u32, u32) {
+    (8, 16)
+}
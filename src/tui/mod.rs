@@ -0,0 +1,1115 @@
+//! Interactive news browsing: the grouped headline list, per-source lists and the detail view.
+pub mod alerts;
+pub mod briefing;
+pub mod calendar;
+pub mod catalog;
+pub mod catchup;
+mod confirm;
+mod detail;
+mod export;
+mod image;
+pub mod morning;
+pub mod queue;
+pub mod reading;
+mod repeat;
+pub mod session;
+pub mod settings;
+mod speak;
+mod sync;
+pub mod tags;
+pub mod top;
+
+pub use sync::flush as flush_sync;
+
+use crate::app::AppContext;
+use crate::open_url::open_url;
+use crate::player;
+use crate::ui::{self, prompt_index, read_key_or_resize, MenuChoice};
+use session::{Selected, Session};
+use anyhow::Result;
+use console::{style, Key, Term};
+use news_cli::collapsed::CollapsedSources;
+use news_cli::config::{FeedOrder, ListTimes, RuntimeConfig};
+use news_cli::history::{SeenStories, SharedHistory};
+use news_cli::menu;
+use news_cli::news::{self, status::FeedStatus, CancellationToken, FeedProgress, Fetched, Story};
+use news_cli::snapshot::Snapshot;
+use news_cli::tr;
+use news_cli::util::dates;
+use news_cli::util::sanitize::{sanitize_for_terminal, sanitize_to_width};
+use news_cli::util::width::{display_width, isolate, truncate_to_width};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use time::OffsetDateTime;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+/// Where the news list came from; shown in its prompt.
+#[derive(Clone, PartialEq, Eq)]
+enum Freshness {
+    Fresh,
+    /// The last session's stories while a fetch runs in the background
+    Stale,
+    /// Every feed failed; the last successful fetch
+    Offline,
+    /// The fetch was stopped with 'b'; the feeds done by then
+    Partial,
+    /// A saved snapshot: its name and when it was taken
+    Snapshot(String, String),
+}
+
+/// Start fetching the News screen's stories in the background, for its first visit to take;
+/// called at launch so the fetch runs while the main menu is up.
+pub fn prefetch(cfg: &RuntimeConfig, app: &AppContext, history: &SharedHistory) {
+    app.set_news_prefetch(spawn_fetch(cfg, app, history));
+}
+
+/// Returns whether the user quit.
+pub async fn run(cfg: &RuntimeConfig, app: &AppContext, history: &SharedHistory) -> Result<bool> {
+    // Usually started at launch, and maybe done already
+    let fetch = app.take_news_prefetch().unwrap_or_else(|| spawn_fetch(cfg, app, history));
+    let cached = if fetch.handle.is_finished() { Vec::new() } else { news::cache::load() };
+    if cached.is_empty() {
+        // Nothing to show meanwhile: wait for the network, or what arrived until 'b'
+        let Some(fetched) = fetch.wait(tr!("news.fetching")).await? else { return Ok(true) };
+        let (stories, freshness) = accept(cfg, history, fetched);
+        return news_menu(cfg, history, stories, freshness, None).await;
+    }
+
+    // Show the last fetch right away (stale-while-revalidate) and swap in fresh stories
+    // when the background fetch finishes; leaving the screen first stops it
+    let _stop = fetch.cancel.clone().drop_guard();
+    let (tx, rx) = oneshot::channel();
+    tokio::spawn(async move {
+        if let Ok(fetched) = fetch.handle.await
+            && tx.send(fetched).is_ok()
+        {
+            ui::notify_refresh();
+        }
+    });
+    let stories = with_badges(history, cached);
+    mark_all_seen(history, &stories);
+    news_menu(cfg, history, stories, Freshness::Stale, Some(rx)).await
+}
+
+/// Browse a saved snapshot (`news-cli snapshot view`) as the News screen lists a fetch. Its
+/// stories are shown as they were listed then: nothing is hidden by age or fetched, and
+/// feeds that are dormant or failing now get no empty header. Returns whether the user quit.
+pub async fn view_snapshot(cfg: &RuntimeConfig, history: &SharedHistory, snapshot: Snapshot) -> Result<bool> {
+    let sources: HashSet<&str> = snapshot.stories.iter().map(|s| s.source.as_str()).collect();
+    let mut cfg = cfg.clone();
+    cfg.max_age_days = None;
+    cfg.feeds.retain(|f| sources.contains(f.name.as_str()));
+    for f in cfg.feeds.iter_mut() {
+        f.max_age_days = None;
+        f.days = None;
+        f.hours = None;
+    }
+    let taken = cfg.dates.format(snapshot.taken_at).unwrap_or_default();
+    let freshness = Freshness::Snapshot(snapshot.name, taken);
+    news_menu(&cfg, history, snapshot.stories, freshness, None).await
+}
+
+/// Every feed being fetched in the background (see [`spawn_fetch`]).
+pub struct Fetch {
+    handle: JoinHandle<Fetched>,
+    cancel: CancellationToken,
+    progress: UnboundedReceiver<FeedProgress>,
+}
+
+impl Fetch {
+    /// Stop it, dropping what it got so far.
+    pub fn cancel(self) {
+        self.cancel.cancel();
+        self.handle.abort();
+    }
+
+    /// Wait for it with `message` and how each feed is going on screen. 'b' stops it early
+    /// and returns the feeds done by then; `None` means the user quit.
+    async fn wait(mut self, message: &str) -> Result<Option<Fetched>> {
+        let term = Term::stdout();
+        let mut feeds: Vec<(String, FeedProgress)> = Vec::new();
+        while !self.handle.is_finished() {
+            while let Ok(p) = self.progress.try_recv() {
+                let name = match &p {
+                    FeedProgress::Queued(feed) | FeedProgress::Fetching(feed) => feed,
+                    FeedProgress::Fetched { feed, .. } | FeedProgress::Failed { feed, .. } => feed,
+                };
+                match feeds.iter_mut().find(|(n, _)| n == name) {
+                    Some(entry) => entry.1 = p,
+                    None => feeds.push((name.clone(), p)),
+                }
+            }
+            term.clear_screen()?;
+            render_progress(&term, message, &feeds);
+            // Redrawn for each report, and now and then so the spinner turns
+            let drawn = Instant::now();
+            let progress = &self.progress;
+            let handle = &self.handle;
+            let key = ui::read_key_until(&term, || {
+                handle.is_finished() || !progress.is_empty() || drawn.elapsed() >= Duration::from_millis(150)
+            })?;
+            match key {
+                Some(Key::Char('b') | Key::Char('B') | Key::Escape) => {
+                    self.cancel.cancel();
+                    break;
+                }
+                Some(Key::Char('q') | Key::Char('Q') | Key::CtrlC) => {
+                    self.cancel();
+                    return Ok(None);
+                }
+                _ => {}
+            }
+        }
+        Ok(Some(self.handle.await?))
+    }
+}
+
+// "Fetching stories… (1/3)" over a line per feed: done with its story count, failed with
+// why, fetching with a spinner, or waiting its turn
+fn render_progress(term: &Term, message: &str, feeds: &[(String, FeedProgress)]) {
+    let cols = term.size().1 as usize;
+    let done = feeds
+        .iter()
+        .filter(|(_, p)| matches!(p, FeedProgress::Fetched { .. } | FeedProgress::Failed { .. }))
+        .count();
+    if feeds.is_empty() {
+        println!("{}", message);
+    } else {
+        println!("{} ({}/{})", message, done, feeds.len());
+    }
+    let name_width = feeds.iter().map(|(n, _)| display_width(n)).max().unwrap_or(0).min(cols / 3);
+    for (name, p) in feeds {
+        let name = sanitize_for_terminal(name);
+        let padded = format!("{}{}", name, " ".repeat(name_width.saturating_sub(display_width(&name))));
+        let line = match p {
+            FeedProgress::Queued(_) => format!("  {} {}", style("·").dim(), style(padded).dim()),
+            FeedProgress::Fetching(_) => format!("  {} {}  {}", ui::spinner(), padded, tr!("news.progress_fetching")),
+            FeedProgress::Fetched { stories, .. } => {
+                format!("  {} {}  {}", style("✓").green(), padded, tr!("news.progress_fetched", stories))
+            }
+            FeedProgress::Failed { error, .. } => {
+                let error = truncate_to_width(&sanitize_for_terminal(error), cols.saturating_sub(name_width + 6));
+                format!("  {} {}  {}", style("✗").red(), padded, style(error).red())
+            }
+        };
+        println!("{}", line);
+    }
+    println!("{}", style(tr!("news.fetch_keys")).dim());
+}
+
+/// Start fetching every feed in `cfg` on its own task.
+fn spawn_fetch(cfg: &RuntimeConfig, app: &AppContext, history: &SharedHistory) -> Fetch {
+    let cancel = CancellationToken::new();
+    let (tx, progress) = mpsc::unbounded_channel();
+    let (cfg, app, history, stop) = (cfg.clone(), app.clone(), history.clone(), cancel.clone());
+    let handle = tokio::spawn(async move { fetch_fresh(&cfg, &app, &history, &stop, tx).await });
+    Fetch { handle, cancel, progress }
+}
+
+// Every feed's stories, after pulling the reader's read state; feeds that failed are
+// reported on stderr and missing, so callers fall back to the cache if all did
+async fn fetch_fresh(
+    cfg: &RuntimeConfig,
+    app: &AppContext,
+    history: &SharedHistory,
+    cancel: &CancellationToken,
+    progress: UnboundedSender<FeedProgress>,
+) -> Fetched {
+    sync::pull(cfg, history).await;
+    // Work from a snapshot so the lock isn't held across network I/O
+    let snapshot = history.lock().unwrap().clone();
+    let fetched = app.fetch(cfg, &snapshot, cancel, progress).await;
+    for d in &fetched.diagnostics {
+        eprintln!("{}", d);
+    }
+    fetched
+}
+
+/// What a fetch leaves to show: its stories, cached for next time if it completed, or if
+/// every feed failed the last successful fetch's.
+fn keep(cfg: &RuntimeConfig, history: &SharedHistory, fetched: Fetched) -> (Vec<Story>, Freshness) {
+    repeat::record_failures(&fetched);
+    if fetched.stories.is_empty() {
+        // Every feed failed; fall back to the last successful fetch rather than an empty screen
+        let cached = with_badges(history, news::cache::load());
+        let freshness = if cached.is_empty() { Freshness::Fresh } else { Freshness::Offline };
+        return (cached, freshness);
+    }
+    crate::commands::check_alerts(&cfg.alerts, &fetched.stories);
+    // Feeds stopped before their turn would be missing from the next stale list
+    if fetched.cancelled {
+        return (fetched.stories, Freshness::Partial);
+    }
+    if let Err(e) = news::cache::save(&fetched.stories) {
+        eprintln!("{}", tr!("news.cache_failed", e));
+    }
+    (fetched.stories, Freshness::Fresh)
+}
+
+/// [`keep`], marking everything shown as seen.
+fn accept(cfg: &RuntimeConfig, history: &SharedHistory, fetched: Fetched) -> (Vec<Story>, Freshness) {
+    let (stories, freshness) = keep(cfg, history, fetched);
+    mark_all_seen(history, &stories);
+    (stories, freshness)
+}
+
+fn with_badges(history: &SharedHistory, mut stories: Vec<Story>) -> Vec<Story> {
+    let h = history.lock().unwrap();
+    for s in stories.iter_mut() {
+        s.is_new = !h.is_seen(&s.link);
+    }
+    stories
+}
+
+// Mark everything shown as seen up front ([NEW] badges are already computed),
+// so an interrupted session still records it
+fn mark_all_seen(history: &SharedHistory, stories: &[Story]) {
+    let mut h = history.lock().unwrap();
+    for s in stories {
+        h.mark_as_seen(&s.link);
+    }
+}
+
+enum Item {
+    Header(String),
+    Story(String, usize), // (source, idx)
+}
+
+/// The grouped headline list: stories by source, their labels and what each label is.
+struct NewsList {
+    by_source: HashMap<String, Vec<Story>>,
+    labels: Vec<String>,
+    index_map: Vec<Item>,
+    header_indices: Vec<usize>,
+    // Terminal height the stories were shared out for
+    rows: u16,
+}
+
+impl NewsList {
+    fn selected(&self, i: usize) -> Option<Selected> {
+        match self.index_map.get(i)? {
+            Item::Header(source) => Some(Selected::Source(source.clone())),
+            Item::Story(source, idx) => self.by_source.get(source)?.get(*idx).map(|s| Selected::Story(s.link.clone())),
+        }
+    }
+
+    /// Index of an entry in a (possibly rebuilt) list.
+    fn position(&self, selected: &Selected) -> Option<usize> {
+        (0..self.index_map.len()).find(|&i| self.selected(i).as_ref() == Some(selected))
+    }
+}
+
+/// Returns `true` if the user quit (so the caller can propagate the quit upward).
+async fn news_menu(
+    cfg: &RuntimeConfig,
+    history: &SharedHistory,
+    mut stories: Vec<Story>,
+    mut freshness: Freshness,
+    mut refresh: Option<oneshot::Receiver<Fetched>>,
+) -> Result<bool> {
+    // Replaced when config.toml changes (see crate::reload)
+    let mut cfg = cfg.clone();
+    let mut list = build_list(&cfg, history, stories.clone());
+    let mut actions = news_actions(&cfg);
+    // Feeds added by a reload (or failed ones fetched again with '.'), fetched on their own
+    let mut added: Option<oneshot::Receiver<Result<Vec<Story>>>> = None;
+    let mut last: Option<usize> = None;
+    // What the last action did, after the prompt
+    let mut status = String::new();
+    // Reopen where the last session left off (restore_session)
+    let mut reopen = session::take_restore();
+    if let Some(saved) = &reopen {
+        last = saved.selected.as_ref().and_then(|s| list.position(s));
+        if saved.source_list.is_none() {
+            ui::resume_arrow_mode(saved.filter.clone());
+        }
+    }
+    loop {
+        if let Some(fresh) = crate::reload::take(&cfg) {
+            let selected = last.and_then(|i| list.selected(i));
+            let (new_feeds, gone) = feed_changes(&cfg, &fresh);
+            stories.retain(|s| !gone.contains(&s.source));
+            if !new_feeds.is_empty() {
+                added = Some(fetch_feeds(&fresh, history, new_feeds));
+            }
+            cfg = fresh;
+            actions = news_actions(&cfg);
+            list = build_list(&cfg, history, stories.clone());
+            last = selected.and_then(|s| list.position(&s));
+        }
+        // Resized since: share the new height out
+        if Term::stdout().size().0 != list.rows {
+            rebuild(&cfg, history, &stories, &mut list, &mut last);
+        }
+        if let Some(rx) = added.as_mut() {
+            match rx.try_recv() {
+                Ok(fetched) => {
+                    added = None;
+                    let fetched = fetched.unwrap_or_else(|e| {
+                        eprintln!("{}", tr!("fetch.failed", "feeds", e));
+                        Vec::new()
+                    });
+                    mark_all_seen(history, &fetched);
+                    crate::commands::check_alerts(&cfg.alerts, &fetched);
+                    // A refetched feed's stories replace what an offline list had of it
+                    let sources: HashSet<&str> = fetched.iter().map(|s| s.source.as_str()).collect();
+                    stories.retain(|s| !sources.contains(s.source.as_str()));
+                    stories.extend(fetched);
+                    if let Err(e) = news::cache::save(&stories) {
+                        eprintln!("{}", tr!("news.cache_failed", e));
+                    }
+                    rebuild(&cfg, history, &stories, &mut list, &mut last);
+                }
+                Err(oneshot::error::TryRecvError::Empty) => {}
+                Err(oneshot::error::TryRecvError::Closed) => added = None,
+            }
+        }
+        if let Some(rx) = refresh.as_mut() {
+            match rx.try_recv() {
+                Ok(fetched) => {
+                    refresh = None;
+                    // A failed refresh keeps the stale list, now labelled offline
+                    let (fetched, f) = accept(&cfg, history, fetched);
+                    if f == Freshness::Offline {
+                        freshness = Freshness::Offline;
+                    } else {
+                        // Keep the selection on the same entry
+                        let selected = last.and_then(|i| list.selected(i));
+                        stories = fetched;
+                        list = build_list(&cfg, history, stories.clone());
+                        freshness = f;
+                        last = selected.and_then(|s| list.position(&s));
+                    }
+                }
+                Err(oneshot::error::TryRecvError::Empty) => {}
+                Err(oneshot::error::TryRecvError::Closed) => {
+                    refresh = None;
+                    freshness = Freshness::Offline;
+                }
+            }
+        }
+        let prompt = match &freshness {
+            Freshness::Fresh => tr!("news.prompt").to_string(),
+            Freshness::Stale => tr!("news.prompt_stale").to_string(),
+            Freshness::Offline => tr!("news.prompt_offline").to_string(),
+            Freshness::Partial => tr!("news.prompt_partial").to_string(),
+            Freshness::Snapshot(name, taken) => tr!("news.prompt_snapshot", name, taken),
+        };
+        let prompt = match status.is_empty() {
+            true => prompt,
+            false => format!("{} {}", prompt, std::mem::take(&mut status)),
+        };
+        if let Some(saved) = reopen.take()
+            && let Some(source) = saved.source_list.clone()
+            && let Some(h) = list.position(&Selected::Source(source.clone()))
+        {
+            last = Some(h);
+            if open_source(&cfg, history, &mut stories, &mut list, &source, Some(saved)).await? {
+                return Ok(true);
+            }
+            continue;
+        }
+        session::showing(None, (0..list.labels.len()).map(|i| list.selected(i)).collect(), last);
+        let choice = prompt_index(&prompt, &list.labels, last, cfg.header.as_deref(), Some(&list.header_indices), &actions)?;
+        if matches!(choice, MenuChoice::Index(_) | MenuChoice::Action(..)) {
+            repeat::record_filter();
+        }
+        match choice {
+            MenuChoice::Back => {
+                session::leave();
+                break;
+            }
+            MenuChoice::Quit => return Ok(true),
+            MenuChoice::Action('i', i) => {
+                last = Some(i);
+                if let Item::Story(source, idx) = &list.index_map[i]
+                    && let Some(st) = list.by_source.get(source).and_then(|v| v.get(*idx))
+                    && detail::show(&cfg, history, st)?
+                {
+                    return Ok(true);
+                }
+                // Headers count what was opened there
+                rebuild(&cfg, history, &stories, &mut list, &mut last);
+            }
+            MenuChoice::Action('t', i) => {
+                last = Some(i);
+                if let Item::Story(source, idx) = &list.index_map[i]
+                    && let Some(st) = list.by_source.get(source).and_then(|v| v.get(*idx))
+                {
+                    tags::edit(st)?;
+                }
+            }
+            MenuChoice::Action('l', i) => {
+                last = Some(i);
+                if let Item::Story(source, idx) = &list.index_map[i]
+                    && let Some(st) = list.by_source.get(source).and_then(|v| v.get(*idx))
+                {
+                    queue::toggle(st);
+                }
+            }
+            MenuChoice::Action('m', i) => {
+                last = Some(i);
+                let source = match &list.index_map[i] {
+                    Item::Header(source) | Item::Story(source, _) => source.clone(),
+                };
+                mark_stories_read(history, stories.iter_mut().filter(|s| s.source == source));
+                rebuild(&cfg, history, &stories, &mut list, &mut last);
+            }
+            MenuChoice::Action('e', i) => {
+                last = Some(i);
+                let mut sections: Vec<(&str, Vec<&Story>)> = Vec::new();
+                for item in &list.index_map {
+                    match item {
+                        Item::Header(source) => sections.push((source.as_str(), Vec::new())),
+                        Item::Story(source, idx) => {
+                            if let (Some((_, shown)), Some(st)) =
+                                (sections.last_mut(), list.by_source.get(source).and_then(|v| v.get(*idx)))
+                            {
+                                shown.push(st);
+                            }
+                        }
+                    }
+                }
+                status = export::list(&cfg, tr!("news.export_title"), &sections);
+            }
+            MenuChoice::Action('v', i) => {
+                last = Some(i);
+                // From the highlighted story, or the first of the highlighted source
+                let lines: Vec<speak::Utterance> = list.index_map[i..]
+                    .iter()
+                    .filter_map(|item| match item {
+                        Item::Story(source, idx) => list.by_source.get(source)?.get(*idx),
+                        Item::Header(_) => None,
+                    })
+                    .map(|s| utterance(&cfg, s))
+                    .collect();
+                if !lines.is_empty() && speak::read_aloud(&cfg, &lines)? {
+                    return Ok(true);
+                }
+            }
+            MenuChoice::Action('z', i) => {
+                let source = match &list.index_map[i] {
+                    Item::Header(source) | Item::Story(source, _) => source.clone(),
+                };
+                let mut collapsed = CollapsedSources::load();
+                collapsed.toggle(&source);
+                if let Err(e) = collapsed.save() {
+                    eprintln!("{}", tr!("news.folds_save_failed", e));
+                }
+                // Stay on the section, whose stories may have just gone
+                last = Some(i);
+                rebuild(&cfg, history, &stories, &mut list, &mut last);
+                last = list.position(&Selected::Source(source)).or(last);
+            }
+            MenuChoice::Action('a', i) => {
+                last = Some(i);
+                mark_stories_read(history, stories.iter_mut());
+                rebuild(&cfg, history, &stories, &mut list, &mut last);
+            }
+            MenuChoice::Action('d', i) => {
+                last = Some(i);
+                switch_times();
+                rebuild(&cfg, history, &stories, &mut list, &mut last);
+            }
+            MenuChoice::Action('.', i) => {
+                last = Some(i);
+                match repeat::last() {
+                    Some(repeat::Action::Open(link)) => {
+                        if let Some(st) = stories.iter().find(|s| s.link == link)
+                            && select_story(&cfg, history, &st.clone())?
+                        {
+                            return Ok(true);
+                        }
+                        last = list.position(&Selected::Story(link)).or(last);
+                        rebuild(&cfg, history, &stories, &mut list, &mut last);
+                    }
+                    Some(repeat::Action::Search(filter)) => ui::resume_arrow_mode(Some(filter)),
+                    Some(repeat::Action::Refetch(names)) if added.is_none() => {
+                        let feeds: Vec<_> = cfg.feeds.iter().filter(|f| names.contains(&f.name)).cloned().collect();
+                        if !feeds.is_empty() {
+                            added = Some(fetch_feeds(&cfg, history, feeds));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            MenuChoice::Index(i) => {
+                last = Some(i);
+                match &list.index_map[i] {
+                    Item::Header(source) => {
+                        let source = source.clone();
+                        if open_source(&cfg, history, &mut stories, &mut list, &source, None).await? {
+                            return Ok(true);
+                        }
+                        last = list.position(&Selected::Source(source)).or(last);
+                    }
+                    Item::Story(source, idx) => {
+                        if let Some(v) = list.by_source.get(source) {
+                            if let Some(st) = v.get(*idx) && select_story(&cfg, history, st)? {
+                                return Ok(true);
+                            }
+                        }
+                        rebuild(&cfg, history, &stories, &mut list, &mut last);
+                    }
+                }
+            }
+            MenuChoice::Refresh => {
+                // Stay in arrow-key mode across the redraw
+                let pos = ui::last_position();
+                if pos.selected.is_some() {
+                    last = pos.selected;
+                    ui::resume_arrow_mode(pos.filter);
+                }
+            }
+            MenuChoice::Action(..) => {}
+        }
+    }
+    Ok(false)
+}
+
+/// The News screen's actions; 'd' depends on list_times.
+fn news_actions(cfg: &RuntimeConfig) -> Vec<(char, &'static str)> {
+    let mut actions = vec![
+        ('i', tr!("news.details")),
+        ('t', tr!("news.tag")),
+        ('l', tr!("news.queue")),
+        ('m', tr!("news.mark_source_read")),
+        ('a', tr!("news.mark_all_read")),
+        ('z', tr!("news.collapse")),
+        ('v', tr!("news.read_aloud")),
+        ('e', tr!("news.export")),
+    ];
+    actions.extend(times_action(cfg));
+    actions.push(('.', tr!("news.repeat")));
+    actions
+}
+
+/// A story as read aloud: "World. Central bank holds rates steady".
+fn utterance(cfg: &RuntimeConfig, story: &Story) -> speak::Utterance {
+    speak::Utterance {
+        shown: format!("[{}] {}", sanitize_for_terminal(&story.source), story_title(cfg, story)),
+        said: format!("{}. {}", story.source, list_title(cfg, story)),
+    }
+}
+
+/// Fetch only `feeds` (with the rest of `cfg`) on their own task; the screen is woken when
+/// they are in.
+fn fetch_feeds(cfg: &RuntimeConfig, history: &SharedHistory, feeds: Vec<news_cli::Feed>) -> oneshot::Receiver<Result<Vec<Story>>> {
+    let (tx, rx) = oneshot::channel();
+    let only = RuntimeConfig { feeds, ..cfg.clone() };
+    let snapshot = history.lock().unwrap().clone();
+    tokio::spawn(async move {
+        let _ = tx.send(news::collect_stories(&only, &snapshot).await.map_err(Into::into));
+        ui::notify_refresh();
+    });
+    rx
+}
+
+/// Feeds `fresh` adds to `old`, and names of those it drops (a renamed feed is both).
+fn feed_changes(old: &RuntimeConfig, fresh: &RuntimeConfig) -> (Vec<news_cli::Feed>, HashSet<String>) {
+    let before: HashSet<&str> = old.feeds.iter().map(|f| f.name.as_str()).collect();
+    let after: HashSet<&str> = fresh.feeds.iter().map(|f| f.name.as_str()).collect();
+    let added = fresh.feeds.iter().filter(|f| !before.contains(f.name.as_str())).cloned().collect();
+    let gone = before.difference(&after).map(|n| n.to_string()).collect();
+    (added, gone)
+}
+
+/// [`source_menu`] for one of the list's sections. Returns `true` if the user quit.
+async fn open_source(
+    cfg: &RuntimeConfig,
+    history: &SharedHistory,
+    stories: &mut [Story],
+    list: &mut NewsList,
+    source: &str,
+    restore: Option<Session>,
+) -> Result<bool> {
+    let Some(v) = list.by_source.get_mut(source) else { return Ok(false) };
+    if source_menu(cfg, history, source, v, restore).await? {
+        return Ok(true);
+    }
+    // Carry over stories marked read there
+    let read: HashSet<&str> = v.iter().filter(|s| !s.is_new).map(|s| s.link.as_str()).collect();
+    for s in stories.iter_mut().filter(|s| s.source == source && read.contains(s.link.as_str())) {
+        s.is_new = false;
+    }
+    *list = build_list(cfg, history, stories.to_vec());
+    Ok(false)
+}
+
+// Rebuild after stories were read, keeping the selection on the same entry: with
+// feed_order "most_new" the sources may have moved
+fn rebuild(cfg: &RuntimeConfig, history: &SharedHistory, stories: &[Story], list: &mut NewsList, last: &mut Option<usize>) {
+    let selected = last.and_then(|i| list.selected(i));
+    *list = build_list(cfg, history, stories.to_vec());
+    if let Some(s) = selected {
+        *last = list.position(&s).or(*last);
+    }
+}
+
+/// The grouped list; source headers count new, unread and total stories as of `history`.
+fn build_list(cfg: &RuntimeConfig, history: &SharedHistory, stories: Vec<Story>) -> NewsList {
+    // Group stories by source
+    let mut by_source: HashMap<String, Vec<Story>> = HashMap::new();
+    for s in stories {
+        by_source.entry(s.source.clone()).or_default().push(s);
+    }
+    // A feed whose last fetch failed keeps its header even without stories, so it is noticed;
+    // so does one that is dormant (outside its days / hours), to say why it is empty
+    let statuses = news::status::load();
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    for f in &cfg.feeds {
+        if statuses.get(&f.name).is_some_and(|s| s.failing()) || !f.is_active(&cfg.dates, now) {
+            by_source.entry(f.name.clone()).or_default();
+        }
+    }
+    // Drop stories older than their feed's max_age_days, counting them for the header
+    let mut hidden: HashMap<String, usize> = HashMap::new();
+    for (source, items) in by_source.iter_mut() {
+        let max_age = cfg.feeds.iter().find(|f| f.name == *source).and_then(|f| f.max_age_days).or(cfg.max_age_days);
+        let Some(days) = max_age else { continue };
+        let cutoff = now.saturating_sub(i64::try_from(days).unwrap_or(i64::MAX).saturating_mul(86_400));
+        let before = items.len();
+        items.retain(|s| s.published.is_none_or(|p| p >= cutoff));
+        if items.len() < before {
+            hidden.insert(source.clone(), before - items.len());
+        }
+    }
+    // Sort each source by most recent first (fallback: keep original order)
+    for (_src, vecs) in by_source.iter_mut() {
+        vecs.sort_by(|a, b| match (a.published, b.published) {
+            (Some(da), Some(db)) => db.cmp(&da), // newest first
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+    }
+    let theme = crate::theme::current();
+    let history = history.lock().unwrap();
+    let history = &*history;
+    let order = source_order(cfg, history, &by_source);
+    // Folded sections list no stories, only their header
+    let collapsed = CollapsedSources::load();
+    let rows = Term::stdout().size().0;
+    let shown = story_rows(history, &by_source, &order, &collapsed, rows as usize);
+    let shown = |source: &str| shown.get(source).copied().unwrap_or(0);
+    // One column width for the whole list
+    let listed: Vec<&Story> = order.iter().flat_map(|s| by_source[s].iter().take(shown(s))).collect();
+    let mut times = time_column(cfg, &listed).into_iter();
+    // Build a flat list in feed_order
+    let mut labels: Vec<String> = Vec::new();
+    let mut index_map: Vec<Item> = Vec::new();
+    let mut header_indices: Vec<usize> = Vec::new();
+    for source in order {
+        let items = &by_source[&source];
+        let safe_source = sanitize_for_terminal(&source.to_uppercase());
+        header_indices.push(labels.len());
+        let header = with_icon(cfg, &source, header_label(&safe_source, progress(history, items), hidden.get(&source)));
+        let mut status = feed_status(cfg, statuses.get(&source), now);
+        if let Some(f) = cfg.feeds.iter().find(|f| f.name == source && !f.is_active(&cfg.dates, now)) {
+            let schedule = sanitize_for_terminal(&f.schedule().unwrap_or_default());
+            status.push_str(&style(tr!("news.dormant", schedule)).dim().to_string());
+        }
+        if collapsed.contains(&source) {
+            status.push_str(&style(tr!("news.collapsed")).dim().to_string());
+        }
+        labels.push(format!("{}{}", theme.feed_header(&source, header), status));
+        index_map.push(Item::Header(source.clone()));
+        for (idx, it) in items.iter().take(shown(&source)).enumerate() {
+            let safe_title = story_title(cfg, it);
+            let title = if it.is_new { format!("{} {}", theme.new_badge(), safe_title) } else { safe_title };
+            let time = times.next().unwrap_or_default();
+            labels.push(format!("  - {}{}", time, with_icon(cfg, &source, title)));
+            index_map.push(Item::Story(source.clone(), idx));
+        }
+    }
+
+    NewsList { by_source, labels, index_map, header_indices, rows }
+}
+
+/// Stories every unfolded source lists (if it has them) before the rest is shared out.
+const MIN_STORIES_PER_SOURCE: usize = 3;
+/// Rows of the news list taken by the header, breadcrumb, prompt, key help, position line and
+/// pinned section header.
+const LIST_CHROME: usize = 6;
+
+/// Stories listed per source (besides the header): the `rows` of the screen left after the
+/// chrome and the headers, shared out by unread stories with [`menu::share_rows`], each
+/// source getting a few first (so a short screen scrolls rather than hiding sources).
+fn story_rows(
+    history: &SeenStories,
+    by_source: &HashMap<String, Vec<Story>>,
+    order: &[String],
+    collapsed: &CollapsedSources,
+    rows: usize,
+) -> HashMap<String, usize> {
+    let open: Vec<&String> = order.iter().filter(|s| !collapsed.contains(s)).collect();
+    let wants: Vec<(usize, usize)> = open
+        .iter()
+        .map(|s| {
+            let items = &by_source[*s];
+            (progress(history, items).unread, items.len())
+        })
+        .collect();
+    let budget = rows.saturating_sub(LIST_CHROME + order.len());
+    let shares = menu::share_rows(budget, MIN_STORIES_PER_SOURCE, &wants);
+    open.into_iter().cloned().zip(shares).collect()
+}
+
+/// Sources in the order of `feed_order`: config order, alphabetical, most new stories first
+/// or pinned first. Pinned feeds lead in every order but "config"; ties keep config order.
+fn source_order(cfg: &RuntimeConfig, history: &SeenStories, by_source: &HashMap<String, Vec<Story>>) -> Vec<String> {
+    let mut order: Vec<String> = cfg.feeds.iter().filter(|f| by_source.contains_key(&f.name)).map(|f| f.name.clone()).collect();
+    // Sources not in the config (defensive), by name
+    let mut others: Vec<String> = by_source.keys().filter(|s| !cfg.feeds.iter().any(|f| f.name == **s)).cloned().collect();
+    others.sort();
+    order.extend(others);
+    let unpinned = |source: &str| !cfg.feeds.iter().any(|f| f.name == source && f.pin);
+    match cfg.feed_order {
+        FeedOrder::Config => {}
+        FeedOrder::Pinned => order.sort_by_key(|s| unpinned(s)),
+        FeedOrder::Alphabetical => order.sort_by_key(|s| (unpinned(s), s.to_lowercase())),
+        FeedOrder::MostNew => {
+            order.sort_by_key(|s| (unpinned(s), std::cmp::Reverse(progress(history, &by_source[s]).new)))
+        }
+    }
+    order
+}
+
+// "📰 text" for sources with an icon (see `Feed::icon`)
+fn with_icon(cfg: &RuntimeConfig, source: &str, text: String) -> String {
+    match cfg.feeds.iter().find(|f| f.name == source).and_then(|f| f.icon()) {
+        Some(icon) => format!("{} {}", sanitize_for_terminal(icon), text),
+        None => text,
+    }
+}
+
+/// How far the user is through one source's list.
+struct Progress {
+    /// Not listed before this fetch and not read since
+    new: usize,
+    unread: usize,
+    total: usize,
+}
+
+fn progress(history: &SeenStories, items: &[Story]) -> Progress {
+    let unread: Vec<&Story> = items.iter().filter(|s| !history.is_read(&s.link)).collect();
+    Progress { new: unread.iter().filter(|s| s.is_new).count(), unread: unread.len(), total: items.len() }
+}
+
+fn header_label(source: &str, p: Progress, hidden: Option<&usize>) -> String {
+    match hidden {
+        Some(1) => tr!("news.header_hidden_one", source, p.new, p.unread, p.total),
+        Some(n) => tr!("news.header_hidden", source, p.new, p.unread, p.total, n),
+        None => tr!("news.header", source, p.new, p.unread, p.total),
+    }
+}
+
+// " · fetched 2h ago, newest item 3d ago" after a source header, in the style of list_times
+// (nothing with "off"); a failed last fetch is pointed out
+fn feed_status(cfg: &RuntimeConfig, status: Option<&FeedStatus>, now: i64) -> String {
+    let mode = list_times(cfg);
+    let Some(status) = status.filter(|_| mode != ListTimes::Off) else { return String::new() };
+    let when = |ts: i64| match mode {
+        ListTimes::Absolute => cfg.dates.format(ts).unwrap_or_default(),
+        _ => dates::relative(ts, now),
+    };
+    let mut parts = vec![match status.last_success {
+        Some(ts) => tr!("news.fetched", when(ts)),
+        None => tr!("news.never_fetched").to_string(),
+    }];
+    if let Some(ts) = status.newest_item {
+        parts.push(tr!("news.newest_item", when(ts)));
+    }
+    let mut note = style(format!(" · {}", parts.join(", "))).dim().to_string();
+    if status.failing() {
+        note.push_str(&crate::theme::current().negative.apply_to(tr!("news.last_fetch_failed")).to_string());
+    }
+    if let Some(until) = status.backing_off(now) {
+        note.push_str(&style(tr!("news.backing_off", cfg.dates.format(until).unwrap_or_default())).dim().to_string());
+    }
+    note
+}
+
+/// Returns `true` if the user quit (so the caller can propagate the quit upward).
+async fn source_menu(
+    cfg: &RuntimeConfig,
+    history: &SharedHistory,
+    source: &str,
+    entries: &mut [Story],
+    restore: Option<Session>,
+) -> Result<bool> {
+    let _crumb = ui::enter(source);
+    let mut labels = source_labels(cfg, entries);
+    let mut actions = vec![
+        ('i', tr!("news.details")),
+        ('t', tr!("news.tag")),
+        ('l', tr!("news.queue")),
+        ('m', tr!("news.mark_all_read")),
+    ];
+    actions.extend(times_action(cfg));
+    let mut last: Option<usize> = None;
+    if let Some(saved) = restore {
+        if let Some(Selected::Story(link)) = &saved.selected {
+            last = entries.iter().position(|e| e.link == *link);
+        }
+        ui::resume_arrow_mode(saved.filter);
+    }
+    loop {
+        let rows = entries.iter().map(|e| Some(Selected::Story(e.link.clone()))).collect();
+        session::showing(Some(source), rows, last);
+        match prompt_index(
+            &tr!("news.source_prompt", source),
+            &labels,
+            last,
+            cfg.header.as_deref(),
+            None,
+            &actions,
+        )? {
+            MenuChoice::Back => break,
+            MenuChoice::Quit => return Ok(true),
+            MenuChoice::Action('i', i) => {
+                last = Some(i);
+                if let Some(st) = entries.get(i)
+                    && detail::show(cfg, history, st)?
+                {
+                    return Ok(true);
+                }
+            }
+            MenuChoice::Action('t', i) => {
+                last = Some(i);
+                if let Some(st) = entries.get(i) {
+                    tags::edit(st)?;
+                }
+            }
+            MenuChoice::Action('l', i) => {
+                last = Some(i);
+                if let Some(st) = entries.get(i) {
+                    queue::toggle(st);
+                }
+            }
+            MenuChoice::Action('m', i) => {
+                last = Some(i);
+                mark_stories_read(history, entries.iter_mut());
+                labels = source_labels(cfg, entries);
+            }
+            MenuChoice::Action('d', i) => {
+                last = Some(i);
+                switch_times();
+                labels = source_labels(cfg, entries);
+            }
+            MenuChoice::Index(i) => {
+                last = Some(i);
+                if let Some(st) = entries.get(i) && select_story(cfg, history, st)? {
+                    return Ok(true);
+                }
+            }
+            MenuChoice::Action(..) | MenuChoice::Refresh => {}
+        }
+    }
+    Ok(false)
+}
+
+fn source_labels(cfg: &RuntimeConfig, entries: &[Story]) -> Vec<String> {
+    let theme = crate::theme::current();
+    let times = time_column(cfg, &entries.iter().collect::<Vec<_>>());
+    entries
+        .iter()
+        .zip(times)
+        .map(|(e, time)| {
+            let safe_title = story_title(cfg, e);
+            let title = if e.is_new { format!("{} {}", theme.new_badge(), safe_title) } else { safe_title };
+            format!("{}{}", time, title)
+        })
+        .collect()
+}
+
+// Set by 'd' in a story list: relative and absolute times swap for the rest of the session
+static TIMES_SWITCHED: AtomicBool = AtomicBool::new(false);
+
+fn list_times(cfg: &RuntimeConfig) -> ListTimes {
+    match (cfg.list_times, TIMES_SWITCHED.load(Ordering::Relaxed)) {
+        (ListTimes::Relative, true) => ListTimes::Absolute,
+        (ListTimes::Absolute, true) => ListTimes::Relative,
+        (times, _) => times,
+    }
+}
+
+fn switch_times() {
+    TIMES_SWITCHED.fetch_xor(true, Ordering::Relaxed);
+}
+
+/// The 'd' action of story lists, unless list_times is "off".
+fn times_action(cfg: &RuntimeConfig) -> Option<(char, &'static str)> {
+    (cfg.list_times != ListTimes::Off).then(|| ('d', tr!("news.toggle_times")))
+}
+
+/// When each story was published, dimmed and padded to the longest so the titles after it
+/// line up; all empty with list_times = "off".
+fn time_column(cfg: &RuntimeConfig, stories: &[&Story]) -> Vec<String> {
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    let mode = list_times(cfg);
+    let times: Vec<String> = stories
+        .iter()
+        .map(|s| match (mode, s.published) {
+            (ListTimes::Relative, Some(ts)) => dates::relative(ts, now),
+            (ListTimes::Absolute, Some(ts)) => cfg.dates.format(ts).unwrap_or_default(),
+            _ => String::new(),
+        })
+        .collect();
+    let width = times.iter().map(|t| display_width(t)).max().unwrap_or(0);
+    if width == 0 {
+        return vec![String::new(); times.len()];
+    }
+    times
+        .into_iter()
+        .map(|t| format!("{} ", style(format!("{}{}", " ".repeat(width - display_width(&t)), t)).dim()))
+        .collect()
+}
+
+/// Videos get a choice of player or browser; everything else opens in the browser.
+/// Returns `true` if the user quit.
+fn select_story(cfg: &RuntimeConfig, history: &SharedHistory, story: &Story) -> Result<bool> {
+    repeat::record(repeat::Action::Open(story.link.clone()));
+    if !has_web_link(story) {
+        // Nothing for a browser to open (e.g. an IMAP newsletter): read it here instead
+        mark_read(cfg, history, story);
+        return detail::show(cfg, history, story);
+    }
+    let term = Term::stdout();
+    if !player::is_video(&story.link) {
+        if let Err(e) = open_story(cfg, history, story) {
+            show_error(&term, tr!("open.failed", format!("{:#}", e)))?;
+        }
+        return Ok(false);
+    }
+    let player = cfg.player.as_deref().and_then(|p| p.split_whitespace().next()).unwrap_or("mpv");
+    term.write_line("")?;
+    term.write_line(&tr!("news.play_prompt", player))?;
+    loop {
+        match read_key_or_resize(&term)? {
+            Some(Key::Enter | Key::Char('p') | Key::Char('P')) => break,
+            Some(Key::Char('o') | Key::Char('O')) => {
+                if let Err(e) = open_story(cfg, history, story) {
+                    show_error(&term, tr!("open.failed", format!("{:#}", e)))?;
+                }
+                return Ok(false);
+            }
+            Some(Key::Escape | Key::Char('b') | Key::Char('B') | Key::CtrlC) => return Ok(false),
+            Some(Key::Char('q') | Key::Char('Q')) => return Ok(true),
+            _ => {}
+        }
+    }
+    if let Err(e) = play_story(cfg, history, story) {
+        show_error(&term, format!("{:#}", e))?;
+    }
+    Ok(false)
+}
+
+// Left on screen until a key is pressed, as the list redraws over it
+fn show_error(term: &Term, message: String) -> Result<()> {
+    term.write_line(&message)?;
+    term.write_line(tr!("open.any_key"))?;
+    term.read_key()?;
+    Ok(())
+}
+
+/// Like [`open_story`], but in the video player.
+fn play_story(cfg: &RuntimeConfig, history: &SharedHistory, story: &Story) -> Result<()> {
+    player::play(cfg, &story.link)?;
+    mark_read(cfg, history, story);
+    Ok(())
+}
+
+/// The title as listed: with a translation, the original followed by the translated one
+/// (or only the translation with display = "instead").
+fn list_title(cfg: &RuntimeConfig, story: &Story) -> String {
+    match (&story.translation, &cfg.translation) {
+        (Some(t), Some(tc)) if tc.instead() => t.title.clone(),
+        (Some(t), Some(tc)) => format!("{} ({}: {})", story.title, tc.target(), t.title),
+        _ => story.title.clone(),
+    }
+}
+
+/// A story's title as the lists show it: translated per `[translation]`, cut to
+/// `max_title_width`, 🔒 for paywalled feeds and the length of videos.
+fn story_title(cfg: &RuntimeConfig, story: &Story) -> String {
+    // Isolated so an Arabic or Hebrew title doesn't swap places with the badges after it
+    let title = isolate(&sanitize_to_width(&list_title(cfg, story), cfg.max_title_width.unwrap_or(usize::MAX)));
+    let title = if cfg.is_paywalled(&story.source) { format!("🔒 {}", title) } else { title };
+    with_video_meta(cfg, title, story)
+}
+
+// Videos show their length and upload time after the title
+fn with_video_meta(cfg: &RuntimeConfig, title: String, story: &Story) -> String {
+    let Some(secs) = story.duration else { return title };
+    let length = format_length(secs);
+    // The time column already says when
+    let when = story.published.filter(|_| list_times(cfg) == ListTimes::Off).and_then(|ts| cfg.dates.format(ts));
+    let meta = match when {
+        Some(w) => format!("[{}, {}]", length, w),
+        None => format!("[{}]", length),
+    };
+    format!("{} {}", title, style(meta).dim())
+}
+
+/// `m:ss`, or `h:mm:ss` from an hour up.
+fn format_length(secs: u64) -> String {
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// Open in the browser (once `confirm_open` allows) and persist the read right away, so a
+/// crash later in the session doesn't lose it. Returns whether it was opened; a browser that
+/// fails to start is an error and leaves the story unread.
+fn open_story(cfg: &RuntimeConfig, history: &SharedHistory, story: &Story) -> Result<bool> {
+    let link = cfg.open_link(story);
+    if !confirm::confirm_open(cfg, &link)? {
+        return Ok(false);
+    }
+    open_url(cfg, &link)?;
+    mark_read(cfg, history, story);
+    Ok(true)
+}
+
+fn has_web_link(story: &Story) -> bool {
+    story.link.starts_with("http://") || story.link.starts_with("https://")
+}
+
+/// Mark stories read without opening them ('m' / 'a' in the lists): their [NEW] badges go,
+/// the history is saved right away and the sync backend is told. Not logged for Reading Stats.
+fn mark_stories_read<'a>(history: &SharedHistory, stories: impl IntoIterator<Item = &'a mut Story>) {
+    let mut links = Vec::new();
+    {
+        let mut h = history.lock().unwrap();
+        for s in stories {
+            s.is_new = false;
+            h.mark_as_read(&s.link);
+            links.push(s.link.clone());
+        }
+        if let Err(e) = h.save() {
+            eprintln!("{}", tr!("history.save_failed", e));
+        }
+    }
+    sync::push_read_all(links);
+}
+
+fn mark_read(cfg: &RuntimeConfig, history: &SharedHistory, story: &Story) {
+    sync::push_read(&story.link);
+    queue::opened(&story.link);
+    if cfg.reading_stats.track
+        && let Err(e) = news_cli::reading::record(story)
+    {
+        eprintln!("{}", tr!("reading.log_failed", e));
+    }
+    let mut h = history.lock().unwrap();
+    h.mark_as_read(&story.link);
+    if let Err(e) = h.save() {
+        eprintln!("{}", tr!("history.save_failed", e));
+    }
+}
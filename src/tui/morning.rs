@@ -0,0 +1,193 @@
+use super::speak::{self, Utterance};
+use super::{calendar::event_line, keep, mark_all_seen, select_story, spawn_fetch, story_title, with_icon};
+use crate::app::AppContext;
+use crate::ui::{prompt_index, MenuChoice};
+use anyhow::Result;
+use console::style;
+use news_cli::calendar;
+use news_cli::config::RuntimeConfig;
+use news_cli::history::SharedHistory;
+use news_cli::news::Story;
+use news_cli::scoring;
+use news_cli::tr;
+use news_cli::util::sanitize::sanitize_for_terminal;
+use news_cli::weather::{self, Condition, Weather};
+use time::OffsetDateTime;
+
+/// Stories per category.
+const PER_CATEGORY: usize = 3;
+/// How far ahead calendar releases are listed.
+const CALENDAR_HOURS: i64 = 24;
+
+/// The Morning briefing: today's weather (`[weather]`), the best stories of each feed
+/// category, the key stats as last loaded and the next day's calendar releases
+/// (`[calendar]`) in one list. Enter opens a story, 'r' fetches everything again, 'v' reads
+/// it all aloud. Returns `true` if the user quit.
+pub async fn show(cfg: &RuntimeConfig, app: &AppContext, history: &SharedHistory) -> Result<bool> {
+    loop {
+        // Weather and calendar load while the feeds are fetched
+        let weather = cfg.weather.clone().map(|w| {
+            let client = app.client().clone();
+            tokio::spawn(async move { weather::fetch(&client, &w).await })
+        });
+        let events = cfg.calendar.clone().map(|c| {
+            let client = app.client().clone();
+            tokio::spawn(async move { calendar::fetch(&client, &c).await })
+        });
+        let Some(fetched) = spawn_fetch(cfg, app, history).wait(tr!("morning.loading")).await? else { return Ok(true) };
+        let (stories, _) = keep(cfg, history, fetched);
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+
+        let theme = crate::theme::current();
+        let mut labels: Vec<String> = Vec::new();
+        let mut rows: Vec<Option<Story>> = Vec::new();
+        let mut header_indices: Vec<usize> = Vec::new();
+        let mut section = |labels: &mut Vec<String>, rows: &mut Vec<Option<Story>>, title: String| {
+            header_indices.push(labels.len());
+            labels.push(theme.feed_header("", format!("== {} ==", title)).to_string());
+            rows.push(None);
+        };
+        let line = |labels: &mut Vec<String>, rows: &mut Vec<Option<Story>>, text: String| {
+            labels.push(text);
+            rows.push(None);
+        };
+
+        if let (Some(wcfg), Some(weather)) = (&cfg.weather, weather) {
+            let title = match &wcfg.place {
+                Some(place) => tr!("morning.weather_in", sanitize_for_terminal(place)),
+                None => tr!("morning.weather").to_string(),
+            };
+            section(&mut labels, &mut rows, title);
+            match weather.await? {
+                Ok(w) => line(&mut labels, &mut rows, format!("  {}", weather_line(&w))),
+                Err(e) => line(&mut labels, &mut rows, format!("  {}", tr!("morning.weather_failed", e))),
+            }
+        }
+
+        let categories = scoring::top_by_category(cfg, &stories, now, PER_CATEGORY);
+        if categories.is_empty() {
+            section(&mut labels, &mut rows, tr!("morning.top").to_string());
+            let hours = cfg.scoring.window_hours.unwrap_or(24);
+            line(&mut labels, &mut rows, format!("  {}", tr!("morning.no_stories", hours)));
+        }
+        let mut shown: Vec<Story> = Vec::new();
+        for (category, best) in &categories {
+            let name = match category {
+                Some(c) => capitalized(c),
+                None => tr!("morning.other").to_string(),
+            };
+            section(&mut labels, &mut rows, tr!("morning.top_in", sanitize_for_terminal(&name)));
+            for s in best {
+                let title = story_title(cfg, &s.story);
+                let title = if s.story.is_new { format!("{} {}", theme.new_badge(), title) } else { title };
+                let title = with_icon(cfg, &s.story.source, title);
+                labels.push(format!("  - {} ({})", title, sanitize_for_terminal(&s.story.source)));
+                rows.push(Some(s.story.clone()));
+                shown.push(s.story.clone());
+            }
+        }
+        // Only what is listed here counts as seen; the rest stays new for the News screen
+        mark_all_seen(history, &shown);
+
+        section(&mut labels, &mut rows, tr!("morning.stats").to_string());
+        let stats = crate::stats::key_lines(cfg);
+        if stats.is_empty() {
+            line(&mut labels, &mut rows, format!("  {}", style(tr!("morning.no_stats")).dim()));
+        }
+        for l in stats {
+            line(&mut labels, &mut rows, format!("  {}", l));
+        }
+
+        if let Some(events) = events {
+            section(&mut labels, &mut rows, tr!("morning.calendar").to_string());
+            match events.await? {
+                Ok(events) => {
+                    let soon: Vec<_> = events.iter().filter(|e| e.time < now + CALENDAR_HOURS * 3600).collect();
+                    if soon.is_empty() {
+                        line(&mut labels, &mut rows, format!("  {}", tr!("morning.no_events")));
+                    }
+                    for e in soon {
+                        line(&mut labels, &mut rows, event_line(cfg, e));
+                    }
+                }
+                Err(e) => line(&mut labels, &mut rows, format!("  {}", tr!("calendar.failed", format!("{:#}", e)))),
+            }
+        }
+
+        let today = cfg.dates.format_as(now, "%a %Y-%m-%d").unwrap_or_default();
+        let prompt = tr!("morning.prompt", today);
+        let actions = [('r', tr!("morning.refresh")), ('v', tr!("morning.read_aloud"))];
+        let mut last: Option<usize> = None;
+        loop {
+            match prompt_index(&prompt, &labels, last, cfg.header.as_deref(), Some(&header_indices), &actions)? {
+                MenuChoice::Back => return Ok(false),
+                MenuChoice::Quit => return Ok(true),
+                MenuChoice::Action('r', _) => break,
+                MenuChoice::Action('v', i) => {
+                    last = Some(i);
+                    if speak::read_aloud(cfg, &utterances(&labels))? {
+                        return Ok(true);
+                    }
+                }
+                MenuChoice::Index(i) => {
+                    last = Some(i);
+                    if let Some(story) = &rows[i]
+                        && select_story(cfg, history, story)?
+                    {
+                        return Ok(true);
+                    }
+                }
+                MenuChoice::Action(..) | MenuChoice::Refresh => {}
+            }
+        }
+    }
+}
+
+// Every line as shown, said without its styling and "==" / "-" decorations
+fn utterances(labels: &[String]) -> Vec<Utterance> {
+    labels
+        .iter()
+        .map(|l| {
+            let plain = console::strip_ansi_codes(l);
+            let said = plain.trim().trim_matches('=').trim().trim_start_matches("- ").to_string();
+            Utterance { shown: l.clone(), said }
+        })
+        .filter(|u| !u.said.is_empty())
+        .collect()
+}
+
+// "12°C, partly cloudy · 8 to 15°C · 20% chance of rain · wind 9 km/h"
+fn weather_line(w: &Weather) -> String {
+    let unit = sanitize_for_terminal(&w.temperature_unit);
+    let mut parts = vec![format!("{:.0}{}, {}", w.temperature, unit, condition(w.condition))];
+    if let (Some(low), Some(high)) = (w.low, w.high) {
+        parts.push(tr!("morning.low_high", format!("{:.0}", low), format!("{:.0}{}", high, unit)));
+    }
+    if let Some(rain) = w.rain_chance {
+        parts.push(tr!("morning.rain", format!("{:.0}", rain)));
+    }
+    if let Some(wind) = w.wind {
+        parts.push(tr!("morning.wind", format!("{:.0}", wind), sanitize_for_terminal(&w.wind_unit)));
+    }
+    parts.join(" · ")
+}
+
+fn condition(c: Condition) -> &'static str {
+    match c {
+        Condition::Clear => tr!("weather.clear"),
+        Condition::PartlyCloudy => tr!("weather.partly_cloudy"),
+        Condition::Overcast => tr!("weather.overcast"),
+        Condition::Fog => tr!("weather.fog"),
+        Condition::Drizzle => tr!("weather.drizzle"),
+        Condition::Rain => tr!("weather.rain"),
+        Condition::Snow => tr!("weather.snow"),
+        Condition::Thunderstorm => tr!("weather.thunderstorm"),
+        Condition::Unknown => tr!("weather.unknown"),
+    }
+}
+
+// "tech" as "Tech"
+fn capitalized(s: &str) -> String {
+    let mut chars = s.chars();
+    chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
@@ -0,0 +1,99 @@
+use super::{detail, select_story, story_title, with_icon};
+use crate::ui::{prompt_index, MenuChoice};
+use anyhow::Result;
+use news_cli::config::RuntimeConfig;
+use news_cli::history::SharedHistory;
+use news_cli::news::Story;
+use news_cli::queue::Queue;
+use news_cli::tr;
+use news_cli::util::sanitize::sanitize_for_terminal;
+
+/// Main-menu entry with the queue's length; `None` while it is empty.
+pub fn menu_label() -> Option<String> {
+    let queued = Queue::load().items().len();
+    (queued > 0).then(|| tr!("menu.queue", queued))
+}
+
+/// The 'l' action of story lists: queue `story` to read later, or take it off the queue.
+/// Returns what happened, for the detail view's status line.
+pub(super) fn toggle(story: &Story) -> String {
+    let mut queue = Queue::load();
+    let queued = queue.toggle(story);
+    match queue.save() {
+        Err(e) => tr!("queue.save_failed", e),
+        Ok(()) if queued => tr!("queue.added", queue.items().len()),
+        Ok(()) => tr!("queue.removed").to_string(),
+    }
+}
+
+/// Opening a story takes it off the queue.
+pub(super) fn opened(link: &str) {
+    let mut queue = Queue::load();
+    if queue.remove(link)
+        && let Err(e) = queue.save()
+    {
+        eprintln!("{}", tr!("queue.save_failed", e));
+    }
+}
+
+/// The queued stories, next first: Enter reads one (and so takes it off), '+' / '-' move
+/// it. Returns `true` if the user quit; goes back once the queue is empty.
+pub fn show(cfg: &RuntimeConfig, history: &SharedHistory) -> Result<bool> {
+    let mut last: Option<usize> = None;
+    loop {
+        let mut queue = Queue::load();
+        if queue.items().is_empty() {
+            return Ok(false);
+        }
+        let labels: Vec<String> = queue
+            .items()
+            .iter()
+            .map(|q| {
+                let source = with_icon(cfg, &q.story.source, format!("[{}]", sanitize_for_terminal(&q.story.source)));
+                format!("{} {}", source, story_title(cfg, &q.story))
+            })
+            .collect();
+        last = last.map(|i| i.min(labels.len() - 1));
+
+        match prompt_index(
+            tr!("queue.prompt"),
+            &labels,
+            last,
+            cfg.header.as_deref(),
+            None,
+            &[
+                ('i', tr!("news.details")),
+                ('+', tr!("queue.up")),
+                ('-', tr!("queue.down")),
+                ('l', tr!("queue.remove")),
+            ],
+        )? {
+            MenuChoice::Back => return Ok(false),
+            MenuChoice::Quit => return Ok(true),
+            MenuChoice::Action(key @ ('+' | '-'), i) => {
+                last = Some(queue.shift(i, key == '+'));
+                if let Err(e) = queue.save() {
+                    eprintln!("{}", tr!("queue.save_failed", e));
+                }
+            }
+            MenuChoice::Action('l', i) => {
+                last = Some(i);
+                toggle(&queue.items()[i].story);
+            }
+            MenuChoice::Action('i', i) => {
+                last = Some(i);
+                if detail::show(cfg, history, &queue.items()[i].story)? {
+                    return Ok(true);
+                }
+            }
+            MenuChoice::Index(i) => {
+                // The next story moves up into its place
+                last = Some(i);
+                if select_story(cfg, history, &queue.items()[i].story)? {
+                    return Ok(true);
+                }
+            }
+            MenuChoice::Action(..) | MenuChoice::Refresh => {}
+        }
+    }
+}
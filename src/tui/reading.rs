@@ -0,0 +1,139 @@
+use super::detail::wrap;
+use crate::ui::{self, read_key_or_resize};
+use anyhow::Result;
+use console::{style, Key, Term};
+use news_cli::config::RuntimeConfig;
+use news_cli::reading::{self, Summary};
+use news_cli::tr;
+use news_cli::util::sanitize::sanitize_for_terminal;
+use unicode_width::UnicodeWidthStr;
+
+// Sources listed with a bar; the rest are summed into one line
+const MAX_SOURCES: usize = 8;
+
+/// Opens per source, busiest days and most-read topics over the configured periods.
+/// Returns `true` if the user quit.
+pub fn show(cfg: &RuntimeConfig) -> Result<bool> {
+    let term = Term::stdout();
+    let events = reading::load();
+    // Configured periods (days), then all time
+    let mut periods: Vec<Option<u32>> = cfg.reading_stats.periods.iter().map(|&d| Some(d)).collect();
+    periods.push(None);
+    let mut current = 0;
+    loop {
+        let since = periods[current].map(|d| time::OffsetDateTime::now_utc().unix_timestamp() - i64::from(d) * 86_400);
+        let summary = reading::summarize(&events, since, 12);
+        term.clear_screen()?;
+        render(cfg, &summary, &periods, current, &term);
+        let Some(key) = read_key_or_resize(&term)? else { continue };
+        match key {
+            Key::Tab | Key::ArrowRight => current = (current + 1) % periods.len(),
+            Key::BackTab | Key::ArrowLeft => current = (current + periods.len() - 1) % periods.len(),
+            Key::Char('?') => ui::show_keys(
+                tr!("menu.reading_stats"),
+                &[
+                    (
+                        tr!("reading.periods"),
+                        ui::key_rows(&[("Tab / →", tr!("reading.next_period")), ("Shift-Tab / ←", tr!("reading.previous_period"))]),
+                    ),
+                    ui::general_keys(),
+                ],
+            )?,
+            Key::Char('b') | Key::Char('B') | Key::Escape => return Ok(false),
+            Key::Char('q') | Key::Char('Q') | Key::CtrlC => return Ok(true),
+            _ => {}
+        }
+    }
+}
+
+fn period_label(days: Option<u32>) -> String {
+    match days {
+        Some(1) => tr!("reading.one_day").to_string(),
+        Some(d) => tr!("reading.days", d),
+        None => tr!("reading.all_time").to_string(),
+    }
+}
+
+fn render(cfg: &RuntimeConfig, s: &Summary, periods: &[Option<u32>], current: usize, term: &Term) {
+    let width = (term.size().1 as usize).clamp(20, 200);
+    if let Some(h) = cfg.header.as_deref() {
+        println!("{}", h);
+    }
+    if let Some(b) = ui::breadcrumb() {
+        println!("{}", b);
+    }
+    println!("{}", style(tr!("reading.title", s.opens)).bold());
+    let tabs: Vec<String> = periods
+        .iter()
+        .enumerate()
+        .map(|(i, &p)| {
+            let label = period_label(p);
+            if i == current { style(format!("[{}]", label)).reverse().to_string() } else { format!(" {} ", label) }
+        })
+        .collect();
+    println!("{}", tabs.join(" "));
+    println!();
+    if s.opens == 0 {
+        if cfg.reading_stats.track {
+            println!("{}", tr!("reading.nothing_opened"));
+        } else {
+            println!("{}", tr!("reading.not_tracking"));
+        }
+    } else {
+        render_sources(s, width);
+        println!();
+        println!("{}", style(tr!("reading.busiest_days")).bold());
+        let weekdays: Vec<&str> = tr!("reading.weekdays").split_whitespace().collect();
+        let mut days: Vec<(usize, usize)> = s.per_weekday.iter().copied().enumerate().filter(|&(_, n)| n > 0).collect();
+        days.sort_by_key(|&(d, n)| (std::cmp::Reverse(n), d));
+        let days: Vec<String> = days.iter().map(|&(d, n)| format!("{} {}", weekdays.get(d).unwrap_or(&"?"), n)).collect();
+        for line in wrap(&days.join(" · "), width - 2) {
+            println!("  {}", line);
+        }
+        println!();
+        println!("{}", style(tr!("reading.time_of_day")).bold());
+        let slots = [
+            (tr!("reading.night"), 0..6),
+            (tr!("reading.morning"), 6..12),
+            (tr!("reading.afternoon"), 12..18),
+            (tr!("reading.evening"), 18..24),
+        ];
+        let times: Vec<String> = slots
+            .into_iter()
+            .map(|(name, hours)| (name, s.per_hour[hours].iter().sum::<usize>()))
+            .filter(|&(_, n)| n > 0)
+            .map(|(name, n)| format!("{} {}", name, n))
+            .collect();
+        for line in wrap(&times.join(" · "), width - 2) {
+            println!("  {}", line);
+        }
+        println!();
+        println!("{}", style(tr!("reading.topics")).bold());
+        let topics: Vec<String> = s.topics.iter().map(|(w, n)| format!("{} ({})", sanitize_for_terminal(w), n)).collect();
+        for line in wrap(&topics.join(", "), width - 2) {
+            println!("  {}", line);
+        }
+    }
+    println!();
+    println!("{}", tr!("reading.footer"));
+}
+
+fn render_sources(s: &Summary, width: usize) {
+    println!("{}", style(tr!("reading.per_source")).bold());
+    let shown = &s.per_source[..s.per_source.len().min(MAX_SOURCES)];
+    let names: Vec<String> = shown.iter().map(|(name, _)| sanitize_for_terminal(name)).collect();
+    let name_w = names.iter().map(|n| n.width()).max().unwrap_or(0).min(width / 3);
+    let most = shown.first().map(|(_, n)| *n).unwrap_or(1).max(1);
+    let bar_w = width.saturating_sub(name_w + 10).max(1);
+    let theme = crate::theme::current();
+    for ((source, n), name) in shown.iter().zip(names) {
+        let name = wrap(&name, name_w).into_iter().next().unwrap_or_default();
+        let pad = name_w.saturating_sub(name.width());
+        let bar = "█".repeat((n * bar_w).div_ceil(most));
+        println!("  {}{} {} {}", theme.feed_header(source, name), " ".repeat(pad), bar, n);
+    }
+    let rest: usize = s.per_source.iter().skip(MAX_SOURCES).map(|(_, n)| n).sum();
+    if rest > 0 {
+        println!("  {}", tr!("reading.more_sources", s.per_source.len() - MAX_SOURCES, rest));
+    }
+}
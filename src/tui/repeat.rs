@@ -0,0 +1,59 @@
+//! What '.' in the news list does again: the last story opened, the last filter typed, or
+//! fetching the feeds that failed last. Recorded as the screens go; only the newest few are
+//! kept.
+use news_cli::news::{Diagnostic, Fetched};
+use std::sync::Mutex;
+
+/// An action '.' can repeat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Action {
+    /// A story opened (in the browser, player or detail view), by link
+    Open(String),
+    /// A list filtered with '/' for this text
+    Search(String),
+    /// Feeds that failed to fetch, by name
+    Refetch(Vec<String>),
+}
+
+const KEPT: usize = 16;
+
+static DONE: Mutex<Vec<Action>> = Mutex::new(Vec::new());
+
+/// Note `action` as the newest; doing the same thing twice is one entry.
+pub(super) fn record(action: Action) {
+    let mut done = DONE.lock().unwrap();
+    if done.last() == Some(&action) {
+        return;
+    }
+    done.push(action);
+    if done.len() > KEPT {
+        done.remove(0);
+    }
+}
+
+/// The newest action, for '.'.
+pub(super) fn last() -> Option<Action> {
+    DONE.lock().unwrap().last().cloned()
+}
+
+/// Note the feeds `fetched` couldn't get, for '.' to try again.
+pub(super) fn record_failures(fetched: &Fetched) {
+    let failed: Vec<String> = fetched
+        .diagnostics
+        .iter()
+        .filter_map(|d| match d {
+            Diagnostic::Failed { feed, .. } => Some(feed.clone()),
+            _ => None,
+        })
+        .collect();
+    if !failed.is_empty() {
+        record(Action::Refetch(failed));
+    }
+}
+
+/// Note the filter of the list just left, if one was typed.
+pub(super) fn record_filter() {
+    if let Some(filter) = crate::ui::last_position().filter.filter(|f| !f.is_empty()) {
+        record(Action::Search(filter));
+    }
+}
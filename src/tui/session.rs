@@ -0,0 +1,107 @@
+//! Where the user was on the News screen, kept in `session.json` in the state directory so
+//! the next launch can reopen there (`restore_session = true`).
+use crate::ui;
+use news_cli::tr;
+use news_cli::util::atomic::write_atomic;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// The selected list entry; stories are found again by link, so a refetch doesn't matter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum Selected {
+    Story(String),
+    Source(String),
+}
+
+// What a News screen list shows, so the highlighted row can be saved at any moment (even
+// from the Ctrl-C handler)
+struct Showing {
+    source_list: Option<String>,
+    entries: Vec<Option<Selected>>,
+    // The row last chosen, for number-entry mode where nothing is highlighted
+    chosen: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(super) struct Session {
+    /// Feed whose full list was open, if any.
+    #[serde(default)]
+    pub source_list: Option<String>,
+    #[serde(default)]
+    pub selected: Option<Selected>,
+    /// Type-ahead filter of that list.
+    #[serde(default)]
+    pub filter: Option<String>,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+// The list on screen; None once the user has left the News screen
+static CURRENT: Mutex<Option<Showing>> = Mutex::new(None);
+// Loaded at startup and taken by the first News screen
+static RESTORE: Mutex<Option<Session>> = Mutex::new(None);
+
+/// Start tracking the position and load the last session; returns whether it ended on the
+/// News screen.
+pub fn start() -> bool {
+    ENABLED.store(true, Ordering::SeqCst);
+    let saved: Option<Session> = file_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok());
+    let found = saved.is_some();
+    *RESTORE.lock().unwrap() = saved;
+    found
+}
+
+/// The session to reopen, once.
+pub(super) fn take_restore() -> Option<Session> {
+    RESTORE.lock().unwrap().take()
+}
+
+/// A News screen list is about to be shown: `source_list` for a feed's full list, else the
+/// grouped one; `entries` per row.
+pub(super) fn showing(source_list: Option<&str>, entries: Vec<Option<Selected>>, chosen: Option<usize>) {
+    let source_list = source_list.map(String::from);
+    *CURRENT.lock().unwrap() = Some(Showing { source_list, entries, chosen });
+}
+
+/// The user went back to the main menu.
+pub(super) fn leave() {
+    *CURRENT.lock().unwrap() = None;
+}
+
+fn current() -> Option<Session> {
+    let current = CURRENT.lock().unwrap_or_else(|e| e.into_inner());
+    let showing = current.as_ref()?;
+    let pos = ui::last_position();
+    let selected = pos.selected.or(showing.chosen).and_then(|i| showing.entries.get(i).cloned().flatten());
+    Some(Session { source_list: showing.source_list.clone(), selected, filter: pos.filter })
+}
+
+/// Write the current position, or remove the file if the user left the News screen.
+pub fn save() {
+    if !ENABLED.load(Ordering::SeqCst) {
+        return;
+    }
+    let Some(path) = file_path() else { return };
+    let result = match current() {
+        Some(session) => serde_json::to_string(&session)
+            .map_err(io::Error::other)
+            .and_then(|json| write_atomic(&path, json.as_bytes(), false)),
+        None => match fs::remove_file(&path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        },
+    };
+    if let Err(e) = result {
+        eprintln!("{}", tr!("session.save_failed", e));
+    }
+}
+
+fn file_path() -> Option<PathBuf> {
+    news_cli::paths::state_dir().map(|d| d.join("session.json"))
+}
@@ -0,0 +1,89 @@
+use crate::ui::{self, prompt_index, MenuChoice};
+use anyhow::Result;
+use console::style;
+use news_cli::config::edit::{ConfigFile, Kind, SETTINGS};
+use news_cli::config::{self, RuntimeConfig};
+use news_cli::tr;
+use news_cli::util::sanitize::sanitize_for_terminal;
+
+/// Main-menu entry; `None` without a config.toml to edit (built-in defaults, single feed).
+pub fn menu_label(cfg: &RuntimeConfig) -> Option<String> {
+    cfg.path.as_ref().map(|_| tr!("menu.settings").to_string())
+}
+
+/// The key settings of config.toml as a list: Enter edits one (flags switch), 'x' removes
+/// it so the default applies. Changes are written back at once, comments and all, and
+/// `cfg` is reloaded; feeds stay as they were. Returns `true` if the user quit.
+pub fn show(cfg: &mut RuntimeConfig) -> Result<bool> {
+    let Some(path) = cfg.path.clone() else { return Ok(false) };
+    let mut last: Option<usize> = None;
+    let mut status = String::new();
+    loop {
+        let file = ConfigFile::open(&path)?;
+        let width = SETTINGS.iter().map(|s| s.key.len()).max().unwrap_or(0);
+        let labels: Vec<String> = SETTINGS
+            .iter()
+            .map(|s| match file.get(s.key) {
+                Some(v) => format!("{:<width$}  {}", s.key, sanitize_for_terminal(&v)),
+                None => format!("{:<width$}  {}", s.key, style(tr!("settings.default")).dim()),
+            })
+            .collect();
+        let prompt = match status.is_empty() {
+            true => tr!("settings.prompt", path.display()),
+            false => format!("{} {}", tr!("settings.prompt", path.display()), status),
+        };
+        let choice = prompt_index(&prompt, &labels, last, cfg.header.as_deref(), None, &[('x', tr!("settings.reset"))])?;
+        // Kept over redraws (the file watcher wakes the screen after a save)
+        if !matches!(choice, MenuChoice::Refresh) {
+            status.clear();
+        }
+        let (i, input) = match choice {
+            MenuChoice::Back => return Ok(false),
+            MenuChoice::Quit => return Ok(true),
+            MenuChoice::Action('x', i) => (i, String::new()),
+            MenuChoice::Index(i) => {
+                let setting = &SETTINGS[i];
+                let current = file.get(setting.key).unwrap_or_default();
+                let input = match setting.kind {
+                    Kind::Flag => (current != "true").to_string(),
+                    Kind::Choice(words) => {
+                        let prompt = tr!("settings.edit_choice", setting.key, words.join(", "));
+                        match ui::prompt_text(&prompt, &current)? {
+                            Some(input) => input,
+                            None => continue,
+                        }
+                    }
+                    _ => match ui::prompt_text(&tr!("settings.edit", setting.key), &current)? {
+                        Some(input) => input,
+                        None => continue,
+                    },
+                };
+                (i, input)
+            }
+            MenuChoice::Action(..) | MenuChoice::Refresh => continue,
+        };
+        last = Some(i);
+        status = save(cfg, file, i, &input);
+    }
+}
+
+/// Set and write one setting, then reload; what happened, for the prompt.
+fn save(cfg: &mut RuntimeConfig, mut file: ConfigFile, i: usize, input: &str) -> String {
+    let setting = &SETTINGS[i];
+    if let Err(e) = file.set(setting, input).and_then(|_| file.save()) {
+        return tr!("settings.failed", e);
+    }
+    match config::load_file(file.path()) {
+        Ok(fresh) => {
+            crate::theme::init(&fresh.theme);
+            let feeds = std::mem::take(&mut cfg.feeds);
+            *cfg = RuntimeConfig { feeds, ..fresh };
+        }
+        Err(e) => return tr!("settings.failed", e),
+    }
+    // The catalog is set up once at startup
+    match setting.key == "language" {
+        true => tr!("settings.saved_restart").to_string(),
+        false => tr!("settings.saved").to_string(),
+    }
+}
@@ -0,0 +1,140 @@
+//! Reading a list aloud ('v') through `speak_command`, one line at a time, with keys to
+//! pause, skip and go back.
+use crate::ui::{self, read_key_until};
+use anyhow::{bail, Context, Result};
+use console::{style, Key, Term};
+use news_cli::config::RuntimeConfig;
+use news_cli::tr;
+use news_cli::util::sanitize::sanitize_for_terminal;
+use std::cell::RefCell;
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+
+/// A line to read: what the screen shows while it is read, and what is said.
+pub(super) struct Utterance {
+    pub shown: String,
+    pub said: String,
+}
+
+/// `speak_command` when unset.
+#[cfg(target_os = "macos")]
+const DEFAULT_COMMAND: &str = "say";
+#[cfg(not(target_os = "macos"))]
+const DEFAULT_COMMAND: &str = "espeak-ng";
+
+/// Read `lines` aloud in order until the end or 'b'. Space pauses (the line starts over on
+/// resuming), 'n' / right arrow skips ahead, 'p' / left arrow goes back. Returns `true` if
+/// the user quit.
+pub(super) fn read_aloud(cfg: &RuntimeConfig, lines: &[Utterance]) -> Result<bool> {
+    let term = Term::stdout();
+    let speaking: RefCell<Option<Child>> = RefCell::new(None);
+    let stop = || {
+        if let Some(mut child) = speaking.borrow_mut().take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    };
+    let mut current = 0;
+    let mut paused = false;
+    let mut problem: Option<String> = None;
+    while current < lines.len() {
+        if speaking.borrow().is_none() && !paused {
+            match speak(cfg, &lines[current].said) {
+                Ok(child) => *speaking.borrow_mut() = Some(child),
+                Err(e) => {
+                    problem = Some(format!("{:#}", e));
+                    paused = true;
+                }
+            }
+        }
+        term.clear_screen()?;
+        render(lines, current, paused, problem.as_deref());
+        // Wakes up when the line has been said
+        let said = || speaking.borrow_mut().as_mut().is_some_and(|c| !matches!(c.try_wait(), Ok(None)));
+        let key = read_key_until(&term, said)?;
+        match key {
+            None => {
+                if said() {
+                    speaking.borrow_mut().take();
+                    current += 1;
+                }
+            }
+            Some(Key::Char(' ')) => {
+                stop();
+                paused = !paused;
+                problem = None;
+            }
+            Some(Key::Char('n') | Key::Char('N') | Key::ArrowRight) => {
+                stop();
+                current += 1;
+            }
+            Some(Key::Char('p') | Key::Char('P') | Key::ArrowLeft) => {
+                stop();
+                current = current.saturating_sub(1);
+            }
+            Some(Key::Char('?')) => ui::show_keys(
+                tr!("speak.title"),
+                &[
+                    (
+                        tr!("speak.title"),
+                        ui::key_rows(&[
+                            ("Space", tr!("speak.key_pause")),
+                            ("n / →", tr!("speak.key_next")),
+                            ("p / ←", tr!("speak.key_previous")),
+                        ]),
+                    ),
+                    ui::general_keys(),
+                ],
+            )?,
+            Some(Key::Char('b') | Key::Char('B') | Key::Escape) => {
+                stop();
+                return Ok(false);
+            }
+            Some(Key::Char('q') | Key::Char('Q') | Key::CtrlC) => {
+                stop();
+                return Ok(true);
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(false)
+}
+
+fn render(lines: &[Utterance], current: usize, paused: bool, problem: Option<&str>) {
+    let state = if paused { tr!("speak.paused") } else { tr!("speak.reading") };
+    println!("{}", style(tr!("speak.progress", state, current + 1, lines.len())).bold());
+    println!();
+    println!("{}", lines[current].shown);
+    if let Some(next) = lines.get(current + 1) {
+        println!();
+        println!("{}", style(tr!("speak.next", next.shown.trim())).dim());
+    }
+    println!();
+    if let Some(problem) = problem {
+        println!("{}", tr!("speak.failed", sanitize_for_terminal(problem)));
+    }
+    print!("{}", tr!("speak.footer"));
+    let _ = std::io::stdout().flush();
+}
+
+// Start saying `text`: in place of `{text}` in the command, else on its standard input
+fn speak(cfg: &RuntimeConfig, text: &str) -> Result<Child> {
+    let cmd = cfg.speak_command.as_deref().unwrap_or(DEFAULT_COMMAND);
+    let mut parts = cmd.split_whitespace();
+    let Some(program) = parts.next() else { bail!("speak_command is empty") };
+    let args: Vec<String> = parts.map(|p| p.replace("{text}", text)).collect();
+    let piped = !cmd.contains("{text}");
+    // Its own output would scribble over the screen
+    let mut child = Command::new(program)
+        .args(&args)
+        .stdin(if piped { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("failed to start {} (install it, or set speak_command)", program))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that exits early just doesn't take it all
+        let _ = writeln!(stdin, "{}", text);
+    }
+    Ok(child)
+}
@@ -0,0 +1,91 @@
+use news_cli::bookmarks::Bookmarks;
+use news_cli::config::RuntimeConfig;
+use news_cli::history::SharedHistory;
+use news_cli::sync::{self, SyncBackend};
+use news_cli::tr;
+use std::sync::{Arc, Mutex, OnceLock};
+use tokio::task::JoinHandle;
+
+// Connected on the first visit to the news list; None without a (working) [sync] section
+static BACKEND: OnceLock<Option<Arc<dyn SyncBackend>>> = OnceLock::new();
+// Pushes still in flight, awaited by `flush` so quitting doesn't drop them
+static PENDING: Mutex<Vec<JoinHandle<()>>> = Mutex::new(Vec::new());
+
+fn backend() -> Option<Arc<dyn SyncBackend>> {
+    BACKEND.get().and_then(|b| b.clone())
+}
+
+/// Mark what the reader has read as read here, and add its starred entries to the bookmarks.
+pub(super) async fn pull(cfg: &RuntimeConfig, history: &SharedHistory) {
+    let connected = BACKEND.get_or_init(|| {
+        sync::connect(cfg).unwrap_or_else(|e| {
+            eprintln!("{:#}", e);
+            None
+        })
+    });
+    let Some(backend) = connected else { return };
+    let state = match backend.pull().await {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("{}", tr!("sync.failed", backend.name(), format!("{:#}", e)));
+            return;
+        }
+    };
+    {
+        let mut h = history.lock().unwrap();
+        for link in &state.read {
+            h.mark_as_read(link);
+        }
+    }
+    let mut bookmarks = Bookmarks::load();
+    let mut added = false;
+    for story in &state.starred {
+        added |= bookmarks.add(story);
+    }
+    if added && let Err(e) = bookmarks.save() {
+        eprintln!("{}", tr!("bookmarks.save_failed", e));
+    }
+}
+
+/// Tell the reader a story was read. Runs in the background.
+pub(super) fn push_read(link: &str) {
+    push_read_all(vec![link.to_string()]);
+}
+
+/// [`push_read`] for many stories in one request.
+pub(super) fn push_read_all(links: Vec<String>) {
+    let Some(backend) = backend() else { return };
+    if links.is_empty() {
+        return;
+    }
+    spawn(async move {
+        if let Err(e) = backend.mark_read(&links).await {
+            eprintln!("{}", tr!("sync.failed", backend.name(), format!("{:#}", e)));
+        }
+    });
+}
+
+/// Mirror a bookmark change as the reader's star. Runs in the background.
+pub(super) fn push_starred(link: &str, starred: bool) {
+    let Some(backend) = backend() else { return };
+    let link = link.to_string();
+    spawn(async move {
+        if let Err(e) = backend.set_starred(&link, starred).await {
+            eprintln!("{}", tr!("sync.failed", backend.name(), format!("{:#}", e)));
+        }
+    });
+}
+
+fn spawn(task: impl Future<Output = ()> + Send + 'static) {
+    let mut pending = PENDING.lock().unwrap();
+    pending.retain(|h| !h.is_finished());
+    pending.push(tokio::spawn(task));
+}
+
+/// Wait for pushes still in flight.
+pub async fn flush() {
+    let handles = std::mem::take(&mut *PENDING.lock().unwrap());
+    for h in handles {
+        let _ = h.await;
+    }
+}
@@ -0,0 +1,128 @@
+use super::{detail, select_story, story_title, with_icon};
+use crate::editor;
+use crate::ui::{self, prompt_index, MenuChoice};
+use anyhow::Result;
+use news_cli::config::RuntimeConfig;
+use news_cli::history::SharedHistory;
+use news_cli::news::Story;
+use news_cli::tags::{self, Tagged, Tags};
+use news_cli::tr;
+use news_cli::util::sanitize::sanitize_for_terminal;
+
+/// Main-menu entry; `None` until some story is tagged.
+pub fn menu_label() -> Option<String> {
+    (!Tags::load().items().is_empty()).then(|| tr!("menu.tags").to_string())
+}
+
+/// The 't' action of story lists: ask for the story's tags, prefilled with the current ones.
+pub(super) fn edit(story: &Story) -> Result<()> {
+    let mut tags = Tags::load();
+    let current = tags.of(&story.link).join(", ");
+    let prompt = tr!("tags.edit", sanitize_for_terminal(&story.title));
+    let Some(input) = ui::prompt_text(&prompt, &current)? else { return Ok(()) };
+    tags.set(story, tags::parse(&input));
+    if let Err(e) = tags.save() {
+        eprintln!("{}", tr!("tags.save_failed", e));
+    }
+    Ok(())
+}
+
+/// 'n' in the detail view: edit the story's note in $VISUAL/$EDITOR, or on one line without
+/// either. Returns what happened, for the status line.
+pub(super) fn edit_note(story: &Story) -> Result<String> {
+    let mut tags = Tags::load();
+    let current = tags.note(&story.link).unwrap_or_default().to_string();
+    let note = match editor::edit(&current)? {
+        Some(note) => note,
+        None => match ui::prompt_text(tr!("tags.note"), &current.replace('\n', " "))? {
+            Some(line) => line,
+            None => return Ok(String::new()),
+        },
+    };
+    tags.set_note(story, &note);
+    Ok(match tags.save() {
+        Err(e) => tr!("tags.save_failed", e),
+        Ok(()) if tags.note(&story.link).is_some() => tr!("tags.note_saved").to_string(),
+        Ok(()) if current.is_empty() => String::new(),
+        Ok(()) => tr!("tags.note_removed").to_string(),
+    })
+}
+
+/// Tagged stories, one section per tag (by name), newest tagged first, then every story
+/// with a note. Returns `true` if the user quit.
+pub fn show(cfg: &RuntimeConfig, history: &SharedHistory) -> Result<bool> {
+    let mut last: Option<usize> = None;
+    loop {
+        let tags = Tags::load();
+        let theme = crate::theme::current();
+        let mut labels = Vec::new();
+        let mut header_indices = Vec::new();
+        // The story behind each label; headers have none
+        let mut index_map: Vec<Option<&Story>> = Vec::new();
+        // (name, header, stories)
+        let mut sections: Vec<(String, String, Vec<&Tagged>)> = Vec::new();
+        for (tag, count) in tags.names() {
+            sections.push((tag.clone(), tr!("tags.header", sanitize_for_terminal(&tag), count), tags.tagged(&tag)));
+        }
+        let noted = tags.noted();
+        if !noted.is_empty() {
+            sections.push(("notes".to_string(), tr!("tags.notes_header", noted.len()), noted));
+        }
+        for (name, header, mut tagged) in sections {
+            header_indices.push(labels.len());
+            labels.push(theme.feed_header(&name, header).to_string());
+            index_map.push(None);
+            tagged.reverse();
+            for t in tagged {
+                let title = story_title(cfg, &t.story);
+                let source = with_icon(cfg, &t.story.source, format!("[{}]", sanitize_for_terminal(&t.story.source)));
+                labels.push(format!("  - {} {}", source, title));
+                index_map.push(Some(&t.story));
+            }
+        }
+        if labels.is_empty() {
+            return Ok(false);
+        }
+
+        match prompt_index(
+            tr!("tags.prompt"),
+            &labels,
+            last,
+            cfg.header.as_deref(),
+            Some(&header_indices),
+            &[('i', tr!("news.details")), ('t', tr!("news.tag")), ('n', tr!("tags.note_action"))],
+        )? {
+            MenuChoice::Back => return Ok(false),
+            MenuChoice::Quit => return Ok(true),
+            MenuChoice::Action('t', i) => {
+                last = Some(i);
+                if let Some(story) = index_map[i] {
+                    edit(story)?;
+                }
+            }
+            MenuChoice::Action('n', i) => {
+                last = Some(i);
+                if let Some(story) = index_map[i] {
+                    edit_note(story)?;
+                }
+            }
+            MenuChoice::Action('i', i) => {
+                last = Some(i);
+                if let Some(story) = index_map[i]
+                    && detail::show(cfg, history, story)?
+                {
+                    return Ok(true);
+                }
+            }
+            MenuChoice::Index(i) => {
+                last = Some(i);
+                if let Some(story) = index_map[i]
+                    && select_story(cfg, history, story)?
+                {
+                    return Ok(true);
+                }
+            }
+            MenuChoice::Action(..) | MenuChoice::Refresh => {}
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use super::{keep, spawn_fetch, 
+    detail, mark_all_seen, select_story, story_title, switch_times, time_column, times_action,
+    with_icon,
+};
+use crate::app::AppContext;
+use crate::ui::{prompt_index, read_key_or_resize, MenuChoice};
+use anyhow::Result;
+use console::Term;
+use news_cli::config::RuntimeConfig;
+use news_cli::history::SharedHistory;
+use news_cli::news::Story;
+use news_cli::tr;
+use news_cli::scoring;
+use news_cli::util::sanitize::sanitize_for_terminal;
+use std::collections::HashSet;
+use time::OffsetDateTime;
+
+// A line of the list: a topic of several stories, or one story of a topic
+#[derive(Clone, Copy)]
+enum Row {
+    Topic(usize),
+    Story(usize, usize),
+}
+
+/// The best-scoring stories across all feeds (see `[scoring]`); stories about the same event
+/// are one topic, opened and closed with Enter. Returns `true` if the user quit.
+pub async fn show(cfg: &RuntimeConfig, app: &AppContext, history: &SharedHistory) -> Result<bool> {
+    let term = Term::stdout();
+    let Some(fetched) = spawn_fetch(cfg, app, history).wait(tr!("news.fetching")).await? else { return Ok(true) };
+    let (stories, _) = keep(cfg, history, fetched);
+    let topics = scoring::rank_topics(cfg, &stories, OffsetDateTime::now_utc().unix_timestamp());
+    if topics.is_empty() {
+        let hours = cfg.scoring.window_hours.unwrap_or(24);
+        println!("{}", tr!("top.no_stories", hours));
+        while read_key_or_resize(&term)?.is_none() {}
+        return Ok(false);
+    }
+    // Only what is listed here counts as seen (collapsed topics included); the rest stays
+    // new for the News screen
+    let shown: Vec<Story> = topics.iter().flat_map(|t| &t.stories).map(|s| s.story.clone()).collect();
+    mark_all_seen(history, &shown);
+
+    let theme = crate::theme::current();
+    let mut expanded: HashSet<usize> = HashSet::new();
+    let rows = |expanded: &HashSet<usize>| -> Vec<Row> {
+        let mut rows = Vec::new();
+        for (t, topic) in topics.iter().enumerate() {
+            if topic.stories.len() == 1 {
+                rows.push(Row::Story(t, 0));
+                continue;
+            }
+            rows.push(Row::Topic(t));
+            if expanded.contains(&t) {
+                rows.extend((0..topic.stories.len()).map(|k| Row::Story(t, k)));
+            }
+        }
+        rows
+    };
+    let labels = |rows: &[Row], expanded: &HashSet<usize>| -> Vec<String> {
+        let listed: Vec<&Story> = rows
+            .iter()
+            .filter_map(|r| match *r {
+                Row::Story(t, k) => Some(&topics[t].stories[k].story),
+                Row::Topic(_) => None,
+            })
+            .collect();
+        let mut times = time_column(cfg, &listed).into_iter();
+        rows.iter()
+            .map(|r| match *r {
+                Row::Topic(t) => {
+                    let topic = &topics[t];
+                    let marker = if expanded.contains(&t) { "▾" } else { "▸" };
+                    let about = tr!("top.topic", topic.stories.len(), sanitize_for_terminal(&topic.label));
+                    let about = if topic.stories.iter().any(|s| s.story.is_new) {
+                        format!("{} {}", theme.new_badge(), about)
+                    } else {
+                        about
+                    };
+                    format!("[{:.1}] {} {}", topic.score(), marker, about)
+                }
+                Row::Story(t, k) => {
+                    let s = &topics[t].stories[k];
+                    let time = times.next().unwrap_or_default();
+                    let title = story_title(cfg, &s.story);
+                    let title = if s.story.is_new { format!("{} {}", theme.new_badge(), title) } else { title };
+                    let title = with_icon(cfg, &s.story.source, title);
+                    let source = sanitize_for_terminal(&s.story.source);
+                    if topics[t].stories.len() == 1 {
+                        format!("[{:.1}] {}{} ({})", s.score, time, title, source)
+                    } else {
+                        format!("    - {}{} ({})", time, title, source)
+                    }
+                }
+            })
+            .collect()
+    };
+    let story_at = |rows: &[Row], i: usize| match rows.get(i) {
+        Some(&Row::Story(t, k)) => Some(&topics[t].stories[k].story),
+        _ => None,
+    };
+    let mut actions = vec![('i', tr!("news.details")), ('t', tr!("news.tag")), ('l', tr!("news.queue"))];
+    actions.extend(times_action(cfg));
+    let mut last: Option<usize> = None;
+    loop {
+        let rows = rows(&expanded);
+        match prompt_index(
+            tr!("top.prompt"),
+            &labels(&rows, &expanded),
+            last,
+            cfg.header.as_deref(),
+            None,
+            &actions,
+        )? {
+            MenuChoice::Back => return Ok(false),
+            MenuChoice::Quit => return Ok(true),
+            MenuChoice::Action('i', i) => {
+                last = Some(i);
+                if let Some(s) = story_at(&rows, i)
+                    && detail::show(cfg, history, s)?
+                {
+                    return Ok(true);
+                }
+            }
+            MenuChoice::Action('t', i) => {
+                last = Some(i);
+                if let Some(s) = story_at(&rows, i) {
+                    super::tags::edit(s)?;
+                }
+            }
+            MenuChoice::Action('l', i) => {
+                last = Some(i);
+                if let Some(s) = story_at(&rows, i) {
+                    super::queue::toggle(s);
+                }
+            }
+            MenuChoice::Action('d', i) => {
+                last = Some(i);
+                switch_times();
+            }
+            MenuChoice::Index(i) => {
+                last = Some(i);
+                if let Some(&Row::Topic(t)) = rows.get(i) {
+                    if !expanded.remove(&t) {
+                        expanded.insert(t);
+                    }
+                } else if let Some(s) = story_at(&rows, i)
+                    && select_story(cfg, history, s)?
+                {
+                    return Ok(true);
+                }
+            }
+            MenuChoice::Action(..) | MenuChoice::Refresh => {}
+        }
+    }
+}
@@ -1,40 +1,190 @@
 use anyhow::{anyhow, Result};
-use console::{Key, Term};
+use console::{style, Key, Term};
 use dialoguer::Input;
+use news_cli::menu::{self, action_help, List, Styles};
+use news_cli::tr;
+use news_cli::util::sanitize::sanitize_for_terminal;
+use news_cli::util::width::{display_width, truncate_to_width};
+use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
 
-pub enum MenuChoice {
-    Back,
-    Quit,
-    Index(usize),
+pub use news_cli::menu::{ListPosition, MenuChoice};
+
+// Bumped by notify_refresh; key waits compare it like the terminal size
+static REFRESH: AtomicU64 = AtomicU64::new(0);
+
+/// A spinner frame that turns with the time, so each redraw shows the next one.
+pub fn spinner() -> char {
+    const FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+    let millis = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
+    FRAMES[(millis / 150 % FRAMES.len() as u128) as usize]
+}
+
+/// Wake whatever is waiting for a key (e.g. new stories arrived in the background).
+/// Screens just redraw; `prompt_index` and arrow navigation return [`MenuChoice::Refresh`].
+pub fn notify_refresh() {
+    REFRESH.fetch_add(1, Ordering::SeqCst);
+}
+
+fn refresh_generation() -> u64 {
+    REFRESH.load(Ordering::SeqCst)
+}
+
+// Set by resume_arrow_mode: the next prompt_index starts in arrow mode with this filter
+static RESUME: Mutex<Option<Option<String>>> = Mutex::new(None);
+// Where the current (or last) prompt_index is
+static LAST: Mutex<ListPosition> = Mutex::new(ListPosition { selected: None, filter: None });
+
+// Screens entered below the main menu, for the breadcrumb line
+static TRAIL: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Keeps a screen on the breadcrumb until dropped; see [`enter`].
+pub struct Crumb;
+
+impl Drop for Crumb {
+    fn drop(&mut self) {
+        TRAIL.lock().unwrap_or_else(|e| e.into_inner()).pop();
+    }
+}
+
+/// Add `name` to the breadcrumb shown under the header ("Main > News > BBC") while the
+/// returned guard lives.
+pub fn enter(name: &str) -> Crumb {
+    TRAIL.lock().unwrap().push(sanitize_for_terminal(name));
+    Crumb
+}
+
+/// The breadcrumb line, dimmed; `None` on the main menu.
+pub fn breadcrumb() -> Option<String> {
+    let trail = TRAIL.lock().unwrap();
+    (!trail.is_empty()).then(|| style(format!("{} > {}", tr!("menu.root"), trail.join(" > "))).dim().to_string())
+}
+
+/// One category of the '?' overlay: its name and (keys, what they do) rows.
+pub type KeyGroup = (&'static str, Vec<(String, String)>);
+
+/// Rows for a [`KeyGroup`].
+pub fn key_rows(rows: &[(&str, &str)]) -> Vec<(String, String)> {
+    rows.iter().map(|(k, what)| (k.to_string(), what.to_string())).collect()
+}
+
+/// Full-screen list of the keys a screen understands, opened with '?'; any key closes it.
+pub fn show_keys(screen: &str, groups: &[KeyGroup]) -> io::Result<()> {
+    let term = Term::stdout();
+    let width = groups.iter().flat_map(|(_, rows)| rows).map(|(k, _)| display_width(k)).max().unwrap_or(0);
+    loop {
+        term.clear_screen()?;
+        let (rows, cols) = term.size();
+        let mut lines = vec![style(tr!("keys.title", screen)).bold().to_string()];
+                for (name, group) in groups {
+            lines.push(String::new());
+            lines.push(style(name).underlined().to_string());
+            for (keys, what) in group {
+                lines.push(format!("  {}{}  {}", keys, " ".repeat(width - display_width(keys)), what));
+            }
+        }
+        lines.push(String::new());
+        lines.push(tr!("keys.return").to_string());
+        // Long rows wrap; the blank lines go first when that doesn't fit
+        let height: usize = lines.iter().map(|l| console::measure_text_width(l).max(1).div_ceil(cols.max(1) as usize)).sum();
+        let gap = height < rows as usize;
+        for line in lines.iter().filter(|l| gap || !l.is_empty()) {
+            println!("{}", line);
+        }
+        if read_key_or_resize(&term)?.is_some() {
+            return Ok(());
+        }
+    }
+}
+
+/// Back, quit and help, the same on every screen.
+pub fn general_keys() -> KeyGroup {
+    (
+        tr!("keys.general"),
+        key_rows(&[("b / Esc", tr!("keys.back")), ("q / Ctrl-C", tr!("keys.quit")), ("?", tr!("keys.help"))]),
+    )
+}
+
+/// The '?' overlay for [`prompt_menu`] and [`prompt_index`] lists.
+fn list_keys(prompt: &str, sections: bool, actions: &[(char, &str)]) -> io::Result<()> {
+    let mut groups: Vec<KeyGroup> = vec![(
+        tr!("keys.choosing"),
+        key_rows(&[
+            ("N + Enter", tr!("keys.open_n")),
+            ("↑ / ↓", tr!("keys.move")),
+            ("Home / End, PgUp / PgDn", tr!("keys.first_last")),
+            ("Enter", tr!("keys.open_highlighted")),
+            (tr!("keys.digits"), tr!("keys.go_to_n")),
+        ]),
+    )];
+    if sections {
+        groups.push((
+            tr!("keys.sections"),
+            key_rows(&[("Tab / Shift-Tab", tr!("keys.next_prev_section")), ("s", tr!("keys.jump_source"))]),
+        ));
+    }
+    groups.push((
+        tr!("keys.filtering"),
+        key_rows(&[("/", tr!("keys.filter")), ("Esc", tr!("keys.clear_filter"))]),
+    ));
+    if !actions.is_empty() {
+        let rows = actions
+            .iter()
+            .map(|(k, label)| (format!("{} / {}N + Enter", k, k), tr!("keys.action", label)))
+            .collect();
+        groups.push((tr!("keys.actions"), rows));
+    }
+    groups.push(general_keys());
+    // The prompt's first sentence names the screen, e.g. "News (b = back, ...)"
+    let screen = prompt.split(" (").next().unwrap_or(prompt);
+    show_keys(screen, &groups)
 }
 
+/// Open the next [`prompt_index`] straight in arrow-key mode (so the default item is
+/// highlighted and scrolled to), with `filter` already typed. Used to restore a session.
+pub fn resume_arrow_mode(filter: Option<String>) {
+    *RESUME.lock().unwrap() = Some(filter);
+}
+
+/// Highlighted item and filter of the current [`prompt_index`], or where the last one
+/// returned; empty unless it is in arrow-key mode.
+pub fn last_position() -> ListPosition {
+    LAST.lock().unwrap().clone()
+}
+
+/// The main menu. Before the first key, [`notify_refresh`] returns [`MenuChoice::Refresh`]
+/// so the caller can rebuild the items (e.g. after a config reload).
 pub fn prompt_menu(
     prompt: &str,
     items: &[&str],
     default: Option<usize>,
     header: Option<&str>,
 ) -> Result<MenuChoice> {
-    // Clear on menu entry
     let term = Term::stdout();
-    let _ = term.clear_screen();
-
-    // Render initial view
-    if let Some(h) = header {
-        println!("{}", h);
-    }
-    println!("{}", prompt);
-    for (i, it) in items.iter().enumerate() {
-        println!("{}: {}", i + 1, it);
-    }
-    println!("Type a number + Enter, or use arrow keys + Enter. 'b' = back, 'q' = quit.");
-
-    // First key decides input mode: arrow-navigation vs text input
-    let key = term.read_key()?;
+    let generation = refresh_generation();
+    // First key decides input mode: arrow-navigation vs text input. Redraw on resize until then.
+    let key = loop {
+        let _ = term.clear_screen();
+        print_list(
+            &term,
+            header,
+            prompt,
+            items,
+            tr!("menu.help"),
+        );
+        match read_key_or_resize(&term)? {
+            Some(Key::Char('?')) => list_keys(prompt, false, &[])?,
+            Some(k) => break k,
+            None if refresh_generation() != generation => return Ok(MenuChoice::Refresh),
+            None => {}
+        }
+    };
     match key {
         Key::ArrowUp | Key::ArrowDown | Key::Home | Key::End | Key::PageUp | Key::PageDown => {
-            return arrow_select(prompt, items, default, header, None);
+            return arrow_select(prompt, items, default, header, None, &[], None);
         }
-        Key::Char('q') | Key::Char('Q') => {
+        Key::Char('q') | Key::Char('Q') | Key::CtrlC => {
             return Ok(MenuChoice::Quit);
         }
         Key::Char('b') | Key::Char('B') => {
@@ -44,27 +194,25 @@ pub fn prompt_menu(
             if let Some(d) = default {
                 return Ok(MenuChoice::Index(d));
             }
-            return Err(anyhow!("no selection"));
+            return Err(anyhow!(tr!("list.no_selection")));
         }
         Key::Char(c) => {
             // Fall back to text input initialized with the first typed char
             let mut builder = Input::new();
-            builder = builder.with_prompt("Selection").allow_empty(true);
+            builder = builder.with_prompt(tr!("list.selection")).allow_empty(true);
             if !c.is_control() {
                 let init = c.to_string();
                 // with_initial_text is available in dialoguer 0.11
                 builder = builder.with_initial_text(init);
             }
-            let input: String = builder.interact_text()?;
-            return parse_selection(&input, items, default);
+            let Some(input) = read_selection(builder)? else { return Ok(MenuChoice::Quit) };
+            return parse_selection(&input, items, default, &[]);
         }
         _ => {
             // Unknown key -> fallback to plain text input
-            let input: String = Input::new()
-                .with_prompt("Selection")
-                .allow_empty(true)
-                .interact_text()?;
-            return parse_selection(&input, items, default);
+            let builder = Input::new().with_prompt(tr!("list.selection")).allow_empty(true);
+            let Some(input) = read_selection(builder)? else { return Ok(MenuChoice::Quit) };
+            return parse_selection(&input, items, default, &[]);
         }
     }
 }
@@ -75,24 +223,34 @@ pub fn prompt_index(
     default: Option<usize>,
     header: Option<&str>,
     header_indices: Option<&[usize]>,
+    actions: &[(char, &str)],
 ) -> Result<MenuChoice> {
     let term = Term::stdout();
-    let _ = term.clear_screen();
-    if let Some(h) = header {
-        println!("{}", h);
-    }
-    println!("{}", prompt);
-    for (i, it) in labels.iter().enumerate() {
-        println!("{}: {}", i + 1, it);
+    let items: Vec<&str> = labels.iter().map(|s| s.as_str()).collect();
+    *LAST.lock().unwrap() = ListPosition::default();
+    if let Some(filter) = RESUME.lock().unwrap().take() {
+        return arrow_select(prompt, &items, default, header, header_indices, actions, filter);
     }
-    println!("Type a number + Enter, or use arrow keys + Enter. 'b' = back, 'q' = quit. Tab = next section");
-
-    let key = term.read_key()?;
+    let help = tr!("list.help", action_help(actions));
+    let sections = header_indices.is_some_and(|h| !h.is_empty());
+    let generation = refresh_generation();
+    let key = loop {
+        let _ = term.clear_screen();
+        print_list(&term, header, prompt, &items, &help);
+        match read_key_or_resize(&term)? {
+            Some(Key::Char('?')) => list_keys(prompt, sections, actions)?,
+            Some(k) => break k,
+            None => {}
+        }
+        if refresh_generation() != generation {
+            return Ok(MenuChoice::Refresh);
+        }
+    };
     match key {
         Key::ArrowUp | Key::ArrowDown | Key::Home | Key::End | Key::PageUp | Key::PageDown => {
-            return arrow_select_ref(prompt, labels, default, header, header_indices);
+            return arrow_select(prompt, &items, default, header, header_indices, actions, None);
         }
-        Key::Char('q') | Key::Char('Q') => {
+        Key::Char('q') | Key::Char('Q') | Key::CtrlC => {
             return Ok(MenuChoice::Quit);
         }
         Key::Char('b') | Key::Char('B') => {
@@ -102,42 +260,156 @@ pub fn prompt_index(
             if let Some(d) = default {
                 return Ok(MenuChoice::Index(d));
             }
-            return Err(anyhow!("no selection"));
+            return Err(anyhow!(tr!("list.no_selection")));
         }
         Key::Char(c) => {
             let mut builder = Input::new();
-            builder = builder.with_prompt("Selection").allow_empty(true);
+            builder = builder.with_prompt(tr!("list.selection")).allow_empty(true);
             if !c.is_control() {
                 builder = builder.with_initial_text(c.to_string());
             }
-            let s: String = builder.interact_text()?;
-            return parse_selection(
-                &s,
-                &labels.iter().map(|s| s.as_str()).collect::<Vec<_>>(),
-                default,
-            );
+            let Some(s) = read_selection(builder)? else { return Ok(MenuChoice::Quit) };
+            return parse_selection(&s, &items, default, actions);
         }
         _ => {
-            let s: String = Input::new()
-                .with_prompt("Selection")
-                .allow_empty(true)
-                .interact_text()?;
-            return parse_selection(
-                &s,
-                &labels.iter().map(|s| s.as_str()).collect::<Vec<_>>(),
-                default,
-            );
+            let builder = Input::new().with_prompt(tr!("list.selection")).allow_empty(true);
+            let Some(s) = read_selection(builder)? else { return Ok(MenuChoice::Quit) };
+            return parse_selection(&s, &items, default, actions);
+        }
+    }
+}
+
+// Non-arrow listing: every item on its own line, cut to the terminal width so numbering
+// stays aligned.
+fn print_list(term: &Term, header: Option<&str>, prompt: &str, items: &[&str], help: &str) {
+    let cols = term.size().1 as usize;
+    if let Some(h) = header {
+        println!("{}", h);
+    }
+    if let Some(b) = breadcrumb() {
+        println!("{}", b);
+    }
+    println!("{}", prompt);
+    for (i, it) in items.iter().enumerate() {
+        let number = format!("{}: ", i + 1);
+        println!("{}{}", number, truncate_to_width(it, cols.saturating_sub(number.len())));
+    }
+    println!("{}", help);
+}
+
+/// Wait for a key, but return `None` as soon as the terminal is resized (or
+/// [`notify_refresh`] is called) so the caller can redraw. (A SIGWINCH handler would make
+/// console's read report Ctrl-C.)
+pub fn read_key_or_resize(term: &Term) -> io::Result<Option<Key>> {
+    read_key_until(term, || false)
+}
+
+/// [`read_key_or_resize`] that also returns `None` once `done` turns true (checked a few
+/// times a second), e.g. when a background fetch finishes.
+#[cfg(unix)]
+pub fn read_key_until(term: &Term, done: impl Fn() -> bool) -> io::Result<Option<Key>> {
+    let size = term.size();
+    if done() {
+        return Ok(None);
+    }
+    if !term.is_term() {
+        return read_key(term).map(Some);
+    }
+    // Input must be byte-at-a-time for poll to see a single keypress
+    let saved = tty::cbreak();
+    let generation = refresh_generation();
+    let redraw = loop {
+        let mut fd = libc::pollfd { fd: libc::STDIN_FILENO, events: libc::POLLIN, revents: 0 };
+        // SAFETY: `fd` is a valid pollfd for the duration of the call
+        let ready = unsafe { libc::poll(&mut fd, 1, 200) };
+        if ready > 0 {
+            break false;
+        }
+        if term.size() != size || refresh_generation() != generation || done() {
+            break true;
+        }
+    };
+    tty::restore(saved);
+    if redraw {
+        return Ok(None);
+    }
+    read_key(term).map(Some)
+}
+
+#[cfg(not(unix))]
+pub fn read_key_until(term: &Term, done: impl Fn() -> bool) -> io::Result<Option<Key>> {
+    if done() {
+        return Ok(None);
+    }
+    read_key(term).map(Some)
+}
+
+#[cfg(unix)]
+mod tty {
+    use std::mem::MaybeUninit;
+
+    pub fn cbreak() -> Option<libc::termios> {
+        let mut t = MaybeUninit::<libc::termios>::uninit();
+        // SAFETY: tcgetattr fully initializes `t` when it returns 0; we only modify a copy
+        unsafe {
+            if libc::tcgetattr(libc::STDIN_FILENO, t.as_mut_ptr()) != 0 {
+                return None;
+            }
+            let original = t.assume_init();
+            let mut raw = original;
+            raw.c_lflag &= !(libc::ICANON | libc::ECHO);
+            raw.c_cc[libc::VMIN] = 1;
+            raw.c_cc[libc::VTIME] = 0;
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw);
+            Some(original)
+        }
+    }
+
+    pub fn restore(saved: Option<libc::termios>) {
+        if let Some(t) = saved {
+            // SAFETY: `t` came from a successful tcgetattr on the same fd
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &t);
+            }
         }
     }
 }
 
-fn parse_selection(input: &str, items: &[&str], default: Option<usize>) -> Result<MenuChoice> {
+// console re-raises SIGINT for the shutdown handler and then reports Ctrl-C as an
+// Interrupted read; surface it as a key so menus can treat it like 'q'.
+fn read_key(term: &Term) -> io::Result<Key> {
+    match term.read_key() {
+        Err(e) if e.kind() == io::ErrorKind::Interrupted => Ok(Key::CtrlC),
+        other => other,
+    }
+}
+
+/// A line of free text, starting from `initial`; `None` means the user hit Ctrl-C.
+pub fn prompt_text(prompt: &str, initial: &str) -> Result<Option<String>> {
+    read_selection(Input::new().with_prompt(prompt).with_initial_text(initial).allow_empty(true))
+}
+
+/// Text-mode selection; `None` means the user hit Ctrl-C.
+fn read_selection(input: Input<String>) -> Result<Option<String>> {
+    match input.interact_text() {
+        Ok(s) => Ok(Some(s)),
+        Err(dialoguer::Error::IO(e)) if e.kind() == io::ErrorKind::Interrupted => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn parse_selection(
+    input: &str,
+    items: &[&str],
+    default: Option<usize>,
+    actions: &[(char, &str)],
+) -> Result<MenuChoice> {
     let s = input.trim();
     if s.is_empty() {
         if let Some(d) = default {
             return Ok(MenuChoice::Index(d));
         }
-        return Err(anyhow!("no selection"));
+        return Err(anyhow!(tr!("list.no_selection")));
     }
     if s.eq_ignore_ascii_case("q") {
         return Ok(MenuChoice::Quit);
@@ -145,11 +417,29 @@ fn parse_selection(input: &str, items: &[&str], default: Option<usize>) -> Resul
     if s.eq_ignore_ascii_case("b") {
         return Ok(MenuChoice::Back);
     }
+    // "i5" = action 'i' on item 5; a bare "i" acts on the current item
+    let mut chars = s.chars();
+    if let Some(key) = chars.next().map(|c| c.to_ascii_lowercase())
+        && actions.iter().any(|(k, _)| *k == key)
+    {
+        if chars.as_str().trim().is_empty() && !items.is_empty() {
+            return Ok(MenuChoice::Action(key, default.unwrap_or(0)));
+        }
+        let idx: usize = chars
+            .as_str()
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!(tr!("list.invalid_selection")))?;
+        if idx == 0 || idx > items.len() {
+            return Err(anyhow!(tr!("list.out_of_range")));
+        }
+        return Ok(MenuChoice::Action(key, idx - 1));
+    }
     let idx: usize = s
         .parse::<usize>()
-        .map_err(|_| anyhow!("invalid selection"))?;
+        .map_err(|_| anyhow!(tr!("list.invalid_selection")))?;
     if idx == 0 || idx > items.len() {
-        return Err(anyhow!("out of range"));
+        return Err(anyhow!(tr!("list.out_of_range")));
     }
     Ok(MenuChoice::Index(idx - 1))
 }
@@ -160,110 +450,60 @@ fn arrow_select(
     default: Option<usize>,
     header: Option<&str>,
     header_indices: Option<&[usize]>,
+    actions: &[(char, &str)],
+    filter: Option<String>,
 ) -> Result<MenuChoice> {
-    let term = Term::stdout();
     let mut sel = default.unwrap_or(0).min(items.len().saturating_sub(1));
-    let mut top: usize = 0;
-    loop {
-        term.clear_screen()?;
-        if let Some(h) = header {
-            println!("{}", h);
-        }
-        println!("{}", prompt);
-
-        let (rows_u16, _cols_u16) = term.size();
-        let rows: usize = rows_u16 as usize;
-        let reserved: usize = 2 + if header.is_some() { 1 } else { 0 }; // header + prompt + help
-        let mut max_visible: usize = rows.saturating_sub(reserved);
-        if max_visible < 3 {
-            max_visible = 3;
-        }
-        if max_visible > items.len() {
-            max_visible = items.len();
-        }
+    // Type-ahead filter; `Some` while the user is typing one (started with '/')
+    let mut filter = filter;
+    let theme = crate::theme::current();
+    let list = List {
+        prompt,
+        items,
+        header,
+        crumb: breadcrumb(),
+        sections: header_indices,
+        actions,
+        styles: Styles { selection: theme.selection.clone(), header: theme.header.clone() },
+    };
+    let mut term = Console { term: Term::stdout(), generation: refresh_generation() };
+    let choice = menu::navigate(&mut term, &list, &mut sel, &mut filter).map_err(Into::into);
+    *LAST.lock().unwrap() = ListPosition { selected: Some(sel), filter: filter.filter(|f| !f.is_empty()) };
+    choice
+}
 
-        // keep selection in viewport
-        if sel < top {
-            top = sel;
-        }
-        let end = top + max_visible;
-        if sel >= end {
-            top = sel + 1 - max_visible;
-        }
+// The real terminal for menu::navigate
+struct Console {
+    term: Term,
+    generation: u64,
+}
 
-        let end = (top + max_visible).min(items.len());
-        for i in top..end {
-            if i == sel {
-                println!("> {}: {}", i + 1, items[i]);
-            } else {
-                println!("  {}: {}", i + 1, items[i]);
-            }
-        }
-        println!("Use arrows + Enter. 'b' = back, 'q' = quit. Tab = next section");
+impl menu::Terminal for Console {
+    fn size(&self) -> (usize, usize) {
+        let (rows, cols) = self.term.size();
+        (rows as usize, cols as usize)
+    }
 
-        match term.read_key()? {
-            Key::ArrowUp => {
-                if sel > 0 {
-                    sel -= 1;
-                }
-            }
-            Key::ArrowDown => {
-                if sel + 1 < items.len() {
-                    sel += 1;
-                }
-            }
-            Key::Home => {
-                sel = 0;
-            }
-            Key::End => {
-                if !items.is_empty() {
-                    sel = items.len() - 1;
-                }
-            }
-            Key::PageUp => {
-                let step: usize = max_visible.saturating_sub(1).max(1);
-                sel = sel.saturating_sub(step);
-            }
-            Key::PageDown => {
-                let step: usize = max_visible.saturating_sub(1).max(1);
-                sel = (sel + step).min(items.len().saturating_sub(1));
-            }
-            Key::Tab => {
-                if let Some(hidx) = header_indices {
-                    if !hidx.is_empty() {
-                        // find first header strictly greater than sel
-                        let mut next = hidx[0];
-                        for &idx in hidx {
-                            if idx > sel {
-                                next = idx;
-                                break;
-                            }
-                        }
-                        sel = next.min(items.len().saturating_sub(1));
-                    }
-                }
-            }
-            Key::Enter => {
-                return Ok(MenuChoice::Index(sel));
-            }
-            Key::Char('q') | Key::Char('Q') => {
-                return Ok(MenuChoice::Quit);
-            }
-            Key::Char('b') | Key::Char('B') | Key::Escape => {
-                return Ok(MenuChoice::Back);
-            }
-            _ => {}
-        }
+    fn next_input(&mut self) -> io::Result<menu::Input> {
+        Ok(match read_key_or_resize(&self.term)? {
+            Some(key) => menu::Input::Key(key),
+            None if refresh_generation() != self.generation => menu::Input::Refresh,
+            None => menu::Input::Redraw,
+        })
     }
-}
 
-fn arrow_select_ref(
-    prompt: &str,
-    labels: &[String],
-    default: Option<usize>,
-    header: Option<&str>,
-    header_indices: Option<&[usize]>,
-) -> Result<MenuChoice> {
-    let items: Vec<&str> = labels.iter().map(|s| s.as_str()).collect();
-    arrow_select(prompt, &items, default, header, header_indices)
+    fn draw(&mut self, lines: &[String]) -> io::Result<()> {
+        // No trailing newline: filling the last row would scroll the top line away
+        self.term.clear_screen()?;
+        print!("{}", lines.join("\n"));
+        io::Write::flush(&mut io::stdout())
+    }
+
+    fn show_help(&mut self, list: &List) -> io::Result<()> {
+        list_keys(list.prompt, list.has_sections(), list.actions)
+    }
+
+    fn moved(&mut self, position: &ListPosition) {
+        *LAST.lock().unwrap() = position.clone();
+    }
 }
@@ -0,0 +1,47 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Write `contents` so that `path` always holds either the old or the new data, never a torn
+/// mix: write a sibling temp file, fsync it, then rename over the target. With `keep_backup`,
+/// the previous version is first copied to `<path>.bak`.
+pub fn write_atomic(path: &Path, contents: &[u8], keep_backup: bool) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = sibling(path, "tmp");
+    {
+        let mut f = File::create(&tmp)?;
+        f.write_all(contents)?;
+        f.sync_all()?;
+    }
+    if keep_backup && path.is_file() {
+        fs::copy(path, backup_path(path))?;
+    }
+    fs::rename(&tmp, path)
+}
+
+/// Run `f` holding an exclusive lock on `<path>.lock`, so read-modify-write cycles on `path`
+/// from several processes don't interleave. Blocks while another process holds it.
+pub fn with_lock<T>(path: &Path, f: impl FnOnce() -> io::Result<T>) -> io::Result<T> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let lock = File::options().create(true).truncate(false).write(true).open(sibling(path, "lock"))?;
+    lock.lock()?;
+    // Closing the file releases the lock
+    f()
+}
+
+pub fn backup_path(path: &Path) -> PathBuf {
+    sibling(path, "bak")
+}
+
+fn sibling(path: &Path, ext: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(ext);
+    path.with_file_name(name)
+}
@@ -0,0 +1,108 @@
+//! Line charts drawn with box-drawing characters, e.g. the Stats screen's yield curve:
+//! points evenly spaced left to right, values on a y axis scaled to their range.
+
+/// What a plotted cell shows, so the caller can color it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    /// The `n`th value.
+    Point(usize),
+    /// The line from the `n`th value to the next one that is present.
+    Segment(usize),
+}
+
+/// `values` as a chart `width` columns wide and `height` rows high (plus the x axis and a
+/// row of `labels` under the points), y-axis labels showing `decimals` places. Missing
+/// values get no point; the line joins their neighbours. `paint` styles each plotted
+/// character. Empty if there is nothing to plot.
+pub fn line_chart(
+    labels: &[String],
+    values: &[Option<f64>],
+    width: usize,
+    height: usize,
+    decimals: usize,
+    paint: impl Fn(Mark, char) -> String,
+) -> Vec<String> {
+    let present: Vec<(usize, f64)> = values.iter().enumerate().filter_map(|(i, v)| Some((i, (*v)?))).collect();
+    if present.is_empty() || height == 0 {
+        return Vec::new();
+    }
+    let max = present.iter().map(|(_, v)| *v).fold(f64::MIN, f64::max);
+    let min = present.iter().map(|(_, v)| *v).fold(f64::MAX, f64::min);
+    let (top, bottom) = (format!("{:.*}", decimals, max), format!("{:.*}", decimals, min));
+    let axis_width = top.chars().count().max(bottom.chars().count());
+    // At least a column per value
+    let plot_width = width.saturating_sub(axis_width + 1).max(values.len());
+    let column = |i: usize| match values.len() {
+        1 => plot_width / 2,
+        n => i * (plot_width - 1) / (n - 1),
+    };
+    let row = |v: f64| {
+        if max == min {
+            height / 2
+        } else {
+            ((max - v) / (max - min) * (height - 1) as f64).round() as usize
+        }
+    };
+
+    let mut grid: Vec<Vec<Option<(char, Mark)>>> = vec![vec![None; plot_width]; height];
+    for pair in present.windows(2) {
+        let ((a, va), (b, vb)) = (pair[0], pair[1]);
+        let (x0, x1) = (column(a), column(b));
+        let (r0, r1) = (row(va) as f64, row(vb) as f64);
+        let mark = Mark::Segment(a);
+        let mut prev = row(va);
+        for x in x0 + 1..=x1 {
+            let r = (r0 + (r1 - r0) * (x - x0) as f64 / (x1 - x0) as f64).round() as usize;
+            // Steep stretches: a vertical run in the column before
+            let (high, low) = (prev.min(r), prev.max(r));
+            for cells in grid.iter_mut().take(low).skip(high + 1) {
+                cells[x - 1] = Some(('│', mark));
+            }
+            let ch = match r.cmp(&prev) {
+                std::cmp::Ordering::Equal => '─',
+                std::cmp::Ordering::Less => '╱',
+                std::cmp::Ordering::Greater => '╲',
+            };
+            grid[r][x] = Some((ch, mark));
+            prev = r;
+        }
+    }
+    for &(i, v) in &present {
+        grid[row(v)][column(i)] = Some(('●', Mark::Point(i)));
+    }
+
+    let mut lines: Vec<String> = Vec::with_capacity(height + 2);
+    for (r, cells) in grid.iter().enumerate() {
+        let (label, tick) = match r {
+            0 => (top.as_str(), '┤'),
+            _ if r == height - 1 => (bottom.as_str(), '┤'),
+            _ => ("", '│'),
+        };
+        let plotted: String = cells
+            .iter()
+            .map(|cell| match cell {
+                Some((ch, mark)) => paint(*mark, *ch),
+                None => " ".to_string(),
+            })
+            .collect();
+        lines.push(format!("{:>w$}{}{}", label, tick, plotted.trim_end(), w = axis_width));
+    }
+    lines.push(format!("{:>w$}└{}", "", "─".repeat(plot_width), w = axis_width));
+    // Each label centred under its point, dropped if it would run into the one before
+    let mut under: Vec<char> = vec![' '; plot_width];
+    let mut free_from = 0;
+    for (i, label) in labels.iter().enumerate().take(values.len()) {
+        let len = label.chars().count();
+        let start = column(i).saturating_sub(len / 2).min(plot_width.saturating_sub(len));
+        if start < free_from || start + len > plot_width {
+            continue;
+        }
+        for (k, ch) in label.chars().enumerate() {
+            under[start + k] = ch;
+        }
+        free_from = start + len + 1;
+    }
+    let under: String = under.into_iter().collect();
+    lines.push(format!("{:>w$} {}", "", under.trim_end(), w = axis_width));
+    lines
+}
@@ -0,0 +1,135 @@
+//! Story timestamps as people read them: in `display_timezone` (an IANA name such as
+//! "Europe/Berlin"), else the local timezone, laid out by `date_format` (strftime).
+//! Named zones come from the tz database, so daylight saving time is applied per date.
+use crate::tr;
+use anyhow::{anyhow, bail, Result};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Datelike, Local, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+
+/// `date_format` when none is configured.
+pub const DEFAULT_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+#[derive(Debug, Clone, Copy)]
+enum Zone {
+    Named(Tz),
+    // The system's offset rules when its zone has no IANA name
+    Local,
+}
+
+/// Where and how to show timestamps; see the module docs.
+#[derive(Debug, Clone)]
+pub struct DateDisplay {
+    zone: Zone,
+    format: String,
+}
+
+impl Default for DateDisplay {
+    fn default() -> Self {
+        DateDisplay { zone: local_zone(), format: DEFAULT_FORMAT.to_string() }
+    }
+}
+
+impl DateDisplay {
+    /// `timezone` is an IANA name, "UTC" or "local" (the default); `format` a strftime
+    /// string. Errors name what is wrong with either.
+    pub fn new(timezone: Option<&str>, format: Option<&str>) -> Result<Self> {
+        let zone = match timezone {
+            None => local_zone(),
+            Some(tz) if tz.eq_ignore_ascii_case("local") => local_zone(),
+            Some(tz) => Zone::Named(parse_zone(tz)?),
+        };
+        let format = match format {
+            Some(f) => {
+                check_format(f)?;
+                f.to_string()
+            }
+            None => DEFAULT_FORMAT.to_string(),
+        };
+        Ok(DateDisplay { zone, format })
+    }
+
+    /// `ts` (UNIX epoch seconds) in the configured zone and format.
+    pub fn format(&self, ts: i64) -> Option<String> {
+        self.format_as(ts, &self.format)
+    }
+
+    /// Like [`format`](Self::format), followed by the zone's abbreviation ("CEST") unless
+    /// the format already shows the zone.
+    pub fn format_with_zone(&self, ts: i64) -> Option<String> {
+        if self.format.contains("%Z") || self.format.contains("%z") || self.format.contains("%:z") {
+            return self.format(ts);
+        }
+        self.format_as(ts, &format!("{} %Z", self.format))
+    }
+
+    /// `ts` in the configured zone with another layout, e.g. "%Y-%m-%d" for the day.
+    pub fn format_as(&self, ts: i64, format: &str) -> Option<String> {
+        let utc = DateTime::<Utc>::from_timestamp(ts, 0)?;
+        Some(match self.zone {
+            Zone::Named(tz) => utc.with_timezone(&tz).format(format).to_string(),
+            Zone::Local => Local.from_utc_datetime(&utc.naive_utc()).format(format).to_string(),
+        })
+    }
+
+    /// The weekday (0 = Monday) and hour of `ts` in the configured zone.
+    pub fn weekday_and_hour(&self, ts: i64) -> Option<(u32, u32)> {
+        let utc = DateTime::<Utc>::from_timestamp(ts, 0)?;
+        Some(match self.zone {
+            Zone::Named(tz) => {
+                let t = utc.with_timezone(&tz);
+                (t.weekday().num_days_from_monday(), t.hour())
+            }
+            Zone::Local => {
+                let t = Local.from_utc_datetime(&utc.naive_utc());
+                (t.weekday().num_days_from_monday(), t.hour())
+            }
+        })
+    }
+}
+
+/// How long before `now` `ts` was (both UNIX epoch seconds), coarsely: "just now", "5m ago",
+/// "2h ago", "3d ago", "2w ago", "4mo ago", "1y ago". Times after `now` are "just now".
+pub fn relative(ts: i64, now: i64) -> String {
+    let secs = now.saturating_sub(ts);
+    let (minutes, hours, days) = (secs / 60, secs / 3600, secs / 86_400);
+    if minutes < 1 {
+        tr!("dates.just_now").to_string()
+    } else if hours < 1 {
+        tr!("dates.minutes_ago", minutes)
+    } else if days < 1 {
+        tr!("dates.hours_ago", hours)
+    } else if days < 14 {
+        tr!("dates.days_ago", days)
+    } else if days < 60 {
+        tr!("dates.weeks_ago", days / 7)
+    } else if days < 365 {
+        tr!("dates.months_ago", days / 30)
+    } else {
+        tr!("dates.years_ago", days / 365)
+    }
+}
+
+/// The tz database zone called `name`.
+pub fn parse_zone(name: &str) -> Result<Tz> {
+    name.parse::<Tz>()
+        .map_err(|_| anyhow!("unknown timezone \"{}\" (expected an IANA name such as \"Europe/Berlin\", or \"UTC\")", name))
+}
+
+/// Reject strftime strings chrono can't render (e.g. "%Q").
+pub fn check_format(format: &str) -> Result<()> {
+    if StrftimeItems::new(format).any(|i| matches!(i, Item::Error)) {
+        bail!("invalid date format \"{}\"", format);
+    }
+    Ok(())
+}
+
+// $TZ, else the system setting. A named zone shows abbreviations like "EST" rather than
+// bare offsets; anything else (e.g. a POSIX rule in $TZ) is left to chrono's `Local`.
+fn local_zone() -> Zone {
+    let name = match std::env::var("TZ") {
+        Ok(tz) if !tz.is_empty() => Some(tz.trim_start_matches(':').to_string()),
+        _ => iana_time_zone::get_timezone().ok(),
+    };
+    name.and_then(|n| n.parse::<Tz>().ok()).map_or(Zone::Local, Zone::Named)
+}
@@ -0,0 +1,329 @@
+//! Full-content HTML (e.g. RSS `content:encoded`) as terminal-friendly text.
+use regex::Regex;
+use std::sync::OnceLock;
+use url::Url;
+
+/// Render an HTML fragment as text. Blocks are separated by blank lines; lines inside a
+/// block (preformatted text, the footnote list) by single newlines, and long lines are
+/// left for the caller to wrap. List items start with `- ` or `1. `, quotes with `> `,
+/// `<pre>` lines are indented four spaces. `<strong>`/`<b>` and headings become `*text*`,
+/// `<em>`/`<i>` `_text_`. Links become numbered footnotes (`text[1]`) listed at the end,
+/// resolved against `base`. Scripts, styles and images without alt text are dropped.
+pub fn to_text(html: &str, base: Option<&Url>) -> String {
+    let mut r = Renderer { base, ..Renderer::default() };
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        r.text(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(end) = tag_end(rest) else {
+            // A stray '<' is text
+            r.text("<");
+            rest = &rest[1..];
+            continue;
+        };
+        r.tag(&rest[1..end]);
+        rest = &rest[end + 1..];
+    }
+    r.text(rest);
+    r.finish()
+}
+
+#[derive(Default)]
+struct Renderer<'a> {
+    base: Option<&'a Url>,
+    blocks: Vec<String>,
+    cur: String,
+    // Whitespace seen since the last word; emitted as one space before the next
+    space: bool,
+    // An emphasis marker was just opened, so no space goes between it and the text
+    opened: bool,
+    // Prefix for the current block ("- ", "2. ", with list nesting)
+    marker: String,
+    lists: Vec<Option<usize>>,
+    quote: usize,
+    pre: usize,
+    // Inside <script>, <style> and the like
+    skip: usize,
+    link: Option<String>,
+    footnotes: Vec<String>,
+}
+
+impl Renderer<'_> {
+    fn text(&mut self, raw: &str) {
+        if self.skip > 0 || raw.is_empty() {
+            return;
+        }
+        let text = decode_entities(raw);
+        if self.pre > 0 {
+            self.cur.push_str(&text);
+            return;
+        }
+        for ch in text.chars() {
+            if ch.is_whitespace() {
+                self.space = true;
+                continue;
+            }
+            if self.space && !self.cur.is_empty() && !self.opened {
+                self.cur.push(' ');
+            }
+            self.space = false;
+            self.opened = false;
+            self.cur.push(ch);
+        }
+    }
+
+    fn open_mark(&mut self, mark: &str) {
+        if self.space && !self.cur.is_empty() && !self.opened {
+            self.cur.push(' ');
+        }
+        self.space = false;
+        self.cur.push_str(mark);
+        self.opened = true;
+    }
+
+    fn close_mark(&mut self, mark: &str) {
+        if self.opened {
+            // Nothing between the markers
+            self.cur.truncate(self.cur.len() - mark.len());
+            self.opened = false;
+        } else {
+            self.cur.push_str(mark);
+        }
+    }
+
+    fn tag(&mut self, inner: &str) {
+        let closing = inner.starts_with('/');
+        let inner = inner.trim_start_matches('/');
+        let name_len = inner.find(|c: char| c.is_whitespace() || c == '/').unwrap_or(inner.len());
+        let name = inner[..name_len].to_ascii_lowercase();
+        let attrs = &inner[name_len..];
+        match name.as_str() {
+            "script" | "style" | "noscript" | "template" | "head" | "svg" => {
+                if closing {
+                    self.skip = self.skip.saturating_sub(1);
+                } else if !inner.ends_with('/') {
+                    self.skip += 1;
+                }
+            }
+            _ if self.skip > 0 => {}
+            "br" if self.pre > 0 => self.cur.push('\n'),
+            "br" | "hr" => self.flush(),
+            "p" | "div" | "section" | "article" | "header" | "footer" | "figure" | "figcaption" | "table" | "tr"
+            | "dl" | "dt" | "dd" | "aside" | "main" | "nav" => self.flush(),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                if closing {
+                    self.close_mark("*");
+                    self.flush();
+                } else {
+                    self.flush();
+                    self.open_mark("*");
+                }
+            }
+            "blockquote" => {
+                self.flush();
+                self.quote = if closing { self.quote.saturating_sub(1) } else { self.quote + 1 };
+            }
+            "pre" => {
+                self.flush();
+                self.pre = if closing { self.pre.saturating_sub(1) } else { self.pre + 1 };
+            }
+            "ul" | "ol" => {
+                self.flush();
+                if closing {
+                    self.lists.pop();
+                } else {
+                    self.lists.push((name == "ol").then_some(0));
+                }
+            }
+            "li" if !closing => {
+                self.flush();
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                self.marker = match self.lists.last_mut() {
+                    Some(Some(n)) => {
+                        *n += 1;
+                        format!("{}{}. ", indent, n)
+                    }
+                    _ => format!("{}- ", indent),
+                };
+            }
+            "li" => self.flush(),
+            "strong" | "b" => {
+                if closing { self.close_mark("*") } else { self.open_mark("*") }
+            }
+            "em" | "i" => {
+                if closing { self.close_mark("_") } else { self.open_mark("_") }
+            }
+            "a" if !closing => self.link = attr(attrs, "href").and_then(|h| self.resolve(&h)),
+            "a" => {
+                if let Some(href) = self.link.take() {
+                    let n = match self.footnotes.iter().position(|f| *f == href) {
+                        Some(i) => i + 1,
+                        None => {
+                            self.footnotes.push(href);
+                            self.footnotes.len()
+                        }
+                    };
+                    self.space = false;
+                    self.cur.push_str(&format!("[{}]", n));
+                }
+            }
+            "img" => {
+                if let Some(alt) = attr(attrs, "alt").filter(|a| !a.trim().is_empty()) {
+                    self.text(&format!(" [image: {}] ", alt.trim()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Only absolute http(s) targets are worth a footnote; anchors and scripts are not
+    fn resolve(&self, href: &str) -> Option<String> {
+        let href = decode_entities(href.trim());
+        let url = match Url::parse(&href) {
+            Ok(u) => u,
+            Err(_) => self.base?.join(&href).ok()?,
+        };
+        matches!(url.scheme(), "http" | "https").then(|| url.to_string())
+    }
+
+    fn flush(&mut self) {
+        let prefix = "> ".repeat(self.quote);
+        let block = if self.pre > 0 {
+            let body = self.cur.trim_matches('\n');
+            body.lines().map(|l| format!("{}    {}", prefix, l.trim_end())).collect::<Vec<_>>().join("\n")
+        } else {
+            let body = self.cur.trim();
+            if body.is_empty() { String::new() } else { format!("{}{}{}", prefix, self.marker, body) }
+        };
+        if !block.trim().is_empty() {
+            self.blocks.push(block);
+        }
+        self.cur.clear();
+        self.marker.clear();
+        self.space = false;
+        self.opened = false;
+    }
+
+    fn finish(mut self) -> String {
+        self.flush();
+        if !self.footnotes.is_empty() {
+            let notes: Vec<String> = self.footnotes.iter().enumerate().map(|(i, u)| format!("[{}] {}", i + 1, u)).collect();
+            self.blocks.push(notes.join("\n"));
+        }
+        self.blocks.join("\n\n")
+    }
+}
+
+/// The `src` of the first `<img>` in an HTML fragment, resolved against `base`; only
+/// http(s) URLs count.
+pub fn first_image(html: &str, base: Option<&Url>) -> Option<String> {
+    static IMG: OnceLock<Regex> = OnceLock::new();
+    let re = IMG.get_or_init(|| Regex::new(r"(?i)<img\b([^>]*)>").expect("valid regex"));
+    re.captures_iter(html).find_map(|c| {
+        let src = decode_entities(attr(&c[1], "src")?.trim());
+        let url = match Url::parse(&src) {
+            Ok(u) => u,
+            Err(_) => base?.join(&src).ok()?,
+        };
+        matches!(url.scheme(), "http" | "https").then(|| url.to_string())
+    })
+}
+
+/// Whether a URL path ends in a common image extension.
+pub fn looks_like_image(path: &str) -> bool {
+    let lower = path.to_ascii_lowercase();
+    [".jpg", ".jpeg", ".png", ".gif", ".webp"].iter().any(|ext| lower.ends_with(ext))
+}
+
+// Index of the '>' closing the tag at the start of `s`, skipping quoted attribute values
+fn tag_end(s: &str) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    for (i, c) in s.char_indices().skip(1) {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(i),
+            (None, '<') => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The value of attribute `name` in a tag's attribute text (entities not decoded).
+pub(crate) fn attr(attrs: &str, name: &str) -> Option<String> {
+    static ATTR: OnceLock<Regex> = OnceLock::new();
+    let re = ATTR.get_or_init(|| {
+        Regex::new(r#"(?i)([a-z_:][-a-z0-9_:.]*)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#).expect("valid regex")
+    });
+    re.captures_iter(attrs).find(|c| c[1].eq_ignore_ascii_case(name)).and_then(|c| {
+        c.get(2).or_else(|| c.get(3)).or_else(|| c.get(4)).map(|m| m.as_str().to_string())
+    })
+}
+
+/// Decode named entities common in feeds and all numeric ones; unknown entities stay as is.
+pub fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest[1..].find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &rest[1..end + 1];
+            let ch = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                "ndash" => Some('–'),
+                "mdash" => Some('—'),
+                "hellip" => Some('…'),
+                "lsquo" => Some('‘'),
+                "rsquo" => Some('’'),
+                "ldquo" => Some('“'),
+                "rdquo" => Some('”'),
+                "laquo" => Some('«'),
+                "raquo" => Some('»'),
+                "copy" => Some('©'),
+                "reg" => Some('®'),
+                "trade" => Some('™'),
+                "euro" => Some('€'),
+                "pound" => Some('£'),
+                "deg" => Some('°'),
+                "middot" => Some('·'),
+                "bull" => Some('•'),
+                _ => {
+                    let num = entity.strip_prefix('#')?;
+                    let code = match num.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => num.parse().ok()?,
+                    };
+                    char::from_u32(code)
+                }
+            }?;
+            Some((ch, end + 2))
+        });
+        match decoded {
+            Some((ch, len)) => {
+                out.push(ch);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
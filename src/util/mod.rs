@@ -1 +1,8 @@
+pub mod atomic;
+pub mod chart;
+pub mod dates;
+pub mod html;
 pub mod sanitize;
+pub mod tls;
+pub mod tracking;
+pub mod width;
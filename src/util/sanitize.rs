@@ -1,31 +1,97 @@
+use super::html::decode_entities;
+use super::width::truncate_to_width;
 use regex::Regex;
+use std::iter::Peekable;
+use std::str::Chars;
+use std::sync::OnceLock;
+use unicode_normalization::UnicodeNormalization;
 
-// Remove ANSI escape sequences and non-printable control chars from untrusted text
-// Collapse newlines/tabs to spaces and truncate to a reasonable length for terminal display.
+// Remove terminal escape sequences (CSI, OSC, DCS and the other string and two-byte
+// escapes, in their 7-bit and 8-bit forms), bidi controls and the C0/C1 controls from
+// untrusted text, and normalize it to NFC so accents combine into one character. Newlines
+// and tabs become spaces; letters, marks and emoji of every script are kept. Fitting to the
+// terminal width is the caller's job (see util::width, or sanitize_to_width), since only it
+// knows how many columns are left.
 pub fn sanitize_for_terminal(s: &str) -> String {
-    // Regex to strip CSI (ESC[ ... cmd) sequences
-    // This intentionally keeps it simple; it covers common ANSI sequences used for styling/movement.
-    // If the regex fails to compile (shouldn't), we fallback to raw string handling.
-    let re = Regex::new(r"\x1B\[[0-9;?]*[ -/]*[@-~]").ok();
-    let no_ansi = if let Some(r) = &re {
-        r.replace_all(s, "").into_owned()
-    } else {
-        s.to_string()
-    };
+    let mut cleaned = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\x1b' => match chars.next() {
+                Some('[') => skip_csi(&mut chars),
+                Some(']' | 'P' | 'X' | '^' | '_') => skip_string(&mut chars),
+                // nF escapes (e.g. ESC ( B): intermediates, then a final byte
+                Some(' '..='/') => {
+                    while chars.next_if(|c| (' '..='/').contains(c)).is_some() {}
+                    chars.next();
+                }
+                // Two-byte escapes (ESC c, ESC 7, ...) or a lone ESC
+                _ => {}
+            },
+            '\u{9b}' => skip_csi(&mut chars),
+            '\u{9d}' | '\u{90}' | '\u{98}' | '\u{9e}' | '\u{9f}' => skip_string(&mut chars),
+            '\n' | '\r' | '\t' => cleaned.push(' '),
+            // Bidi embeddings, overrides and isolates: one left open reorders the rest of the
+            // line (lists isolate titles themselves, see util::width::isolate)
+            '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}' => {}
+            c if c.is_control() => {}
+            c => cleaned.push(c),
+        }
+    }
+    cleaned.trim().nfc().collect()
+}
+
+/// [`sanitize_for_terminal`], cut to at most `max` terminal columns (ending in '…' if
+/// anything was dropped). Wide characters count twice, combining marks not at all.
+pub fn sanitize_to_width(s: &str, max: usize) -> String {
+    truncate_to_width(&sanitize_for_terminal(s), max)
+}
 
-    // Remove other control characters (C0 and DEL), keep basic space
-    let mut cleaned = String::with_capacity(no_ansi.len());
-    for ch in no_ansi.chars() {
-        let keep = (ch >= ' ' && ch != '\x7f') || ch == ' ';
-        if keep {
-            cleaned.push(ch);
+// Parameters and intermediates, up to and including the final byte
+fn skip_csi(chars: &mut Peekable<Chars<'_>>) {
+    for c in chars.by_ref() {
+        if ('@'..='~').contains(&c) {
+            break;
         }
     }
+}
 
-    // Normalize whitespace and trim
-    let collapsed = cleaned.replace(['\n', '\r', '\t'], " ");
-    let trimmed = collapsed.trim();
+// OSC / DCS / SOS / PM / APC payloads run to the string terminator (ESC \ or 8-bit ST);
+// xterm also ends OSC at BEL. An unterminated one swallows the rest of the text.
+fn skip_string(chars: &mut Peekable<Chars<'_>>) {
+    while let Some(c) = chars.next() {
+        match c {
+            '\x07' | '\u{9c}' => break,
+            '\x1b' => {
+                chars.next_if_eq(&'\\');
+                break;
+            }
+            _ => {}
+        }
+    }
+}
 
-    // Truncate to 200 chars to avoid overly wide UI
-    trimmed.chars().take(200).collect()
+/// Summaries (and some titles) are HTML fragments: drop tags, comments and the contents
+/// of `<script>`/`<style>`, decode entities (named ones common in feeds and all numeric
+/// ones, see [`decode_entities`]) and collapse whitespace. Block tags (`<p>`, `<br>`, ...)
+/// separate words, inline ones (`<b>`, `<a>`, ...) don't; a '<' that doesn't start a tag,
+/// as in "a < b", is kept.
+pub fn html_to_text(html: &str) -> String {
+    static MARKUP: OnceLock<Regex> = OnceLock::new();
+    let re = MARKUP.get_or_init(|| {
+        Regex::new(r"(?is)<!--.*?(?:-->|$)|<(?:script|style)\b[^>]*>.*?(?:</(?:script|style)\s*>|$)|</?([a-z][a-z0-9]*)[^<>]*>")
+            .expect("valid regex")
+    });
+    let stripped = re.replace_all(html, |c: &regex::Captures| match c.get(1) {
+        Some(tag) if INLINE_TAGS.contains(&tag.as_str().to_ascii_lowercase().as_str()) => "",
+        _ => " ",
+    });
+    let text = decode_entities(&stripped);
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
 }
+
+// Tags inside a line of text, which mustn't split the word around them
+const INLINE_TAGS: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "cite", "code", "del", "em", "font", "i", "ins", "kbd", "mark", "q", "s", "small",
+    "span", "strike", "strong", "sub", "sup", "time", "u", "var",
+];
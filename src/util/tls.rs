@@ -0,0 +1,14 @@
+//! TLS for the protocols reqwest doesn't speak (IMAP, SMTP): rustls with the webpki roots.
+use anyhow::Result;
+use std::sync::Arc;
+use tokio_rustls::rustls::{self, ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
+
+pub fn connector() -> Result<TlsConnector> {
+    let roots = RootCertStore::from_iter(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    let config = ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Ok(TlsConnector::from(Arc::new(config)))
+}
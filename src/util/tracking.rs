@@ -0,0 +1,42 @@
+use url::{form_urlencoded, Url};
+
+// Query parameters that only carry campaign/referral tracking. A trailing '*' matches by prefix.
+const DEFAULT_TRACKING_PARAMS: &[&str] = &[
+    "utm_*", "gclid", "dclid", "fbclid", "msclkid", "yclid", "igshid", "mc_cid", "mc_eid",
+    "_hsenc", "_hsmi", "ref", "ref_src",
+];
+
+/// Remove tracking query parameters (built-in defaults plus `extra` from config) from `url`.
+/// Keys are compared percent-decoded (`utm%5Fsource` is `utm_source`); remaining parameters
+/// keep their original order and encoding.
+pub fn strip_tracking_params(url: &mut Url, extra: &[String]) {
+    let Some(query) = url.query() else { return };
+    let kept: Vec<&str> = query
+        .split('&')
+        .filter(|pair| {
+            let key = form_urlencoded::parse(pair.as_bytes()).next().map(|(k, _)| k).unwrap_or_default();
+            !key.is_empty() && !is_tracking_param(&key, extra)
+        })
+        .collect();
+    if kept.is_empty() {
+        url.set_query(None);
+    } else {
+        let joined = kept.join("&");
+        url.set_query(Some(&joined));
+    }
+}
+
+fn is_tracking_param(key: &str, extra: &[String]) -> bool {
+    let key = key.to_ascii_lowercase();
+    DEFAULT_TRACKING_PARAMS
+        .iter()
+        .copied()
+        .chain(extra.iter().map(|s| s.as_str()))
+        .any(|pattern| {
+            let pattern = pattern.to_ascii_lowercase();
+            match pattern.strip_suffix('*') {
+                Some(prefix) => key.starts_with(prefix),
+                None => key == pattern,
+            }
+        })
+}
@@ -0,0 +1,118 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Terminal columns `s` occupies. ANSI CSI sequences (from styling) take no space, and an
+/// emoji sequence (👩‍💻, 👍🏽, ☀️) counts as the one wide glyph terminals draw for it.
+pub fn display_width(s: &str) -> usize {
+    runs(s)
+        .map(|run| match run {
+            Run::Escape(_) => 0,
+            Run::Text(text) => text.width(),
+        })
+        .sum()
+}
+
+/// Cut `s` to at most `max` columns, ending in '…' when anything was dropped. Characters
+/// are never split from their accents or emoji modifiers. Escape sequences and closing
+/// bidi isolates (see [`isolate`]) are always kept, so styles and isolates opened before the
+/// cut still get closed.
+pub fn truncate_to_width(s: &str, max: usize) -> String {
+    if display_width(s) <= max {
+        return s.to_string();
+    }
+    let budget = max.saturating_sub(1); // room for the ellipsis
+    let mut out = String::with_capacity(s.len());
+    let mut width = 0;
+    let mut cut = false;
+    for run in runs(s) {
+        let text = match run {
+            Run::Escape(e) => {
+                out.push_str(e);
+                continue;
+            }
+            Run::Text(text) => text,
+        };
+        for g in text.graphemes(true) {
+            if cut {
+                if g == POP_ISOLATE {
+                    out.push_str(g);
+                }
+                continue;
+            }
+            let w = g.width();
+            if width + w > budget {
+                if max > 0 {
+                    out.push('…');
+                }
+                cut = true;
+                continue;
+            }
+            width += w;
+            out.push_str(g);
+        }
+    }
+    out
+}
+
+const FIRST_STRONG_ISOLATE: &str = "\u{2068}";
+const POP_ISOLATE: &str = "\u{2069}";
+
+/// `s` wrapped in a bidi isolate (FSI … PDI) if it has right-to-left letters, so a Hebrew or
+/// Arabic title takes its own direction without reordering what is around it (the "> "
+/// marker, numbers, badges). Text without any is returned as is.
+pub fn isolate(s: &str) -> String {
+    match s.chars().any(is_rtl) {
+        true => format!("{}{}{}", FIRST_STRONG_ISOLATE, s, POP_ISOLATE),
+        false => s.to_string(),
+    }
+}
+
+// Letters of the right-to-left scripts: Hebrew, Arabic, Syriac, Thaana, N'Ko and their
+// presentation forms, and the historic ones of the SMP
+fn is_rtl(c: char) -> bool {
+    matches!(c,
+        '\u{0590}'..='\u{08FF}'
+        | '\u{FB1D}'..='\u{FDFF}'
+        | '\u{FE70}'..='\u{FEFF}'
+        | '\u{10800}'..='\u{10FFF}'
+        | '\u{1E800}'..='\u{1EFFF}')
+}
+
+enum Run<'a> {
+    Text(&'a str),
+    Escape(&'a str),
+}
+
+// `s` as runs of text and escape sequences, in order
+fn runs(s: &str) -> impl Iterator<Item = Run<'_>> {
+    let mut rest = s;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let len = match rest.find('\x1b') {
+            Some(0) => {
+                let (escape, after) = rest.split_at(escape_len(rest));
+                rest = after;
+                return Some(Run::Escape(escape));
+            }
+            Some(i) => i,
+            None => rest.len(),
+        };
+        let (text, after) = rest.split_at(len);
+        rest = after;
+        Some(Run::Text(text))
+    })
+}
+
+// Bytes of the `ESC [ ... final` sequence `s` starts with; just the ESC if it's another kind
+fn escape_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    if bytes.get(1) != Some(&b'[') {
+        return 1;
+    }
+    match bytes[2..].iter().position(|b| (b'@'..=b'~').contains(b)) {
+        Some(end) => end + 3,
+        None => s.len(),
+    }
+}
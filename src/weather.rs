@@ -0,0 +1,132 @@
+//! Today's weather for the Morning briefing, from Open-Meteo's free forecast API (no key
+//! needed): the current temperature and conditions, the day's high and low and the chance
+//! of rain.
+use crate::config::WeatherConfig;
+use crate::error::Result;
+use reqwest::Client;
+use serde::Deserialize;
+
+/// `weather.url` when unset.
+pub const DEFAULT_URL: &str = "https://api.open-meteo.com/v1/forecast";
+
+/// The sky, from a WMO weather code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Condition {
+    Clear,
+    PartlyCloudy,
+    Overcast,
+    Fog,
+    Drizzle,
+    Rain,
+    Snow,
+    Thunderstorm,
+    /// A code this doesn't know
+    Unknown,
+}
+
+impl Condition {
+    /// The condition a WMO code (as Open-Meteo reports it) stands for.
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            0 => Condition::Clear,
+            1 | 2 => Condition::PartlyCloudy,
+            3 => Condition::Overcast,
+            45 | 48 => Condition::Fog,
+            51..=57 => Condition::Drizzle,
+            61..=67 | 80..=82 => Condition::Rain,
+            71..=77 | 85 | 86 => Condition::Snow,
+            95..=99 => Condition::Thunderstorm,
+            _ => Condition::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Weather {
+    pub temperature: f64,
+    /// "°C" or "°F"
+    pub temperature_unit: String,
+    pub condition: Condition,
+    pub wind: Option<f64>,
+    /// "km/h" or "mp/h"
+    pub wind_unit: String,
+    /// Today's highest and lowest temperature
+    pub high: Option<f64>,
+    pub low: Option<f64>,
+    /// Today's highest chance of rain, in percent
+    pub rain_chance: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct Forecast {
+    current: Current,
+    #[serde(default)]
+    current_units: Units,
+    #[serde(default)]
+    daily: Daily,
+}
+
+#[derive(Deserialize)]
+struct Current {
+    temperature_2m: f64,
+    #[serde(default)]
+    weather_code: Option<u32>,
+    #[serde(default)]
+    wind_speed_10m: Option<f64>,
+}
+
+#[derive(Deserialize, Default)]
+struct Units {
+    #[serde(default)]
+    temperature_2m: Option<String>,
+    #[serde(default)]
+    wind_speed_10m: Option<String>,
+}
+
+// One value per day, today first
+#[derive(Deserialize, Default)]
+struct Daily {
+    #[serde(default)]
+    temperature_2m_max: Vec<Option<f64>>,
+    #[serde(default)]
+    temperature_2m_min: Vec<Option<f64>>,
+    #[serde(default)]
+    precipitation_probability_max: Vec<Option<f64>>,
+}
+
+/// The weather in an Open-Meteo forecast response.
+pub fn parse(json: &str) -> Result<Weather> {
+    let f: Forecast = serde_json::from_str(json)?;
+    let today = |values: &[Option<f64>]| values.first().copied().flatten();
+    Ok(Weather {
+        temperature: f.current.temperature_2m,
+        temperature_unit: f.current_units.temperature_2m.unwrap_or_else(|| "°C".into()),
+        condition: f.current.weather_code.map_or(Condition::Unknown, Condition::from_code),
+        wind: f.current.wind_speed_10m,
+        wind_unit: f.current_units.wind_speed_10m.unwrap_or_else(|| "km/h".into()),
+        high: today(&f.daily.temperature_2m_max),
+        low: today(&f.daily.temperature_2m_min),
+        rain_chance: today(&f.daily.precipitation_probability_max),
+    })
+}
+
+/// Today's weather at the configured place. Unreachable or failing servers are
+/// [`Error::Network`](crate::Error::Network), an answer that isn't a forecast
+/// [`Error::Parse`](crate::Error::Parse).
+pub async fn fetch(client: &Client, cfg: &WeatherConfig) -> Result<Weather> {
+    let url = cfg.url.as_deref().unwrap_or(DEFAULT_URL);
+    let mut query = vec![
+        ("latitude", cfg.latitude.to_string()),
+        ("longitude", cfg.longitude.to_string()),
+        ("current", "temperature_2m,weather_code,wind_speed_10m".into()),
+        ("daily", "temperature_2m_max,temperature_2m_min,precipitation_probability_max".into()),
+        ("forecast_days", "1".into()),
+        ("timezone", "auto".into()),
+    ];
+    if cfg.imperial() {
+        query.push(("temperature_unit", "fahrenheit".into()));
+        query.push(("wind_speed_unit", "mph".into()));
+    }
+    let response = client.get(url).query(&query).send().await?.error_for_status()?;
+    parse(&response.text().await?)
+}
@@ -0,0 +1,117 @@
+use news_cli::config::{AppConfig, Feed};
+use news_cli::tr;
+use anyhow::{Context, Result};
+use console::{style, Term};
+use dialoguer::{Confirm, Input, MultiSelect};
+use std::{fs, path::Path};
+use url::Url;
+
+// Offered as checkboxes on first run; the first two are the built-in defaults.
+const SUGGESTED_FEEDS: &[(&str, &str)] = &[
+    ("HN Front", "https://hnrss.org/frontpage"),
+    ("BBC World", "https://feeds.bbci.co.uk/news/world/rss.xml"),
+    ("NPR News", "https://feeds.npr.org/1001/rss.xml"),
+    ("The Guardian World", "https://www.theguardian.com/world/rss"),
+    ("Al Jazeera", "https://www.aljazeera.com/xml/rss/all.xml"),
+    ("Ars Technica", "https://feeds.arstechnica.com/arstechnica/index"),
+    ("The Verge", "https://www.theverge.com/rss/index.xml"),
+    ("CBC Top Stories", "https://www.cbc.ca/webfeed/rss/rss-topstories"),
+];
+
+/// Interactive first-run setup. Returns `Ok(false)` if the user declined, in which case
+/// the built-in defaults are used and setup is offered again next launch.
+pub fn run(path: &Path) -> Result<bool> {
+    let term = Term::stdout();
+    let _ = term.clear_screen();
+    println!("{}", style(tr!("wizard.welcome")).bold());
+    println!("{}", tr!("wizard.no_config", path.display()));
+    println!();
+    let go = Confirm::new()
+        .with_prompt(tr!("wizard.set_up"))
+        .default(true)
+        .interact()?;
+    if !go {
+        return Ok(false);
+    }
+
+    let labels: Vec<String> = SUGGESTED_FEEDS
+        .iter()
+        .map(|(name, url)| format!("{}  {}", name, style(url).dim()))
+        .collect();
+    let defaults: Vec<bool> = (0..SUGGESTED_FEEDS.len()).map(|i| i < 2).collect();
+    let picked = MultiSelect::new()
+        .with_prompt(tr!("wizard.pick"))
+        .items(&labels)
+        .defaults(&defaults)
+        .interact()?;
+    let mut feeds: Vec<Feed> = picked
+        .into_iter()
+        .map(|i| Feed {
+            name: SUGGESTED_FEEDS[i].0.into(),
+            url: SUGGESTED_FEEDS[i].1.into(),
+            ..Feed::default()
+        })
+        .collect();
+
+    loop {
+        let url: String = Input::new()
+            .with_prompt(tr!("wizard.add_url"))
+            .allow_empty(true)
+            .validate_with(|s: &String| -> Result<(), String> {
+                if s.trim().is_empty() {
+                    return Ok(());
+                }
+                match Url::parse(s.trim()) {
+                    Ok(u) if u.scheme() == "http" || u.scheme() == "https" => Ok(()),
+                    _ => Err(tr!("wizard.not_a_url").into()),
+                }
+            })
+            .interact_text()?;
+        let url = url.trim().to_string();
+        if url.is_empty() {
+            break;
+        }
+        let suggested = Url::parse(&url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.trim_start_matches("www.").to_string()))
+            .unwrap_or_else(|| "Custom".into());
+        let name: String = Input::new()
+            .with_prompt(tr!("wizard.name"))
+            .default(suggested)
+            .interact_text()?;
+        feeds.push(Feed { name, url, ..Feed::default() });
+    }
+
+    let open_command: String = Input::new()
+        .with_prompt(tr!("wizard.browser"))
+        .allow_empty(true)
+        .interact_text()?;
+    let minutes: u64 = Input::new()
+        .with_prompt(tr!("wizard.interval"))
+        .default(15)
+        .interact_text()?;
+
+    let cfg = AppConfig {
+        feeds,
+        open_command: Some(open_command.trim().to_string()).filter(|s| !s.is_empty()),
+        refresh_interval_secs: Some(minutes.max(1) * 60),
+        ..AppConfig::default()
+    };
+    write_config(path, &cfg)?;
+    println!();
+    println!("{}", tr!("wizard.wrote", path.display()));
+    println!("{}", tr!("wizard.catalog"));
+    println!("{}", tr!("wizard.continue"));
+    let _ = term.read_key();
+    Ok(true)
+}
+
+fn write_config(path: &Path, cfg: &AppConfig) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let body = toml::to_string_pretty(cfg).context("failed to serialize config")?;
+    let txt = format!("# news-cli configuration (generated by first-run setup)\n\n{}", body);
+    fs::write(path, txt).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(())
+}
@@ -0,0 +1,40 @@
+//! Economic calendar feed parsing and filtering.
+
+use news_cli::calendar::{parse, upcoming, Impact};
+use news_cli::config::CalendarConfig;
+
+const FEED: &str = r#"[
+  {"title": "CPI m/m", "country": "USD", "date": "2026-03-11T08:30:00-04:00", "impact": "High", "forecast": "0.3%", "previous": "0.4%"},
+  {"title": "Overnight Rate", "country": "CAD", "date": "2026-03-11T09:45:00-04:00", "impact": "High", "forecast": "2.25%", "previous": "2.50%"},
+  {"title": "Bank Holiday", "country": "CAD", "date": "2026-03-09T00:00:00-04:00", "impact": "Holiday", "forecast": "", "previous": ""},
+  {"title": "GDP q/q", "country": "EUR", "date": "2026-03-10T05:00:00-04:00", "impact": "Medium", "forecast": "0.1%", "previous": "0.0%"},
+  {"title": "Broken", "country": "USD", "date": "soon", "impact": "High"}
+]"#;
+
+// 2026-03-10 00:00 UTC
+const NOW: i64 = 1_773_100_800;
+
+#[test]
+fn feed_is_parsed() {
+    let events = parse(FEED).unwrap();
+    assert_eq!(events.len(), 4, "undated entry kept: {:#?}", events);
+    let cpi = &events[0];
+    assert_eq!(cpi.time, 1_773_232_200);
+    assert_eq!((cpi.country.as_str(), cpi.impact), ("USD", Impact::High));
+    assert_eq!((cpi.previous.as_deref(), cpi.forecast.as_deref()), (Some("0.4%"), Some("0.3%")));
+    assert_eq!((events[2].impact, events[2].previous.as_deref()), (Impact::Low, None));
+}
+
+#[test]
+fn upcoming_filters_by_country_impact_and_time() {
+    let cfg = CalendarConfig { countries: vec!["cad".into(), "USD".into()], ..CalendarConfig::default() };
+    let titles = |cfg: &CalendarConfig| -> Vec<String> {
+        upcoming(parse(FEED).unwrap(), cfg, NOW).into_iter().map(|e| e.title).collect()
+    };
+    // The holiday is past; EUR isn't configured
+    assert_eq!(titles(&cfg), ["CPI m/m", "Overnight Rate"]);
+    let everything = CalendarConfig { countries: vec!["USD".into(), "CAD".into(), "EUR".into()], ..cfg.clone() };
+    assert_eq!(titles(&everything), ["GDP q/q", "CPI m/m", "Overnight Rate"]);
+    let high = CalendarConfig { impact: Some("high".into()), ..everything };
+    assert_eq!(titles(&high), ["CPI m/m", "Overnight Rate"]);
+}
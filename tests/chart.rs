@@ -0,0 +1,38 @@
+//! Terminal line charts.
+
+use news_cli::util::chart::{line_chart, Mark};
+
+fn labels(names: &[&str]) -> Vec<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+#[test]
+fn chart_scales_to_range_and_marks_segments() {
+    let values = [Some(2.0), Some(3.0), Some(2.5)];
+    // Segment 1 (falling from 3.00 to 2.50) in brackets
+    let lines = line_chart(&labels(&["3M", "5Y", "10Y"]), &values, 30, 5, 2, |mark, ch| match mark {
+        Mark::Segment(1) => format!("[{}]", ch),
+        _ => ch.to_string(),
+    });
+    assert_eq!(lines.len(), 7, "{:#?}", lines);
+    assert!(lines[0].starts_with("3.00┤"), "{:#?}", lines);
+    assert!(lines[4].starts_with("2.00┤"), "{:#?}", lines);
+    assert!(lines[5].starts_with("    └"), "{:#?}", lines);
+    let plot = lines[..5].join("\n");
+    assert_eq!(plot.matches('●').count(), 3, "{}", plot);
+    assert!(plot.contains("[╲]"), "falling segment not marked:\n{}", plot);
+    assert!(!plot.contains("[╱]"), "rising segment marked:\n{}", plot);
+    let under = &lines[6];
+    assert!(under.contains("3M") && under.contains("5Y") && under.contains("10Y"), "{}", under);
+}
+
+#[test]
+fn chart_skips_missing_values() {
+    let values = [Some(1.0), None, Some(1.0)];
+    let lines = line_chart(&labels(&["a", "b", "c"]), &values, 20, 3, 1, |_, ch| ch.to_string());
+    let plot = lines[..3].join("\n");
+    assert_eq!(plot.matches('●').count(), 2, "{}", plot);
+    // Flat: one row, joined across the gap
+    assert!(lines[1].contains("●──"), "{}", plot);
+    assert!(line_chart(&labels(&["a"]), &[None], 20, 3, 1, |_, ch| ch.to_string()).is_empty());
+}
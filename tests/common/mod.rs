@@ -0,0 +1,41 @@
+//! Helpers shared by the integration tests (`mod common;` in each file that uses them).
+#![allow(dead_code)]
+
+use news_cli::Story;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+/// A fresh directory for HOME and the XDG directories, with `state/news-cli` in it. Removed
+/// when dropped, so keep it alive for the whole test.
+pub fn state_dir(name: &str) -> TempDir {
+    let dir = tempfile::Builder::new().prefix(&format!("news-cli-{}-", name)).tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("state/news-cli")).unwrap();
+    dir
+}
+
+/// news-cli with `args`, run from the crate root in UTC with its directories under `state`.
+pub fn run(state: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_news-cli"))
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env("HOME", state)
+        .env("XDG_CONFIG_HOME", state.join("config"))
+        .env("XDG_STATE_HOME", state.join("state"))
+        .env("XDG_CACHE_HOME", state.join("cache"))
+        .env("TZ", "UTC")
+        .output()
+        .expect("run news-cli")
+}
+
+/// [`run`], which must succeed; returns its stdout.
+pub fn run_ok(state: &Path, args: &[&str]) -> String {
+    let out = run(state, args);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    String::from_utf8(out.stdout).unwrap()
+}
+
+/// A story with just a link, in the World feed.
+pub fn story(link: &str) -> Story {
+    serde_json::from_value(serde_json::json!({ "title": "A story", "link": link, "source": "World" })).unwrap()
+}
@@ -0,0 +1,40 @@
+//! Story dates in a configured timezone and format.
+
+use news_cli::util::dates::{relative, DateDisplay};
+
+// 2026-01-15 12:00 and 2026-07-15 12:00 UTC
+const WINTER: i64 = 1_768_478_400;
+const SUMMER: i64 = 1_784_116_800;
+
+#[test]
+fn named_zone_follows_daylight_saving() {
+    let berlin = DateDisplay::new(Some("Europe/Berlin"), None).unwrap();
+    assert_eq!(berlin.format(WINTER).unwrap(), "2026-01-15 13:00");
+    assert_eq!(berlin.format(SUMMER).unwrap(), "2026-07-15 14:00");
+    assert_eq!(berlin.format_with_zone(SUMMER).unwrap(), "2026-07-15 14:00 CEST");
+
+    let toronto = DateDisplay::new(Some("America/Toronto"), Some("%d.%m.%Y %H:%M %Z")).unwrap();
+    assert_eq!(toronto.format(WINTER).unwrap(), "15.01.2026 07:00 EST");
+    assert_eq!(toronto.format_with_zone(SUMMER).unwrap(), "15.07.2026 08:00 EDT");
+    assert_eq!(toronto.format_as(WINTER, "%Y-%m-%d").unwrap(), "2026-01-15");
+}
+
+#[test]
+fn bad_settings_are_rejected() {
+    assert!(DateDisplay::new(Some("Mars/Olympus_Mons"), None).is_err());
+    assert!(DateDisplay::new(None, Some("%Y-%Q")).is_err());
+    assert!(DateDisplay::new(Some("local"), Some("%H:%M")).is_ok());
+}
+
+#[test]
+fn relative_times_grow_coarser_with_age() {
+    let now = WINTER;
+    assert_eq!(relative(now - 20, now), "just now");
+    assert_eq!(relative(now + 600, now), "just now");
+    assert_eq!(relative(now - 5 * 60, now), "5m ago");
+    assert_eq!(relative(now - 2 * 3600 - 59, now), "2h ago");
+    assert_eq!(relative(now - 3 * 86_400, now), "3d ago");
+    assert_eq!(relative(now - 20 * 86_400, now), "2w ago");
+    assert_eq!(relative(now - 100 * 86_400, now), "3mo ago");
+    assert_eq!(relative(now - 800 * 86_400, now), "2y ago");
+}
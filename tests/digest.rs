@@ -0,0 +1,223 @@
+//! `news-cli digest` against the fixture feeds: files, sendmail and a fake SMTP server.
+
+mod common;
+
+use common::state_dir;
+use news_cli::digest::smtp;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, DuplexStream};
+use tokio::task::JoinHandle;
+
+// The fixture config with `digest` as its [digest] section
+fn config(state: &Path, digest: &str) -> PathBuf {
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    let path = state.join("digest.toml");
+    std::fs::write(&path, format!("{}\n[digest]\n{}\n", fixture, digest)).unwrap();
+    path
+}
+
+fn digest(state: &Path, config: &Path, args: &[&str]) -> Output {
+    let out = Command::new(env!("CARGO_BIN_EXE_news-cli"))
+        .args(["--feeds", config.to_str().unwrap(), "digest"])
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env("HOME", state)
+        .env("XDG_STATE_HOME", state.join("state"))
+        .env("XDG_CACHE_HOME", state.join("cache"))
+        .env("TZ", "UTC")
+        .output()
+        .expect("run news-cli digest");
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    out
+}
+
+#[test]
+fn only_new_stories_go_into_the_next_digest() {
+    let dir = state_dir("file");
+    let state = dir.path();
+    let config = config(state, "subject = \"Morning news\"");
+    let file = state.join("digest.md");
+    digest(state, &config, &["--out", file.to_str().unwrap()]);
+    let md = std::fs::read_to_string(&file).unwrap();
+    assert!(md.starts_with("# Morning news "), "{}", md);
+    let world = md.find("\n## World\n\n").expect("no World section");
+    let tech = md.find("\n## Tech\n\n").expect("no Tech section");
+    assert!(world < tech, "sources not in config order:\n{}", md);
+    assert!(
+        md.contains("- [Central bank holds rates steady amid slowing growth](https://example.com/world/rates) (2026-02-02 09:00)\n"),
+        "{}",
+        md
+    );
+    assert!(md.find("Tech story number 12").unwrap() < md.find("Tech story number 11").unwrap(), "not newest first:\n{}", md);
+
+    std::fs::remove_file(&file).unwrap();
+    let again = digest(state, &config, &["--out", file.to_str().unwrap()]);
+    assert!(String::from_utf8_lossy(&again.stderr).contains("No new stories since the last digest."));
+    assert!(!file.exists(), "empty digest written");
+}
+
+#[test]
+fn sendmail_gets_an_html_message() {
+    let dir = state_dir("sendmail");
+    let state = dir.path();
+    let mail = state.join("mail.eml");
+    let config = config(
+        state,
+        &format!("format = \"html\"\nto = [\"me@example.com\"]\nsendmail = \"tee {}\"", mail.display()),
+    );
+    let out = digest(state, &config, &[]);
+    assert!(out.stdout.is_empty(), "printed although mailed");
+    let msg = std::fs::read_to_string(&mail).unwrap();
+    assert!(msg.contains("To: me@example.com\r\n"), "{}", msg);
+    assert!(msg.contains("Subject: News digest "), "{}", msg);
+    assert!(msg.contains("Content-Type: text/html; charset=utf-8\r\n"), "{}", msg);
+    assert!(msg.contains("<h2>World</h2>\r\n"), "{}", msg);
+}
+
+#[test]
+fn smtp_server_receives_the_digest() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut writer = stream;
+        let mut transcript = String::new();
+        writer.write_all(b"220 fake ESMTP\r\n").unwrap();
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 0 {
+            transcript.push_str(&line);
+            let reply: &[u8] = match line.trim_end() {
+                l if l.starts_with("EHLO") => b"250-fake\r\n250 8BITMIME\r\n",
+                "DATA" => {
+                    writer.write_all(b"354 go ahead\r\n").unwrap();
+                    loop {
+                        line.clear();
+                        reader.read_line(&mut line).unwrap();
+                        transcript.push_str(&line);
+                        if line == ".\r\n" {
+                            break;
+                        }
+                    }
+                    b"250 queued\r\n"
+                }
+                "QUIT" => {
+                    writer.write_all(b"221 bye\r\n").unwrap();
+                    break;
+                }
+                _ => b"250 ok\r\n",
+            };
+            writer.write_all(reply).unwrap();
+            line.clear();
+        }
+        transcript
+    });
+
+    let dir = state_dir("smtp");
+
+    let state = dir.path();
+    let config = config(
+        state,
+        &format!("to = [\"me@example.com\"]\nfrom = \"News <news@example.com>\"\nsmtp = \"127.0.0.1:{}\"", port),
+    );
+    digest(state, &config, &[]);
+    let transcript = server.join().unwrap();
+    assert!(transcript.contains("MAIL FROM:<news@example.com>\r\n"), "{}", transcript);
+    assert!(transcript.contains("RCPT TO:<me@example.com>\r\n"), "{}", transcript);
+    assert!(transcript.contains("From: News <news@example.com>\r\n"), "{}", transcript);
+    assert!(transcript.contains("\r\n## Tech\r\n"), "{}", transcript);
+    assert!(transcript.ends_with("\r\n.\r\nQUIT\r\n"), "{}", transcript);
+}
+
+#[test]
+fn digest_goes_to_the_given_sinks() {
+    let dir = state_dir("sinks");
+    let state = dir.path();
+    let config = config(state, "");
+    let file = state.join("digest.md");
+    let spec = format!("file:{}", file.display());
+    let out = digest(state, &config, &["--to", &spec, "--to", "json"]);
+    let md = std::fs::read_to_string(&file).unwrap();
+    assert!(md.starts_with("# News digest "), "{}", md);
+    assert!(md.contains("\n## World\n\n"), "{}", md);
+    let printed: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(printed["format"], "markdown");
+    assert_eq!(printed["body"].as_str(), Some(md.as_str()));
+}
+
+// Plays the server's side of `script` (the start of what the client should send, then the
+// reply; "" for the greeting, "." for the whole message) over an in-memory stream, and
+// returns the client's end and, when done, everything the client sent
+fn scripted(script: Vec<(&'static str, &'static str)>) -> (DuplexStream, JoinHandle<String>) {
+    let (client, server) = tokio::io::duplex(64 * 1024);
+    let server = tokio::spawn(async move {
+        let mut server = tokio::io::BufReader::new(server);
+        let mut transcript = String::new();
+        for (expect, reply) in script {
+            if !expect.is_empty() {
+                let mut line = String::new();
+                loop {
+                    line.clear();
+                    assert!(server.read_line(&mut line).await.unwrap() > 0, "client hung up after {}", transcript);
+                    transcript.push_str(&line);
+                    if expect != "." || line == ".\r\n" {
+                        break;
+                    }
+                }
+                assert!(line.starts_with(expect), "expected {}, got {}", expect, line);
+            }
+            server.get_mut().write_all(reply.as_bytes()).await.unwrap();
+        }
+        transcript
+    });
+    (client, server)
+}
+
+#[tokio::test]
+async fn smtp_logs_in_and_dot_stuffs_the_message() {
+    let (stream, server) = scripted(vec![
+        ("", "220 fake ESMTP\r\n"),
+        ("EHLO", "250-fake\r\n250-AUTH PLAIN LOGIN\r\n250 8BITMIME\r\n"),
+        // "\0me\0secret"
+        ("AUTH PLAIN AG1lAHNlY3JldA==\r\n", "235 2.7.0 accepted\r\n"),
+        ("MAIL FROM:<news@example.com>\r\n", "250 ok\r\n"),
+        ("RCPT TO:<a@example.com>\r\n", "250 ok\r\n"),
+        ("RCPT TO:<b@example.com>\r\n", "250 ok\r\n"),
+        ("DATA\r\n", "354 go ahead\r\n"),
+        (".", "250 queued\r\n"),
+        ("QUIT\r\n", "221 bye\r\n"),
+    ]);
+    let to = ["a@example.com".to_string(), " b@example.com".to_string()];
+    let message = "Subject: News digest\n\n.hidden\nlast line\n";
+    smtp::session(stream, "localhost", false, Some(("me", "secret")), "news@example.com", &to, message).await.unwrap();
+    let transcript = server.await.unwrap();
+    assert!(transcript.contains("DATA\r\nSubject: News digest\r\n\r\n..hidden\r\nlast line\r\n.\r\nQUIT\r\n"), "{}", transcript);
+}
+
+#[tokio::test]
+async fn smtp_stops_at_a_refused_recipient() {
+    let (stream, server) = scripted(vec![
+        ("", "220 fake ESMTP\r\n"),
+        ("EHLO", "250 fake\r\n"),
+        ("MAIL FROM:", "250 ok\r\n"),
+        ("RCPT TO:<nobody@example.com>", "550 5.1.1 no such user\r\n"),
+    ]);
+    let to = ["nobody@example.com".to_string()];
+    let err = smtp::session(stream, "mail.example.com", false, None, "news@example.com", &to, "hi").await.unwrap_err();
+    assert_eq!(err.to_string(), "550 5.1.1 no such user");
+    assert!(!server.await.unwrap().contains("DATA"));
+}
+
+#[tokio::test]
+async fn smtp_keeps_passwords_off_plain_connections_to_other_machines() {
+    let (stream, server) = scripted(vec![("", "220 fake ESMTP\r\n"), ("EHLO", "250-fake\r\n250 AUTH PLAIN\r\n")]);
+    let to = ["me@example.com".to_string()];
+    let err = smtp::session(stream, "mail.example.com", false, Some(("me", "secret")), "news@example.com", &to, "hi")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("offers no TLS; not sending the password in the clear"), "{}", err);
+    assert!(!server.await.unwrap().contains("AUTH"));
+}
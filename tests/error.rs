@@ -0,0 +1,25 @@
+//! The library's error kinds: what callers may work around and what they must report.
+
+use news_cli::{calendar, config, Error};
+
+#[test]
+fn broken_config_is_a_config_error() {
+    let dir = std::env::temp_dir().join(format!("news-cli-error-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("broken.toml");
+    std::fs::write(&path, "[[feeds]\nname = ").unwrap();
+    let err = config::load(Some(path.to_str().unwrap().to_string())).unwrap_err();
+    assert!(matches!(err, Error::Config(_)), "{:?}", err);
+    assert!(!err.is_transient());
+    assert!(err.to_string().contains("invalid config"), "{}", err);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn unreadable_content_is_a_parse_error_and_size_limits_are_transient() {
+    let err = calendar::parse("<html>not json</html>").unwrap_err();
+    assert!(matches!(err, Error::Parse(_)), "{:?}", err);
+    assert!(!err.is_transient());
+    assert!(Error::TooLarge(100).is_transient());
+    assert_eq!(Error::TooLarge(100).to_string(), "feed too large (>100 bytes)");
+}
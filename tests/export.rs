@@ -0,0 +1,95 @@
+//! `news-cli export` against the fixture feeds and a seeded state directory.
+
+mod common;
+
+use common::state_dir;
+use std::path::Path;
+use std::process::Command;
+
+fn export(state: &Path, args: &[&str]) -> String {
+    let out = Command::new(env!("CARGO_BIN_EXE_news-cli"))
+        .args(["--feeds", "tests/fixtures/config.toml", "export"])
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env("HOME", state)
+        .env("XDG_STATE_HOME", state.join("state"))
+        .env("XDG_CACHE_HOME", state.join("cache"))
+        // Day headings are in local time
+        .env("TZ", "UTC")
+        .output()
+        .expect("run news-cli export");
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    String::from_utf8(out.stdout).unwrap()
+}
+
+#[test]
+fn markdown_groups_fetched_stories_by_day() {
+    let dir = state_dir("md");
+    let state = dir.path();
+    let md = export(state, &["--format", "md"]);
+    assert!(md.starts_with("## 2026-02-02\n\n"), "{}", md);
+    assert!(md.contains("- [Central bank holds rates steady amid slowing growth](https://example.com/world/rates) (World)\n"), "{}", md);
+    assert!(md.contains("\n## 2026-02-01\n\n"), "{}", md);
+    // The fixture stories are months old
+    assert_eq!(export(state, &["--since", "7d"]), "");
+}
+
+#[test]
+fn csv_quotes_fields() {
+    let dir = state_dir("csv");
+    let state = dir.path();
+    let csv = export(state, &["--format", "csv"]);
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("title,link,source,date,note"));
+    assert!(
+        csv.contains("Election results: coalition talks begin,https://example.com/world/election,World,2026-02-02T07:30:00Z,\r\n"),
+        "{}",
+        csv
+    );
+    assert_eq!(lines.count(), 15);
+}
+
+#[test]
+fn opened_exports_reading_log() {
+    let dir = state_dir("opened");
+    let state = dir.path();
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+    let log = format!(
+        "{{\"at\":{0},\"offset\":0,\"source\":\"World\",\"title\":\"Rates, \\\"held\\\"\",\"link\":\"https://example.com/world/rates\"}}\n\
+         {{\"at\":{1},\"offset\":0,\"source\":\"Tech\",\"title\":\"Old read\",\"link\":\"https://example.com/tech/old\"}}\n",
+        now - 60,
+        now - 30 * 86_400
+    );
+    std::fs::write(state.join("state/news-cli/reading_log.jsonl"), log).unwrap();
+    let csv = export(state, &["--opened", "--since", "1w", "--format", "csv"]);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 2, "{}", csv);
+    assert!(lines[1].starts_with("\"Rates, \"\"held\"\"\",https://example.com/world/rates,World,"), "{}", csv);
+}
+
+#[test]
+fn tagged_exports_stories_with_the_tag_and_notes() {
+    let dir = state_dir("tagged");
+    let state = dir.path();
+    let story = |title: &str, link: &str| {
+        format!("{{\"source\":\"World\",\"title\":\"{}\",\"link\":\"{}\",\"published\":1769990400,\"is_new\":false}}", title, link)
+    };
+    let tags = format!(
+        "{{\"items\":[{{\"story\":{},\"tags\":[\"rates\",\"economy\"],\"note\":\"For the essay, see p. 2\\nAsk Sam\",\"tagged_at\":1769990400}},\
+         {{\"story\":{},\"tags\":[\"sport\"],\"tagged_at\":1769990400}}]}}",
+        story("Rates held", "https://example.com/world/rates"),
+        story("Cup final", "https://example.com/world/cup")
+    );
+    std::fs::write(state.join("state/news-cli/tags.json"), tags).unwrap();
+    let md = export(state, &["--tag", "Economy"]);
+    assert_eq!(
+        md,
+        "## 2026-02-02\n\n- [Rates held](https://example.com/world/rates) (World)\n  > For the essay, see p. 2\n  > Ask Sam\n"
+    );
+    let csv = export(state, &["--tag", "economy", "--format", "csv"]);
+    assert!(csv.ends_with(",World,2026-02-02T00:00:00Z,\"For the essay, see p. 2\nAsk Sam\"\r\n"), "{}", csv);
+    // Notes follow their story into every export
+    let md = export(state, &["--format", "md"]);
+    assert!(md.contains("(World)\n  > For the essay, see p. 2\n"), "{}", md);
+    assert_eq!(export(state, &["--tag", "politics"]), "");
+}
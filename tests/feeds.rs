@@ -0,0 +1,499 @@
+//! End to end through `fetch`: the feeds in tests/fixtures/feeds via `--fixture-dir`, and
+//! the same kinds of trouble served over HTTP by a mock server.
+
+mod common;
+
+use common::state_dir;
+use std::path::Path;
+use std::process::Output;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn news_cli(state: &Path, args: &[&str]) -> Output {
+    tokio::process::Command::new(env!("CARGO_BIN_EXE_news-cli"))
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env("HOME", state)
+        .env("XDG_STATE_HOME", state.join("state"))
+        .env("XDG_CACHE_HOME", state.join("cache"))
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .env_remove("LANG")
+        .output()
+        .await
+        .expect("run news-cli")
+}
+
+// (source, title) of each story printed by `--to json`
+fn stories(out: &Output) -> Vec<(String, String)> {
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .map(|l| {
+            let v: serde_json::Value = serde_json::from_str(l).unwrap();
+            (v["source"].as_str().unwrap().to_string(), v["title"].as_str().unwrap().to_string())
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn fixture_dir_feeds_are_merged_deduplicated_and_remembered() {
+    let dir = state_dir("fixtures");
+    let state = dir.path();
+    let args = ["--fixture-dir", "tests/fixtures/feeds", "fetch", "--to", "json", "--new", "--mark-seen"];
+    let first = news_cli(state, &args).await;
+    assert!(first.status.success());
+    let stderr = String::from_utf8_lossy(&first.stderr);
+    assert!(stderr.contains("Failed to fetch malformed: failed to parse feed"), "{}", stderr);
+    let found = stories(&first);
+    let titles: Vec<&str> = found.iter().map(|(_, t)| t.as_str()).collect();
+    // RSS, Atom and JSON Feed; the ISO-8859-1 file decoded
+    for title in ["RSS story one", "Atom story one", "JSON story one", "JSON story two", "Café crème prêt à servir"] {
+        assert!(titles.contains(&title), "{:?}", titles);
+    }
+    // The Atom entry's tracking parameter stripped, it and the RSS item are one story
+    assert_eq!(titles.iter().filter(|t| t.starts_with("Shared story")).count(), 1, "{:?}", titles);
+    assert_eq!(found.len(), 6, "{:?}", found);
+
+    let again = news_cli(state, &args).await;
+    assert!(again.status.success());
+    assert!(stories(&again).is_empty(), "{}", String::from_utf8_lossy(&again.stdout));
+    let _ = std::fs::remove_dir_all(state);
+}
+
+#[tokio::test]
+async fn served_feeds_survive_redirects_charsets_and_bad_bodies() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/moved.xml"))
+        .respond_with(ResponseTemplate::new(301).insert_header("Location", "/rss.xml"))
+        .mount(&server)
+        .await;
+    let fixture = |name: &str| std::fs::read(Path::new("tests/fixtures/feeds").join(name)).unwrap();
+    Mock::given(method("GET"))
+        .and(path("/rss.xml"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(fixture("rss.xml"), "application/rss+xml"))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/latin1.xml"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw(fixture("latin1.xml"), "application/rss+xml; charset=iso-8859-1"),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/broken.xml"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(fixture("malformed.xml"), "application/rss+xml"))
+        .mount(&server)
+        .await;
+    let mut huge = String::from("<?xml version=\"1.0\"?><rss version=\"2.0\"><channel><title>Huge</title>");
+    for i in 0..5000 {
+        huge.push_str(&format!("<item><title>Story {i}</title><link>https://example.com/huge/{i}</link></item>"));
+    }
+    huge.push_str("</channel></rss>");
+    Mock::given(method("GET"))
+        .and(path("/huge.xml"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(huge, "application/rss+xml"))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/gone.xml"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let dir = state_dir("served");
+
+    let state = dir.path();
+    let config = state.join("served.toml");
+    let feed = |name: &str, file: &str| format!("[[feeds]]\nname = \"{}\"\nurl = \"{}/{}\"\n", name, server.uri(), file);
+    std::fs::write(
+        &config,
+        [
+            feed("Moved", "moved.xml"),
+            feed("Latin", "latin1.xml"),
+            feed("Broken", "broken.xml"),
+            feed("Huge", "huge.xml") + "max_bytes = 10000\n",
+            feed("Gone", "gone.xml"),
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+    let out = news_cli(state, &["--feeds", config.to_str().unwrap(), "fetch", "--to", "json"]).await;
+    assert!(out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("Failed to fetch Broken: failed to parse feed"), "{}", stderr);
+    assert!(stderr.contains("Failed to fetch Huge:"), "{}", stderr);
+    assert!(stderr.contains("Failed to fetch Gone:"), "{}", stderr);
+    let mut found = stories(&out);
+    found.sort();
+    assert_eq!(
+        found,
+        [
+            ("Latin".to_string(), "Café crème prêt à servir".to_string()),
+            ("Moved".to_string(), "RSS story one".to_string()),
+            ("Moved".to_string(), "Shared story".to_string()),
+        ],
+    );
+    let _ = std::fs::remove_dir_all(state);
+}
+
+#[tokio::test]
+async fn retry_after_skips_the_feed_until_then() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/busy.xml"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "120"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let dir = state_dir("retry-after");
+    let state = dir.path();
+    let config = state.join("busy.toml");
+    std::fs::write(&config, format!("[[feeds]]\nname = \"Busy\"\nurl = \"{}/busy.xml\"\n", server.uri())).unwrap();
+    let args = ["--feeds", config.to_str().unwrap(), "fetch", "--to", "json"];
+    let first = news_cli(state, &args).await;
+    let stderr = String::from_utf8_lossy(&first.stderr);
+    assert!(stderr.contains("Failed to fetch Busy:"), "{}", stderr);
+    let status = std::fs::read_to_string(state.join("state/news-cli/feeds.json")).unwrap();
+    assert!(status.contains("\"retry_after\""), "{}", status);
+
+    // Not asked again: the mock expects a single request
+    let again = news_cli(state, &args).await;
+    let stderr = String::from_utf8_lossy(&again.stderr);
+    assert!(stderr.contains("Skipping Busy: the server asked to wait until"), "{}", stderr);
+    let _ = std::fs::remove_dir_all(state);
+}
+
+#[tokio::test]
+async fn respect_robots_reads_robots_txt_once_and_honors_crawl_delay() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/robots.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("User-agent: *\nCrawl-delay: 1\n"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let rss = std::fs::read("tests/fixtures/feeds/rss.xml").unwrap();
+    for file in ["/a.xml", "/b.xml"] {
+        Mock::given(method("GET"))
+            .and(path(file))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(rss.clone(), "application/rss+xml"))
+            .mount(&server)
+            .await;
+    }
+    let dir = state_dir("robots");
+    let state = dir.path();
+    let config = state.join("robots.toml");
+    let feed = |name: &str, file: &str| format!("[[feeds]]\nname = \"{}\"\nurl = \"{}/{}\"\n", name, server.uri(), file);
+    std::fs::write(
+        &config,
+        format!("[fetch]\nper_host_rps = 100\nrespect_robots = true\n\n{}\n{}", feed("A", "a.xml"), feed("B", "b.xml")),
+    )
+    .unwrap();
+    let started = std::time::Instant::now();
+    let out = news_cli(state, &["--feeds", config.to_str().unwrap(), "fetch", "--to", "json"]).await;
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    // The same items in both, so listed once
+    assert_eq!(stories(&out).len(), 2, "{}", String::from_utf8_lossy(&out.stdout));
+    // robots.txt, then a second apart each feed
+    assert!(started.elapsed() >= std::time::Duration::from_secs(2), "{:?}", started.elapsed());
+    let _ = std::fs::remove_dir_all(state);
+}
+
+#[tokio::test]
+async fn brotli_feeds_are_cached_and_refetched_conditionally() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rss.xml"))
+        .and(header("If-None-Match", "\"v1\""))
+        .respond_with(ResponseTemplate::new(304))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let compressed = std::fs::read("tests/fixtures/rss.xml.br").unwrap();
+    Mock::given(method("GET"))
+        .and(path("/rss.xml"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("ETag", "\"v1\"")
+                .insert_header("Content-Encoding", "br")
+                .set_body_raw(compressed, "application/rss+xml"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    let dir = state_dir("conditional");
+    let state = dir.path();
+    let config = state.join("conditional.toml");
+    std::fs::write(&config, format!("[[feeds]]\nname = \"Rss\"\nurl = \"{}/rss.xml\"\n", server.uri())).unwrap();
+    let args = ["--feeds", config.to_str().unwrap(), "fetch", "--to", "json"];
+    let transfer = || {
+        let status: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(state.join("state/news-cli/feeds.json")).unwrap()).unwrap();
+        status["Rss"].clone()
+    };
+
+    let first = news_cli(state, &args).await;
+    assert_eq!(stories(&first).len(), 2, "{}", String::from_utf8_lossy(&first.stderr));
+    let status = transfer();
+    let bytes = std::fs::metadata("tests/fixtures/feeds/rss.xml").unwrap().len();
+    assert_eq!(status["last_transfer"]["bytes"], bytes, "{}", status);
+    assert_eq!(status["last_transfer"]["cache_hits"], 0, "{}", status);
+
+    // 304: the same stories from the cached body, nothing downloaded
+    let again = news_cli(state, &args).await;
+    assert_eq!(stories(&again), stories(&first), "{}", String::from_utf8_lossy(&again.stderr));
+    let status = transfer();
+    assert_eq!(status["last_transfer"]["bytes"], 0, "{}", status);
+    assert_eq!(status["last_transfer"]["cache_hits"], 1, "{}", status);
+    assert_eq!(status["total_bytes"], bytes, "{}", status);
+    let _ = std::fs::remove_dir_all(state);
+}
+
+#[tokio::test]
+async fn titles_are_decoded_and_stripped_of_tags() {
+    let server = MockServer::start().await;
+    let item = |n: u32, title: &str| format!("<item><title>{}</title><link>https://example.com/t/{}</link></item>", title, n);
+    let body = format!(
+        "<?xml version=\"1.0\"?><rss version=\"2.0\"><channel><title>Escaped</title>{}{}{}</channel></rss>",
+        item(1, "AT&amp;amp;T&amp;#8217;s &lt;b&gt;new&lt;/b&gt; plan"),
+        item(2, "<![CDATA[Q&amp;A: <em>Rust</em>, &hellip; &amp; you]]>"),
+        item(3, "Why 1 &lt; 2"),
+    );
+    Mock::given(method("GET"))
+        .and(path("/escaped.xml"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/rss+xml"))
+        .mount(&server)
+        .await;
+    let dir = state_dir("escaped");
+    let state = dir.path();
+    let config = state.join("escaped.toml");
+    std::fs::write(&config, format!("[[feeds]]\nname = \"Escaped\"\nurl = \"{}/escaped.xml\"\n", server.uri())).unwrap();
+    let out = news_cli(state, &["--feeds", config.to_str().unwrap(), "fetch", "--to", "json"]).await;
+    assert!(out.status.success());
+    let mut titles: Vec<String> = stories(&out).into_iter().map(|(_, title)| title).collect();
+    titles.sort();
+    assert_eq!(titles, ["AT&T’s new plan", "Q&A: Rust, … & you", "Why 1 < 2"]);
+    let _ = std::fs::remove_dir_all(state);
+}
+
+#[tokio::test]
+async fn strict_mode_refuses_local_targets() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rss.xml"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_raw(std::fs::read("tests/fixtures/feeds/rss.xml").unwrap(), "application/rss+xml"),
+        )
+        .mount(&server)
+        .await;
+    let port = server.address().port();
+    let dir = state_dir("strict");
+    let state = dir.path();
+    let config = state.join("strict.toml");
+    let feeds = format!(
+        "[[feeds]]\nname = \"Literal\"\nurl = \"http://127.0.0.1:{port}/rss.xml\"\n\n\
+         [[feeds]]\nname = \"Named\"\nurl = \"http://localhost:{port}/rss.xml\"\n\n\
+         [[feeds]]\nname = \"File\"\nurl = \"tests/fixtures/world.xml\"\n\n\
+         [[feeds]]\nname = \"Command\"\nkind = \"command\"\ncommand = \"echo []\"\n"
+    );
+    std::fs::write(&config, &feeds).unwrap();
+    // The same feeds load without strict mode
+    let out = news_cli(state, &["--feeds", config.to_str().unwrap(), "fetch", "--to", "json"]).await;
+    assert!(out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).is_empty(), "{}", String::from_utf8_lossy(&out.stderr));
+
+    std::fs::write(&config, format!("[fetch]\nstrict = true\n\n{}", feeds)).unwrap();
+    let out = news_cli(state, &["--feeds", config.to_str().unwrap(), "fetch", "--to", "json"]).await;
+    assert!(out.status.success());
+    assert!(stories(&out).is_empty(), "{}", String::from_utf8_lossy(&out.stdout));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("Literal: 127.0.0.1 is a private or local address"), "{}", stderr);
+    assert!(stderr.contains("localhost resolves only to private or local addresses"), "{}", stderr);
+    assert!(stderr.contains("File: tests/fixtures/world.xml is a local file"), "{}", stderr);
+    assert!(stderr.contains("Command: command feeds are off"), "{}", stderr);
+
+    // doctor probes the same feeds, and runs no command either
+    let ran = state.join("ran");
+    let command = format!("[[feeds]]\nname = \"Tool\"\nkind = \"command\"\ncommand = \"touch {}\"\n", ran.display());
+    std::fs::write(&config, format!("[fetch]\nstrict = true\n\n{}\n{}", feeds, command)).unwrap();
+    let out = news_cli(state, &["--feeds", config.to_str().unwrap(), "doctor"]).await;
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("command feeds are off"), "{}", stdout);
+    assert!(stdout.contains("127.0.0.1 is a private or local address"), "{}", stdout);
+    assert!(stdout.contains("is a local file"), "{}", stdout);
+    assert!(!ran.exists());
+    let _ = std::fs::remove_dir_all(state);
+}
+
+#[test]
+fn strict_mode_knows_private_addresses() {
+    use news_cli::news::strict::{check_url, is_private};
+
+    let private = [
+        "127.0.0.1", "10.1.2.3", "172.20.0.1", "192.168.1.1", "169.254.169.254", "100.64.0.1", "::1", "fd00::1",
+        "fe80::1", "::ffff:192.168.0.1", "224.0.0.251", "239.255.255.250", "ff02::1", "198.18.0.1", "198.19.255.1",
+        "240.0.0.1", "255.255.255.255", "64:ff9b::7f00:1", "64:ff9b::a9fe:a9fe", "::127.0.0.1", "::10.0.0.1",
+    ];
+    for private in private {
+        assert!(is_private(private.parse().unwrap()), "{}", private);
+    }
+    for public in ["1.1.1.1", "172.32.0.1", "198.20.0.1", "223.255.255.1", "2606:4700::1111", "64:ff9b::101:101"] {
+        assert!(!is_private(public.parse().unwrap()), "{}", public);
+    }
+    assert!(check_url(&"https://example.com/feed".parse().unwrap()).is_ok());
+    assert!(check_url(&"http://[::1]:8080/".parse().unwrap()).is_err());
+    assert!(check_url(&"file:///etc/passwd".parse().unwrap()).is_err());
+}
+
+#[tokio::test]
+async fn strict_mode_covers_briefing_pages_and_link_checks() {
+    use news_cli::config::{BriefingConfig, FetchLimits};
+    use news_cli::news::Story;
+    use news_cli::{briefing, linkcheck};
+
+    let server = MockServer::start().await;
+    let completion = serde_json::json!({ "choices": [{ "message": { "content": "- All quiet" } }] });
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(completion))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/article"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("<p>Internal only</p>"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("HEAD")).and(path("/article")).respond_with(ResponseTemplate::new(200)).expect(0).mount(&server).await;
+
+    let bcfg = BriefingConfig {
+        api_base: format!("{}/v1", server.uri()),
+        model: "test-model".into(),
+        include_text: true,
+        ..Default::default()
+    };
+    let link = format!("{}/article", server.uri());
+    let story: Story =
+        serde_json::from_value(serde_json::json!({ "title": "Inside", "link": link, "source": "Intranet" })).unwrap();
+    let strict = FetchLimits { strict: Some(true), ..Default::default() };
+    // Only the fetch without strict mode reads the page
+    for limits in [&strict, &FetchLimits::default()] {
+        let text = briefing::generate(&bcfg, limits, std::slice::from_ref(&story)).await.unwrap();
+        assert_eq!(text, "- All quiet");
+    }
+
+    let checked = linkcheck::check(&strict, &link.parse().unwrap()).await;
+    assert!(checked.warnings.iter().any(|w| w.contains("private or local address")), "{:?}", checked.warnings);
+    // A name for the same address doesn't resolve (and the HEAD mock sees neither)
+    let named = link.replace("127.0.0.1", "localhost");
+    let checked = linkcheck::check(&strict, &named.parse().unwrap()).await;
+    assert!(checked.warnings.iter().any(|w| w.contains("resolves only to private")), "{:?}", checked.warnings);
+}
+
+#[tokio::test]
+async fn a_web_page_configured_as_a_feed_says_where_its_feed_is() {
+    let server = MockServer::start().await;
+    let page = "<!DOCTYPE html><html><head><title>Example</title>\
+                <link rel=\"stylesheet\" href=\"/style.css\">\
+                <link rel=\"alternate\" type=\"application/rss+xml\" title=\"News\" href=\"/feed.xml\">\
+                </head><body><p>Hello</p></body></html>";
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(page, "text/html; charset=utf-8"))
+        .mount(&server)
+        .await;
+
+    let dir = state_dir("webpage");
+
+    let state = dir.path();
+    let config = state.join("webpage.toml");
+    std::fs::write(&config, format!("[[feeds]]\nname = \"Home\"\nurl = \"{}/\"\n", server.uri())).unwrap();
+    let out = news_cli(state, &["--feeds", config.to_str().unwrap(), "fetch", "--to", "json"]).await;
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    let feed = format!("{}/feed.xml", server.uri());
+    assert!(stderr.contains("looks like a web page, not a feed"), "{}", stderr);
+    assert!(stderr.contains(&feed), "{}", stderr);
+
+    // Not on a terminal, doctor says what to change rather than asking
+    let out = news_cli(state, &["--feeds", config.to_str().unwrap(), "doctor"]).await;
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("web page"), "{}", stdout);
+    assert!(stdout.contains(&format!("Set url = \"{}\" for Home", feed)), "{}", stdout);
+    let _ = std::fs::remove_dir_all(state);
+}
+
+#[test]
+fn feed_links_are_found_in_pages() {
+    use news_cli::news::discover::{feed_links, looks_like_html};
+
+    let base: url::Url = "https://example.com/blog/".parse().unwrap();
+    let html = "<head><LINK REL='alternate' TYPE='application/atom+xml' HREF='atom.xml'>\
+                <link type=\"application/feed+json\" rel=\"alternate\" href=\"https://cdn.example.com/f.json?a=1&amp;b=2\">\
+                <link rel=\"alternate\" hreflang=\"de\" href=\"/de/\"><link rel=\"alternate\" type=\"application/atom+xml\" href=\"atom.xml\"></head>";
+    let found: Vec<String> = feed_links(html, &base).iter().map(|u| u.to_string()).collect();
+    assert_eq!(found, ["https://example.com/blog/atom.xml", "https://cdn.example.com/f.json?a=1&b=2"]);
+    assert!(looks_like_html(None, b"\xef\xbb\xbf\n  <!doctype html><html>"));
+    assert!(looks_like_html(Some("text/html; charset=utf-8"), b"<?xml version=\"1.0\"?>"));
+    assert!(!looks_like_html(Some("application/rss+xml"), b"<?xml version=\"1.0\"?><rss/>"));
+}
+
+#[tokio::test]
+async fn import_urls_adds_listed_sites_as_feeds() {
+    let server = MockServer::start().await;
+    let fixture = |name: &str| std::fs::read(Path::new("tests/fixtures/feeds").join(name)).unwrap();
+    Mock::given(method("GET"))
+        .and(path("/rss.xml"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(fixture("rss.xml"), "application/rss+xml"))
+        .mount(&server)
+        .await;
+    let page = "<html><head><title>A blog</title>\
+                <link rel=\"alternate\" type=\"application/atom+xml\" href=\"/atom.xml\"></head></html>";
+    Mock::given(method("GET"))
+        .and(path("/blog/"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(page, "text/html"))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/atom.xml"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(fixture("atom.xml"), "application/atom+xml"))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET")).and(path("/gone")).respond_with(ResponseTemplate::new(404)).mount(&server).await;
+
+    let dir = state_dir("import-urls");
+
+    let state = dir.path();
+    let config = state.join("import.toml");
+    let existing = format!("# My feeds\n[[feeds]]\nname = \"Known\"\nurl = \"{}/known.xml\"\n", server.uri());
+    std::fs::write(&config, &existing).unwrap();
+    let list = state.join("blogroll.md");
+    let uri = server.uri();
+    std::fs::write(
+        &list,
+        format!(
+            "# Blogroll\n\n- [Mine]({uri}/rss.xml)\n- <{uri}/blog/>\n* {uri}/gone, sadly.\n{uri}/known.xml/\n{uri}/rss.xml\n"
+        ),
+    )
+    .unwrap();
+
+    let out = news_cli(state, &["--feeds", config.to_str().unwrap(), "import-urls", list.to_str().unwrap()]).await;
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(out.status.success(), "{}{}", stdout, String::from_utf8_lossy(&out.stderr));
+    assert!(stdout.contains(&format!("Already configured: {}/known.xml/", uri)), "{}", stdout);
+    assert!(stdout.contains(&format!("Skipped {}/gone: ", uri)), "{}", stdout);
+    assert!(stdout.contains("Added 2 feeds"), "{}", stdout);
+    let written = std::fs::read_to_string(&config).unwrap();
+    // Appended after what was there, titled from the feeds; the page stands in for its feed
+    assert!(written.starts_with(&existing), "{}", written);
+    assert!(written.contains(&format!("name = \"RSS fixture\"\nurl = \"{}/rss.xml\"", uri)), "{}", written);
+    assert!(written.contains(&format!("url = \"{}/atom.xml\"", uri)), "{}", written);
+    assert_eq!(written.matches("[[feeds]]").count(), 3, "{}", written);
+
+    // Nothing new the second time
+    let again = news_cli(state, &["--feeds", config.to_str().unwrap(), "import-urls", list.to_str().unwrap()]).await;
+    assert!(String::from_utf8_lossy(&again.stdout).contains("No feeds added."));
+    let _ = std::fs::remove_dir_all(state);
+}
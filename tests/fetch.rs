@@ -0,0 +1,667 @@
+//! Fetching the fixture feeds: per-feed limits, per-host pacing, connection reuse, data
+//! saver mode and returned diagnostics; and providers against scripted or mock servers.
+
+use news_cli::config::Feed;
+use news_cli::news::provider::SourceProvider;
+use std::process::Command;
+
+#[test]
+fn per_feed_max_bytes_overrides_fetch_section() {
+    let state = std::env::temp_dir().join(format!("news-cli-fetch-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&state);
+    std::fs::create_dir_all(&state).unwrap();
+    let config = state.join("limits.toml");
+    std::fs::write(
+        &config,
+        "[fetch]\nmax_bytes = 100\n\n\
+         [[feeds]]\nname = \"World\"\nurl = \"tests/fixtures/world.xml\"\n\n\
+         [[feeds]]\nname = \"Tech\"\nurl = \"tests/fixtures/tech.xml\"\nmax_bytes = 1000000\n",
+    )
+    .unwrap();
+    let out = Command::new(env!("CARGO_BIN_EXE_news-cli"))
+        .args(["--feeds", config.to_str().unwrap(), "fetch", "--to", "json"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env("HOME", &state)
+        .env("XDG_STATE_HOME", state.join("state"))
+        .env("XDG_CACHE_HOME", state.join("cache"))
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .env_remove("LANG")
+        .output()
+        .expect("run news-cli fetch");
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let sources: Vec<String> = stdout
+        .lines()
+        .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap()["source"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(sources.len(), 12, "{}", stdout);
+    assert!(sources.iter().all(|s| s == "Tech"), "{}", stdout);
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    assert!(stderr.contains("Failed to fetch World: feed too large"), "{}", stderr);
+    assert!(stderr.contains("raise max_bytes"), "{}", stderr);
+}
+
+#[test]
+fn feeds_on_one_host_are_rate_limited() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::time::{Duration, Instant};
+
+    // Serves the tech fixture for every request, noting when each arrived
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let arrivals = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let seen = arrivals.clone();
+    std::thread::spawn(move || {
+        let body = std::fs::read("tests/fixtures/tech.xml").unwrap();
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            seen.lock().unwrap().push(Instant::now());
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/rss+xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            stream.write_all(head.as_bytes()).unwrap();
+            stream.write_all(&body).unwrap();
+        }
+    });
+
+    let state = std::env::temp_dir().join(format!("news-cli-fetch-rate-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&state);
+    std::fs::create_dir_all(&state).unwrap();
+    let config = state.join("rate.toml");
+    let feeds: String = ["a", "b", "c"]
+        .iter()
+        .map(|n| format!("[[feeds]]\nname = \"{}\"\nurl = \"http://127.0.0.1:{}/{}\"\n\n", n, port, n))
+        .collect();
+    std::fs::write(&config, format!("[fetch]\nper_host_rps = 5\n\n{}", feeds)).unwrap();
+    let out = Command::new(env!("CARGO_BIN_EXE_news-cli"))
+        .args(["--feeds", config.to_str().unwrap(), "fetch", "--to", "json"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env("HOME", &state)
+        .env("XDG_STATE_HOME", state.join("state"))
+        .env("XDG_CACHE_HOME", state.join("cache"))
+        .output()
+        .expect("run news-cli fetch");
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+
+    let arrivals = arrivals.lock().unwrap();
+    assert_eq!(arrivals.len(), 3);
+    for pair in arrivals.windows(2) {
+        // 5 per second = 200 ms apart; allow for timer slack
+        assert!(pair[1] - pair[0] >= Duration::from_millis(180), "requests {:?} apart", pair[1] - pair[0]);
+    }
+}
+
+#[tokio::test]
+async fn shared_context_reuses_connections() {
+    use news_cli::config::{AppConfig, Feed, RuntimeConfig};
+    use news_cli::news::provider::FetchContext;
+    use news_cli::{NewsClient, SeenStories};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    // Keep-alive server that counts the connections it accepts
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let connections = Arc::new(AtomicUsize::new(0));
+    let counter = connections.clone();
+    std::thread::spawn(move || {
+        let body = std::fs::read("tests/fixtures/tech.xml").unwrap();
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            counter.fetch_add(1, Ordering::SeqCst);
+            let body = body.clone();
+            std::thread::spawn(move || {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                        return;
+                    }
+                    while line.len() > 2 {
+                        line.clear();
+                        reader.read_line(&mut line).unwrap();
+                    }
+                    let head = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len());
+                    stream.write_all(head.as_bytes()).unwrap();
+                    stream.write_all(&body).unwrap();
+                }
+            });
+        }
+    });
+
+    let feed = Feed { name: "Tech".into(), url: format!("http://127.0.0.1:{}/tech", port), ..Default::default() };
+    let cfg = RuntimeConfig::from(AppConfig { feeds: vec![feed], ..Default::default() });
+    let ctx = FetchContext::new(cfg.fetch.clone()).unwrap();
+    let history = SeenStories::default();
+    for _ in 0..2 {
+        let stories = NewsClient::with_context(&cfg, ctx.clone()).fetch(&history).await.unwrap();
+        assert_eq!(stories.len(), 12);
+    }
+    assert_eq!(connections.load(Ordering::SeqCst), 1, "second fetch opened a new connection");
+}
+
+#[tokio::test]
+async fn fetch_all_returns_problems_instead_of_printing() {
+    use news_cli::config::{AppConfig, Feed, RuntimeConfig};
+    use news_cli::news::{self, Diagnostic};
+    use news_cli::SeenStories;
+
+    let feeds = vec![
+        Feed { name: "Tech".into(), url: "tests/fixtures/tech.xml".into(), ..Default::default() },
+        Feed { name: "Gone".into(), url: "tests/fixtures/missing.xml".into(), ..Default::default() },
+        Feed { name: "Odd".into(), url: "x".into(), kind: Some("gopher".into()), ..Default::default() },
+    ];
+    let cfg = RuntimeConfig::from(AppConfig { feeds, ..Default::default() });
+    // Runs on another task, as in a UI that keeps drawing meanwhile
+    let fetched = tokio::spawn(async move { news::fetch_all(&cfg, &SeenStories::default()).await })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(fetched.stories.len(), 12);
+    let feeds: Vec<Option<&str>> = fetched.diagnostics.iter().map(|d| d.feed()).collect();
+    assert_eq!(feeds, [Some("Gone"), Some("Odd")]);
+    assert!(matches!(&fetched.diagnostics[0], Diagnostic::Failed { .. }));
+    assert!(matches!(&fetched.diagnostics[1], Diagnostic::UnknownKind { kind, .. } if kind == "gopher"));
+}
+
+#[tokio::test]
+async fn cancelling_returns_the_feeds_done_so_far() {
+    use news_cli::config::{AppConfig, Feed, RuntimeConfig};
+    use news_cli::news::CancellationToken;
+    use news_cli::{NewsClient, SeenStories};
+    use std::time::{Duration, Instant};
+
+    let feeds = vec![
+        Feed { name: "Tech".into(), url: "tests/fixtures/tech.xml".into(), ..Default::default() },
+        Feed { name: "Slow".into(), kind: Some("command".into()), command: Some("sleep 30".into()), ..Default::default() },
+    ];
+    let cfg = RuntimeConfig::from(AppConfig { feeds, ..Default::default() });
+    let cancel = CancellationToken::new();
+    let stop = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        stop.cancel();
+    });
+    let started = Instant::now();
+    let fetched = NewsClient::new(&cfg).unwrap().fetch_all_cancellable(&SeenStories::default(), &cancel).await;
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(fetched.cancelled);
+    assert_eq!(fetched.stories.len(), 12);
+    assert!(fetched.stories.iter().all(|s| s.source == "Tech"));
+}
+
+#[tokio::test]
+async fn progress_is_reported_per_feed() {
+    use news_cli::config::{AppConfig, Feed, RuntimeConfig};
+    use news_cli::news::FeedProgress;
+    use news_cli::{NewsClient, SeenStories};
+
+    let feeds = vec![
+        Feed { name: "Tech".into(), url: "tests/fixtures/tech.xml".into(), ..Default::default() },
+        Feed { name: "Odd".into(), kind: Some("carrier-pigeon".into()), ..Default::default() },
+    ];
+    let cfg = RuntimeConfig::from(AppConfig { feeds, ..Default::default() });
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut client = NewsClient::new(&cfg).unwrap();
+    client.report_progress(tx);
+    client.fetch_all(&SeenStories::default()).await;
+    drop(client);
+    let mut seen = Vec::new();
+    while let Some(p) = rx.recv().await {
+        seen.push(p);
+    }
+    assert!(seen.contains(&FeedProgress::Queued("Tech".into())), "{:?}", seen);
+    assert!(seen.contains(&FeedProgress::Fetched { feed: "Tech".into(), stories: 12 }), "{:?}", seen);
+    assert!(seen.iter().any(|p| matches!(p, FeedProgress::Failed { feed, .. } if feed == "Odd")), "{:?}", seen);
+}
+
+#[test]
+fn data_saver_drops_images_and_asks_changed_since() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    // Serves the full fixture with a Date but neither ETag nor Last-Modified, noting each
+    // request's If-Modified-Since
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let asked = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let seen = asked.clone();
+    std::thread::spawn(move || {
+        let body = std::fs::read("tests/fixtures/full.xml").unwrap();
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut since = None;
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                if let Some(v) = line.to_ascii_lowercase().strip_prefix("if-modified-since:") {
+                    since = Some(v.trim().to_string());
+                }
+                line.clear();
+            }
+            let modified = since.is_some();
+            seen.lock().unwrap().push(since);
+            let head = match modified {
+                true => "HTTP/1.1 304 Not Modified\r\nDate: Mon, 02 Feb 2026 12:00:00 GMT\r\nConnection: close\r\n\r\n".to_string(),
+                false => format!(
+                    "HTTP/1.1 200 OK\r\nDate: Mon, 02 Feb 2026 12:00:00 GMT\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                ),
+            };
+            stream.write_all(head.as_bytes()).unwrap();
+            if !modified {
+                stream.write_all(&body).unwrap();
+            }
+        }
+    });
+
+    let state = std::env::temp_dir().join(format!("news-cli-fetch-saver-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&state);
+    std::fs::create_dir_all(&state).unwrap();
+    let config = state.join("saver.toml");
+    std::fs::write(&config, format!("[[feeds]]\nname = \"Long\"\nurl = \"http://127.0.0.1:{}/full\"\n", port)).unwrap();
+    for _ in 0..2 {
+        let out = Command::new(env!("CARGO_BIN_EXE_news-cli"))
+            .args(["--feeds", config.to_str().unwrap(), "--data-saver", "fetch", "--to", "json"])
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .env("HOME", &state)
+            .env("XDG_STATE_HOME", state.join("state"))
+            .env("XDG_CACHE_HOME", state.join("cache"))
+            .output()
+            .expect("run news-cli fetch");
+        assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+        let stdout = String::from_utf8(out.stdout).unwrap();
+        let story: serde_json::Value = serde_json::from_str(stdout.lines().next().unwrap()).unwrap();
+        assert_eq!(story["title"], "How the tide tables are made");
+        assert!(story["image"].is_null(), "{}", stdout);
+    }
+    let asked = asked.lock().unwrap();
+    assert_eq!(*asked, [None, Some("mon, 02 feb 2026 12:00:00 gmt".to_string())]);
+}
+
+// A fake IMAP server on the other end of the returned stream: for each (expected, reply) it
+// reads one command line, checks it starts with `expected` (nothing is read for ""), and
+// writes `reply`. The task returns what the client sent.
+fn imap_server(script: Vec<(&'static str, String)>) -> (tokio::io::DuplexStream, tokio::task::JoinHandle<String>) {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+
+    let (client, server) = tokio::io::duplex(256 * 1024);
+    let server = tokio::spawn(async move {
+        let mut server = tokio::io::BufReader::new(server);
+        let mut transcript = String::new();
+        for (expect, reply) in script {
+            if !expect.is_empty() {
+                let mut line = String::new();
+                assert!(server.read_line(&mut line).await.unwrap() > 0, "client hung up after {}", transcript);
+                transcript.push_str(&line);
+                assert!(line.starts_with(expect), "expected {}, got {}", expect, line);
+            }
+            server.get_mut().write_all(reply.as_bytes()).await.unwrap();
+        }
+        // Whatever else the client says before hanging up
+        let mut rest = String::new();
+        let _ = tokio::io::AsyncReadExt::read_to_string(&mut server, &mut rest).await;
+        transcript + &rest
+    });
+    (client, server)
+}
+
+fn imap_feed(instance: &str) -> Feed {
+    Feed {
+        name: "Mail".into(),
+        kind: Some("imap".into()),
+        instance: Some(instance.into()),
+        account: Some("me".into()),
+        folder: Some("News".into()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn imap_reads_unread_mail_without_marking_it() {
+    use news_cli::news::provider::imap;
+
+    let message = "From: Ann Example <ann@example.com>\r\nSubject: Weekly notes\r\n\
+                   Date: Mon, 02 Feb 2026 12:00:00 +0000\r\n\r\nHello there.\r\n";
+    let (stream, server) = imap_server(vec![
+        ("", "* OK IMAP ready\r\n".into()),
+        ("a1 LOGIN \"me\" \"p\\\\w\\\"d\"\r\n", "a1 OK logged in\r\n".into()),
+        ("a2 EXAMINE \"News\"\r\n", "* 2 EXISTS\r\n* OK [UIDVALIDITY 42] ids\r\na2 OK [READ-ONLY] done\r\n".into()),
+        ("a3 UID SEARCH UNSEEN\r\n", "* SEARCH 7\r\na3 OK\r\n".into()),
+        (
+            "a4 UID FETCH 7 BODY.PEEK[]\r\n",
+            format!("* 1 FETCH (UID 7 BODY[] {{{}}}\r\n{})\r\na4 OK\r\n", message.len(), message),
+        ),
+        ("a5 LOGOUT\r\n", "* BYE\r\na5 OK\r\n".into()),
+    ]);
+    let stories = imap::session(stream, &imap_feed("[::1]:1143"), "p\\w\"d", 1_000_000).await.unwrap();
+    server.await.unwrap();
+    assert_eq!(stories.len(), 1);
+    let story = &stories[0];
+    assert_eq!(story.title, "Weekly notes");
+    assert_eq!(story.link, "imap://me@[::1]:1143/News;UIDVALIDITY=42/;UID=7");
+    assert_eq!(story.author.as_deref(), Some("Ann Example"));
+    assert_eq!(story.summary.as_deref().map(str::trim), Some("Hello there."));
+    assert_eq!(story.published, Some(1_770_033_600));
+}
+
+#[tokio::test]
+async fn imap_stops_at_a_refused_command_and_oversized_messages() {
+    use news_cli::news::provider::imap;
+
+    let (stream, server) = imap_server(vec![
+        ("", "* OK IMAP ready\r\n".into()),
+        ("a1 LOGIN", "a1 NO [AUTHENTICATIONFAILED] wrong password\r\n".into()),
+    ]);
+    let err = imap::session(stream, &imap_feed("imap.example.com"), "nope", 1_000_000).await.unwrap_err();
+    assert!(format!("{:#}", err).contains("login failed: NO [AUTHENTICATIONFAILED] wrong password"), "{:#}", err);
+    server.await.unwrap();
+
+    let (stream, server) = imap_server(vec![
+        ("", "* OK IMAP ready\r\n".into()),
+        ("a1 LOGIN", "a1 OK\r\n".into()),
+        ("a2 EXAMINE", "a2 OK\r\n".into()),
+        ("a3 UID SEARCH", "* SEARCH 1\r\na3 OK\r\n".into()),
+        ("a4 UID FETCH 1", "* 1 FETCH (UID 1 BODY[] {5000}\r\n".into()),
+    ]);
+    let err = imap::session(stream, &imap_feed("imap.example.com"), "pw", 100).await.unwrap_err();
+    assert!(err.to_string().contains("message too large (5000 bytes)"), "{:#}", err);
+    server.await.unwrap();
+
+    let (stream, server) = imap_server(vec![("", format!("* OK {}", "x".repeat(100_000)))]);
+    let err = imap::session(stream, &imap_feed("imap.example.com"), "pw", 1_000_000).await.unwrap_err();
+    assert!(err.to_string().contains("response line too long"), "{:#}", err);
+    drop(server);
+}
+
+#[tokio::test]
+async fn imap_refuses_line_breaks_in_what_it_quotes() {
+    use news_cli::news::provider::imap;
+
+    let (stream, server) = imap_server(vec![("", "* OK IMAP ready\r\n".into())]);
+    let err = imap::session(stream, &imap_feed("imap.example.com"), "pw\r\na2 DELETE INBOX", 1_000_000).await.unwrap_err();
+    assert!(err.to_string().contains("can't contain line breaks"), "{:#}", err);
+    assert_eq!(server.await.unwrap(), "");
+}
+
+#[tokio::test]
+async fn imap_in_strict_mode_only_connects_to_public_addresses() {
+    use news_cli::config::FetchLimits;
+    use news_cli::news::provider::{FetchContext, ImapProvider};
+
+    let ctx = FetchContext::new(FetchLimits { strict: Some(true), ..Default::default() }).unwrap();
+    let feed = Feed { token: Some("pw".into()), ..imap_feed("localhost:1993") };
+    let err = ImapProvider.fetch(&ctx, &feed).await.unwrap_err();
+    assert!(format!("{:#}", err).contains("localhost resolves only to private or local addresses"), "{:#}", err);
+}
+
+// The stories `provider` makes of `feed`, which points at a mock server
+async fn provider_stories(provider: &dyn SourceProvider, feed: Feed) -> Vec<news_cli::news::Story> {
+    use news_cli::config::FetchLimits;
+    use news_cli::news::provider::FetchContext;
+
+    let ctx = FetchContext::new(FetchLimits::default()).unwrap();
+    provider.fetch(&ctx, &feed).await.unwrap()
+}
+
+#[tokio::test]
+async fn mastodon_titles_credit_boosts_and_show_content_warnings() {
+    use news_cli::news::provider::MastodonProvider;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let statuses = serde_json::json!([
+        {
+            "created_at": "2026-02-02T12:00:00.000Z",
+            "url": "https://social.example/@bob/2",
+            "uri": "https://social.example/users/bob/statuses/2",
+            "content": "",
+            "account": { "id": "2", "acct": "bob", "display_name": "Bob" },
+            "reblog": {
+                "created_at": "2026-02-01T09:00:00.000Z",
+                "url": "https://other.example/@ann/1",
+                "uri": "https://other.example/users/ann/statuses/1",
+                "content": "<p>Tide tables are out. More inside.</p>",
+                "account": { "id": "1", "acct": "ann@other.example", "display_name": "Ann" },
+                "media_attachments": [{ "type": "image", "preview_url": "https://other.example/tide.png" }]
+            }
+        },
+        {
+            "created_at": "2026-02-02T13:00:00.000Z",
+            "url": null,
+            "uri": "https://social.example/users/cy/statuses/3",
+            "content": "<p>The ending of the film</p>",
+            "spoiler_text": " Film spoilers ",
+            "account": { "id": "3", "acct": "cy", "display_name": "" }
+        }
+    ]);
+    Mock::given(method("GET"))
+        .and(path("/api/v1/timelines/tag/rust"))
+        .and(query_param("limit", "40"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(statuses))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let feed = Feed {
+        name: "Rust".into(),
+        kind: Some("mastodon".into()),
+        instance: Some(server.uri()),
+        account: Some("#rust".into()),
+        ..Default::default()
+    };
+    let stories = provider_stories(&MastodonProvider, feed).await;
+    let got: Vec<(&str, &str, Option<&str>)> =
+        stories.iter().map(|s| (s.title.as_str(), s.link.as_str(), s.author.as_deref())).collect();
+    assert_eq!(
+        got,
+        [
+            (
+                "Tide tables are out. (boosted by @bob)",
+                "https://other.example/@ann/1",
+                Some("Ann (@ann@other.example)")
+            ),
+            ("CW: Film spoilers", "https://social.example/users/cy/statuses/3", Some("@cy")),
+        ]
+    );
+    assert_eq!(stories[0].image.as_deref(), Some("https://other.example/tide.png"));
+    assert_eq!(stories[1].summary.as_deref(), Some("The ending of the film"));
+}
+
+#[tokio::test]
+async fn bluesky_lists_fetch_their_members_posts() {
+    use news_cli::news::provider::BlueskyProvider;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let list = "at://did:plc:team/app.bsky.graph.list/3kfriends";
+    let server = MockServer::start().await;
+    let feed = serde_json::json!({ "feed": [
+        {
+            "post": {
+                "uri": "at://did:plc:ann/app.bsky.feed.post/3kpost1",
+                "author": { "handle": "ann.example.com", "displayName": "Ann" },
+                "record": { "text": "Harbour   walk\ntoday", "createdAt": "2026-02-02T12:00:00Z" }
+            }
+        },
+        {
+            "post": {
+                "uri": "at://did:plc:cy/app.bsky.feed.post/3kpost2",
+                "author": { "handle": "cy.example.com" },
+                "record": { "text": "New map of the bay" }
+            },
+            "reason": { "$type": "app.bsky.feed.defs#reasonRepost", "by": { "handle": "bob.example.com" } }
+        }
+    ]});
+    Mock::given(method("GET"))
+        .and(path("/xrpc/app.bsky.feed.getListFeed"))
+        .and(query_param("list", list))
+        .respond_with(ResponseTemplate::new(200).set_body_json(feed))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let feed = Feed {
+        name: "Friends".into(),
+        kind: Some("bluesky".into()),
+        instance: Some(server.uri()),
+        account: Some(list.into()),
+        ..Default::default()
+    };
+    let stories = provider_stories(&BlueskyProvider, feed).await;
+    let got: Vec<(&str, &str, Option<&str>)> =
+        stories.iter().map(|s| (s.title.as_str(), s.link.as_str(), s.author.as_deref())).collect();
+    assert_eq!(
+        got,
+        [
+            ("Harbour walk today", "https://bsky.app/profile/ann.example.com/post/3kpost1", Some("Ann (@ann.example.com)")),
+            (
+                "New map of the bay (reposted by @bob.example.com)",
+                "https://bsky.app/profile/cy.example.com/post/3kpost2",
+                Some("@cy.example.com")
+            ),
+        ]
+    );
+    assert_eq!(stories[0].published, Some(1_770_033_600));
+}
+
+#[tokio::test]
+async fn github_releases_skip_drafts_and_mark_pre_releases() {
+    use news_cli::news::provider::GithubProvider;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let releases = serde_json::json!([
+        {
+            "name": "v2.0 draft", "tag_name": "v2.0", "html_url": "https://github.com/acme/tool/releases/tag/v2.0",
+            "draft": true
+        },
+        {
+            "name": "", "tag_name": "v1.1-rc1", "html_url": "https://github.com/acme/tool/releases/tag/v1.1-rc1",
+            "prerelease": true, "published_at": "2026-02-02T12:00:00Z", "body": "Testing the new parser",
+            "author": { "login": "ann" }
+        },
+        { "name": "Tool 1.0", "tag_name": "v1.0", "html_url": "https://github.com/acme/tool/releases/tag/v1.0", "body": " " }
+    ]);
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/tool/releases"))
+        .and(query_param("per_page", "10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(releases))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let feed = Feed {
+        name: "Tool".into(),
+        kind: Some("github".into()),
+        instance: Some(server.uri()),
+        account: Some("acme/tool".into()),
+        ..Default::default()
+    };
+    let stories = provider_stories(&GithubProvider, feed).await;
+    let got: Vec<(&str, &str, Option<&str>)> =
+        stories.iter().map(|s| (s.title.as_str(), s.link.as_str(), s.summary.as_deref())).collect();
+    assert_eq!(
+        got,
+        [
+            (
+                "acme/tool v1.1-rc1 released (pre-release)",
+                "https://github.com/acme/tool/releases/tag/v1.1-rc1",
+                Some("Testing the new parser")
+            ),
+            ("acme/tool Tool 1.0 released", "https://github.com/acme/tool/releases/tag/v1.0", None),
+        ]
+    );
+    assert_eq!(stories[0].author.as_deref(), Some("ann"));
+    assert_eq!(stories[0].published, Some(1_770_033_600));
+}
+
+#[tokio::test]
+async fn github_notifications_link_to_web_pages() {
+    use news_cli::news::provider::GithubProvider;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let api = format!("{}/repos/acme/tool", server.uri());
+    let notification = |kind: &str, url: Option<String>| {
+        serde_json::json!({
+            "subject": { "title": format!("A {}", kind), "url": url, "type": kind },
+            "repository": { "full_name": "acme/tool", "html_url": "https://github.com/acme/tool" },
+            "updated_at": "2026-02-02T12:00:00Z",
+            "reason": "review_requested"
+        })
+    };
+    let list = serde_json::json!([
+        notification("Issue", Some(format!("{}/issues/7", api))),
+        notification("PullRequest", Some(format!("{}/pulls/8", api))),
+        notification("Commit", Some(format!("{}/commits/abc123", api))),
+        notification("Release", Some(format!("{}/releases/9", api))),
+        notification("Discussion", None),
+        // Not on this API server
+        notification("Issue", Some("https://elsewhere.example/repos/acme/tool/issues/1".into())),
+    ]);
+    Mock::given(method("GET"))
+        .and(path("/notifications"))
+        .and(header("authorization", "Bearer t0ken"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let feed = Feed {
+        name: "Inbox".into(),
+        kind: Some("github".into()),
+        instance: Some(server.uri()),
+        account: Some("notifications".into()),
+        token: Some("t0ken".into()),
+        ..Default::default()
+    };
+    let stories = provider_stories(&GithubProvider, feed).await;
+    let links: Vec<&str> = stories.iter().map(|s| s.link.as_str()).collect();
+    assert_eq!(
+        links,
+        [
+            "https://github.com/acme/tool/issues/7",
+            "https://github.com/acme/tool/pull/8",
+            "https://github.com/acme/tool/commit/abc123",
+            "https://github.com/acme/tool/releases",
+            "https://github.com/acme/tool",
+            "https://github.com/acme/tool",
+        ]
+    );
+    assert_eq!(stories[1].title, "[acme/tool] A PullRequest");
+    assert_eq!(stories[1].summary.as_deref(), Some("PullRequest (review requested)"));
+}
+
+#[tokio::test]
+async fn miniflux_sync_follows_strict_mode() {
+    use news_cli::config::FetchLimits;
+    use news_cli::sync::{MinifluxBackend, SyncBackend};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/entries"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "total": 0, "entries": [] })))
+        .mount(&server)
+        .await;
+    let open = MinifluxBackend::new(&server.uri(), "token", &[], &FetchLimits::default()).unwrap();
+    assert!(open.pull().await.unwrap().read.is_empty());
+
+    let strict = FetchLimits { strict: Some(true), ..Default::default() };
+    let err = MinifluxBackend::new(&server.uri(), "token", &[], &strict).err().unwrap();
+    assert!(err.to_string().contains("127.0.0.1 is a private or local address"), "{}", err);
+    let by_name = format!("http://localhost:{}", server.address().port());
+    let err = MinifluxBackend::new(&by_name, "token", &[], &strict).unwrap().pull().await.unwrap_err();
+    assert!(format!("{:?}", err).contains("localhost resolves only to private or local addresses"), "{:?}", err);
+}
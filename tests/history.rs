@@ -0,0 +1,50 @@
+//! `news-cli history export/import` between two state directories ("machines").
+
+mod common;
+
+use common::{run_ok, state_dir};
+use std::process::Command;
+
+#[test]
+fn import_merges_another_machines_history() {
+    let desktop_dir = state_dir("desktop");
+    let desktop = desktop_dir.path();
+    let laptop_dir = state_dir("laptop");
+    let laptop = laptop_dir.path();
+    run_ok(desktop, &["--feeds", "tests/fixtures/world.xml", "fetch", "--mark-seen"]);
+    run_ok(laptop, &["--feeds", "tests/fixtures/full.xml", "fetch", "--mark-seen"]);
+
+    let file = desktop.join("history.json");
+    let out = run_ok(desktop, &["history", "export", file.to_str().unwrap()]);
+    assert_eq!(out, format!("Exported 3 seen links to {}.\n", file.display()));
+    let exported = std::fs::read_to_string(&file).unwrap();
+    assert!(exported.contains("\"format\": \"news-cli-history\""), "{}", exported);
+    assert!(exported.contains("\"seen_at\""), "{}", exported);
+
+    let out = run_ok(laptop, &["history", "import", file.to_str().unwrap()]);
+    assert_eq!(out, "Imported 3 links (3 new); the history now has 4.\n");
+    // Importing again changes nothing
+    let out = run_ok(laptop, &["history", "import", file.to_str().unwrap()]);
+    assert_eq!(out, "Imported 3 links (0 new); the history now has 4.\n");
+
+    let merged = run_ok(laptop, &["history", "export"]);
+    for link in ["https://example.com/world/rates", "https://example.com/longreads/tides"] {
+        assert!(merged.contains(link), "{} missing:\n{}", link, merged);
+    }
+}
+
+#[test]
+fn import_rejects_other_json() {
+    let dir = state_dir("reject");
+    let state = dir.path();
+    let file = state.join("other.json");
+    std::fs::write(&file, r#"{"format":"something-else","version":1,"exported_at":0,"seen":[]}"#).unwrap();
+    let out = Command::new(env!("CARGO_BIN_EXE_news-cli"))
+        .args(["history", "import", file.to_str().unwrap()])
+        .env("HOME", state)
+        .env("XDG_STATE_HOME", state.join("state"))
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("not a news-cli history export"));
+}
@@ -0,0 +1,74 @@
+//! The message catalogs in src/i18n must stay in step: every locale has every English
+//! message, with the same placeholders, and every message the code asks for.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+fn messages(locale: &str) -> BTreeMap<String, String> {
+    let path = format!("{}/src/i18n/{}.toml", env!("CARGO_MANIFEST_DIR"), locale);
+    let table: toml::Table = std::fs::read_to_string(&path).unwrap().parse().unwrap();
+    let mut out = BTreeMap::new();
+    for (section, entries) in table {
+        for (key, value) in entries.as_table().unwrap_or_else(|| panic!("{}: [{}] is not a section", path, section)) {
+            let text = value.as_str().unwrap_or_else(|| panic!("{}: {}.{} is not a string", path, section, key));
+            out.insert(format!("{}.{}", section, key), text.to_string());
+        }
+    }
+    out
+}
+
+fn placeholders(text: &str) -> BTreeSet<String> {
+    text.split('{').skip(1).filter_map(|rest| rest.split_once('}')).map(|(n, _)| n.to_string()).collect()
+}
+
+#[test]
+fn catalogs_match_english() {
+    let en = messages("en");
+    for locale in news_cli::i18n::LOCALES.iter().filter(|l| **l != "en") {
+        let other = messages(locale);
+        let missing: Vec<&String> = en.keys().filter(|k| !other.contains_key(*k)).collect();
+        let extra: Vec<&String> = other.keys().filter(|k| !en.contains_key(*k)).collect();
+        assert!(missing.is_empty() && extra.is_empty(), "{}: missing {:?}, unknown {:?}", locale, missing, extra);
+        for (key, text) in &en {
+            assert_eq!(placeholders(text), placeholders(&other[key]), "{}: placeholders of {}", locale, key);
+        }
+    }
+}
+
+// Every .rs file under `dir`
+fn sources(dir: &std::path::Path, out: &mut Vec<std::path::PathBuf>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            sources(&path, out);
+        } else if path.extension().is_some_and(|e| e == "rs") {
+            out.push(path);
+        }
+    }
+}
+
+#[test]
+fn every_message_the_code_uses_is_in_every_catalog() {
+    let catalogs: Vec<(&str, BTreeMap<String, String>)> =
+        news_cli::i18n::LOCALES.iter().map(|l| (*l, messages(l))).collect();
+    let used = regex::Regex::new(r#"\btr!\(\s*"([a-z_]+\.[a-z0-9_]+)""#).unwrap();
+    let mut files = Vec::new();
+    sources(&std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), &mut files);
+    let mut missing = Vec::new();
+    for file in &files {
+        let code = std::fs::read_to_string(file).unwrap();
+        for key in used.captures_iter(&code).map(|c| c[1].to_string()) {
+            for (locale, catalog) in &catalogs {
+                if !catalog.contains_key(&key) {
+                    missing.push(format!("{} in {}.toml (used in {})", key, locale, file.display()));
+                }
+            }
+        }
+    }
+    assert!(missing.is_empty(), "messages missing from the catalogs:\n{}", missing.join("\n"));
+}
+
+#[test]
+fn fill_replaces_numbered_placeholders() {
+    assert_eq!(news_cli::i18n::fill("{1} of {0}, {x}", &[&"a", &2]), "2 of a, {x}");
+    assert_eq!(news_cli::i18n::fill("{0} {", &[&1]), "1 {");
+}
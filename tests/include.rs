@@ -0,0 +1,82 @@
+//! Splitting config.toml with `include`.
+
+use news_cli::config::load_file;
+use std::path::{Path, PathBuf};
+
+// A config dir with config.toml and `files` (relative path, contents)
+fn config_dir(name: &str, main: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("news-cli-include-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("feeds.d")).unwrap();
+    std::fs::write(dir.join("config.toml"), main).unwrap();
+    for (path, contents) in files {
+        std::fs::write(dir.join(path), contents).unwrap();
+    }
+    dir.join("config.toml")
+}
+
+fn feed(name: &str, file: &str) -> String {
+    let url = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(file);
+    format!("[[feeds]]\nname = \"{}\"\nurl = \"{}\"\n", name, url.display())
+}
+
+#[test]
+fn included_feeds_and_settings_are_merged_in_order() {
+    let main = format!("include = [\"feeds.d/*.toml\", \"alerts.toml\"]\nrefresh_interval_secs = 300\n\n{}", feed("World", "world.xml"));
+    let path = config_dir(
+        "merge",
+        &main,
+        &[
+            ("feeds.d/b-tech.toml", &format!("[fetch]\ntimeout_secs = 20\n\n{}", feed("Tech", "tech.xml"))),
+            ("feeds.d/a-german.toml", &feed("German", "german.xml")),
+            ("feeds.d/notes.txt", "not a config"),
+            ("alerts.toml", "[[alerts]]\nname = \"Rates\"\nkeywords = [\"rates\"]\n"),
+        ],
+    );
+    let cfg = load_file(&path).unwrap();
+    let names: Vec<&str> = cfg.feeds.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, ["World", "German", "Tech"]);
+    assert_eq!(cfg.refresh_interval_secs, Some(300));
+    assert_eq!(cfg.fetch.timeout_secs, Some(20));
+    assert_eq!(cfg.alerts.len(), 1);
+    let included: Vec<String> = cfg.included.iter().map(|p| p.file_name().unwrap().to_string_lossy().into_owned()).collect();
+    assert_eq!(included, ["a-german.toml", "b-tech.toml", "alerts.toml"]);
+}
+
+#[test]
+fn conflicts_name_both_files() {
+    let main = format!("include = [\"feeds.d/*.toml\"]\nrefresh_interval_secs = 300\n\n{}", feed("World", "world.xml"));
+    let path = config_dir(
+        "conflict",
+        &main,
+        &[
+            ("feeds.d/a.toml", &format!("refresh_interval_secs = 600\n\n{}", feed("Tech", "tech.xml"))),
+            ("feeds.d/b.toml", &feed("Tech", "german.xml")),
+        ],
+    );
+    let err = load_file(&path).unwrap_err().to_string();
+    let dir = path.parent().unwrap().join("feeds.d");
+    let main = path.display();
+    let (a, b) = (dir.join("a.toml"), dir.join("b.toml"));
+    assert!(err.contains(&format!("refresh_interval_secs is set in {} and in {}", main, a.display())), "{}", err);
+    assert!(err.contains(&format!("feeds \"Tech\" is in {} and in {}", a.display(), b.display())), "{}", err);
+}
+
+#[test]
+fn the_same_value_twice_is_no_conflict_but_missing_files_and_nesting_are_errors() {
+    let main = format!("include = [\"feeds.d/*.toml\"]\nlist_times = \"off\"\n\n{}", feed("World", "world.xml"));
+    let path = config_dir("same", &main, &[("feeds.d/a.toml", "list_times = \"off\"\n")]);
+    assert!(load_file(&path).is_ok());
+
+    let path = config_dir("missing", &format!("include = [\"extra.toml\"]\n\n{}", feed("World", "world.xml")), &[]);
+    let err = load_file(&path).unwrap_err().to_string();
+    assert!(err.contains("include \"extra.toml\": no such file"), "{}", err);
+
+    let path = config_dir(
+        "nested",
+        &format!("include = [\"feeds.d/*.toml\"]\n\n{}", feed("World", "world.xml")),
+        &[("feeds.d/a.toml", "include = [\"../more.toml\"]\n")],
+    );
+    let err = load_file(&path).unwrap_err().to_string();
+    assert!(err.contains("include only works in"), "{}", err);
+}
@@ -0,0 +1,60 @@
+//! Following a link's redirects before opening it, and what makes a host look unusual.
+
+use news_cli::config::FetchLimits;
+use news_cli::linkcheck::{check, host_warnings, MAX_REDIRECTS};
+use url::Url;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn redirect(to: &str) -> ResponseTemplate {
+    ResponseTemplate::new(302).insert_header("Location", to)
+}
+
+#[tokio::test]
+async fn redirects_are_followed_to_the_destination() {
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD")).and(path("/short")).respond_with(redirect("/long")).mount(&server).await;
+    // Same server under another name: another site as far as the reader can tell
+    let elsewhere = server.uri().replace("127.0.0.1", "localhost");
+    let target = format!("{}/article", elsewhere);
+    Mock::given(method("HEAD")).and(path("/long")).respond_with(redirect(&target)).mount(&server).await;
+    Mock::given(method("HEAD")).and(path("/article")).respond_with(ResponseTemplate::new(200)).mount(&server).await;
+
+    let link: Url = format!("{}/short", server.uri()).parse().unwrap();
+    let checked = check(&FetchLimits::default(), &link).await;
+    assert_eq!(checked.redirects(), 2);
+    assert_eq!(checked.destination().as_str(), target);
+    assert!(checked.refused.is_none());
+    assert!(checked.warnings.iter().any(|w| w.contains("localhost")), "{:?}", checked.warnings);
+    // The link itself names an IP address
+    assert!(checked.warnings.iter().any(|w| w.contains("127.0.0.1")), "{:?}", checked.warnings);
+}
+
+#[tokio::test]
+async fn redirects_away_from_the_web_or_in_circles_are_refused() {
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD")).and(path("/app")).respond_with(redirect("ms-settings:privacy")).mount(&server).await;
+    Mock::given(method("HEAD")).and(path("/loop")).respond_with(redirect("/loop")).mount(&server).await;
+
+    let checked = check(&FetchLimits::default(), &format!("{}/app", server.uri()).parse().unwrap()).await;
+    assert!(checked.refused.as_deref().is_some_and(|r| r.contains("ms-settings")), "{:?}", checked.refused);
+
+    let checked = check(&FetchLimits::default(), &format!("{}/loop", server.uri()).parse().unwrap()).await;
+    assert!(checked.refused.is_some());
+    assert_eq!(checked.redirects(), MAX_REDIRECTS + 1);
+}
+
+#[test]
+fn lookalike_hosts_are_pointed_out() {
+    let url = |s: &str| s.parse::<Url>().unwrap();
+    // An ordinary link, and one moving only to www. and https
+    assert!(host_warnings(&url("https://example.com/a"), &url("https://example.com/b")).is_empty());
+    assert!(host_warnings(&url("http://example.com/a"), &url("https://www.example.com/a")).is_empty());
+    // "pаypal.com" with a Cyrillic "а" mixes alphabets; an all-Greek name is only international
+    let mixed = host_warnings(&url("https://pаypal.com/login"), &url("https://pаypal.com/login"));
+    assert_eq!(mixed.len(), 1);
+    assert!(mixed[0].contains("Latin + Cyrillic") && mixed[0].contains("xn--"), "{:?}", mixed);
+    let greek = host_warnings(&url("https://ελλάδα.gr/"), &url("https://ελλάδα.gr/"));
+    assert_eq!(greek.len(), 1);
+    assert!(!greek[0].contains(" + "), "{:?}", greek);
+}
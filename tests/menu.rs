@@ -0,0 +1,169 @@
+//! Arrow-key list navigation, driven by scripted keys instead of a terminal.
+
+use console::Key;
+use news_cli::menu::{navigate, share_rows, Input, List, ListPosition, MenuChoice, Styles, Terminal};
+use std::collections::VecDeque;
+use std::io;
+
+// Hands out the scripted keys and keeps every frame (without colors)
+struct Script {
+    keys: VecDeque<Input>,
+    size: (usize, usize),
+    frames: Vec<Vec<String>>,
+    positions: Vec<ListPosition>,
+}
+
+impl Script {
+    fn new(keys: impl IntoIterator<Item = Key>) -> Self {
+        let keys = keys.into_iter().map(Input::Key).collect();
+        Script { keys, size: (30, 200), frames: Vec::new(), positions: Vec::new() }
+    }
+
+    fn last_frame(&self) -> &[String] {
+        self.frames.last().expect("drawn")
+    }
+}
+
+impl Terminal for Script {
+    fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    fn next_input(&mut self) -> io::Result<Input> {
+        Ok(self.keys.pop_front().expect("more keys than scripted"))
+    }
+
+    fn draw(&mut self, lines: &[String]) -> io::Result<()> {
+        self.frames.push(lines.iter().map(|l| console::strip_ansi_codes(l).into_owned()).collect());
+        Ok(())
+    }
+
+    fn moved(&mut self, position: &ListPosition) {
+        self.positions.push(position.clone());
+    }
+}
+
+fn list<'a>(items: &'a [&'a str], sections: Option<&'a [usize]>) -> List<'a> {
+    let actions = &[('i', "details")];
+    List { prompt: "Pick one", items, header: None, crumb: None, sections, actions, styles: Styles::default() }
+}
+
+fn run(script: &mut Script, list: &List, start: usize) -> MenuChoice {
+    let (mut sel, mut filter) = (start, None);
+    navigate(script, list, &mut sel, &mut filter).unwrap()
+}
+
+#[test]
+fn arrows_digits_and_keys_choose_items() {
+    let items = ["one", "two", "three", "four", "five"];
+    let list = list(&items, None);
+    let choose = |keys: Vec<Key>, start| run(&mut Script::new(keys), &list, start);
+    assert_eq!(choose(vec![Key::ArrowDown, Key::ArrowDown, Key::Enter], 0), MenuChoice::Index(2));
+    assert_eq!(choose(vec![Key::ArrowUp, Key::ArrowUp, Key::Enter], 1), MenuChoice::Index(0));
+    assert_eq!(choose(vec![Key::End, Key::ArrowDown, Key::Enter], 0), MenuChoice::Index(4));
+    assert_eq!(choose(vec![Key::Home, Key::Enter], 3), MenuChoice::Index(0));
+    // Digits jump without opening; Enter opens
+    assert_eq!(choose(vec![Key::Char('4'), Key::Enter], 0), MenuChoice::Index(3));
+    assert_eq!(choose(vec![Key::Char('i')], 2), MenuChoice::Action('i', 2));
+    assert_eq!(choose(vec![Key::Char('b')], 0), MenuChoice::Back);
+    assert_eq!(choose(vec![Key::CtrlC], 0), MenuChoice::Quit);
+
+    let mut script = Script::new([Key::ArrowDown]);
+    script.keys.push_back(Input::Refresh);
+    assert_eq!(run(&mut script, &list, 0), MenuChoice::Refresh);
+    assert_eq!(script.positions.last().unwrap().selected, Some(1));
+}
+
+#[test]
+fn tab_jumps_between_sections_and_wraps() {
+    let items = ["A", "a1", "a2", "B", "b1", "b2", "C", "c1", "c2"];
+    let sections = [0, 3, 6];
+    let list = list(&items, Some(&sections));
+    let choose = |keys: Vec<Key>, start| run(&mut Script::new(keys), &list, start);
+    assert_eq!(choose(vec![Key::Tab, Key::Enter], 1), MenuChoice::Index(3));
+    assert_eq!(choose(vec![Key::Tab, Key::Tab, Key::Enter], 1), MenuChoice::Index(6));
+    assert_eq!(choose(vec![Key::Tab, Key::Enter], 7), MenuChoice::Index(0));
+    // Shift-Tab: this section's header, then the one before; wraps to the last
+    assert_eq!(choose(vec![Key::BackTab, Key::Enter], 5), MenuChoice::Index(3));
+    assert_eq!(choose(vec![Key::BackTab, Key::BackTab, Key::Enter], 5), MenuChoice::Index(0));
+    assert_eq!(choose(vec![Key::BackTab, Key::Enter], 0), MenuChoice::Index(6));
+    // 's' opens the section picker; 'b' is the second section
+    assert_eq!(choose(vec![Key::Char('s'), Key::Char('b'), Key::Enter], 0), MenuChoice::Index(3));
+    // Left arrow folds the section in lists with a 'z' action
+    let folding = List { actions: &[('z', "fold")], ..list };
+    assert_eq!(run(&mut Script::new([Key::ArrowLeft]), &folding, 4), MenuChoice::Action('z', 4));
+}
+
+#[test]
+fn viewport_scrolls_with_the_selection() {
+    let labels: Vec<String> = (1..=30).map(|n| format!("Item {}", n)).collect();
+    let items: Vec<&str> = labels.iter().map(|s| s.as_str()).collect();
+    let list = list(&items, None);
+    let mut script = Script::new([Key::End, Key::PageUp, Key::Enter]);
+    // Prompt, 7 items, the position line and the help line
+    script.size = (10, 200);
+    assert_eq!(run(&mut script, &list, 0), MenuChoice::Index(23));
+
+    let first = &script.frames[0];
+    assert_eq!(first.len(), 10, "{:#?}", first);
+    assert_eq!(first[1], "> 1: Item 1");
+    assert_eq!(first[7], "  7: Item 7");
+    assert_eq!(first[8], "item 1/30");
+    let end = &script.frames[1];
+    assert_eq!(end[1], "  24: Item 24", "{:#?}", end);
+    assert_eq!(end[7], "> 30: Item 30");
+    assert_eq!(end[8], "item 30/30");
+    // A page up moves the highlight to the top row without scrolling
+    let page_up = script.last_frame();
+    assert_eq!(page_up[1], "> 24: Item 24", "{:#?}", page_up);
+}
+
+#[test]
+fn filter_narrows_the_list_and_keeps_section_headers() {
+    let items = ["World", "Rates held steady", "Election results", "Tech", "New phone", "Chip rates"];
+    let sections = [0, 3];
+    let list = list(&items, Some(&sections));
+    let typed = "/rat".chars().map(Key::Char);
+    let mut script = Script::new(typed.chain([Key::ArrowDown, Key::ArrowDown, Key::ArrowDown, Key::Enter]));
+    assert_eq!(run(&mut script, &list, 0), MenuChoice::Index(5));
+    // World (highlighted), its match, then Tech (kept for "Chip rates") and its match
+    let shown: Vec<&str> = script.frames[4].iter().map(|l| l.as_str()).collect();
+    assert!(shown.contains(&"> 1: World"), "{:#?}", shown);
+    assert!(shown.contains(&"  2: Rates held steady"), "{:#?}", shown);
+    assert!(!shown.iter().any(|l| l.contains("Election")), "{:#?}", shown);
+    assert!(shown.contains(&"  4: Tech"), "{:#?}", shown);
+    assert!(!shown.iter().any(|l| l.contains("New phone")), "{:#?}", shown);
+    assert_eq!(script.positions.last().unwrap().filter.as_deref(), Some("rat"));
+}
+
+#[test]
+fn right_to_left_and_emoji_labels_keep_their_columns() {
+    use news_cli::util::width::display_width;
+
+    let items = ["שלום עולם [NEW]", "👩‍💻 Developer news, cut before the end of it", "plain"];
+    let list = list(&items, None);
+    let mut script = Script::new([Key::Enter]);
+    script.size = (30, 24);
+    run(&mut script, &list, 0);
+    let frame = script.last_frame();
+    // The Hebrew label is isolated, so the marker and number stay on its left
+    assert!(frame.contains(&"> 1: \u{2068}שלום עולם [NEW]\u{2069}".to_string()), "{:?}", frame);
+    // The ZWJ emoji is one wide glyph: the cut line fills the row exactly
+    let emoji = frame.iter().find(|l| l.starts_with("  2: ")).expect("second item shown");
+    assert!(emoji.starts_with("  2: 👩‍💻 Developer"), "{}", emoji);
+    assert!(emoji.ends_with('…'), "{}", emoji);
+    assert_eq!(display_width(emoji), 24, "{}", emoji);
+}
+
+#[test]
+fn rows_go_to_sections_with_unread_items() {
+    // A quiet section takes what it has; the busy one gets the rest
+    assert_eq!(share_rows(20, 3, &[(2, 2), (40, 40)]), [2, 18]);
+    // Everyone gets the floor; then unread items count, not how many there are
+    assert_eq!(share_rows(12, 3, &[(0, 30), (5, 30), (20, 30)]), [3, 3, 6]);
+    assert_eq!(share_rows(28, 3, &[(0, 30), (5, 30), (20, 30)]), [3, 5, 20]);
+    // Nothing past what a section has, even with rows to spare
+    assert_eq!(share_rows(50, 3, &[(1, 4), (2, 6)]), [4, 6]);
+    // The floor is kept even beyond the budget
+    assert_eq!(share_rows(4, 3, &[(9, 9), (9, 9)]), [3, 3]);
+}
@@ -0,0 +1,108 @@
+//! Config overrides from NEWS_CLI_* variables and --set.
+
+use news_cli::config::overrides::{apply_all, from_env, parse_set, Override};
+use news_cli::config::{AppConfig, Feed};
+use std::process::Command;
+
+fn config() -> AppConfig {
+    AppConfig {
+        feeds: vec![Feed { name: "World".into(), url: "tests/fixtures/world.xml".into(), ..Feed::default() }],
+        ..AppConfig::default()
+    }
+}
+
+fn set(arg: &str) -> Override {
+    parse_set(arg).unwrap()
+}
+
+#[test]
+fn env_names_map_to_dotted_keys() {
+    let vars = [
+        ("NEWS_CLI_THEME__NO_COLOR", "true"),
+        ("NEWS_CLI_REFRESH_INTERVAL_SECS", "60"),
+        ("NEWS_CLI_STATE_DIR", "/tmp/state"),
+        ("NEWS_CLI_SYNC_TOKEN", "secret"),
+        ("HOME", "/root"),
+    ];
+    let found = from_env(vars.iter().map(|(k, v)| (k.to_string(), v.to_string())));
+    let keys: Vec<(&str, &str)> = found.iter().map(|o| (o.key.as_str(), o.value.as_str())).collect();
+    assert_eq!(keys, [("refresh_interval_secs", "60"), ("theme.no_color", "true")]);
+    assert_eq!(found[1].origin, "NEWS_CLI_THEME__NO_COLOR");
+}
+
+#[test]
+fn values_go_into_tables_and_list_entries() {
+    let cfg = apply_all(
+        config(),
+        &[
+            set("theme.no_color=true"),
+            set("fetch.timeout_secs=5"),
+            set("display_timezone=Europe/Berlin"),
+            set("tracking_params=[\"fbclid\", \"mc_*\"]"),
+            set("feeds.0.url=tests/fixtures/tech.xml"),
+            // Later ones win
+            set("fetch.timeout_secs=7"),
+        ],
+    )
+    .unwrap();
+    assert!(cfg.theme.unwrap().no_color);
+    assert_eq!(cfg.fetch.unwrap().timeout_secs, Some(7));
+    assert_eq!(cfg.display_timezone.as_deref(), Some("Europe/Berlin"));
+    assert_eq!(cfg.tracking_params, ["fbclid", "mc_*"]);
+    assert_eq!(cfg.feeds[0].url, "tests/fixtures/tech.xml");
+}
+
+#[test]
+fn unknown_keys_and_bad_values_name_their_origin() {
+    let err = apply_all(config(), &[set("fetch.timeout=5")]).unwrap_err().to_string();
+    assert!(err.contains("--set fetch.timeout") && err.contains("timeout"), "{}", err);
+    let err = apply_all(config(), &[set("refresh_interval_secs=soon")]).unwrap_err().to_string();
+    assert!(err.contains("--set refresh_interval_secs"), "{}", err);
+    let err = apply_all(config(), &[set("feeds.3.url=x")]).unwrap_err().to_string();
+    assert_eq!(err, "--set feeds.3.url: feeds has no entry 3");
+    assert!(parse_set("no_equals_sign").is_none());
+}
+
+#[test]
+fn set_and_env_override_the_config_file() {
+    let state = std::env::temp_dir().join(format!("news-cli-overrides-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&state);
+    std::fs::create_dir_all(&state).unwrap();
+    let run = |args: &[&str], env: &[(&str, &str)]| {
+        let out = Command::new(env!("CARGO_BIN_EXE_news-cli"))
+            .args(args)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .env("HOME", &state)
+            .env("XDG_STATE_HOME", state.join("state"))
+            .env("XDG_CACHE_HOME", state.join("cache"))
+            .envs(env.iter().copied())
+            .output()
+            .expect("run news-cli fetch");
+        (out.status.success(), String::from_utf8(out.stdout).unwrap(), String::from_utf8(out.stderr).unwrap())
+    };
+    let sources = |stdout: &str| -> Vec<String> {
+        stdout
+            .lines()
+            .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap()["source"].as_str().unwrap().to_string())
+            .collect()
+    };
+    let base = ["--feeds", "tests/fixtures/config.toml"];
+
+    let (ok, stdout, stderr) = run(&[&base[..], &["--set", "feeds.1.max_bytes=100", "fetch", "--to", "json"]].concat(), &[]);
+    assert!(ok, "{}", stderr);
+    assert!(sources(&stdout).iter().all(|s| s == "World"), "{}", stdout);
+    assert!(stderr.contains("Failed to fetch Tech: feed too large"), "{}", stderr);
+
+    // The command line wins over the environment
+    let env = [("NEWS_CLI_FEEDS__0__MAX_BYTES", "100")];
+    let (ok, stdout, _) = run(&[&base[..], &["fetch", "--to", "json"]].concat(), &env);
+    assert!(ok);
+    assert!(sources(&stdout).iter().all(|s| s == "Tech"), "{}", stdout);
+    let (ok, stdout, _) = run(&[&base[..], &["--set", "feeds.0.max_bytes=1000000", "fetch", "--to", "json"]].concat(), &env);
+    assert!(ok);
+    assert!(sources(&stdout).iter().any(|s| s == "World"), "{}", stdout);
+
+    let (ok, _, stderr) = run(&[&base[..], &["--set", "fetch.timeout=5", "fetch"]].concat(), &[]);
+    assert!(!ok);
+    assert!(stderr.contains("--set fetch.timeout"), "{}", stderr);
+}
@@ -0,0 +1,41 @@
+//! Reading queue bookkeeping (no state directory involved).
+
+mod common;
+
+use common::story;
+use news_cli::queue::Queue;
+
+fn links(queue: &Queue) -> Vec<&str> {
+    queue.items().iter().map(|q| q.story.link.as_str()).collect()
+}
+
+#[test]
+fn toggle_appends_and_takes_off() {
+    let mut queue = Queue::default();
+    assert!(queue.toggle(&story("a")));
+    assert!(queue.toggle(&story("b")));
+    assert!(queue.toggle(&story("c")));
+    assert_eq!(links(&queue), ["a", "b", "c"]);
+    assert_eq!(queue.position("c"), Some(2));
+
+    assert!(!queue.toggle(&story("b")));
+    assert_eq!(links(&queue), ["a", "c"]);
+    assert!(queue.remove("a"));
+    assert!(!queue.remove("a"));
+    assert_eq!(links(&queue), ["c"]);
+}
+
+#[test]
+fn shift_moves_one_place_and_stops_at_the_ends() {
+    let mut queue = Queue::default();
+    for link in ["a", "b", "c"] {
+        queue.toggle(&story(link));
+    }
+    assert_eq!(queue.shift(2, true), 1);
+    assert_eq!(links(&queue), ["a", "c", "b"]);
+    assert_eq!(queue.shift(0, true), 0);
+    assert_eq!(queue.shift(0, false), 1);
+    assert_eq!(links(&queue), ["c", "a", "b"]);
+    assert_eq!(queue.shift(2, false), 2);
+    assert_eq!(links(&queue), ["c", "a", "b"]);
+}
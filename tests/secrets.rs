@@ -0,0 +1,73 @@
+//! "secret:<name>" config values, kept in the passphrase-encrypted file store.
+
+use news_cli::secrets::valid_name;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+#[test]
+fn secret_names() {
+    assert!(valid_name("miniflux"));
+    assert!(valid_name("github.work-token_2"));
+    assert!(!valid_name(""));
+    assert!(!valid_name("a/b"));
+    assert!(!valid_name("with space"));
+}
+
+#[test]
+fn stored_secrets_fill_in_config_values() {
+    let dir = std::env::temp_dir().join(format!("news-cli-secrets-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let world = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/world.xml");
+    let write_config = |token: &str| {
+        let txt = format!(
+            "[secrets]\nstore = \"file\"\n\n[[feeds]]\nname = \"World\"\nurl = \"{}\"\ntoken = \"{}\"\n",
+            world.display(),
+            token
+        );
+        std::fs::write(dir.join("config.toml"), txt).unwrap();
+    };
+    let run = |args: &[&str], stdin: &str, passphrase: &str| {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_news-cli"))
+            .args(args)
+            .env("HOME", &dir)
+            .env("NEWS_CLI_CONFIG_DIR", &dir)
+            .env("XDG_STATE_HOME", dir.join("state"))
+            .env("XDG_CACHE_HOME", dir.join("cache"))
+            .env("NEWS_CLI_SECRETS_PASSPHRASE", passphrase)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("run news-cli");
+        child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+        let out = child.wait_with_output().unwrap();
+        (out.status.success(), String::from_utf8(out.stdout).unwrap(), String::from_utf8(out.stderr).unwrap())
+    };
+
+    write_config("secret:world");
+    let (ok, stdout, stderr) = run(&["secret", "set", "world"], "hunter2\n", "open sesame");
+    assert!(ok, "{}", stderr);
+    assert!(stdout.contains("\"secret:world\""), "{}", stdout);
+    let stored = std::fs::read(dir.join("secrets.age")).unwrap();
+    assert!(!String::from_utf8_lossy(&stored).contains("hunter2"));
+
+    let (ok, stdout, stderr) = run(&["fetch", "--to", "json"], "", "open sesame");
+    assert!(ok, "{}", stderr);
+    assert!(stdout.contains("\"source\":\"World\""), "{}", stdout);
+
+    let (ok, _, stderr) = run(&["fetch"], "", "wrong");
+    assert!(!ok);
+    assert!(stderr.contains("can't decrypt"), "{}", stderr);
+
+    write_config("secret:other");
+    let (ok, _, stderr) = run(&["fetch"], "", "open sesame");
+    assert!(!ok);
+    assert!(stderr.contains("feeds[0].token: no secret \"other\""), "{}", stderr);
+
+    let (ok, _, stderr) = run(&["secret", "remove", "world"], "", "open sesame");
+    assert!(ok, "{}", stderr);
+    let (ok, _, stderr) = run(&["secret", "remove", "world"], "", "open sesame");
+    assert!(!ok);
+    assert!(stderr.contains("no secret \"world\""), "{}", stderr);
+}
@@ -0,0 +1,79 @@
+//! `news-cli serve` against the fixture feeds: start it on a free port, fetch each endpoint
+//! over a plain TCP socket and check the merged list comes back in every format.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+
+struct Server {
+    child: Child,
+    addr: String,
+}
+
+impl Server {
+    fn start() -> Self {
+        let state = std::env::temp_dir().join(format!("news-cli-serve-{}", std::process::id()));
+        let mut child = Command::new(env!("CARGO_BIN_EXE_news-cli"))
+            .args(["--feeds", "tests/fixtures/config.toml", "serve", "--addr", "127.0.0.1:0"])
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .env("HOME", &state)
+            .env("XDG_STATE_HOME", state.join("state"))
+            .env("XDG_CACHE_HOME", state.join("cache"))
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("spawn news-cli serve");
+        let mut line = String::new();
+        BufReader::new(child.stdout.take().unwrap()).read_line(&mut line).unwrap();
+        // "Serving N stories on http://127.0.0.1:PORT/ (...)"
+        let addr = line
+            .split("http://")
+            .nth(1)
+            .and_then(|rest| rest.split('/').next())
+            .unwrap_or_else(|| panic!("unexpected banner: {:?}", line))
+            .to_string();
+        Server { child, addr }
+    }
+
+    fn get(&self, path: &str) -> (String, String) {
+        let mut stream = TcpStream::connect(&self.addr).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: {}\r\n\r\n", path, self.addr).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        (head.to_string(), body.to_string())
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn serves_merged_feeds() {
+    let server = Server::start();
+
+    let (head, body) = server.get("/feed.json");
+    assert!(head.starts_with("HTTP/1.1 200"), "{}", head);
+    let feed: serde_json::Value = serde_json::from_str(&body).unwrap();
+    let items = feed["items"].as_array().unwrap();
+    assert_eq!(items.len(), 15);
+    // Newest first, across both fixture feeds
+    assert_eq!(items[0]["title"], "Tech story number 12");
+
+    let (head, body) = server.get("/feed.rss");
+    assert!(head.contains("application/rss+xml"), "{}", head);
+    assert_eq!(body.matches("<item>").count(), 15);
+
+    let (_, body) = server.get("/feed.atom");
+    assert_eq!(body.matches("<entry>").count(), 15);
+
+    let (head, body) = server.get("/");
+    assert!(head.contains("text/html"), "{}", head);
+    assert!(body.contains("href=\"/feed.rss\""));
+
+    let (head, _) = server.get("/missing");
+    assert!(head.starts_with("HTTP/1.1 404"), "{}", head);
+}
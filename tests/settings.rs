@@ -0,0 +1,67 @@
+//! Editing config.toml in place for the Settings screen.
+
+use news_cli::config::edit::{ConfigFile, SETTINGS};
+use news_cli::config::edit::Setting;
+use std::path::PathBuf;
+
+const CONFIG: &str = r#"# My feeds
+refresh_interval_secs = 300 # five minutes
+
+[[feeds]]
+name = "World"
+url = "tests/fixtures/world.xml"
+"#;
+
+fn config_file(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("news-cli-settings-{}-{}.toml", name, std::process::id()));
+    std::fs::write(&path, CONFIG).unwrap();
+    path
+}
+
+fn setting(key: &str) -> &'static Setting {
+    SETTINGS.iter().find(|s| s.key == key).unwrap()
+}
+
+#[test]
+fn set_keeps_comments_and_the_rest_of_the_file() {
+    let path = config_file("keep");
+    let mut file = ConfigFile::open(&path).unwrap();
+    assert_eq!(file.get("refresh_interval_secs").as_deref(), Some("300"));
+    assert_eq!(file.get("fetch.timeout_secs"), None);
+
+    file.set(setting("refresh_interval_secs"), "600").unwrap();
+    file.set(setting("fetch.timeout_secs"), "30").unwrap();
+    file.set(setting("open_command"), "firefox {url}").unwrap();
+    file.save().unwrap();
+
+    let written = std::fs::read_to_string(&path).unwrap();
+    assert!(written.starts_with("# My feeds\nrefresh_interval_secs = 600 # five minutes\n"), "{}", written);
+    assert!(written.contains("open_command = \"firefox {url}\""), "{}", written);
+    assert!(written.contains("[fetch]\ntimeout_secs = 30"), "{}", written);
+    assert!(written.contains("[[feeds]]\nname = \"World\""), "{}", written);
+    let cfg = news_cli::config::load_file(&path).unwrap();
+    assert_eq!(cfg.refresh_interval_secs, Some(600));
+    assert_eq!(cfg.fetch.timeout_secs, Some(30));
+    assert_eq!(cfg.path.as_deref(), Some(path.as_path()));
+
+    // Empty puts the default back
+    let mut file = ConfigFile::open(&path).unwrap();
+    file.set(setting("refresh_interval_secs"), "").unwrap();
+    file.save().unwrap();
+    assert!(!std::fs::read_to_string(&path).unwrap().contains("refresh_interval_secs"));
+}
+
+#[test]
+fn bad_values_are_refused_and_nothing_is_written() {
+    let path = config_file("refuse");
+    let mut file = ConfigFile::open(&path).unwrap();
+    let err = file.set(setting("refresh_interval_secs"), "soon").unwrap_err();
+    assert_eq!(err.to_string(), "refresh_interval_secs: expected a whole number, got \"soon\"");
+    let err = file.set(setting("feed_order"), "random").unwrap_err();
+    assert!(err.to_string().contains("config, alphabetical, most_new, pinned"), "{}", err);
+
+    // Well-typed but invalid for the config (validate) isn't saved either
+    file.set(setting("date_format"), "%Q").unwrap();
+    assert!(file.save().is_err());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), CONFIG);
+}
@@ -0,0 +1,51 @@
+//! `news-cli snapshot save/list` and snapshot names.
+
+mod common;
+
+use common::{run, state_dir};
+use news_cli::snapshot::valid_name;
+
+#[test]
+fn save_keeps_the_fetched_stories_under_a_name() {
+    let dir = state_dir("save");
+    let state = dir.path();
+    let out = run(state, &["--feeds", "tests/fixtures/world.xml", "snapshot", "save", "election-night"]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    let path = state.join("state/news-cli/snapshots/election-night.json");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        format!("Saved 3 stories as \"election-night\" ({}).\n", path.display())
+    );
+    let saved = std::fs::read_to_string(&path).unwrap();
+    assert!(saved.contains("https://example.com/world/rates"), "{}", saved);
+    // Saving doesn't mark anything seen
+    assert!(!state.join("state/news-cli/seen_stories.json").exists());
+
+    let out = run(state, &["snapshot", "list"]);
+    let listed = String::from_utf8_lossy(&out.stdout);
+    assert!(listed.starts_with("election-night  "), "{}", listed);
+    assert!(listed.ends_with("  3 stories\n"), "{}", listed);
+}
+
+#[test]
+fn save_rejects_names_that_are_not_file_names() {
+    let dir = state_dir("bad_name");
+    let state = dir.path();
+    let out = run(state, &["--feeds", "tests/fixtures/world.xml", "snapshot", "save", "../x"]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("invalid snapshot name"));
+
+    let out = run(state, &["snapshot", "view", "missing"]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("no snapshot \"missing\""));
+}
+
+#[test]
+fn names_are_plain_file_names() {
+    for ok in ["election-night", "2026-11-04", "bug_123.v2"] {
+        assert!(valid_name(ok), "{}", ok);
+    }
+    for bad in ["", ".hidden", "a/b", "..", "with space"] {
+        assert!(!valid_name(bad), "{:?}", bad);
+    }
+}
@@ -0,0 +1,42 @@
+//! Tag parsing and bookkeeping (no state directory involved).
+
+mod common;
+
+use common::story;
+use news_cli::tags::{parse, Tags};
+
+#[test]
+fn parse_trims_lowercases_and_drops_repeats() {
+    assert_eq!(parse("Rust, async ,RUST,, web dev "), ["rust", "async", "web dev"]);
+    assert!(parse(" , ").is_empty());
+}
+
+#[test]
+fn set_replaces_and_clears_tags() {
+    let mut tags = Tags::default();
+    tags.set(&story("https://example.com/a"), parse("rates, economy"));
+    tags.set(&story("https://example.com/b"), parse("economy"));
+    assert_eq!(tags.names(), [("economy".to_string(), 2), ("rates".to_string(), 1)]);
+    assert_eq!(tags.tagged("economy").len(), 2);
+
+    tags.set(&story("https://example.com/a"), parse("rates"));
+    assert_eq!(tags.of("https://example.com/a"), ["rates"]);
+    tags.set(&story("https://example.com/b"), Vec::new());
+    assert_eq!(tags.names(), [("rates".to_string(), 1)]);
+    assert!(tags.of("https://example.com/b").is_empty());
+}
+
+#[test]
+fn notes_keep_untagged_stories() {
+    let mut tags = Tags::default();
+    let a = story("https://example.com/a");
+    tags.set_note(&a, "  Why I kept this\n");
+    tags.set(&a, parse("rates"));
+    tags.set(&a, Vec::new());
+    assert_eq!(tags.note(&a.link), Some("Why I kept this"));
+    assert_eq!(tags.noted().len(), 1);
+    assert!(tags.names().is_empty());
+
+    tags.set_note(&a, " ");
+    assert!(tags.items().is_empty());
+}
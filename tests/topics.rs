@@ -0,0 +1,30 @@
+//! Grouping stories about the same event by their titles.
+
+use news_cli::topics::{cluster, label, words};
+
+#[test]
+fn words_drop_stopwords_punctuation_and_plurals() {
+    let w: Vec<String> = words("The ECB raises rates: what it means for you").into_iter().collect();
+    assert_eq!(w, ["ecb", "mean", "raise", "rate", "you"].map(String::from));
+}
+
+#[test]
+fn similar_titles_cluster_across_sources() {
+    let titles = [
+        "ECB raises interest rates by a quarter point",
+        "Apple unveils a foldable iPhone",
+        "ECB raises rates for the first time in a year",
+        "Apple iPhone sales slump in China",
+        "European Central Bank: ECB raises rates",
+        "Storm season forecast revised upward",
+    ];
+    assert_eq!(cluster(&titles), [vec![0, 2, 4], vec![1], vec![3], vec![5]]);
+    let ecb = [titles[0], titles[2], titles[4]];
+    assert_eq!(label(&ecb), "ECB raises rates");
+    assert_eq!(label(&[titles[5]]), "Storm season forecast revised upward");
+}
+
+#[test]
+fn two_shared_words_are_not_a_topic() {
+    assert_eq!(cluster(&["Apple shares fall", "Tesla shares fall"]), [vec![0], vec![1]]);
+}
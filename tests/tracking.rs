@@ -0,0 +1,39 @@
+//! Tracking query parameters removed from story links.
+
+use news_cli::util::tracking::strip_tracking_params;
+use url::Url;
+
+fn stripped(url: &str, extra: &[&str]) -> String {
+    let mut url = Url::parse(url).unwrap();
+    let extra: Vec<String> = extra.iter().map(|s| s.to_string()).collect();
+    strip_tracking_params(&mut url, &extra);
+    url.to_string()
+}
+
+#[test]
+fn utm_parameters_and_click_ids_go_but_the_rest_keeps_its_order() {
+    assert_eq!(
+        stripped("https://example.com/a?z=1&utm_source=rss&b=two%20words&UTM_Campaign=x&fbclid=abc&a=3", &[]),
+        "https://example.com/a?z=1&b=two%20words&a=3"
+    );
+    assert_eq!(stripped("https://example.com/a?utm_medium=feed&gclid=1#top", &[]), "https://example.com/a#top");
+    // Only whole keys match, unless the pattern ends in '*'
+    assert_eq!(stripped("https://example.com/?refresh=1&ref=home", &[]), "https://example.com/?refresh=1");
+}
+
+#[test]
+fn configured_parameters_are_stripped_too() {
+    assert_eq!(
+        stripped("https://example.com/?id=7&source=newsletter&trk_list=9&trk=1", &["source", "TRK_*"]),
+        "https://example.com/?id=7&trk=1"
+    );
+}
+
+#[test]
+fn percent_encoded_keys_are_compared_decoded() {
+    assert_eq!(
+        stripped("https://example.com/?utm%5Fsource=rss&%66bclid=1&q=a%26b&page=2", &[]),
+        "https://example.com/?q=a%26b&page=2"
+    );
+    assert_eq!(stripped("https://example.com/?my%2Dparam=1&keep=1", &["my-param"]), "https://example.com/?keep=1");
+}
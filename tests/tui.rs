@@ -0,0 +1,1553 @@
+//! End-to-end tests for the interactive menus.
+//!
+//! Each test runs the real binary inside a pseudo-terminal against the fixture
+//! feeds in `tests/fixtures`, feeds it a scripted key sequence, and snapshots
+//! the rendered screen (via a vt100 emulator) with insta. Review changes with
+//! `cargo insta review`.
+
+mod common;
+
+use common::state_dir;
+use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const ROWS: u16 = 30;
+const COLS: u16 = 80;
+
+const ENTER: &str = "\r";
+const DOWN: &str = "\x1b[B";
+const TAB: &str = "\t";
+const CTRL_C: &str = "\x03";
+
+struct Session {
+    parser: Arc<Mutex<vt100::Parser>>,
+    writer: Box<dyn Write + Send>,
+    child: Box<dyn Child + Send + Sync>,
+    master: Box<dyn MasterPty + Send>,
+}
+
+impl Session {
+    /// Launch the app with the fixture config; `state` isolates config/history per test.
+    fn spawn(state: &Path) -> Self {
+        Self::spawn_with(state, &["--feeds", "tests/fixtures/config.toml"])
+    }
+
+    fn spawn_with(state: &Path, args: &[&str]) -> Self {
+        let pair = native_pty_system()
+            .openpty(PtySize { rows: ROWS, cols: COLS, pixel_width: 0, pixel_height: 0 })
+            .expect("open pty");
+        let mut cmd = CommandBuilder::new(env!("CARGO_BIN_EXE_news-cli"));
+        cmd.args(args);
+        cmd.cwd(env!("CARGO_MANIFEST_DIR"));
+        cmd.env("HOME", state);
+        cmd.env("XDG_CONFIG_HOME", state.join("config"));
+        cmd.env("XDG_STATE_HOME", state.join("state"));
+        cmd.env("XDG_CACHE_HOME", state.join("cache"));
+        cmd.env("TERM", "xterm-256color");
+        // Keep image previews on the text fallback whatever terminal runs the tests
+        cmd.env_remove("TERM_PROGRAM");
+        cmd.env_remove("LC_TERMINAL");
+        // English UI whatever the locale of the machine running the tests
+        for var in ["LC_ALL", "LC_MESSAGES", "LANG"] {
+            cmd.env_remove(var);
+        }
+        // Dates show in local time unless display_timezone is set
+        cmd.env("TZ", "UTC");
+        // Notes are typed inline rather than in the tester's editor
+        cmd.env_remove("VISUAL");
+        cmd.env_remove("EDITOR");
+        // An error's backtrace would scroll the message itself off the screen
+        cmd.env_remove("RUST_BACKTRACE");
+        cmd.env_remove("RUST_LIB_BACKTRACE");
+        let child = pair.slave.spawn_command(cmd).expect("spawn news-cli");
+        drop(pair.slave);
+
+        let parser = Arc::new(Mutex::new(vt100::Parser::new(ROWS, COLS, 0)));
+        let mut reader = pair.master.try_clone_reader().expect("pty reader");
+        let sink = parser.clone();
+        thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            loop {
+                match reader.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => sink.lock().unwrap().process(&buf[..n]),
+                }
+            }
+        });
+        let writer = pair.master.take_writer().expect("pty writer");
+        Session { parser, writer, child, master: pair.master }
+    }
+
+    fn send(&mut self, keys: &str) {
+        self.writer.write_all(keys.as_bytes()).unwrap();
+        self.writer.flush().unwrap();
+    }
+
+    /// Resize the pty (the app gets SIGWINCH) and the emulator.
+    fn resize(&mut self, rows: u16, cols: u16) {
+        self.master
+            .resize(PtySize { rows, cols, pixel_width: 0, pixel_height: 0 })
+            .expect("resize pty");
+        self.parser.lock().unwrap().screen_mut().set_size(rows, cols);
+    }
+
+    fn screen(&self) -> String {
+        self.parser.lock().unwrap().screen().contents()
+    }
+
+    /// Wait until `needle` is on screen and the frame has stopped changing, then return it.
+    fn wait_for(&self, needle: &str) -> String {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut last = String::new();
+        loop {
+            let now = self.screen();
+            if now.contains(needle) && now == last {
+                return now;
+            }
+            if Instant::now() > deadline {
+                panic!("timed out waiting for {:?}; screen was:\n{}", needle, now);
+            }
+            last = now;
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    /// Type a menu number the way a user would: first key opens the prompt, Enter confirms.
+    fn choose(&mut self, number: &str) {
+        self.send(number);
+        self.wait_for("Selection");
+        self.send(ENTER);
+    }
+
+    fn quit(mut self) {
+        self.send("q");
+        self.wait_exit();
+    }
+
+    fn wait_exit(mut self) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while self.child.try_wait().unwrap().is_none() {
+            if Instant::now() > deadline {
+                let _ = self.child.kill();
+                panic!("news-cli did not exit");
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+}
+
+#[test]
+fn main_menu() {
+    let dir = state_dir("main_menu");
+    let state = dir.path();
+    let s = Session::spawn(state);
+    insta::assert_snapshot!(s.wait_for("Main Menu"));
+    s.quit();
+}
+
+#[test]
+fn news_list_sections_and_badges() {
+    let dir = state_dir("news_list");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    insta::assert_snapshot!(s.wait_for("News (b = back"));
+    s.quit();
+}
+
+#[test]
+fn tab_jumps_to_next_section() {
+    let dir = state_dir("tab_jump");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.send(DOWN);
+    s.wait_for("Use arrows or type");
+    s.send(TAB);
+    insta::assert_snapshot!(s.wait_for("> 5: == TECH"));
+    s.quit();
+}
+
+#[test]
+fn source_menu_lists_all_entries() {
+    let dir = state_dir("source_menu");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.choose("5");
+    insta::assert_snapshot!(s.wait_for("Tech - all entries"));
+    s.quit();
+}
+
+#[test]
+fn long_titles_reflow_on_resize() {
+    let dir = state_dir("resize");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.choose("5");
+    s.wait_for("Tech - all entries");
+    s.resize(ROWS, 50);
+    insta::assert_snapshot!(s.wait_for("headlin…"));
+    s.quit();
+}
+
+#[test]
+fn arrow_view_fits_tiny_window() {
+    let dir = state_dir("tiny");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.send(DOWN);
+    s.wait_for("Use arrows or type");
+    s.resize(6, 40);
+    s.send(DOWN);
+    s.send(DOWN);
+    insta::assert_snapshot!(s.wait_for("> 3:"));
+    s.quit();
+}
+
+#[test]
+fn slash_filters_list() {
+    let dir = state_dir("filter");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.send(DOWN);
+    s.wait_for("Use arrows or type");
+    s.send("/strm");
+    insta::assert_snapshot!(s.wait_for("Filter: strm_"));
+    s.send("\x1b");
+    s.wait_for("'/' = filter");
+    s.quit();
+}
+
+#[test]
+fn typed_number_jumps_in_arrow_mode() {
+    let dir = state_dir("jump");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.send(DOWN);
+    s.wait_for("Use arrows or type");
+    s.send("12");
+    insta::assert_snapshot!(s.wait_for("Go to: 12_"));
+    s.quit();
+}
+
+#[test]
+fn scrolled_list_pins_section_header() {
+    let dir = state_dir("sticky");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.send(DOWN);
+    s.wait_for("Use arrows or type");
+    s.resize(14, 80);
+    s.send("\x1b[F"); // End
+    insta::assert_snapshot!(s.wait_for("item 17/17, section 2/2"));
+    s.quit();
+}
+
+#[test]
+fn jump_to_source_and_back_tab() {
+    let dir = state_dir("jump_source");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.send(DOWN);
+    s.wait_for("Use arrows or type");
+    s.send("s");
+    insta::assert_snapshot!(s.wait_for("Jump to source"));
+    s.send("b");
+    s.wait_for("> 5: == TECH");
+    s.send("\x1b[Z"); // Shift-Tab
+    s.wait_for("> 1: == WORLD");
+    s.quit();
+}
+
+#[test]
+fn detail_view_shows_metadata() {
+    let dir = state_dir("detail_view");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.choose("i2");
+    insta::assert_snapshot!(s.wait_for("'c' = copy link"));
+    s.send("b");
+    s.wait_for("News (b = back");
+    s.quit();
+}
+
+#[test]
+fn detail_view_renders_full_content() {
+    let dir = state_dir("full_content");
+    let state = dir.path();
+    let mut s = Session::spawn_with(state, &["--feeds", "tests/fixtures/full.xml"]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.choose("i2");
+    insta::assert_snapshot!(s.wait_for("scroll (0%)"));
+    s.send(" ");
+    let frame = s.wait_for("scroll (100%)");
+    assert!(frame.contains("[2] https://example.org/gauges"), "footnotes not at the end:\n{}", frame);
+    s.send("b");
+    s.wait_for("News (b = back");
+    s.quit();
+}
+
+#[test]
+fn command_feed_lists_stories() {
+    let dir = state_dir("command_feed");
+    let state = dir.path();
+    let mut s = Session::spawn_with(state, &["--feeds", "tests/fixtures/command.toml"]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    insta::assert_snapshot!(s.wait_for("Scripted story two"));
+    s.quit();
+}
+
+#[test]
+fn video_offers_player() {
+    let dir = state_dir("video");
+    let state = dir.path();
+    let mut s = Session::spawn_with(state, &["--feeds", "tests/fixtures/command.toml"]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("Scripted video");
+    s.choose("2");
+    insta::assert_snapshot!(s.wait_for("Play in mpv?"));
+    s.send("\x1b");
+    s.wait_for("News (b = back");
+    s.quit();
+}
+
+#[test]
+fn mail_story_opens_in_detail_view() {
+    let dir = state_dir("mail");
+    let state = dir.path();
+    let mut s = Session::spawn_with(state, &["--feeds", "tests/fixtures/command.toml"]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("Emailed newsletter");
+    s.choose("5");
+    insta::assert_snapshot!(s.wait_for("three things worth reading"));
+    s.send("b");
+    s.wait_for("News (b = back");
+    s.quit();
+}
+
+/// Minimal HTTP API stand-in: answers every request with `body` and records request lines.
+fn fake_api(body: &'static str) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let log = requests.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { break };
+            let mut buf = [0u8; 8192];
+            let n = stream.read(&mut buf).unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..n]).into_owned();
+            log.lock().unwrap().push(request.lines().next().unwrap_or("").to_string());
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+        }
+    });
+    (addr, requests)
+}
+
+#[test]
+fn sync_marks_reader_read_stories_as_seen() {
+    let dir = state_dir("sync");
+    let state = dir.path();
+    let (url, requests) = fake_api(
+        r#"{"total":1,"entries":[{"id":7,"url":"https://example.com/world/rates","title":"Central bank holds rates steady amid slowing growth","status":"read","starred":false,"published_at":"2026-02-02T10:00:00Z","feed":{"title":"World"}}]}"#,
+    );
+    let config = state.join("sync.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    std::fs::write(
+        &config,
+        format!("{}\n[sync]\nbackend = \"miniflux\"\nurl = \"{}\"\ntoken = \"secret\"\n", fixture, url),
+    )
+    .unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    let frame = s.wait_for("News (b = back");
+    let rates = frame.lines().find(|l| l.contains("Central bank")).unwrap();
+    assert!(!rates.contains("[NEW]"), "read in the reader but still new:\n{}", frame);
+    assert!(frame.contains("[NEW] Election results"), "unread story lost its badge:\n{}", frame);
+    s.quit();
+    assert!(requests.lock().unwrap()[0].starts_with("GET /v1/entries?"));
+}
+
+#[test]
+fn briefing_menu_shows_model_output() {
+    let dir = state_dir("briefing");
+    let state = dir.path();
+    let (url, requests) = fake_api(
+        r#"{"choices":[{"message":{"role":"assistant","content":"- Rates held steady as growth slows.\n- Coalition talks begin after the election."}}]}"#,
+    );
+    let config = state.join("briefing.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    std::fs::write(
+        &config,
+        format!("{}\n[briefing]\napi_base = \"{}/v1\"\nmodel = \"test-model\"\ntop = 5\n", fixture, url),
+    )
+    .unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("6: Briefing");
+    s.choose("6");
+    insta::assert_snapshot!(s.wait_for("'r' = regenerate"));
+    s.send("b");
+    s.wait_for("Main Menu");
+    s.quit();
+    assert_eq!(requests.lock().unwrap()[0], "POST /v1/chat/completions HTTP/1.1");
+}
+
+#[test]
+fn calendar_lists_upcoming_releases() {
+    let (url, requests) = fake_api(
+        r#"[{"title": "CPI m/m", "country": "USD", "date": "2099-03-11T08:30:00-04:00", "impact": "High", "forecast": "0.3%", "previous": "0.4%"},
+            {"title": "German ZEW", "country": "EUR", "date": "2099-03-11T06:00:00-04:00", "impact": "High"}]"#,
+    );
+    let dir = state_dir("calendar");
+    let state = dir.path();
+    let config = state.join("calendar.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    std::fs::write(&config, format!("{}\n[calendar]\ncountries = [\"USD\"]\nurl = \"{}/week.json\"\n", fixture, url)).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Calendar");
+    s.choose("6");
+    let frame = s.wait_for("CPI m/m");
+    assert!(frame.contains("Wed 2099-03-11"), "no day heading:\n{}", frame);
+    assert!(frame.contains("12:30  USD  !!!  CPI m/m  (previous 0.4%, forecast 0.3%)"), "event line:\n{}", frame);
+    assert!(!frame.contains("ZEW"), "unconfigured country listed:\n{}", frame);
+    assert_eq!(requests.lock().unwrap()[0], "GET /week.json HTTP/1.1");
+    s.send("b");
+    s.wait_for("Main Menu");
+    s.quit();
+}
+
+#[test]
+fn morning_briefing_puts_weather_and_top_stories_together() {
+    let (url, requests) = fake_api(
+        r#"{"current_units": {"temperature_2m": "°C", "wind_speed_10m": "km/h"},
+            "current": {"temperature_2m": 11.6, "weather_code": 2, "wind_speed_10m": 9.4},
+            "daily": {"temperature_2m_max": [15.2], "temperature_2m_min": [7.9], "precipitation_probability_max": [20]}}"#,
+    );
+    let dir = state_dir("morning");
+    let state = dir.path();
+    let config = state.join("morning.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    let fixture = fixture.replace("name = \"World\"", "name = \"World\"\ncategory = \"world\"");
+    // The fixture stories are long past a day old
+    let extra = format!("\n[scoring]\nwindow_hours = 1000000\n\n[weather]\nlatitude = 52.52\nlongitude = 13.41\nplace = \"Berlin\"\nurl = \"{}/v1/forecast\"\n", url);
+    std::fs::write(&config, fixture + &extra).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("5: Morning briefing");
+    s.choose("5");
+    let frame = s.wait_for("== Weather in Berlin ==");
+    assert!(frame.contains("12°C, partly cloudy · 8 to 15°C · 20% chance of rain · wind 9 km/h"), "weather line:\n{}", frame);
+    assert!(frame.contains("== Top stories: World =="), "no category section:\n{}", frame);
+    assert!(frame.contains("Central bank holds rates steady"), "world story missing:\n{}", frame);
+    assert!(frame.contains("== Top stories: Other =="), "uncategorized feeds missing:\n{}", frame);
+    assert!(frame.contains("== Key stats =="), "no stats section:\n{}", frame);
+    assert!(requests.lock().unwrap()[0].starts_with("GET /v1/forecast?latitude=52.52&longitude=13.41&"));
+    s.send("b");
+    s.wait_for("Main Menu");
+    s.quit();
+}
+
+#[test]
+fn alerts_menu_collects_matches() {
+    let dir = state_dir("alerts");
+    let state = dir.path();
+    let config = state.join("alerts.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    std::fs::write(
+        &config,
+        format!(
+            "{}\n[[alerts]]\nname = \"Rates\"\nkeywords = [\"RATES\"]\n\n[[alerts]]\nname = \"Early tech\"\nregex = [\"number 0[23]\\\\b\"]\n",
+            fixture
+        ),
+    )
+    .unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("6: Alerts");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.send("b");
+    s.wait_for("6: Alerts (3 new)");
+    s.choose("6");
+    let frame = s.wait_for("== Early tech == (2 matches)");
+    assert!(frame.contains("== Rates == (1 matches)"), "missing alert section:\n{}", frame);
+    assert!(frame.contains("[World] Central bank holds rates steady"), "missing hit:\n{}", frame);
+    assert!(frame.contains("[Tech] Tech story number 03"), "missing hit:\n{}", frame);
+    assert!(!frame.contains("number 04"), "unmatched story listed:\n{}", frame);
+    s.send("b");
+    let menu = s.wait_for("Main Menu");
+    assert!(!menu.contains("new)"), "alerts still new after review:\n{}", menu);
+    s.quit();
+}
+
+#[test]
+fn t_tags_stories_for_the_tags_menu() {
+    let dir = state_dir("tags");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    let menu = s.wait_for("Main Menu");
+    assert!(!menu.contains("Tags"), "tags offered before any were set:\n{}", menu);
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.choose("t2");
+    s.wait_for("Tags for");
+    s.send("Rates, economy ,rates\r");
+    s.wait_for("News (b = back");
+    s.send("b");
+    s.wait_for("6: Tags");
+    s.choose("6");
+    let frame = s.wait_for("== rates == (1 stories)");
+    assert!(frame.contains("== economy == (1 stories)"), "missing tag section:\n{}", frame);
+    assert!(frame.contains("[World] Central bank holds rates steady"), "missing story:\n{}", frame);
+    // Clearing the tags empties the screen
+    s.choose("t2");
+    s.wait_for("Tags for");
+    s.send(&"\x7f".repeat(20));
+    s.send(ENTER);
+    let menu = s.wait_for("Main Menu");
+    assert!(!menu.contains("Tags"), "tags still offered:\n{}", menu);
+    s.quit();
+}
+
+#[test]
+fn n_writes_a_note_shown_in_the_detail_view() {
+    let dir = state_dir("notes");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.choose("i2");
+    s.wait_for("'n' = note");
+    s.send("n");
+    s.wait_for("Note (empty removes it)");
+    s.send("Quote this in Friday's piece\r");
+    let frame = s.wait_for("Note saved.");
+    assert!(frame.contains("Note:    Quote this in Friday's piece"), "{}", frame);
+    s.send("b");
+    s.wait_for("News (b = back");
+    s.send("b");
+    s.choose("6");
+    let frame = s.wait_for("== Notes == (1 stories)");
+    assert!(frame.contains("[World] Central bank holds rates steady"), "missing story:\n{}", frame);
+    s.quit();
+}
+
+#[test]
+fn a_opens_the_story_through_the_archive_service() {
+    let dir = state_dir("archive");
+    let state = dir.path();
+    let opened = state.join("opened.txt");
+    let opener = state.join("open.sh");
+    std::fs::write(&opener, format!("#!/bin/sh\necho \"$1\" >> {}\n", opened.display())).unwrap();
+    std::fs::set_permissions(&opener, std::os::unix::fs::PermissionsExt::from_mode(0o755)).unwrap();
+    let config = state.join("archive.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    let settings = format!("open_command = \"{}\"\narchive_url = \"https://archive.ph/newest/{{url}}\"\n", opener.display());
+    std::fs::write(&config, settings + &fixture).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.choose("i2");
+    s.wait_for("'a' = archive");
+    s.send("a");
+    s.wait_for("Opened the archived copy.");
+    s.quit();
+    let opened = std::fs::read_to_string(&opened).unwrap();
+    assert_eq!(opened.trim(), "https://archive.ph/newest/https://example.com/world/rates");
+}
+
+#[test]
+fn paywalled_feeds_are_marked_and_open_in_the_archive() {
+    let dir = state_dir("paywalled");
+    let state = dir.path();
+    let opened = state.join("opened.txt");
+    let opener = state.join("open.sh");
+    std::fs::write(&opener, format!("#!/bin/sh\necho \"$1\" >> {}\n", opened.display())).unwrap();
+    std::fs::set_permissions(&opener, std::os::unix::fs::PermissionsExt::from_mode(0o755)).unwrap();
+    let config = state.join("paywalled.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    let fixture = fixture.replace("name = \"World\"", "name = \"World\"\npaywalled = true");
+    let settings = format!("open_command = \"{}\"\narchive_paywalled = true\n", opener.display());
+    std::fs::write(&config, settings + &fixture).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    let frame = s.wait_for("News (b = back");
+    assert!(frame.contains("🔒 Central bank holds rates steady"), "no lock:\n{}", frame);
+    assert!(frame.contains("[NEW] Tech story number 12"), "tech marked too:\n{}", frame);
+    s.choose("2");
+    s.wait_for("News (b = back");
+    s.quit();
+    let opened = std::fs::read_to_string(&opened).unwrap();
+    assert_eq!(opened.trim(), "https://web.archive.org/web/https://example.com/world/rates");
+}
+
+#[test]
+fn folded_sections_stay_folded() {
+    let dir = state_dir("collapsed");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.choose("z1");
+    let frame = s.wait_for("· folded");
+    assert!(!frame.contains("Central bank holds rates steady"), "folded section lists stories:\n{}", frame);
+    s.quit();
+
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    let frame = s.wait_for("· folded");
+    assert!(!frame.contains("Central bank holds rates steady"), "unfolded after restart:\n{}", frame);
+    s.choose("z1");
+    let frame = s.wait_for("Central bank holds rates steady");
+    assert!(!frame.contains("· folded"), "still folded:\n{}", frame);
+    s.quit();
+}
+
+#[test]
+fn dot_repeats_the_last_action() {
+    let dir = state_dir("repeat");
+    let state = dir.path();
+    let opened = state.join("opened.txt");
+    let opener = state.join("open.sh");
+    std::fs::write(&opener, format!("#!/bin/sh\necho \"$1\" >> {}\n", opened.display())).unwrap();
+    std::fs::set_permissions(&opener, std::os::unix::fs::PermissionsExt::from_mode(0o755)).unwrap();
+    let config = state.join("repeat.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    std::fs::write(&config, format!("open_command = \"{}\"\n{}", opener.display(), fixture)).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.choose("2");
+    s.wait_for("News (b = back");
+    s.choose(".");
+    s.wait_for("News (b = back");
+    s.quit();
+    let opened = std::fs::read_to_string(&opened).unwrap();
+    assert_eq!(opened.lines().collect::<Vec<_>>(), ["https://example.com/world/rates"; 2]);
+}
+
+#[test]
+fn v_reads_the_list_aloud_from_the_highlighted_story() {
+    let dir = state_dir("speak");
+    let state = dir.path();
+    let said = state.join("said.txt");
+    let speaker = state.join("speak.sh");
+    std::fs::write(&speaker, format!("#!/bin/sh\ncat >> {}\n", said.display())).unwrap();
+    std::fs::set_permissions(&speaker, std::os::unix::fs::PermissionsExt::from_mode(0o755)).unwrap();
+    let config = state.join("speak.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    std::fs::write(&config, format!("speak_command = \"{}\"\n{}", speaker.display(), fixture)).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.choose("v2");
+    // Back in the list once every story has been said
+    s.wait_for("News (b = back");
+    s.quit();
+    let said = std::fs::read_to_string(&said).unwrap();
+    let lines: Vec<&str> = said.lines().collect();
+    assert_eq!(lines.len(), 15, "{}", said);
+    assert_eq!(lines[0], "World. Central bank holds rates steady amid slowing growth");
+}
+
+#[test]
+fn e_saves_the_list_and_a_story_as_html_and_pdf() {
+    let dir = state_dir("export");
+    let state = dir.path();
+    let out = state.join("out");
+    let config = state.join("export.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    // cp stands in for a converter: the "PDF" is a copy of the page
+    std::fs::write(&config, format!("export_dir = \"{}\"\npdf_command = \"cp\"\n{}", out.display(), fixture)).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.choose("e");
+    s.wait_for("Saved ");
+    s.choose("i2");
+    s.wait_for("Source:");
+    s.send("e");
+    s.wait_for("Saved ");
+    s.quit();
+    let mut files: Vec<String> = std::fs::read_dir(&out).unwrap().map(|e| e.unwrap().file_name().into_string().unwrap()).collect();
+    files.sort();
+    assert_eq!(files.len(), 4, "{:?}", files);
+    let page = std::fs::read_to_string(out.join("central-bank-holds-rates-steady-amid-slowing-growth.html")).unwrap();
+    assert!(page.contains("<h1>Central bank holds rates steady amid slowing growth</h1>"), "{}", page);
+    assert!(out.join("central-bank-holds-rates-steady-amid-slowing-growth.pdf").is_file());
+    let list = files.iter().find(|f| f.starts_with("news-") && f.ends_with(".html")).unwrap();
+    let list = std::fs::read_to_string(out.join(list)).unwrap();
+    assert!(list.contains("<h2>World</h2>"), "{}", list);
+    assert!(list.contains("href=\"https://example.com/world/rates\""), "{}", list);
+}
+
+#[test]
+fn l_queues_stories_read_in_order_from_the_queue() {
+    let dir = state_dir("queue");
+    let state = dir.path();
+    let config = state.join("queue.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    std::fs::write(&config, format!("open_command = \"true\"\n{}", fixture)).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.choose("l3");
+    s.wait_for("News (b = back");
+    s.choose("l2");
+    s.wait_for("News (b = back");
+    s.send("b");
+    s.wait_for("6: Queue (2)");
+    s.choose("6");
+    let frame = s.wait_for("Queue (b = back");
+    assert!(frame.contains("1: [World] Election results"), "not in queue order:\n{}", frame);
+    assert!(frame.contains("2: [World] Central bank"), "not in queue order:\n{}", frame);
+    // Central bank first, then read it
+    s.choose("+2");
+    let frame = s.wait_for("1: [World] Central bank");
+    assert!(frame.contains("2: [World] Election results"), "{}", frame);
+    s.choose("1");
+    let frame = s.wait_for("1: [World] Election results");
+    assert!(!frame.contains("Central bank"), "opened story still queued:\n{}", frame);
+    s.send("b");
+    s.wait_for("6: Queue (1)");
+    s.quit();
+}
+
+#[test]
+fn settings_edit_config_in_place() {
+    let dir = state_dir("settings");
+    let state = dir.path();
+    let config = state.join("settings.toml");
+    std::fs::copy("tests/fixtures/config.toml", &config).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("7");
+    // The prompt ("Settings in <path>") and "Saved." scroll off once the list outgrows 30 rows
+    let frame = s.wait_for("'x' = reset to default");
+    assert!(frame.contains("list_times"), "{}", frame);
+    // restore_session is a flag: Enter switches it
+    s.choose("15");
+    s.wait_for("restore_session             true");
+    s.choose("17");
+    s.wait_for("max_title_width (empty = default)");
+    s.send(&format!("60{}", ENTER));
+    s.wait_for("max_title_width             60");
+    s.quit();
+    let written = std::fs::read_to_string(&config).unwrap();
+    assert!(written.contains("# \"2h ago\" would change as the fixture stories age"), "{}", written);
+    assert!(written.contains("restore_session = true"), "{}", written);
+    assert!(written.contains("max_title_width = 60"), "{}", written);
+}
+
+#[test]
+fn catalog_adds_feeds_to_the_config() {
+    let dir = state_dir("catalog");
+    let state = dir.path();
+    let config = state.join("catalog.toml");
+    std::fs::copy("tests/fixtures/config.toml", &config).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("8");
+    s.wait_for("Feed catalog (b = back");
+    // 1 is the World heading; 'a' adds without the preview's fetch
+    s.choose("a2");
+    let frame = s.wait_for("Added BBC World");
+    assert!(frame.contains("BBC World (in config.toml)"), "{}", frame);
+    s.choose("a2");
+    s.wait_for("BBC World is in config.toml already.");
+    s.quit();
+    let written = std::fs::read_to_string(&config).unwrap();
+    assert!(written.starts_with("header = \"Fixture Header\""), "{}", written);
+    assert_eq!(written.matches("https://feeds.bbci.co.uk/news/world/rss.xml").count(), 1, "{}", written);
+}
+
+#[test]
+fn config_changes_reload_the_news_list() {
+    let dir = state_dir("reload");
+    let state = dir.path();
+    let config = state.join("reload.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    let (world, tech) = fixture.split_at(fixture.find("[[feeds]]\nname = \"Tech\"").unwrap());
+    std::fs::write(&config, world).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    let frame = s.wait_for("News (b = back");
+    assert!(!frame.contains("== TECH =="), "{}", frame);
+    // Add a feed and drop the other one while the list is open
+    std::fs::write(&config, format!("header = \"Reloaded\"\nlist_times = \"off\"\n\n{}", tech)).unwrap();
+    let frame = s.wait_for("== TECH ==");
+    assert!(frame.contains("Reloaded"), "{}", frame);
+    assert!(!frame.contains("== WORLD =="), "removed feed still listed:\n{}", frame);
+    s.quit();
+}
+
+#[test]
+fn old_stories_are_hidden_per_feed() {
+    let dir = state_dir("max_age");
+    let state = dir.path();
+    let config = state.join("max_age.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    let fixture = fixture.replace("name = \"Tech\"", "name = \"Tech\"\nmax_age_days = 36500");
+    std::fs::write(&config, format!("max_age_days = 1\n{}", fixture)).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    let frame = s.wait_for("News (b = back");
+    assert!(frame.contains("== WORLD == (0 new / 0 unread / 0 total, 3 older items hidden)"), "world not filtered:\n{}", frame);
+    assert!(frame.contains("== TECH == (12 new / 12 unread / 12 total)"), "tech override ignored:\n{}", frame);
+    assert!(!frame.contains("Central bank"), "old story still listed:\n{}", frame);
+    s.quit();
+}
+
+#[test]
+fn snapshot_view_lists_the_saved_stories() {
+    let dir = state_dir("snapshot");
+    let state = dir.path();
+    let s = Session::spawn_with(state, &["--feeds", "tests/fixtures/config.toml", "snapshot", "save", "night"]);
+    s.wait_exit();
+    // Browsed later with max_age_days hiding everything: the snapshot is shown as it was
+    let config = state.join("snapshot.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    std::fs::write(&config, format!("max_age_days = 1\n{}", fixture)).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap(), "snapshot", "view", "night"]);
+    let frame = s.wait_for("Snapshot \"night\" of");
+    assert!(frame.contains("== WORLD == (3 new / 3 unread / 3 total)"), "{}", frame);
+    assert!(frame.contains("Central bank"), "{}", frame);
+    s.send("b");
+    s.wait_exit();
+}
+
+#[test]
+fn top_stories_ranks_across_feeds() {
+    let dir = state_dir("top_stories");
+    let state = dir.path();
+    let config = state.join("top.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    let fixture = fixture.replace("name = \"Tech\"", "name = \"Tech\"\nweight = 2.0");
+    std::fs::write(
+        &config,
+        format!(
+            "{}\n[scoring]\nwindow_hours = 100000\nhalf_life_hours = 1000000\ntop = 3\nkeywords = {{ \"number 07\" = 3.0 }}\ngroup_topics = false\n",
+            fixture
+        ),
+    )
+    .unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("2: Top Stories");
+    s.choose("2");
+    let frame = s.wait_for("Top Stories (b = back");
+    let rank = |n: &str| frame.lines().find(|l| l.starts_with(&format!("{}: [", n))).unwrap_or_default().to_string();
+    assert!(rank("1").contains("Tech story number 07 (Tech)"), "keyword boost not ranked first:\n{}", frame);
+    assert!(rank("2").contains("Tech story number 12"), "weighted feed not ranked next:\n{}", frame);
+    assert!(rank("3").contains("Tech story number 11"), "newer story not ranked higher:\n{}", frame);
+    assert!(!frame.contains("4: ["), "more than top stories listed:\n{}", frame);
+    s.send("b");
+    s.wait_for("Main Menu");
+    s.quit();
+}
+
+#[test]
+fn top_stories_collapse_similar_titles_into_topics() {
+    let dir = state_dir("top_topics");
+    let state = dir.path();
+    let config = state.join("topics.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    std::fs::write(&config, format!("{}\n[scoring]\nwindow_hours = 100000\nhalf_life_hours = 1000000\n", fixture)).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("2: Top Stories");
+    s.choose("2");
+    let frame = s.wait_for("Top Stories (b = back");
+    let topic = frame.lines().find(|l| l.contains("▸ [NEW] 11 stories about Tech story number")).unwrap_or_default();
+    assert!(!topic.is_empty(), "tech stories not grouped:\n{}", frame);
+    assert!(frame.contains("Central bank holds rates steady"), "single story missing:\n{}", frame);
+    assert!(!frame.contains("Tech story number 07"), "collapsed topic lists its stories:\n{}", frame);
+    let n = topic.split(':').next().unwrap().trim_start_matches(['>', ' ']).to_string();
+    s.choose(&n);
+    let frame = s.wait_for("▾ [NEW] 11 stories about");
+    assert!(frame.contains("    - [NEW] Tech story number 12 (Tech)"), "topic not opened:\n{}", frame);
+    s.choose(&n);
+    let frame = s.wait_for("▸ [NEW] 11 stories about");
+    assert!(!frame.contains("Tech story number 12"), "topic not closed:\n{}", frame);
+    s.quit();
+}
+
+#[test]
+fn stats_show_cached_values_and_changes() {
+    let dir = state_dir("stats_cache");
+    let state = dir.path();
+    let cache = state.join("cache/news-cli");
+    std::fs::create_dir_all(&cache).unwrap();
+    let fetched = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+    let entries = format!(
+        r#"{{"policy_rate": {{"fetched": {0}, "reading": {{"Rate": {{"value": 2.5, "date": "2025-10-29", "previous": 2.75}}}}}},
+            "inflation": {{"fetched": {0}, "reading": {{"Rate": {{"value": 2.4, "date": "2025-09-01", "previous": 2.4}}}}}},
+            "crypto:usd:bitcoin": {{"fetched": {0}, "reading": {{"Prices": [{{"coin": "bitcoin", "price": 67187.5,
+                "change_24h": -1.23, "updated": 1761744000}}]}}}},
+            "fx:USD/CAD": {{"fetched": {0}, "reading": {{"Quote": {{"value": 1.3785, "date": "2025-10-29", "previous": 1.3764}}}}}},
+            "yields:TB.CDN.90D.MID,BD.CDN.2YR.DQ.YLD,BD.CDN.5YR.DQ.YLD,BD.CDN.10YR.DQ.YLD,BD.CDN.LONG.DQ.YLD":
+                {{"fetched": {0}, "reading": {{"Curve": {{"points": [["3M", 2.4], ["2Y", 2.3], ["5Y", 2.6], ["10Y", 3.1], ["Long", 3.5]],
+                "date": "2025-10-29"}}}}}}}}"#,
+        fetched
+    );
+    std::fs::write(cache.join("stats.json"), entries).unwrap();
+    let config = state.join("stats.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    std::fs::write(&config, format!("{}\n[stats.fx]\npairs = [\"usd/cad\"]\n[stats.crypto]\ncoins = [\"bitcoin\"]\n", fixture)).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("4");
+    let frame = s.wait_for("Policy rate (BoC): 2.50% (▼ 0.25, -9.1% from 2.75%) (as of 2025-10-29)");
+    assert!(
+        frame.contains("Inflation YoY (CPI, BoC): 2.40% (unchanged from 2.40%) (as of 2025-09-01)"),
+        "inflation not cached:\n{}",
+        frame
+    );
+    assert!(frame.contains("Exchange rates:"), "no fx section:\n{}", frame);
+    assert!(frame.contains("3M 2.40%") && frame.contains("Long 3.50%"), "no yield chart:\n{}", frame);
+    assert!(
+        frame.contains("bitcoin: 67187.50 USD (▼ 1.2% in 24h) (as of 2025-10-29 13:20)"),
+        "no crypto prices:\n{}",
+        frame
+    );
+    assert!(frame.contains("3.50┤") && frame.contains("2.30┤"), "yield axis not scaled:\n{}", frame);
+    assert!(frame.contains("USD/CAD: 1.3785 (▲ 0.0021, +0.2% from 1.3764) (as of 2025-10-29)"), "fx not shown:\n{}", frame);
+    assert!(frame.contains("'r' to refresh"), "no refresh key:\n{}", frame);
+    s.send("b");
+    s.wait_for("Main Menu");
+    s.quit();
+}
+
+#[test]
+fn stats_layout_orders_groups_and_relabels() {
+    let dir = state_dir("stats_layout");
+    let state = dir.path();
+    let cache = state.join("cache/news-cli");
+    std::fs::create_dir_all(&cache).unwrap();
+    let fetched = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+    let entries = format!(
+        r#"{{"policy_rate": {{"fetched": {0}, "reading": {{"Rate": {{"value": 2.5, "date": "2025-10-29"}}}}}},
+            "yields:TB.CDN.90D.MID,BD.CDN.2YR.DQ.YLD,BD.CDN.5YR.DQ.YLD,BD.CDN.10YR.DQ.YLD,BD.CDN.LONG.DQ.YLD":
+                {{"fetched": {0}, "reading": {{"Curve": {{"points": [["3M", 2.4], ["2Y", 2.3], ["5Y", 2.6], ["10Y", 3.1], ["Long", 3.5]],
+                "date": "2025-10-29"}}}}}}}}"#,
+        fetched
+    );
+    std::fs::write(cache.join("stats.json"), entries).unwrap();
+    let config = state.join("stats.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    let layout = "[[stats.layout]]\ntitle = \"Canada\"\nitems = [\"yield_curve\", \"policy_rate\"]\n\
+                  [stats.labels]\npolicy_rate = \"Overnight rate\"\n";
+    std::fs::write(&config, format!("{}\n{}", fixture, layout)).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("4");
+    let frame = s.wait_for("Overnight rate: 2.50%");
+    let (group, curve, rate) =
+        (frame.find("Canada").unwrap(), frame.find("Yield Curve (BoC):").unwrap(), frame.find("Overnight rate").unwrap());
+    assert!(group < curve && curve < rate, "not in layout order:\n{}", frame);
+    assert!(!frame.contains("Inflation") && !frame.contains("Population"), "unlisted items shown:\n{}", frame);
+    s.send("b");
+    s.wait_for("Main Menu");
+    s.quit();
+}
+
+#[test]
+fn catch_up_triages_one_story_at_a_time() {
+    let dir = state_dir("catch_up");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    s.wait_for("3: Catch-up");
+    s.choose("3");
+    let frame = s.wait_for("Catch-up: 1 of 15");
+    assert!(frame.contains("Tech story number 12"), "newest story not first:\n{}", frame);
+    s.send("b");
+    let frame = s.wait_for("Catch-up: 2 of 15");
+    assert!(frame.contains("Tech story number 11") && frame.contains("Bookmarked."), "no next story:\n{}", frame);
+    s.send("s");
+    s.wait_for("Catch-up: 3 of 15");
+    s.send("m");
+    s.wait_for("Mute which topic? 1 = number, 2 = story, 3 = tech");
+    s.send("3");
+    let frame = s.wait_for("Catch-up: 3 of 6");
+    assert!(frame.contains("Muted \"tech\": 9 stories hidden."), "missing status:\n{}", frame);
+    assert!(frame.contains("Central bank holds rates steady"), "muted story still shown:\n{}", frame);
+    s.send("\x1b");
+    s.wait_for("Main Menu");
+    s.quit();
+    let bookmarks = std::fs::read_to_string(state.join("state/news-cli/bookmarks.json")).unwrap();
+    assert!(bookmarks.contains("https://example.com/tech/story-12"), "bookmark not saved:\n{}", bookmarks);
+    let muted = std::fs::read_to_string(state.join("state/news-cli/muted.json")).unwrap();
+    assert!(muted.contains("\"tech\""), "topic not saved:\n{}", muted);
+}
+
+#[test]
+fn foreign_stories_are_translated() {
+    let dir = state_dir("translation");
+    let state = dir.path();
+    let (url, requests) = fake_api(
+        r#"{"translatedText":["Central bank leaves rates unchanged","The central bank decided on Monday not to change its key rate."]}"#,
+    );
+    let config = state.join("translation.toml");
+    std::fs::write(
+        &config,
+        format!(
+            "[[feeds]]\nname = \"Nachrichten\"\nurl = \"tests/fixtures/german.xml\"\n\n[translation]\nbackend = \"libretranslate\"\nurl = \"{}\"\n",
+            url
+        ),
+    )
+    .unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    let frame = s.wait_for("News (b = back");
+    assert!(frame.contains("Zentralbank lässt Zinsen unverändert (en: Central bank"), "no translated title:\n{}", frame);
+    s.choose("i2");
+    let frame = s.wait_for("Translation (de → en):");
+    assert!(frame.contains("The central bank decided on Monday"), "no translated summary:\n{}", frame);
+    assert!(frame.contains("Die Notenbank hat am Montag"), "original summary missing:\n{}", frame);
+    s.quit();
+    assert_eq!(requests.lock().unwrap().as_slice(), ["POST /translate HTTP/1.1"]);
+}
+
+#[test]
+fn concurrent_instances_merge_history() {
+    let dir = state_dir("concurrent");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    // Another instance saves its own seen links while this one is still open
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_news-cli"))
+        .args(["--feeds", "tests/fixtures/full.xml", "fetch", "--mark-seen"])
+        .env("HOME", state)
+        .env("XDG_CONFIG_HOME", state.join("config"))
+        .env("XDG_STATE_HOME", state.join("state"))
+        .env("XDG_CACHE_HOME", state.join("cache"))
+        .stdout(std::process::Stdio::null())
+        .status()
+        .unwrap();
+    assert!(status.success());
+    s.send("b");
+    s.wait_for("Main Menu");
+    s.quit();
+    let history = std::fs::read_to_string(state.join("state/news-cli/seen_stories.json")).unwrap();
+    assert!(history.contains("https://example.com/longreads/tides"), "other instance's links lost:\n{}", history);
+    assert!(history.contains("https://example.com/world/rates"), "own links lost:\n{}", history);
+}
+
+#[test]
+fn reading_stats_count_opened_stories() {
+    let dir = state_dir("reading_stats");
+    let state = dir.path();
+    let config = state.join("reading.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    std::fs::write(&config, format!("open_command = \"true\"\n{}", fixture)).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.choose("2");
+    s.wait_for("News (b = back");
+    s.choose("3");
+    s.wait_for("News (b = back");
+    s.send("b");
+    s.wait_for("6: Reading Stats");
+    s.choose("6");
+    let frame = s.wait_for("Reading Stats (2 opens)");
+    assert!(frame.contains("[7 days]"), "first period not selected:\n{}", frame);
+    assert!(frame.lines().any(|l| l.trim_start().starts_with("World") && l.ends_with(" 2")), "missing source bar:\n{}", frame);
+    assert!(frame.contains("bank (1), begin (1), central (1)"), "missing topics:\n{}", frame);
+    s.send(TAB);
+    s.send(TAB);
+    s.send(TAB);
+    s.wait_for("[all time]");
+    s.send("b");
+    s.wait_for("Main Menu");
+    s.quit();
+    let log = std::fs::read_to_string(state.join("state/news-cli/reading_log.jsonl")).unwrap();
+    assert_eq!(log.lines().count(), 2);
+}
+
+#[test]
+fn source_headers_count_reads_live() {
+    let dir = state_dir("progress");
+    let state = dir.path();
+    let config = state.join("progress.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    std::fs::write(&config, format!("open_command = \"true\"\n{}", fixture)).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("== WORLD == (3 new / 3 unread / 3 total)");
+    s.choose("2");
+    s.wait_for("== WORLD == (2 new / 2 unread / 3 total)");
+    s.choose("m1");
+    s.wait_for("== WORLD == (0 new / 0 unread / 3 total)");
+    s.send("b");
+    s.wait_for("Main Menu");
+    s.choose("1");
+    // Listed before, so no longer new; the rest of Tech is still unread
+    let frame = s.wait_for("== TECH == (0 new / 12 unread / 12 total)");
+    assert!(frame.contains("== WORLD == (0 new / 0 unread / 3 total)"), "reads not kept:\n{}", frame);
+    s.quit();
+}
+
+#[test]
+fn feeds_ordered_by_most_new() {
+    let dir = state_dir("feed_order");
+    let state = dir.path();
+    let config = state.join("feed_order.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    std::fs::write(&config, format!("feed_order = \"most_new\"\n{}", fixture)).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    let frame = s.wait_for("== WORLD ==");
+    assert!(frame.find("== TECH ==") < frame.find("== WORLD =="), "tech not first:\n{}", frame);
+    s.choose("m1");
+    let frame = s.wait_for("== TECH == (0 new");
+    assert!(frame.find("== WORLD ==") < frame.find("== TECH =="), "world not moved up:\n{}", frame);
+    s.quit();
+}
+
+#[test]
+fn d_switches_between_dates_and_relative_times() {
+    let dir = state_dir("list_times");
+    let state = dir.path();
+    let config = state.join("list_times.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    std::fs::write(&config, fixture.replace("list_times = \"off\"", "list_times = \"absolute\"")).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("2026-02-02 09:00 [NEW] Central bank holds rates");
+    s.choose("d");
+    let frame = s.wait_for(" ago [NEW] Central bank holds rates");
+    assert!(!frame.contains("2026-02-02"), "{}", frame);
+    // The source list follows the switch
+    s.choose("1");
+    s.wait_for("World - all entries");
+    s.wait_for(" ago [NEW] Central bank holds rates");
+    s.quit();
+}
+
+#[test]
+fn source_headers_show_fetch_status() {
+    let dir = state_dir("feed_status");
+    let state = dir.path();
+    let config = state.join("feed_status.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    let fixture = fixture.replace("list_times = \"off\"\n", "");
+    std::fs::write(&config, format!("{}\n[[feeds]]\nname = \"Gone\"\nurl = \"http://127.0.0.1:9/gone.xml\"\n", fixture)).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    let frame = s.wait_for("== GONE == (0 new / 0 unread / 0 total) · never fetched (last fetch failed)");
+    let world = frame.lines().find(|l| l.contains("== WORLD ==")).unwrap();
+    assert!(world.contains("· fetched just now, newest item "), "{}", frame);
+    s.quit();
+    let status: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(state.join("state/news-cli/feeds.json")).unwrap()).unwrap();
+    // Mon, 02 Feb 2026 09:00:00 +0000
+    assert_eq!(status["World"]["newest_item"], 1_770_022_800);
+    assert!(status["Gone"]["error"].is_string(), "{}", status);
+}
+
+#[test]
+fn feeds_outside_their_hours_are_dormant() {
+    let dir = state_dir("dormant");
+    let state = dir.path();
+    let config = state.join("dormant.toml");
+    // Two hours from now (UTC) for an hour: dormant for the whole test
+    let hour = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() / 3600 % 24;
+    let hours = format!("{}-{}", (hour + 2) % 24, (hour + 3) % 24);
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    let fixture = fixture.replace("name = \"Tech\"", &format!("name = \"Tech\"\nhours = \"{}\"", hours));
+    std::fs::write(&config, fixture).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    let frame = s.wait_for(&format!("· dormant (active {})", hours));
+    assert!(frame.contains("== TECH == (0 new / 0 unread / 0 total)"), "{}", frame);
+    assert!(!frame.contains("Tech story number"), "dormant feed fetched:\n{}", frame);
+    assert!(frame.contains("Central bank holds rates steady"), "{}", frame);
+    s.quit();
+}
+
+#[test]
+fn cached_stories_show_while_refreshing() {
+    let dir = state_dir("stale");
+    let state = dir.path();
+    let script = state.join("slow.sh");
+    std::fs::write(&script, "sleep 2\ncat tests/fixtures/stories.jsonl\n").unwrap();
+    let config = state.join("slow.toml");
+    std::fs::write(
+        &config,
+        format!("[[feeds]]\nname = \"Script\"\nkind = \"command\"\ncommand = \"sh {}\"\n", script.display()),
+    )
+    .unwrap();
+    std::fs::create_dir_all(state.join("cache/news-cli")).unwrap();
+    std::fs::write(
+        state.join("cache/news-cli/stories.json"),
+        r#"[{"title":"Cached story","link":"https://example.com/cached","source":"Script"}]"#,
+    )
+    .unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    let frame = s.wait_for("News (refreshing");
+    assert!(frame.contains("Cached story"), "cache not shown:\n{}", frame);
+    let frame = s.wait_for("News (b = back");
+    assert!(frame.contains("[NEW] Scripted story one"), "fresh stories not swapped in:\n{}", frame);
+    assert!(!frame.contains("Cached story"), "stale story still listed:\n{}", frame);
+    s.quit();
+}
+
+#[test]
+fn news_are_fetched_while_the_main_menu_is_up() {
+    let dir = state_dir("prefetch");
+    let state = dir.path();
+    let script = state.join("slow.sh");
+    std::fs::write(&script, "sleep 1\ncat tests/fixtures/stories.jsonl\n").unwrap();
+    let config = state.join("slow.toml");
+    std::fs::write(
+        &config,
+        format!("[[feeds]]\nname = \"Script\"\nkind = \"command\"\ncommand = \"sh {}\"\n", script.display()),
+    )
+    .unwrap();
+    std::fs::create_dir_all(state.join("cache/news-cli")).unwrap();
+    std::fs::write(
+        state.join("cache/news-cli/stories.json"),
+        r#"[{"title":"Cached story","link":"https://example.com/cached","source":"Script"}]"#,
+    )
+    .unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    std::thread::sleep(std::time::Duration::from_secs(2));
+    s.choose("1");
+    // The fetch finished meanwhile: fresh stories at once, no stale list first
+    let frame = s.wait_for("News (");
+    assert!(frame.contains("News (b = back"), "not fetched ahead:\n{}", frame);
+    assert!(frame.contains("[NEW] Scripted story one"), "{}", frame);
+    s.quit();
+}
+
+#[test]
+fn b_stops_a_slow_fetch_showing_what_arrived() {
+    let dir = state_dir("fetch_stopped");
+    let state = dir.path();
+    let config = state.join("slow.toml");
+    std::fs::write(
+        &config,
+        "[[feeds]]\nname = \"World\"\nurl = \"tests/fixtures/world.xml\"\n\n\
+         [[feeds]]\nname = \"Slow\"\nkind = \"command\"\ncommand = \"sleep 30\"\n",
+    )
+    .unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    let progress = s.wait_for("✓ World");
+    assert!(progress.contains("(1/2)"), "{}", progress);
+    assert!(progress.contains("Slow"), "{}", progress);
+    s.send("b");
+    let frame = s.wait_for("News (stopped early");
+    assert!(frame.contains("== WORLD =="), "{}", frame);
+    assert!(!frame.contains("SLOW"), "{}", frame);
+    s.quit();
+    // Only complete fetches become the cached list
+    assert!(!state.join("cache/news-cli/stories.json").exists());
+}
+
+#[test]
+fn badges_cleared_on_next_visit() {
+    let dir = state_dir("badges_cleared");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("[NEW]");
+    s.send("b");
+    s.wait_for("Main Menu");
+    s.quit();
+
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    let frame = s.wait_for("News (b = back");
+    assert!(!frame.contains("[NEW]"), "stories still badged after revisit:\n{}", frame);
+    insta::assert_snapshot!(frame);
+    s.quit();
+}
+
+#[test]
+fn mark_source_and_all_read() {
+    let dir = state_dir("mark_read");
+    let state = dir.path();
+    let history = state.join("state/news-cli/seen_stories.json");
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("[NEW]");
+    s.choose("m1");
+    let frame = s.wait_for("News (b = back");
+    let world: Vec<&str> = frame.lines().skip_while(|l| !l.contains("== WORLD ==")).take(4).collect();
+    assert!(world.iter().all(|l| !l.contains("[NEW]")), "world still badged:\n{}", frame);
+    assert!(frame.contains("[NEW] Tech story number 12"), "other source marked too:\n{}", frame);
+    // Saved right away, not only on exit
+    let saved = std::fs::read_to_string(&history).unwrap();
+    assert!(saved.contains("https://example.com/world/rates"), "history not saved:\n{}", saved);
+    s.choose("a");
+    let frame = s.wait_for("News (b = back");
+    assert!(!frame.contains("[NEW]"), "stories still badged after mark all:\n{}", frame);
+    assert!(std::fs::read_to_string(&history).unwrap().contains("https://example.com/tech/story-01"));
+    s.quit();
+}
+
+#[test]
+fn feed_icons_mark_headers_and_stories() {
+    let dir = state_dir("icons");
+    let state = dir.path();
+    let config = state.join("icons.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    let fixture = fixture
+        .replace("name = \"World\"\n", "name = \"World\"\ncategory = \"world\"\n")
+        .replace("name = \"Tech\"\n", "name = \"Tech\"\nicon = \"[T]\"\n");
+    std::fs::write(&config, fixture).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    let frame = s.wait_for("News (b = back");
+    assert!(frame.contains("🌍 == WORLD =="), "category icon missing:\n{}", frame);
+    assert!(frame.contains("  - 🌍 [NEW] Central bank"), "story icon missing:\n{}", frame);
+    assert!(frame.contains("[T] == TECH =="), "configured icon missing:\n{}", frame);
+    s.quit();
+}
+
+#[test]
+fn session_restore_reopens_news_list() {
+    let dir = state_dir("session_restore");
+    let state = dir.path();
+    let config = state.join("restore.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    std::fs::write(&config, format!("restore_session = true\n{}", fixture)).unwrap();
+    let args = ["--feeds", config.to_str().unwrap()];
+
+    // Highlight a story and leave with a filter typed
+    let mut s = Session::spawn_with(state, &args);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.send(DOWN);
+    s.send(DOWN);
+    s.wait_for("> 2: ");
+    s.send("/rates");
+    s.wait_for("Filter: rates_");
+    s.send(DOWN);
+    s.wait_for("> 2:   - [NEW] Central bank");
+    s.send(CTRL_C);
+    s.wait_exit();
+
+    let mut s = Session::spawn_with(state, &args);
+    let frame = s.wait_for("Filter: rates_");
+    assert!(frame.contains("> 2:   - Central bank holds rates steady"), "selection not restored:\n{}", frame);
+    s.send("\x1b");
+    s.wait_for("'/' = filter");
+    s.send("q");
+    s.wait_exit();
+
+    // A source's full list reopens too; backing out to the main menu forgets the session
+    let mut s = Session::spawn_with(state, &args);
+    s.wait_for("'/' = filter");
+    s.send(TAB);
+    s.send(ENTER);
+    s.wait_for("Tech - all entries");
+    s.send("q");
+    s.wait_exit();
+
+    let mut s = Session::spawn_with(state, &args);
+    s.wait_for("Tech - all entries");
+    s.send("b");
+    s.wait_for("News (b = back");
+    s.send("b");
+    s.wait_for("Main Menu");
+    s.quit();
+
+    let s = Session::spawn_with(state, &args);
+    s.wait_for("Main Menu");
+    s.quit();
+}
+
+#[test]
+fn quit_from_nested_menu_saves_history() {
+    let dir = state_dir("nested_quit");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.choose("5");
+    let frame = s.wait_for("Tech - all entries");
+    assert!(frame.contains("Main > News > Tech"), "missing breadcrumb:\n{}", frame);
+    s.quit();
+    let history = std::fs::read_to_string(state.join("state/news-cli/seen_stories.json")).unwrap();
+    assert!(history.contains("https://example.com/tech/story-12"), "history not saved on quit:\n{}", history);
+}
+
+#[test]
+fn question_mark_lists_keys() {
+    let dir = state_dir("key_help");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("News (b = back");
+    s.send("?");
+    insta::assert_snapshot!(s.wait_for("Press any key to return."));
+    s.send("x");
+    s.wait_for("News (b = back");
+    s.choose("i2");
+    s.wait_for("'?' = keys");
+    s.send("?");
+    let frame = s.wait_for("Keys: Story");
+    assert!(frame.contains("o / Enter") && frame.contains("copy the link"), "story keys missing:\n{}", frame);
+    s.send("x");
+    s.wait_for("'c' = copy link");
+    s.quit();
+}
+
+#[test]
+fn language_setting_translates_menus() {
+    let dir = state_dir("language");
+    let state = dir.path();
+    let config = state.join("de.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    std::fs::write(&config, format!("language = \"de\"\n{}", fixture)).unwrap();
+    let mut s = Session::spawn_with(state, &["--feeds", config.to_str().unwrap()]);
+    let frame = s.wait_for("Hauptmenü");
+    assert!(frame.contains("1: Nachrichten") && frame.contains("Beenden"), "menu not translated:\n{}", frame);
+    s.send("1");
+    s.wait_for("Auswahl: 1");
+    s.send(ENTER);
+    let frame = s.wait_for("Nachrichten (b = zurück");
+    assert!(frame.contains("Hauptmenü > Nachrichten"), "breadcrumb not translated:\n{}", frame);
+    s.send("?");
+    let frame = s.wait_for("Zurück mit einer beliebigen Taste.");
+    // The German action rows wrap at 80 columns, so the top of the overlay scrolls away
+    assert!(frame.contains("zu Eintrag N springen"), "key overlay not translated:\n{}", frame);
+    s.send("x");
+    s.wait_for("Nachrichten (b = zurück");
+    s.quit();
+}
+
+#[test]
+fn ctrl_c_keeps_session_history() {
+    let dir = state_dir("ctrl_c");
+    let state = dir.path();
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    s.wait_for("[NEW]");
+    s.send(CTRL_C);
+    s.wait_exit();
+
+    let mut s = Session::spawn(state);
+    s.wait_for("Main Menu");
+    s.choose("1");
+    let frame = s.wait_for("News (b = back");
+    assert!(!frame.contains("[NEW]"), "Ctrl-C lost the session's history:\n{}", frame);
+    s.quit();
+}
+
+#[test]
+fn first_run_wizard_writes_config() {
+    let dir = state_dir("wizard");
+    let state = dir.path();
+    let mut s = Session::spawn_with(state, &[]);
+    s.wait_for("Set one up now?");
+    s.send("y");
+    s.wait_for("Pick some feeds");
+    s.send(ENTER);
+    s.wait_for("Add another feed URL");
+    s.send(ENTER);
+    s.wait_for("Browser command");
+    s.send(ENTER);
+    s.wait_for("Refresh interval");
+    s.send(ENTER);
+    s.wait_for("Press any key");
+    s.send(" ");
+    s.wait_for("Main Menu");
+    s.quit();
+
+    let written = std::fs::read_to_string(state.join("config/news-cli/config.toml")).unwrap();
+    insta::assert_snapshot!(written);
+}
+
+#[test]
+fn new_secrets_file_needs_the_passphrase_twice() {
+    let dir = state_dir("secrets_passphrase");
+    let state = dir.path();
+    let config = state.join("config/news-cli");
+    std::fs::create_dir_all(&config).unwrap();
+    std::fs::write(config.join("config.toml"), "feeds = []\n\n[secrets]\nstore = \"file\"\n").unwrap();
+    let mut s = Session::spawn_with(state, &["secret", "set", "miniflux"]);
+    // Hidden input flushes what was typed before it started reading, so give it time after
+    // each prompt
+    for (prompt, typed) in [("Value for miniflux", "token\r"), ("Passphrase for secrets.age", "one\r"), ("Repeat the passphrase", "two\r")] {
+        s.wait_for(prompt);
+        thread::sleep(Duration::from_millis(300));
+        s.send(typed);
+    }
+    s.wait_for("the passphrases differ");
+    s.wait_exit();
+    assert!(!config.join("secrets.age").exists(), "created with a mistyped passphrase");
+}
@@ -0,0 +1,86 @@
+//! `news-cli watch` pushing new alert matches to a webhook and an ntfy server.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[test]
+fn alert_matches_are_pushed() {
+    // Answers every request with 200, keeping (request line, body)
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::<(String, String)>::new()));
+    let log = requests.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut length = 0;
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                if let Some(v) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = v.trim().parse().unwrap();
+                }
+                line.clear();
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            log.lock().unwrap().push((request_line.trim_end().to_string(), String::from_utf8(body).unwrap()));
+            stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n").unwrap();
+        }
+    });
+
+    let state = std::env::temp_dir().join(format!("news-cli-watch-push-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&state);
+    std::fs::create_dir_all(&state).unwrap();
+    let config = state.join("push.toml");
+    let fixture = std::fs::read_to_string("tests/fixtures/config.toml").unwrap();
+    std::fs::write(
+        &config,
+        format!(
+            "{}\n[[alerts]]\nname = \"Rates\"\nkeywords = [\"central bank\"]\nwebhook = \"{1}/hook\"\nntfy = \"{1}/oncall\"\n",
+            fixture, base
+        ),
+    )
+    .unwrap();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_news-cli"))
+        .args(["--feeds", config.to_str().unwrap(), "watch", "--interval", "3600", "--to", "json"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env("HOME", &state)
+        .env("XDG_STATE_HOME", state.join("state"))
+        .env("XDG_CACHE_HOME", state.join("cache"))
+        .stdout(Stdio::null())
+        .spawn()
+        .expect("run news-cli watch");
+    let start = Instant::now();
+    while requests.lock().unwrap().len() < 2 && start.elapsed() < Duration::from_secs(10) {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 2, "{:?}", requests);
+    let (hook_line, hook) = &requests[0];
+    assert_eq!(hook_line, "POST /hook HTTP/1.1");
+    let hook: serde_json::Value = serde_json::from_str(hook).unwrap();
+    assert_eq!(
+        hook,
+        serde_json::json!([{
+            "alert": "Rates",
+            "title": "Central bank holds rates steady amid slowing growth",
+            "link": "https://example.com/world/rates",
+            "source": "World",
+        }])
+    );
+    let (ntfy_line, ntfy) = &requests[1];
+    assert_eq!(ntfy_line, "POST / HTTP/1.1");
+    let ntfy: serde_json::Value = serde_json::from_str(ntfy).unwrap();
+    assert_eq!(ntfy["topic"], "oncall");
+    assert_eq!(ntfy["click"], "https://example.com/world/rates");
+    assert_eq!(ntfy["message"], "World (Rates)");
+}
@@ -0,0 +1,39 @@
+//! Open-Meteo forecast parsing.
+
+use news_cli::weather::{parse, Condition};
+
+const FORECAST: &str = r#"{
+  "latitude": 52.52, "longitude": 13.42,
+  "current_units": {"time": "iso8601", "temperature_2m": "°C", "weather_code": "wmo code", "wind_speed_10m": "km/h"},
+  "current": {"time": "2026-10-17T07:00", "temperature_2m": 11.6, "weather_code": 2, "wind_speed_10m": 9.4},
+  "daily_units": {"temperature_2m_max": "°C"},
+  "daily": {"time": ["2026-10-17"], "temperature_2m_max": [15.2], "temperature_2m_min": [7.9], "precipitation_probability_max": [20]}
+}"#;
+
+#[test]
+fn forecast_is_parsed() {
+    let w = parse(FORECAST).unwrap();
+    assert_eq!((w.temperature, w.temperature_unit.as_str()), (11.6, "°C"));
+    assert_eq!(w.condition, Condition::PartlyCloudy);
+    assert_eq!((w.wind, w.wind_unit.as_str()), (Some(9.4), "km/h"));
+    assert_eq!((w.low, w.high, w.rain_chance), (Some(7.9), Some(15.2), Some(20.0)));
+}
+
+#[test]
+fn missing_daily_values_and_codes_are_tolerated() {
+    let w = parse(r#"{"current": {"temperature_2m": -3.0, "weather_code": 42}, "daily": {"temperature_2m_max": [null]}}"#).unwrap();
+    assert_eq!((w.condition, w.high, w.low, w.wind), (Condition::Unknown, None, None, None));
+    assert_eq!(w.temperature_unit, "°C");
+    assert!(parse(r#"{"error": true, "reason": "Latitude must be in range"}"#).is_err());
+}
+
+#[test]
+fn weather_codes_map_to_conditions() {
+    let cases = [(0, Condition::Clear), (3, Condition::Overcast), (45, Condition::Fog), (55, Condition::Drizzle)];
+    for (code, condition) in cases {
+        assert_eq!(Condition::from_code(code), condition, "code {}", code);
+    }
+    assert_eq!(Condition::from_code(81), Condition::Rain);
+    assert_eq!(Condition::from_code(86), Condition::Snow);
+    assert_eq!(Condition::from_code(95), Condition::Thunderstorm);
+}